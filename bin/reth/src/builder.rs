@@ -19,11 +19,14 @@ use reth_blockchain_tree::{config::BlockchainTreeConfig, ShareableBlockchainTree
 use reth_config::Config;
 use reth_db::{
     database::Database,
+    database_backup::DatabaseBackup,
     database_metrics::{DatabaseMetadata, DatabaseMetrics},
 };
 use reth_interfaces::p2p::either::EitherDownloader;
 use reth_network::NetworkEvents;
 use reth_network_api::{NetworkInfo, PeersInfo};
+#[cfg(not(feature = "optimism"))]
+use reth_node_core::args::read_secret_key;
 use reth_node_core::{
     cli::{
         components::{RethNodeComponentsImpl, RethRpcServerHandles},
@@ -109,7 +112,9 @@ pub struct NodeBuilderWithDatabase<DB> {
     pub data_dir: ChainPath<DataDirPath>,
 }
 
-impl<DB: Database + DatabaseMetrics + DatabaseMetadata + 'static> NodeBuilderWithDatabase<DB> {
+impl<DB: Database + DatabaseMetrics + DatabaseMetadata + DatabaseBackup + 'static>
+    NodeBuilderWithDatabase<DB>
+{
     /// Launch the node with the given extensions and executor
     pub async fn launch<E: RethCliExt>(
         mut self,
@@ -179,6 +184,8 @@ impl<DB: Database + DatabaseMetrics + DatabaseMetadata + 'static> NodeBuilderWit
             sync_metrics_tx.clone(),
             tree_config,
             evm_config,
+            config.stages.execution.index_contract_creators,
+            config.stages.index_address_appearances.enabled,
         )?;
         let canon_state_notification_sender = tree.canon_state_notification_sender();
         let blockchain_tree = ShareableBlockchainTree::new(tree);
@@ -251,7 +258,16 @@ impl<DB: Database + DatabaseMetrics + DatabaseMetadata + 'static> NodeBuilderWit
 
         // The default payload builder is implemented on the unit type.
         #[cfg(not(feature = "optimism"))]
-        let payload_builder = reth_ethereum_payload_builder::EthereumPayloadBuilder::default();
+        let payload_builder = reth_ethereum_payload_builder::EthereumPayloadBuilder::default()
+            .set_refund_signer(
+                self.config
+                    .builder
+                    .refund_secret_key
+                    .as_deref()
+                    .map(read_secret_key)
+                    .transpose()
+                    .wrap_err("failed to load the refund secret key")?,
+            );
 
         #[cfg(not(feature = "optimism"))]
         let payload_builder: PayloadBuilderHandle<EthEngineTypes> =
@@ -405,8 +421,11 @@ impl<DB: Database + DatabaseMetrics + DatabaseMetadata + 'static> NodeBuilderWit
         self.config.adjust_instance_ports();
 
         // Start RPC servers
-        let rpc_server_handles =
-            self.config.rpc.start_servers(&components, engine_api, jwt_secret, &mut ext).await?;
+        let rpc_server_handles = self
+            .config
+            .rpc
+            .start_servers(&components, engine_api, jwt_secret, Arc::clone(&self.db), &mut ext)
+            .await?;
 
         // Run consensus engine to completion
         let (tx, rx) = oneshot::channel();
@@ -0,0 +1,39 @@
+use clap::Parser;
+use human_bytes::human_bytes;
+use reth_db::database_backup::DatabaseBackup;
+use std::path::PathBuf;
+use tracing::info;
+
+/// The arguments for the `reth db backup` command
+#[derive(Parser, Debug)]
+pub struct Command {
+    /// The directory to write the backup to. Must not contain a database yet.
+    ///
+    /// The backup is a consistent copy of the database at the time the command is started, and
+    /// can be taken while the node is running.
+    path: PathBuf,
+
+    /// Omits free pages from the backup, which makes it smaller than the original database.
+    #[arg(long)]
+    compact: bool,
+}
+
+impl Command {
+    /// Execute `db backup` command
+    pub fn execute<DB: DatabaseBackup>(self, db: &DB) -> eyre::Result<()> {
+        info!(target: "reth::cli", path = ?self.path, compact = self.compact, "Starting database backup");
+
+        db.backup(&self.path, self.compact, &mut |progress| {
+            info!(
+                target: "reth::cli",
+                copied = %human_bytes(progress.copied_bytes as f64),
+                total = %human_bytes(progress.total_bytes as f64),
+                "Backing up database"
+            );
+        })?;
+
+        info!(target: "reth::cli", path = ?self.path, "Database backup finished");
+
+        Ok(())
+    }
+}
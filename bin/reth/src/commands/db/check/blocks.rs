@@ -0,0 +1,359 @@
+use super::{lowest_unpruned_tx, repair_in_batches, Check, CheckReport, REPAIR_BATCH_SIZE};
+use reth_db::{
+    cursor::{DbCursorRO, DbCursorRW},
+    database::Database,
+    tables,
+    transaction::{DbTx, DbTxMut},
+};
+use reth_primitives::{stage::StageId, BlockNumber, PruneSegment, TxNumber};
+use reth_provider::{
+    BlockReader, DatabaseProviderRO, ProviderFactory, PruneCheckpointReader, StageCheckpointReader,
+    TransactionsProviderExt,
+};
+use std::ops::Range;
+
+/// Checks that the transaction ranges of `BlockBodyIndices` are contiguous, and that they match
+/// `Transactions` and `TransactionBlock`.
+pub(crate) fn check_block_bodies<DB: Database>(
+    provider: &DatabaseProviderRO<DB>,
+) -> eyre::Result<CheckReport> {
+    let tx = provider.tx_ref();
+    let mut report = CheckReport::default();
+
+    let lowest_tx = lowest_unpruned_tx(provider, PruneSegment::Transactions)?;
+    let mut transactions = tx.cursor_read::<tables::Transactions>()?;
+    let mut transaction_blocks = tx.cursor_read::<tables::TransactionBlock>()?;
+
+    let mut previous_block: Option<BlockNumber> = None;
+    let mut next_tx_num = 0;
+    for entry in tx.cursor_read::<tables::BlockBodyIndices>()?.walk(None)? {
+        let (number, body) = entry?;
+
+        if let Some(previous) = previous_block.filter(|previous| previous + 1 != number) {
+            report
+                .push(|| format!("body indices of blocks {}..{number} are missing", previous + 1));
+        }
+        if body.first_tx_num() != next_tx_num {
+            report.push(|| {
+                format!(
+                    "block {number} starts at transaction {}, but the previous block ends before \
+                     transaction {next_tx_num}",
+                    body.first_tx_num()
+                )
+            });
+        }
+
+        if !body.is_empty() {
+            let last_tx_num = body.last_tx_num();
+            if last_tx_num >= lowest_tx && transactions.seek_exact(last_tx_num)?.is_none() {
+                report.push(|| {
+                    format!("last transaction {last_tx_num} of block {number} is missing")
+                });
+            }
+            match transaction_blocks.seek_exact(last_tx_num)? {
+                Some((_, block)) if block == number => {}
+                Some((_, block)) => report.push(|| {
+                    format!("transaction {last_tx_num} of block {number} maps to block {block}")
+                }),
+                None => report.push(|| {
+                    format!(
+                        "TransactionBlock is missing transaction {last_tx_num} of block {number}"
+                    )
+                }),
+            }
+        }
+
+        next_tx_num = body.next_tx_num();
+        previous_block = Some(number);
+    }
+
+    if let Some((last_tx_num, _)) = transactions.last()? {
+        if last_tx_num >= next_tx_num {
+            report.push(|| format!("transaction {last_tx_num} isn't part of any block"));
+        }
+    }
+    if let Some((last_tx_num, _)) = transaction_blocks.last()? {
+        if last_tx_num >= next_tx_num {
+            report.push(|| {
+                format!("TransactionBlock maps transaction {last_tx_num} beyond the last block")
+            });
+        }
+    }
+
+    let expected_transactions = next_tx_num.saturating_sub(lowest_tx) as usize;
+    let transactions = tx.entries::<tables::Transactions>()?;
+    if transactions != expected_transactions {
+        report.push(|| {
+            format!(
+                "Transactions has {transactions} entries, but the block bodies reference \
+                 {expected_transactions}"
+            )
+        });
+    }
+
+    Ok(report)
+}
+
+/// Rebuilds `TransactionBlock` from `BlockBodyIndices`.
+pub(crate) fn repair_transaction_blocks<DB: Database>(
+    factory: &ProviderFactory<DB>,
+) -> eyre::Result<()> {
+    repair_in_batches(
+        factory,
+        Check::BlockBodies,
+        |provider| Ok(provider.tx_ref().clear::<tables::TransactionBlock>()?),
+        |provider, start: Option<BlockNumber>| {
+            let tx = provider.tx_ref();
+            let mut transaction_blocks = tx.cursor_write::<tables::TransactionBlock>()?;
+            for (i, entry) in tx.cursor_read::<tables::BlockBodyIndices>()?.walk(start)?.enumerate()
+            {
+                let (number, body) = entry?;
+                if i as u64 == REPAIR_BATCH_SIZE {
+                    return Ok(Some(number))
+                }
+                if !body.is_empty() {
+                    transaction_blocks.append(body.last_tx_num(), number)?;
+                }
+            }
+            Ok(None)
+        },
+    )
+}
+
+/// Checks that `CanonicalHeaders` is contiguous, that `Headers` holds the headers with the
+/// canonical hashes, and that `HeaderNumbers` is its exact inverse.
+pub(crate) fn check_header_numbers<DB: Database>(
+    provider: &DatabaseProviderRO<DB>,
+) -> eyre::Result<CheckReport> {
+    let tx = provider.tx_ref();
+    let mut report = CheckReport::default();
+
+    let mut headers = tx.cursor_read::<tables::Headers>()?;
+    let mut header_numbers = tx.cursor_read::<tables::HeaderNumbers>()?;
+
+    let mut previous_block: Option<BlockNumber> = None;
+    for entry in tx.cursor_read::<tables::CanonicalHeaders>()?.walk(None)? {
+        let (number, hash) = entry?;
+
+        if let Some(previous) = previous_block.filter(|previous| previous + 1 != number) {
+            report.push(|| {
+                format!("canonical hashes of blocks {}..{number} are missing", previous + 1)
+            });
+        }
+        match headers.seek_exact(number)? {
+            Some((_, header)) if header.hash_slow() == hash => {}
+            Some(_) => report.push(|| format!("header of block {number} doesn't hash to {hash}")),
+            None => report.push(|| format!("header of block {number} is missing")),
+        }
+        match header_numbers.seek_exact(hash)? {
+            Some((_, hash_number)) if hash_number == number => {}
+            Some((_, hash_number)) => report.push(|| {
+                format!("HeaderNumbers maps {hash} of block {number} to block {hash_number}")
+            }),
+            None => report.push(|| format!("HeaderNumbers is missing {hash} of block {number}")),
+        }
+
+        previous_block = Some(number);
+    }
+
+    let mut canonical_headers = tx.cursor_read::<tables::CanonicalHeaders>()?;
+    for entry in header_numbers.walk(None)? {
+        let (hash, number) = entry?;
+        match canonical_headers.seek_exact(number)? {
+            Some((_, canonical_hash)) if canonical_hash == hash => {}
+            _ => {
+                report.push(|| format!("HeaderNumbers maps non-canonical {hash} to block {number}"))
+            }
+        }
+    }
+
+    Ok(report)
+}
+
+/// Rebuilds `HeaderNumbers` from `CanonicalHeaders`.
+pub(crate) fn repair_header_numbers<DB: Database>(
+    factory: &ProviderFactory<DB>,
+) -> eyre::Result<()> {
+    repair_in_batches(
+        factory,
+        Check::HeaderNumbers,
+        |provider| Ok(provider.tx_ref().clear::<tables::HeaderNumbers>()?),
+        |provider, start: Option<BlockNumber>| {
+            let tx = provider.tx_ref();
+            for (i, entry) in tx.cursor_read::<tables::CanonicalHeaders>()?.walk(start)?.enumerate()
+            {
+                let (number, hash) = entry?;
+                if i as u64 == REPAIR_BATCH_SIZE {
+                    return Ok(Some(number))
+                }
+                tx.put::<tables::HeaderNumbers>(hash, number)?;
+            }
+            Ok(None)
+        },
+    )
+}
+
+/// Returns the transactions that are expected to be indexed in `TxHashNumber`, which are the
+/// unpruned transactions of all blocks up to the `TransactionLookup` stage checkpoint.
+///
+/// Returns [None] if the body indices of the checkpoint block are missing.
+fn transaction_lookup_range<Provider>(provider: &Provider) -> eyre::Result<Option<Range<TxNumber>>>
+where
+    Provider: BlockReader + StageCheckpointReader + PruneCheckpointReader,
+{
+    let checkpoint =
+        provider.get_stage_checkpoint(StageId::TransactionLookup)?.unwrap_or_default().block_number;
+    let Some(body) = provider.block_body_indices(checkpoint)? else { return Ok(None) };
+
+    let lowest_tx = lowest_unpruned_tx(provider, PruneSegment::TransactionLookup)?
+        .max(lowest_unpruned_tx(provider, PruneSegment::Transactions)?);
+    Ok(Some(lowest_tx..body.next_tx_num().max(lowest_tx)))
+}
+
+/// Checks that `TxHashNumber` holds exactly the hashes of the indexed `Transactions`.
+pub(crate) fn check_transaction_lookup<DB: Database>(
+    provider: &DatabaseProviderRO<DB>,
+) -> eyre::Result<CheckReport> {
+    let tx = provider.tx_ref();
+    let mut report = CheckReport::default();
+
+    let Some(range) = transaction_lookup_range(provider)? else {
+        return Ok(CheckReport::skipped("body indices of the stage checkpoint block are missing"))
+    };
+
+    let mut tx_hash_numbers = tx.cursor_read::<tables::TxHashNumber>()?;
+    for entry in tx.cursor_read::<tables::Transactions>()?.walk_range(range.clone())? {
+        let (tx_num, transaction) = entry?;
+        let hash = transaction.hash();
+        match tx_hash_numbers.seek_exact(hash)? {
+            Some((_, hash_tx_num)) if hash_tx_num == tx_num => {}
+            Some((_, hash_tx_num)) => report.push(|| {
+                format!("TxHashNumber maps {hash} of transaction {tx_num} to {hash_tx_num}")
+            }),
+            None => {
+                report.push(|| format!("TxHashNumber is missing {hash} of transaction {tx_num}"))
+            }
+        }
+    }
+
+    let mut transactions = tx.cursor_read::<tables::Transactions>()?;
+    for entry in tx_hash_numbers.walk(None)? {
+        let (hash, tx_num) = entry?;
+        if !range.contains(&tx_num) {
+            report.push(|| {
+                format!("TxHashNumber maps {hash} to transaction {tx_num}, which isn't indexed")
+            });
+            continue
+        }
+        match transactions.seek_exact(tx_num)? {
+            Some((_, transaction)) if transaction.hash() == hash => {}
+            _ => report.push(|| {
+                format!("TxHashNumber maps {hash} to transaction {tx_num}, which has another hash")
+            }),
+        }
+    }
+
+    Ok(report)
+}
+
+/// Rebuilds `TxHashNumber` from `Transactions`.
+pub(crate) fn repair_transaction_lookup<DB: Database>(
+    factory: &ProviderFactory<DB>,
+) -> eyre::Result<()> {
+    repair_in_batches(
+        factory,
+        Check::TransactionLookup,
+        |provider| Ok(provider.tx_ref().clear::<tables::TxHashNumber>()?),
+        |provider, start: Option<TxNumber>| {
+            let Some(range) = transaction_lookup_range(&**provider)? else { return Ok(None) };
+
+            let start = start.unwrap_or(range.start);
+            if start >= range.end {
+                return Ok(None)
+            }
+            let end = start.saturating_add(REPAIR_BATCH_SIZE).min(range.end);
+            for (hash, tx_num) in provider.transaction_hashes_by_range(start..end)? {
+                provider.tx_ref().put::<tables::TxHashNumber>(hash, tx_num)?;
+            }
+            Ok((end < range.end).then_some(end))
+        },
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use reth_interfaces::test_utils::{generators, generators::random_block_range};
+    use reth_primitives::{stage::StageCheckpoint, B256};
+    use reth_provider::StageCheckpointWriter;
+    use reth_stages::test_utils::TestStageDB;
+
+    #[test]
+    fn block_bodies() {
+        let mut rng = generators::rng();
+        let db = TestStageDB::default();
+        let blocks = random_block_range(&mut rng, 0..=3, B256::ZERO, 1..3);
+        db.insert_blocks(blocks.iter(), None).unwrap();
+        assert!(check_block_bodies(&db.factory.provider().unwrap()).unwrap().is_consistent());
+
+        // The last transaction maps to the genesis block
+        let last_tx_num = blocks.iter().map(|block| block.body.len() as u64).sum::<u64>() - 1;
+        db.commit(|tx| Ok(tx.put::<tables::TransactionBlock>(last_tx_num, 0)?)).unwrap();
+        assert!(!check_block_bodies(&db.factory.provider().unwrap()).unwrap().is_consistent());
+
+        repair_transaction_blocks(&db.factory).unwrap();
+        assert!(check_block_bodies(&db.factory.provider().unwrap()).unwrap().is_consistent());
+        assert!(db.table_is_empty::<tables::SyncStageProgress>().unwrap());
+    }
+
+    #[test]
+    fn header_numbers() {
+        let mut rng = generators::rng();
+        let db = TestStageDB::default();
+        let blocks = random_block_range(&mut rng, 0..=3, B256::ZERO, 0..1);
+        db.insert_headers(blocks.iter().map(|block| &block.header)).unwrap();
+        assert!(check_header_numbers(&db.factory.provider().unwrap()).unwrap().is_consistent());
+
+        db.commit(|tx| {
+            tx.delete::<tables::HeaderNumbers>(blocks[2].hash(), None)?;
+            Ok(tx.put::<tables::HeaderNumbers>(B256::random(), 1)?)
+        })
+        .unwrap();
+        let report = check_header_numbers(&db.factory.provider().unwrap()).unwrap();
+        assert_eq!(report.total, 2);
+
+        repair_header_numbers(&db.factory).unwrap();
+        assert!(check_header_numbers(&db.factory.provider().unwrap()).unwrap().is_consistent());
+    }
+
+    #[test]
+    fn transaction_lookup() {
+        let mut rng = generators::rng();
+        let db = TestStageDB::default();
+        let blocks = random_block_range(&mut rng, 0..=3, B256::ZERO, 1..3);
+        db.insert_blocks(blocks.iter(), None).unwrap();
+        db.insert_tx_hash_numbers(
+            blocks
+                .iter()
+                .flat_map(|block| block.body.iter())
+                .enumerate()
+                .map(|(tx_num, transaction)| (transaction.hash(), tx_num as TxNumber)),
+        )
+        .unwrap();
+        let provider_rw = db.factory.provider_rw().unwrap();
+        provider_rw
+            .save_stage_checkpoint(StageId::TransactionLookup, StageCheckpoint::new(3))
+            .unwrap();
+        provider_rw.commit().unwrap();
+        assert!(check_transaction_lookup(&db.factory.provider().unwrap()).unwrap().is_consistent());
+
+        // A transaction of block 1 maps to the first transaction of the genesis block
+        db.commit(|tx| Ok(tx.put::<tables::TxHashNumber>(blocks[1].body[0].hash(), 0)?)).unwrap();
+        assert!(!check_transaction_lookup(&db.factory.provider().unwrap())
+            .unwrap()
+            .is_consistent());
+
+        repair_transaction_lookup(&db.factory).unwrap();
+        assert!(check_transaction_lookup(&db.factory.provider().unwrap()).unwrap().is_consistent());
+    }
+}
@@ -0,0 +1,152 @@
+use super::{lowest_unpruned_tx, CheckReport};
+use reth_db::{
+    cursor::DbCursorRO, database::Database, models::BlockNumberAddress, tables, transaction::DbTx,
+};
+use reth_primitives::{stage::StageId, BlockNumber, PruneSegment};
+use reth_provider::{BlockReader, DatabaseProviderRO, StageCheckpointReader};
+
+/// Pairs of stages where the first stage can't be ahead of the second one, because it processes
+/// the output of the second one.
+const STAGE_DEPENDENCIES: &[(StageId, StageId)] = &[
+    (StageId::TotalDifficulty, StageId::Headers),
+    (StageId::Bodies, StageId::Headers),
+    (StageId::SenderRecovery, StageId::Bodies),
+    (StageId::Execution, StageId::SenderRecovery),
+    (StageId::AccountHashing, StageId::Execution),
+    (StageId::StorageHashing, StageId::Execution),
+    (StageId::MerkleExecute, StageId::AccountHashing),
+    (StageId::MerkleExecute, StageId::StorageHashing),
+    (StageId::TransactionLookup, StageId::Bodies),
+    (StageId::IndexAccountHistory, StageId::Execution),
+    (StageId::IndexStorageHistory, StageId::Execution),
+];
+
+/// Checks that the stage checkpoints are ordered by the dependencies between the stages, and that
+/// they match the contents of the tables written by the stages.
+pub(crate) fn check_stage_checkpoints<DB: Database>(
+    provider: &DatabaseProviderRO<DB>,
+) -> eyre::Result<CheckReport> {
+    let tx = provider.tx_ref();
+    let mut report = CheckReport::default();
+
+    let checkpoint = |stage: StageId| -> eyre::Result<BlockNumber> {
+        Ok(provider.get_stage_checkpoint(stage)?.unwrap_or_default().block_number)
+    };
+
+    for (stage, dependency) in STAGE_DEPENDENCIES {
+        let (block, dependency_block) = (checkpoint(*stage)?, checkpoint(*dependency)?);
+        if block > dependency_block {
+            report.push(|| {
+                format!("{stage} is at block {block}, ahead of {dependency} at {dependency_block}")
+            });
+        }
+    }
+    let finish = checkpoint(StageId::Finish)?;
+    for stage in StageId::ALL.iter().filter(|stage| **stage != StageId::MerkleUnwind) {
+        let block = checkpoint(*stage)?;
+        if block < finish {
+            report.push(|| format!("{stage} is at block {block}, behind Finish at {finish}"));
+        }
+    }
+
+    let headers = checkpoint(StageId::Headers)?;
+    if tx.get::<tables::CanonicalHeaders>(headers)?.is_none() {
+        report.push(|| format!("canonical hash of checkpoint block {headers} is missing"));
+    }
+    let total_difficulty = checkpoint(StageId::TotalDifficulty)?;
+    if tx.get::<tables::HeaderTD>(total_difficulty)?.is_none() {
+        report
+            .push(|| format!("total difficulty of checkpoint block {total_difficulty} is missing"));
+    }
+
+    let bodies = checkpoint(StageId::Bodies)?;
+    let last_body = tx.cursor_read::<tables::BlockBodyIndices>()?.last()?.map(|(number, _)| number);
+    if last_body != Some(bodies) {
+        report.push(|| format!("last body indices are of block {last_body:?}, not {bodies}"));
+    }
+
+    let execution = checkpoint(StageId::Execution)?;
+    if let Some((block, _)) = tx.cursor_read::<tables::AccountChangeSet>()?.last()? {
+        if block > execution {
+            report.push(|| format!("account changeset of block {block} is past {execution}"));
+        }
+    }
+    if let Some((BlockNumberAddress((block, _)), _)) =
+        tx.cursor_read::<tables::StorageChangeSet>()?.last()?
+    {
+        if block > execution {
+            report.push(|| format!("storage changeset of block {block} is past {execution}"));
+        }
+    }
+
+    // Receipts aren't checked for completeness, because contract log pruning removes them
+    // selectively.
+    if let Some(body) = provider.block_body_indices(execution)? {
+        if let Some((tx_num, _)) = tx.cursor_read::<tables::Receipts>()?.last()? {
+            if tx_num >= body.next_tx_num() {
+                report
+                    .push(|| format!("receipt of transaction {tx_num} is past block {execution}"));
+            }
+        }
+    }
+
+    let sender_recovery = checkpoint(StageId::SenderRecovery)?;
+    if let Some(body) = provider.block_body_indices(sender_recovery)? {
+        let last_sender = tx.cursor_read::<tables::TxSenders>()?.last()?.map(|(tx_num, _)| tx_num);
+        let lowest_tx = lowest_unpruned_tx(provider, PruneSegment::SenderRecovery)?;
+        let expected = (body.next_tx_num() > lowest_tx).then(|| body.next_tx_num() - 1);
+        if last_sender.is_some_and(|tx_num| tx_num >= body.next_tx_num()) ||
+            (expected.is_some() && last_sender != expected)
+        {
+            report.push(|| {
+                format!(
+                    "last sender is of transaction {last_sender:?}, but SenderRecovery at \
+                     {sender_recovery} ends at transaction {expected:?}"
+                )
+            });
+        }
+    }
+
+    Ok(report)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::commands::db::check::Check;
+    use reth_interfaces::test_utils::{generators, generators::random_block_range};
+    use reth_primitives::{stage::StageCheckpoint, B256};
+    use reth_provider::StageCheckpointWriter;
+    use reth_stages::test_utils::TestStageDB;
+
+    #[test]
+    fn stage_checkpoints() {
+        let mut rng = generators::rng();
+        let db = TestStageDB::default();
+        let blocks = random_block_range(&mut rng, 0..=3, B256::ZERO, 0..1);
+        db.insert_headers_with_td(blocks.iter().map(|block| &block.header)).unwrap();
+        db.insert_blocks(blocks.iter(), None).unwrap();
+
+        let save_checkpoints = |checkpoints: &[(StageId, BlockNumber)]| {
+            let provider_rw = db.factory.provider_rw().unwrap();
+            for (stage, block) in checkpoints {
+                provider_rw.save_stage_checkpoint(*stage, StageCheckpoint::new(*block)).unwrap();
+            }
+            provider_rw.commit().unwrap();
+        };
+        save_checkpoints(&[
+            (StageId::Headers, 3),
+            (StageId::TotalDifficulty, 3),
+            (StageId::Bodies, 3),
+        ]);
+        assert!(check_stage_checkpoints(&db.factory.provider().unwrap()).unwrap().is_consistent());
+
+        // Bodies is ahead of Headers, and past the last body
+        save_checkpoints(&[(StageId::Bodies, 4)]);
+        let report = check_stage_checkpoints(&db.factory.provider().unwrap()).unwrap();
+        assert_eq!(report.total, 2);
+
+        // Checkpoints aren't derived from other tables
+        assert!(!Check::StageCheckpoints.repair(&db.factory).unwrap());
+    }
+}
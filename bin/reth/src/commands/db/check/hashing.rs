@@ -0,0 +1,205 @@
+use super::{repair_in_batches, Check, CheckReport, REPAIR_BATCH_SIZE};
+use reth_db::{
+    cursor::{DbCursorRO, DbDupCursorRO},
+    database::Database,
+    tables,
+    transaction::{DbTx, DbTxMut},
+};
+use reth_primitives::{keccak256, stage::StageId, Address, StorageEntry};
+use reth_provider::{DatabaseProviderRO, ProviderFactory, StageCheckpointReader};
+
+/// Checks that `HashedAccount` holds exactly the accounts of `PlainAccountState` under their hashed
+/// addresses.
+pub(crate) fn check_hashed_accounts<DB: Database>(
+    provider: &DatabaseProviderRO<DB>,
+) -> eyre::Result<CheckReport> {
+    if let Some(reason) = hashing_behind_execution(provider, StageId::AccountHashing)? {
+        return Ok(CheckReport::skipped(reason))
+    }
+
+    let tx = provider.tx_ref();
+    let mut report = CheckReport::default();
+
+    let mut hashed_accounts = tx.cursor_read::<tables::HashedAccount>()?;
+    for entry in tx.cursor_read::<tables::PlainAccountState>()?.walk(None)? {
+        let (address, account) = entry?;
+        match hashed_accounts.seek_exact(keccak256(address))? {
+            Some((_, hashed_account)) if hashed_account == account => {}
+            Some(_) => report.push(|| format!("hashed account of {address} differs")),
+            None => report.push(|| format!("HashedAccount is missing {address}")),
+        }
+    }
+
+    let plain_accounts = tx.entries::<tables::PlainAccountState>()?;
+    let hashed_accounts = tx.entries::<tables::HashedAccount>()?;
+    if plain_accounts != hashed_accounts {
+        report.push(|| {
+            format!(
+                "HashedAccount has {hashed_accounts} entries, but PlainAccountState has \
+                 {plain_accounts}"
+            )
+        });
+    }
+
+    Ok(report)
+}
+
+/// Rebuilds `HashedAccount` from `PlainAccountState`.
+pub(crate) fn repair_hashed_accounts<DB: Database>(
+    factory: &ProviderFactory<DB>,
+) -> eyre::Result<()> {
+    repair_in_batches(
+        factory,
+        Check::HashedAccounts,
+        |provider| Ok(provider.tx_ref().clear::<tables::HashedAccount>()?),
+        |provider, start: Option<Address>| {
+            let tx = provider.tx_ref();
+            for (i, entry) in
+                tx.cursor_read::<tables::PlainAccountState>()?.walk(start)?.enumerate()
+            {
+                let (address, account) = entry?;
+                if i as u64 == REPAIR_BATCH_SIZE {
+                    return Ok(Some(address))
+                }
+                tx.put::<tables::HashedAccount>(keccak256(address), account)?;
+            }
+            Ok(None)
+        },
+    )
+}
+
+/// Checks that `HashedStorage` holds exactly the storage of `PlainStorageState` under hashed
+/// addresses and slots.
+pub(crate) fn check_hashed_storages<DB: Database>(
+    provider: &DatabaseProviderRO<DB>,
+) -> eyre::Result<CheckReport> {
+    if let Some(reason) = hashing_behind_execution(provider, StageId::StorageHashing)? {
+        return Ok(CheckReport::skipped(reason))
+    }
+
+    let tx = provider.tx_ref();
+    let mut report = CheckReport::default();
+
+    let mut hashed_storages = tx.cursor_dup_read::<tables::HashedStorage>()?;
+    for entry in tx.cursor_read::<tables::PlainStorageState>()?.walk(None)? {
+        let (address, StorageEntry { key: slot, value }) = entry?;
+        let hashed_slot = keccak256(slot);
+        match hashed_storages.seek_by_key_subkey(keccak256(address), hashed_slot)? {
+            Some(hashed) if hashed.key == hashed_slot && hashed.value == value => {}
+            Some(hashed) if hashed.key == hashed_slot => {
+                report.push(|| format!("hashed value of slot {slot} of {address} differs"))
+            }
+            _ => report.push(|| format!("HashedStorage is missing slot {slot} of {address}")),
+        }
+    }
+
+    let plain_storages = tx.entries::<tables::PlainStorageState>()?;
+    let hashed_storages = tx.entries::<tables::HashedStorage>()?;
+    if plain_storages != hashed_storages {
+        report.push(|| {
+            format!(
+                "HashedStorage has {hashed_storages} entries, but PlainStorageState has \
+                 {plain_storages}"
+            )
+        });
+    }
+
+    Ok(report)
+}
+
+/// Rebuilds `HashedStorage` from `PlainStorageState`.
+///
+/// Batches only end between accounts, so that the storage of an account is rebuilt at once.
+pub(crate) fn repair_hashed_storages<DB: Database>(
+    factory: &ProviderFactory<DB>,
+) -> eyre::Result<()> {
+    repair_in_batches(
+        factory,
+        Check::HashedStorages,
+        |provider| Ok(provider.tx_ref().clear::<tables::HashedStorage>()?),
+        |provider, start: Option<Address>| {
+            let tx = provider.tx_ref();
+            let mut previous_address = None;
+            for (i, entry) in
+                tx.cursor_read::<tables::PlainStorageState>()?.walk(start)?.enumerate()
+            {
+                let (address, StorageEntry { key, value }) = entry?;
+                if i as u64 >= REPAIR_BATCH_SIZE && previous_address != Some(address) {
+                    return Ok(Some(address))
+                }
+                tx.put::<tables::HashedStorage>(
+                    keccak256(address),
+                    StorageEntry { key: keccak256(key), value },
+                )?;
+                previous_address = Some(address);
+            }
+            Ok(None)
+        },
+    )
+}
+
+/// Returns why the hashed state can't be compared with the plain state, which is only possible
+/// when the hashing stage has caught up with the execution stage.
+fn hashing_behind_execution(
+    provider: &impl StageCheckpointReader,
+    stage: StageId,
+) -> eyre::Result<Option<String>> {
+    let execution =
+        provider.get_stage_checkpoint(StageId::Execution)?.unwrap_or_default().block_number;
+    let hashing = provider.get_stage_checkpoint(stage)?.unwrap_or_default().block_number;
+    Ok((hashing != execution)
+        .then(|| format!("{stage} is at block {hashing}, but Execution is at {execution}")))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use reth_interfaces::test_utils::{generators, generators::random_eoa_account_range};
+    use reth_primitives::{B256, U256};
+    use reth_stages::test_utils::TestStageDB;
+
+    #[test]
+    fn hashed_accounts() {
+        let mut rng = generators::rng();
+        let db = TestStageDB::default();
+        let accounts = random_eoa_account_range(&mut rng, 0..3);
+        db.insert_accounts_and_storages(
+            accounts.iter().map(|(address, account)| (*address, (*account, Vec::new()))),
+        )
+        .unwrap();
+        assert!(check_hashed_accounts(&db.factory.provider().unwrap()).unwrap().is_consistent());
+
+        db.commit(|tx| {
+            tx.delete::<tables::HashedAccount>(keccak256(accounts[0].0), None)?;
+            Ok(())
+        })
+        .unwrap();
+        assert!(!check_hashed_accounts(&db.factory.provider().unwrap()).unwrap().is_consistent());
+
+        repair_hashed_accounts(&db.factory).unwrap();
+        assert!(check_hashed_accounts(&db.factory.provider().unwrap()).unwrap().is_consistent());
+    }
+
+    #[test]
+    fn hashed_storages() {
+        let mut rng = generators::rng();
+        let db = TestStageDB::default();
+        let accounts = random_eoa_account_range(&mut rng, 0..3);
+        db.insert_accounts_and_storages(accounts.iter().map(|(address, account)| {
+            let slot = StorageEntry { key: B256::with_last_byte(1), value: U256::from(1) };
+            (*address, (*account, vec![slot]))
+        }))
+        .unwrap();
+        assert!(check_hashed_storages(&db.factory.provider().unwrap()).unwrap().is_consistent());
+
+        db.commit(|tx| {
+            tx.delete::<tables::HashedStorage>(keccak256(accounts[0].0), None)?;
+            Ok(())
+        })
+        .unwrap();
+        assert!(!check_hashed_storages(&db.factory.provider().unwrap()).unwrap().is_consistent());
+
+        repair_hashed_storages(&db.factory).unwrap();
+        assert!(check_hashed_storages(&db.factory.provider().unwrap()).unwrap().is_consistent());
+    }
+}
@@ -0,0 +1,437 @@
+use super::{pruned_block, repair_in_batches, Check, CheckReport, REPAIR_BATCH_SIZE};
+use eyre::WrapErr;
+use reth_db::{
+    cursor::{DbCursorRO, DbDupCursorRO},
+    database::Database,
+    models::{
+        storage_sharded_key::StorageShardedKey, AccountBeforeTx, BlockNumberAddress, ShardedKey,
+    },
+    tables,
+    transaction::{DbTx, DbTxMut},
+    BlockNumberList,
+};
+use reth_primitives::{stage::StageId, BlockNumber, PruneSegment};
+use reth_provider::{
+    AccountExtReader, DatabaseProviderRO, HistoryWriter, ProviderFactory, PruneCheckpointReader,
+    StageCheckpointReader, StorageReader,
+};
+use std::ops::RangeInclusive;
+
+/// Checks that `AccountHistory` indexes exactly the blocks of `AccountChangeSet`.
+///
+/// Blocks whose changesets have been pruned after snapshotting aren't checked against the
+/// changesets.
+pub(crate) fn check_account_history<DB: Database>(
+    provider: &DatabaseProviderRO<DB>,
+) -> eyre::Result<CheckReport> {
+    let tx = provider.tx_ref();
+    let mut report = CheckReport::default();
+    let indexed =
+        indexed_blocks(provider, StageId::IndexAccountHistory, PruneSegment::AccountHistory)?;
+    let unpruned = unpruned_changesets(provider, &indexed, PruneSegment::AccountChangeSets)?;
+
+    let mut history = tx.cursor_read::<tables::AccountHistory>()?;
+    for entry in tx.cursor_read::<tables::AccountChangeSet>()?.walk_range(unpruned.clone())? {
+        let (block, AccountBeforeTx { address, .. }) = entry?;
+        let shard = history.seek(ShardedKey::new(address, block))?;
+        if !shard.is_some_and(|(key, list)| key.key == address && contains(list, block)) {
+            report.push(|| format!("AccountHistory is missing block {block} of account {address}"));
+        }
+    }
+
+    let mut changesets = tx.cursor_dup_read::<tables::AccountChangeSet>()?;
+    for entry in history.walk(None)? {
+        let (key, list) = entry?;
+        let address = key.key;
+        check_shard(
+            &mut report,
+            || format!("account {address}"),
+            key.highest_block_number,
+            &list,
+            &unpruned,
+            |block| {
+                Ok(changesets
+                    .seek_by_key_subkey(block, address)?
+                    .is_some_and(|changeset| changeset.address == address))
+            },
+        )?;
+    }
+
+    Ok(report)
+}
+
+/// Rebuilds `AccountHistory` from `AccountChangeSet` and the account changeset snapshots.
+pub(crate) fn repair_account_history<DB: Database>(
+    factory: &ProviderFactory<DB>,
+) -> eyre::Result<()> {
+    repair_in_batches(
+        factory,
+        Check::AccountHistory,
+        |provider| {
+            ensure_snapshotted(
+                &**provider,
+                StageId::IndexAccountHistory,
+                PruneSegment::AccountHistory,
+                PruneSegment::AccountChangeSets,
+                |block| {
+                    Ok(provider.changed_accounts_and_blocks_with_range(block..=block).map(drop)?)
+                },
+            )?;
+            Ok(provider.tx_ref().clear::<tables::AccountHistory>()?)
+        },
+        |provider, start| {
+            let indexed = indexed_blocks(
+                &**provider,
+                StageId::IndexAccountHistory,
+                PruneSegment::AccountHistory,
+            )?;
+            let Some((range, next)) = batch(indexed, start) else { return Ok(None) };
+            let indices = provider.changed_accounts_and_blocks_with_range(range)?;
+            provider.insert_account_history_index(indices)?;
+            Ok(next)
+        },
+    )
+}
+
+/// Checks that `StorageHistory` indexes exactly the blocks of `StorageChangeSet`.
+///
+/// Blocks whose changesets have been pruned after snapshotting aren't checked against the
+/// changesets.
+pub(crate) fn check_storage_history<DB: Database>(
+    provider: &DatabaseProviderRO<DB>,
+) -> eyre::Result<CheckReport> {
+    let tx = provider.tx_ref();
+    let mut report = CheckReport::default();
+    let indexed =
+        indexed_blocks(provider, StageId::IndexStorageHistory, PruneSegment::StorageHistory)?;
+    let unpruned = unpruned_changesets(provider, &indexed, PruneSegment::StorageChangeSets)?;
+
+    let mut history = tx.cursor_read::<tables::StorageHistory>()?;
+    let changesets = BlockNumberAddress::range(unpruned.clone());
+    for entry in tx.cursor_read::<tables::StorageChangeSet>()?.walk_range(changesets)? {
+        let (BlockNumberAddress((block, address)), changeset) = entry?;
+        let slot = changeset.key;
+        let shard = history.seek(StorageShardedKey::new(address, slot, block))?;
+        if !shard.is_some_and(|(key, list)| {
+            key.address == address && key.sharded_key.key == slot && contains(list, block)
+        }) {
+            report.push(|| {
+                format!("StorageHistory is missing block {block} of slot {slot} of {address}")
+            });
+        }
+    }
+
+    let mut changesets = tx.cursor_dup_read::<tables::StorageChangeSet>()?;
+    for entry in history.walk(None)? {
+        let (key, list) = entry?;
+        let (address, slot) = (key.address, key.sharded_key.key);
+        check_shard(
+            &mut report,
+            || format!("slot {slot} of account {address}"),
+            key.sharded_key.highest_block_number,
+            &list,
+            &unpruned,
+            |block| {
+                Ok(changesets
+                    .seek_by_key_subkey(BlockNumberAddress((block, address)), slot)?
+                    .is_some_and(|changeset| changeset.key == slot))
+            },
+        )?;
+    }
+
+    Ok(report)
+}
+
+/// Rebuilds `StorageHistory` from `StorageChangeSet` and the storage changeset snapshots.
+pub(crate) fn repair_storage_history<DB: Database>(
+    factory: &ProviderFactory<DB>,
+) -> eyre::Result<()> {
+    repair_in_batches(
+        factory,
+        Check::StorageHistory,
+        |provider| {
+            ensure_snapshotted(
+                &**provider,
+                StageId::IndexStorageHistory,
+                PruneSegment::StorageHistory,
+                PruneSegment::StorageChangeSets,
+                |block| {
+                    Ok(provider.changed_storages_and_blocks_with_range(block..=block).map(drop)?)
+                },
+            )?;
+            Ok(provider.tx_ref().clear::<tables::StorageHistory>()?)
+        },
+        |provider, start| {
+            let indexed = indexed_blocks(
+                &**provider,
+                StageId::IndexStorageHistory,
+                PruneSegment::StorageHistory,
+            )?;
+            let Some((range, next)) = batch(indexed, start) else { return Ok(None) };
+            let indices = provider.changed_storages_and_blocks_with_range(range)?;
+            provider.insert_storage_history_index(indices)?;
+            Ok(next)
+        },
+    )
+}
+
+/// Returns the blocks whose changesets are expected to be indexed, which are the unpruned blocks
+/// up to the checkpoint of the indexing stage.
+fn indexed_blocks(
+    provider: &(impl StageCheckpointReader + PruneCheckpointReader),
+    stage: StageId,
+    segment: PruneSegment,
+) -> eyre::Result<RangeInclusive<BlockNumber>> {
+    let checkpoint = provider.get_stage_checkpoint(stage)?.unwrap_or_default().block_number;
+    let start = pruned_block(provider, segment)?.map_or(0, |block| block + 1);
+    Ok(start..=checkpoint)
+}
+
+/// Returns the indexed blocks whose changesets haven't been pruned from the database by the
+/// changeset segment, which are the blocks that can be checked against the changesets.
+fn unpruned_changesets(
+    provider: &impl PruneCheckpointReader,
+    indexed: &RangeInclusive<BlockNumber>,
+    segment: PruneSegment,
+) -> eyre::Result<RangeInclusive<BlockNumber>> {
+    let start = pruned_block(provider, segment)?.map_or(0, |block| block + 1);
+    Ok(start.max(*indexed.start())..=*indexed.end())
+}
+
+/// Fails if indexed blocks have pruned changesets that can't be read from the snapshots either,
+/// since clearing the history would lose their indices for good.
+///
+/// `read_changesets` reads the changesets of a block, and fails if they're unavailable.
+fn ensure_snapshotted(
+    provider: &(impl StageCheckpointReader + PruneCheckpointReader),
+    stage: StageId,
+    history_segment: PruneSegment,
+    changeset_segment: PruneSegment,
+    read_changesets: impl FnOnce(BlockNumber) -> eyre::Result<()>,
+) -> eyre::Result<()> {
+    let indexed = indexed_blocks(provider, stage, history_segment)?;
+    let unpruned = unpruned_changesets(provider, &indexed, changeset_segment)?;
+    if unpruned.start() > indexed.start() {
+        // Snapshots start at the genesis block, so the highest pruned block is the last one that
+        // has to be snapshotted.
+        read_changesets(unpruned.start() - 1).wrap_err(
+            "the history can't be rebuilt, changesets were pruned without being snapshotted",
+        )?;
+    }
+    Ok(())
+}
+
+/// Returns the batch of at most [REPAIR_BATCH_SIZE] indexed blocks that starts at the given block,
+/// or at the first indexed block, together with the block that the next batch starts at.
+///
+/// Returns [None] if there are no indexed blocks left.
+fn batch(
+    indexed: RangeInclusive<BlockNumber>,
+    start: Option<BlockNumber>,
+) -> Option<(RangeInclusive<BlockNumber>, Option<BlockNumber>)> {
+    let start = start.unwrap_or(*indexed.start());
+    let end = *indexed.end();
+    if start > end {
+        return None
+    }
+
+    let batch_end = start.saturating_add(REPAIR_BATCH_SIZE - 1).min(end);
+    Some((start..=batch_end, (batch_end < end).then(|| batch_end + 1)))
+}
+
+/// Returns `true` if the history shard contains the block.
+fn contains(list: BlockNumberList, block: BlockNumber) -> bool {
+    let list = list.0.enable_rank();
+    // Number of blocks in the shard that are lower than the block.
+    let rank = list.rank(block as usize);
+    rank < list.len() && list.select(rank) as u64 == block
+}
+
+/// Checks that the blocks of a history shard are bounded by the shard key, and that every indexed
+/// block with unpruned changesets has a changeset.
+fn check_shard(
+    report: &mut CheckReport,
+    key: impl Fn() -> String,
+    highest_block_number: BlockNumber,
+    list: &BlockNumberList,
+    unpruned: &RangeInclusive<BlockNumber>,
+    mut has_changeset: impl FnMut(BlockNumber) -> eyre::Result<bool>,
+) -> eyre::Result<()> {
+    let mut last_block = None;
+    for block in list.iter(0).map(|block| block as u64) {
+        last_block = Some(block);
+
+        if block > highest_block_number {
+            report.push(|| {
+                format!("shard of {} up to block {highest_block_number} has block {block}", key())
+            });
+        } else if block > *unpruned.end() {
+            report.push(|| format!("{} is indexed at block {block} past the checkpoint", key()));
+        } else if block >= *unpruned.start() && !has_changeset(block)? {
+            report.push(|| format!("{} is indexed at block {block} without a changeset", key()));
+        }
+    }
+
+    // Only the last shard of a key doesn't end at the highest block number of its key.
+    if highest_block_number != u64::MAX && last_block != Some(highest_block_number) {
+        report.push(|| {
+            format!("shard of {} up to block {highest_block_number} ends at {last_block:?}", key())
+        });
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use reth_interfaces::test_utils::{
+        generators,
+        generators::{random_block_range, random_changeset_range, random_eoa_account_range},
+    };
+    use reth_primitives::{stage::StageCheckpoint, PruneCheckpoint, PruneMode, B256};
+    use reth_provider::{PruneCheckpointWriter, StageCheckpointWriter};
+    use reth_snapshot::segments::{self as snapshot_segments, Segment};
+    use reth_stages::test_utils::TestStageDB;
+    use tokio::sync::watch;
+
+    /// Returns a database with indexed account and storage changesets of blocks 0 to 3.
+    fn indexed_db() -> TestStageDB {
+        let mut rng = generators::rng();
+        let db = TestStageDB::default();
+        let accounts = random_eoa_account_range(&mut rng, 0..3);
+        let blocks = random_block_range(&mut rng, 0..=3, B256::ZERO, 0..1);
+        db.insert_blocks(blocks.iter(), None).unwrap();
+        let (changesets, _) = random_changeset_range(
+            &mut rng,
+            blocks.iter(),
+            accounts.into_iter().map(|(address, account)| (address, (account, Vec::new()))),
+            1..2,
+            0..256,
+        );
+        db.insert_changesets(changesets.clone(), None).unwrap();
+        db.insert_history(changesets, None).unwrap();
+
+        let provider_rw = db.factory.provider_rw().unwrap();
+        for stage in [StageId::IndexAccountHistory, StageId::IndexStorageHistory] {
+            provider_rw.save_stage_checkpoint(stage, StageCheckpoint::new(3)).unwrap();
+        }
+        provider_rw.commit().unwrap();
+
+        db
+    }
+
+    #[test]
+    fn account_history() {
+        let db = indexed_db();
+        assert!(check_account_history(&db.factory.provider().unwrap()).unwrap().is_consistent());
+
+        // Index a block that has no changeset of the account
+        db.commit(|tx| {
+            let (key, list) = tx.cursor_read::<tables::AccountHistory>()?.first()?.unwrap();
+            let blocks = list.iter(0).chain([4]).collect::<Vec<_>>();
+            Ok(tx.put::<tables::AccountHistory>(key, BlockNumberList::new_pre_sorted(blocks))?)
+        })
+        .unwrap();
+        assert!(!check_account_history(&db.factory.provider().unwrap()).unwrap().is_consistent());
+
+        repair_account_history(&db.factory).unwrap();
+        assert!(check_account_history(&db.factory.provider().unwrap()).unwrap().is_consistent());
+    }
+
+    #[test]
+    fn storage_history() {
+        let db = indexed_db();
+        assert!(check_storage_history(&db.factory.provider().unwrap()).unwrap().is_consistent());
+
+        db.commit(|tx| {
+            let (key, _) = tx.cursor_read::<tables::StorageHistory>()?.first()?.unwrap();
+            tx.delete::<tables::StorageHistory>(key, None)?;
+            Ok(())
+        })
+        .unwrap();
+        assert!(!check_storage_history(&db.factory.provider().unwrap()).unwrap().is_consistent());
+
+        repair_storage_history(&db.factory).unwrap();
+        assert!(check_storage_history(&db.factory.provider().unwrap()).unwrap().is_consistent());
+    }
+
+    #[test]
+    fn pruned_changesets() {
+        let db = indexed_db();
+        let snapshots = tempfile::tempdir().unwrap();
+        let provider = db.factory.provider().unwrap();
+        snapshot_segments::AccountChangeSets::default()
+            .snapshot(&provider, snapshots.path(), 0..=1)
+            .unwrap();
+        snapshot_segments::StorageChangeSets::default()
+            .snapshot(&provider, snapshots.path(), 0..=1)
+            .unwrap();
+        drop(provider);
+
+        // Prune the changesets of the snapshotted blocks like the changeset prune segments do,
+        // keeping the indices.
+        let provider_rw = db.factory.provider_rw().unwrap();
+        provider_rw
+            .prune_table_with_range::<tables::AccountChangeSet>(
+                0..=1,
+                usize::MAX,
+                |_| false,
+                |_| {},
+            )
+            .unwrap();
+        provider_rw
+            .prune_table_with_range::<tables::StorageChangeSet>(
+                BlockNumberAddress::range(0..=1),
+                usize::MAX,
+                |_| false,
+                |_| {},
+            )
+            .unwrap();
+        for segment in [PruneSegment::AccountChangeSets, PruneSegment::StorageChangeSets] {
+            let checkpoint = PruneCheckpoint {
+                block_number: Some(1),
+                tx_number: None,
+                prune_mode: PruneMode::Before(2),
+            };
+            provider_rw.save_prune_checkpoint(segment, checkpoint).unwrap();
+        }
+        provider_rw.commit().unwrap();
+        let account_history = db.table::<tables::AccountHistory>().unwrap();
+        let storage_history = db.table::<tables::StorageHistory>().unwrap();
+
+        assert!(check_account_history(&db.factory.provider().unwrap()).unwrap().is_consistent());
+        assert!(check_storage_history(&db.factory.provider().unwrap()).unwrap().is_consistent());
+
+        // Without the snapshots, the indices of the pruned blocks can't be rebuilt
+        assert!(repair_account_history(&db.factory).is_err());
+        assert!(repair_storage_history(&db.factory).is_err());
+        assert_eq!(db.table::<tables::AccountHistory>().unwrap(), account_history);
+        assert_eq!(db.table::<tables::StorageHistory>().unwrap(), storage_history);
+
+        let factory = db
+            .factory
+            .clone()
+            .with_snapshots(snapshots.path().to_path_buf(), watch::channel(None).1)
+            .unwrap();
+        repair_account_history(&factory).unwrap();
+        repair_storage_history(&factory).unwrap();
+        assert_eq!(db.table::<tables::AccountHistory>().unwrap(), account_history);
+        assert_eq!(db.table::<tables::StorageHistory>().unwrap(), storage_history);
+    }
+
+    #[test]
+    fn repair_batches() {
+        assert_eq!(batch(0..=5, None), Some((0..=5, None)));
+        assert_eq!(batch(3..=5, Some(5)), Some((5..=5, None)));
+        assert_eq!(batch(4..=3, None), None);
+        assert_eq!(
+            batch(1..=REPAIR_BATCH_SIZE + 5, None),
+            Some((1..=REPAIR_BATCH_SIZE, Some(REPAIR_BATCH_SIZE + 1)))
+        );
+        assert_eq!(
+            batch(1..=REPAIR_BATCH_SIZE + 5, Some(REPAIR_BATCH_SIZE + 1)),
+            Some((REPAIR_BATCH_SIZE + 1..=REPAIR_BATCH_SIZE + 5, None))
+        );
+    }
+}
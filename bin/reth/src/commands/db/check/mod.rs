@@ -0,0 +1,333 @@
+//! Database integrity checker
+
+use clap::{Parser, ValueEnum};
+use rayon::prelude::*;
+use reth_db::{
+    database::Database,
+    table::{Decode, Encode},
+    tables,
+    transaction::DbTxMut,
+};
+use reth_primitives::{stage::StageId, BlockNumber, PruneSegment, TxNumber};
+use reth_provider::{
+    DatabaseProviderRO, DatabaseProviderRW, ProviderFactory, PruneCheckpointReader,
+    StageCheckpointReader, StageCheckpointWriter,
+};
+use std::{fmt, time::Instant};
+use tracing::info;
+
+mod blocks;
+mod checkpoints;
+mod hashing;
+mod history;
+
+/// Maximum number of inconsistencies that are printed for a single check.
+const MAX_REPORTED_INCONSISTENCIES: usize = 20;
+
+/// Number of entries (blocks, transactions or accounts) that are rebuilt in a single committed
+/// transaction when repairing a table.
+const REPAIR_BATCH_SIZE: u64 = 100_000;
+
+/// The arguments for the `reth db check` command
+#[derive(Parser, Debug)]
+pub struct Command {
+    /// The checks to run. If not specified, all checks are run.
+    #[arg(long = "check", value_enum)]
+    checks: Vec<Check>,
+
+    /// Rebuilds tables that can be derived from other tables if their checks fail.
+    ///
+    /// Requires exclusive access to the database, so the node must not be running. Tables are
+    /// rebuilt in batches that are committed one by one, and an interrupted repair resumes where
+    /// it stopped when the command is run again.
+    #[arg(long)]
+    pub repair: bool,
+}
+
+/// A set of invariants that is validated across database tables.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, ValueEnum)]
+pub enum Check {
+    /// `BlockBodyIndices` are contiguous with `Transactions` and `TransactionBlock`.
+    BlockBodies,
+    /// `CanonicalHeaders`, `HeaderNumbers` and `Headers` agree.
+    HeaderNumbers,
+    /// `TxHashNumber` matches the hashes of `Transactions`.
+    TransactionLookup,
+    /// `AccountHistory` shards match `AccountChangeSet`.
+    AccountHistory,
+    /// `StorageHistory` shards match `StorageChangeSet`.
+    StorageHistory,
+    /// `HashedAccount` mirrors `PlainAccountState`.
+    HashedAccounts,
+    /// `HashedStorage` mirrors `PlainStorageState`.
+    HashedStorages,
+    /// Stage checkpoints are consistent with each other and with the table contents.
+    StageCheckpoints,
+}
+
+impl Check {
+    /// Runs the check against the given provider.
+    fn run<DB: Database>(&self, provider: &DatabaseProviderRO<DB>) -> eyre::Result<CheckReport> {
+        match self {
+            Check::BlockBodies => blocks::check_block_bodies(provider),
+            Check::HeaderNumbers => blocks::check_header_numbers(provider),
+            Check::TransactionLookup => blocks::check_transaction_lookup(provider),
+            Check::AccountHistory => history::check_account_history(provider),
+            Check::StorageHistory => history::check_storage_history(provider),
+            Check::HashedAccounts => hashing::check_hashed_accounts(provider),
+            Check::HashedStorages => hashing::check_hashed_storages(provider),
+            Check::StageCheckpoints => checkpoints::check_stage_checkpoints(provider),
+        }
+    }
+
+    /// Rebuilds the tables validated by the check from the tables they're derived from.
+    ///
+    /// Returns `false` if the tables can't be derived from other tables.
+    fn repair<DB: Database>(&self, factory: &ProviderFactory<DB>) -> eyre::Result<bool> {
+        match self {
+            Check::BlockBodies => blocks::repair_transaction_blocks(factory)?,
+            Check::HeaderNumbers => blocks::repair_header_numbers(factory)?,
+            Check::TransactionLookup => blocks::repair_transaction_lookup(factory)?,
+            Check::AccountHistory => history::repair_account_history(factory)?,
+            Check::StorageHistory => history::repair_storage_history(factory)?,
+            Check::HashedAccounts => hashing::repair_hashed_accounts(factory)?,
+            Check::HashedStorages => hashing::repair_hashed_storages(factory)?,
+            Check::StageCheckpoints => return Ok(false),
+        }
+        Ok(true)
+    }
+
+    /// Returns the id under which the progress of an interrupted repair is kept in
+    /// `SyncStageProgress`.
+    fn repair_progress_id(&self) -> StageId {
+        StageId::Other(match self {
+            Check::BlockBodies => "RepairBlockBodies",
+            Check::HeaderNumbers => "RepairHeaderNumbers",
+            Check::TransactionLookup => "RepairTransactionLookup",
+            Check::AccountHistory => "RepairAccountHistory",
+            Check::StorageHistory => "RepairStorageHistory",
+            Check::HashedAccounts => "RepairHashedAccounts",
+            Check::HashedStorages => "RepairHashedStorages",
+            Check::StageCheckpoints => "RepairStageCheckpoints",
+        })
+    }
+}
+
+impl fmt::Display for Check {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.to_possible_value().expect("no skipped variants").get_name())
+    }
+}
+
+/// Outcome of a [Check].
+#[derive(Debug, Default)]
+pub(crate) struct CheckReport {
+    /// The first [MAX_REPORTED_INCONSISTENCIES] inconsistencies that were found.
+    inconsistencies: Vec<String>,
+    /// Total number of inconsistencies that were found.
+    total: usize,
+    /// Why the check was skipped, if it was.
+    skipped: Option<String>,
+}
+
+impl CheckReport {
+    /// Creates a report for a check that couldn't be run.
+    pub(crate) fn skipped(reason: impl Into<String>) -> Self {
+        Self { skipped: Some(reason.into()), ..Default::default() }
+    }
+
+    /// Records an inconsistency.
+    pub(crate) fn push(&mut self, inconsistency: impl FnOnce() -> String) {
+        if self.inconsistencies.len() < MAX_REPORTED_INCONSISTENCIES {
+            self.inconsistencies.push(inconsistency());
+        }
+        self.total += 1;
+    }
+
+    /// Returns `true` if no inconsistencies were found.
+    pub(crate) fn is_consistent(&self) -> bool {
+        self.total == 0
+    }
+
+    /// Prints the outcome of the check.
+    fn print(&self, check: Check) {
+        if let Some(reason) = &self.skipped {
+            println!("{check}: skipped, {reason}");
+        } else if self.is_consistent() {
+            println!("{check}: ok");
+        } else {
+            println!("{check}: {} inconsistencies", self.total);
+            for inconsistency in &self.inconsistencies {
+                println!("  - {inconsistency}");
+            }
+            if self.total > self.inconsistencies.len() {
+                println!("  - ... and {} more", self.total - self.inconsistencies.len());
+            }
+        }
+    }
+}
+
+impl Command {
+    /// Execute `db check` command
+    pub fn execute<DB: Database>(self, factory: ProviderFactory<DB>) -> eyre::Result<()> {
+        let checks =
+            if self.checks.is_empty() { Check::value_variants().to_vec() } else { self.checks };
+
+        // Every check reads from its own transaction, so they don't block each other.
+        let reports = checks
+            .par_iter()
+            .map(|check| {
+                info!(target: "reth::cli", %check, "Running check");
+                let start = Instant::now();
+                let report = check.run(&factory.provider()?)?;
+                info!(target: "reth::cli", %check, elapsed = ?start.elapsed(), "Finished check");
+                Ok((*check, report))
+            })
+            .collect::<eyre::Result<Vec<_>>>()?;
+
+        let mut failed = 0;
+        for (check, report) in reports {
+            report.print(check);
+            if report.is_consistent() {
+                continue
+            }
+
+            if !self.repair {
+                failed += 1;
+                continue
+            }
+
+            info!(target: "reth::cli", %check, "Repairing");
+            if !check.repair(&factory)? {
+                println!("{check}: can't be repaired, the tables aren't derived from other tables");
+                failed += 1;
+                continue
+            }
+
+            let report = check.run(&factory.provider()?)?;
+            print!("after repair, ");
+            report.print(check);
+            if !report.is_consistent() {
+                failed += 1;
+            }
+        }
+
+        if failed > 0 {
+            eyre::bail!("{failed} checks found inconsistencies")
+        }
+
+        Ok(())
+    }
+}
+
+/// Rebuilds the tables of a check in batches of [REPAIR_BATCH_SIZE] entries that are committed
+/// one by one.
+///
+/// `clear` runs before the first batch. `repair_batch` rebuilds the batch that starts at the given
+/// key, or at the first entry if there's no key, and returns the key that the next batch starts at.
+/// That key is committed together with the batch, so that an interrupted repair resumes from it
+/// instead of starting over.
+pub(crate) fn repair_in_batches<DB, K>(
+    factory: &ProviderFactory<DB>,
+    check: Check,
+    clear: impl FnOnce(&DatabaseProviderRW<DB>) -> eyre::Result<()>,
+    mut repair_batch: impl FnMut(&DatabaseProviderRW<DB>, Option<K>) -> eyre::Result<Option<K>>,
+) -> eyre::Result<()>
+where
+    DB: Database,
+    K: Encode + Decode + Clone,
+{
+    let id = check.repair_progress_id();
+
+    let provider_rw = factory.provider_rw()?;
+    let mut next = match provider_rw.get_stage_checkpoint_progress(id)? {
+        Some(progress) => {
+            info!(target: "reth::cli", %check, "Resuming interrupted repair");
+            // An empty key means that the first batch hasn't been committed yet.
+            (!progress.is_empty()).then(|| K::decode(progress)).transpose()?
+        }
+        None => {
+            clear(&provider_rw)?;
+            provider_rw.save_stage_checkpoint_progress(id, Vec::new())?;
+            provider_rw.commit()?;
+            None
+        }
+    };
+
+    loop {
+        let provider_rw = factory.provider_rw()?;
+        let Some(key) = repair_batch(&provider_rw, next)? else {
+            provider_rw.tx_ref().delete::<tables::SyncStageProgress>(id.to_string(), None)?;
+            provider_rw.commit()?;
+            return Ok(())
+        };
+        provider_rw.save_stage_checkpoint_progress(id, key.clone().encode().into())?;
+        provider_rw.commit()?;
+        next = Some(key);
+    }
+}
+
+/// Returns the highest block that has been pruned from the segment, if any.
+pub(crate) fn pruned_block(
+    provider: &impl PruneCheckpointReader,
+    segment: PruneSegment,
+) -> eyre::Result<Option<BlockNumber>> {
+    Ok(provider.get_prune_checkpoint(segment)?.and_then(|checkpoint| checkpoint.block_number))
+}
+
+/// Returns the lowest transaction that hasn't been pruned from the segment.
+pub(crate) fn lowest_unpruned_tx(
+    provider: &impl PruneCheckpointReader,
+    segment: PruneSegment,
+) -> eyre::Result<TxNumber> {
+    Ok(provider
+        .get_prune_checkpoint(segment)?
+        .and_then(|checkpoint| checkpoint.tx_number)
+        .map_or(0, |tx_number| tx_number + 1))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use itertools::Itertools;
+    use reth_interfaces::test_utils::{generators, generators::random_header_range};
+    use reth_primitives::B256;
+    use reth_stages::test_utils::TestStageDB;
+
+    #[test]
+    fn parse_checks() {
+        let command = Command::try_parse_from([
+            "reth",
+            "--check",
+            "transaction-lookup",
+            "--check",
+            "hashed-accounts",
+            "--repair",
+        ])
+        .unwrap();
+        assert_eq!(command.checks, vec![Check::TransactionLookup, Check::HashedAccounts]);
+        assert!(command.repair);
+    }
+
+    #[test]
+    fn resume_interrupted_repair() {
+        let mut rng = generators::rng();
+        let db = TestStageDB::default();
+        let headers = random_header_range(&mut rng, 0..4, B256::ZERO);
+        db.insert_headers(headers.iter()).unwrap();
+
+        // A repair was interrupted after committing the batch before block 2. The entries of the
+        // committed batch are removed to tell them apart from the entries of the resumed repair.
+        let id = Check::HeaderNumbers.repair_progress_id();
+        db.commit(|tx| {
+            tx.clear::<tables::HeaderNumbers>()?;
+            Ok(tx.put::<tables::SyncStageProgress>(id.to_string(), 2u64.encode().to_vec())?)
+        })
+        .unwrap();
+
+        assert!(Check::HeaderNumbers.repair(&db.factory).unwrap());
+        let numbers = db.table::<tables::HeaderNumbers>().unwrap();
+        assert_eq!(numbers.into_iter().map(|(_, number)| number).sorted().collect_vec(), [2, 3]);
+        assert!(db.table_is_empty::<tables::SyncStageProgress>().unwrap());
+    }
+}
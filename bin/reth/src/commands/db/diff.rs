@@ -7,10 +7,12 @@ use clap::Parser;
 use reth_db::{
     cursor::DbCursorRO, database::Database, mdbx::DatabaseArguments, open_db_read_only,
     table::Table, transaction::DbTx, AccountChangeSet, AccountHistory, AccountsTrie,
-    BlockBodyIndices, BlockOmmers, BlockWithdrawals, Bytecodes, CanonicalHeaders, DatabaseEnv,
-    HashedAccount, HashedStorage, HeaderNumbers, HeaderTD, Headers, PlainAccountState,
-    PlainStorageState, PruneCheckpoints, Receipts, StorageChangeSet, StorageHistory, StoragesTrie,
-    SyncStage, SyncStageProgress, Tables, TransactionBlock, Transactions, TxHashNumber, TxSenders,
+    AddressAppearances, BadBlockHashes, BadBlocks, BlockBodyIndices, BlockOmmers, BlockWithdrawals,
+    Bytecodes, CallParticipants, CanonicalHeaders, ContractCreators, DatabaseEnv, HashedAccount,
+    HashedStorage, HeaderNumbers, HeaderTD, Headers, LogAddressIndex, LogTopicIndex,
+    PlainAccountState, PlainStorageState, PruneCheckpoints, Receipts, StorageChangeSet,
+    StorageHistory, StoragesTrie, SyncStage, SyncStageProgress, Tables, TransactionBlock,
+    Transactions, TxHashNumber, TxSenders,
 };
 use std::{
     collections::HashMap,
@@ -141,6 +143,25 @@ impl Command {
                 Tables::PruneCheckpoints => {
                     find_diffs::<PruneCheckpoints>(primary_tx, secondary_tx, output_dir)?
                 }
+                Tables::AddressAppearances => {
+                    find_diffs::<AddressAppearances>(primary_tx, secondary_tx, output_dir)?
+                }
+                Tables::ContractCreators => {
+                    find_diffs::<ContractCreators>(primary_tx, secondary_tx, output_dir)?
+                }
+                Tables::CallParticipants => {
+                    find_diffs::<CallParticipants>(primary_tx, secondary_tx, output_dir)?
+                }
+                Tables::BadBlocks => find_diffs::<BadBlocks>(primary_tx, secondary_tx, output_dir)?,
+                Tables::BadBlockHashes => {
+                    find_diffs::<BadBlockHashes>(primary_tx, secondary_tx, output_dir)?
+                }
+                Tables::LogAddressIndex => {
+                    find_diffs::<LogAddressIndex>(primary_tx, secondary_tx, output_dir)?
+                }
+                Tables::LogTopicIndex => {
+                    find_diffs::<LogTopicIndex>(primary_tx, secondary_tx, output_dir)?
+                }
             };
         }
 
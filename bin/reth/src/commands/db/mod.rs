@@ -15,17 +15,21 @@ use human_bytes::human_bytes;
 use reth_db::{
     database::Database,
     mdbx,
-    mdbx::DatabaseArguments,
+    mdbx::{DatabaseArguments, MaxReadTransactionDuration},
     open_db, open_db_read_only,
     version::{get_db_version, DatabaseVersionError, DB_VERSION},
     Tables,
 };
 use reth_primitives::ChainSpec;
+use reth_provider::ProviderFactory;
 use std::{
     io::{self, Write},
     sync::Arc,
 };
+use tokio::sync::watch;
 
+mod backup;
+mod check;
 mod clear;
 mod diff;
 mod get;
@@ -88,6 +92,10 @@ pub enum Subcommands {
     Clear(clear::Command),
     /// Snapshots tables from database
     Snapshot(snapshots::Command),
+    /// Copies the database to a backup while the node may be running
+    Backup(backup::Command),
+    /// Checks the consistency of the database tables, optionally repairing derived tables
+    Check(check::Command),
     /// Lists current and local database versions
     Version,
     /// Returns the full database path
@@ -241,7 +249,36 @@ impl Command {
                 command.execute(&db)?;
             }
             Subcommands::Snapshot(command) => {
-                command.execute(&db_path, self.db.log_level, self.chain.clone())?;
+                command.execute(
+                    &db_path,
+                    &data_dir.snapshots_path(),
+                    self.db.log_level,
+                    self.chain.clone(),
+                )?;
+            }
+            Subcommands::Backup(command) => {
+                let db = open_db_read_only(
+                    &db_path,
+                    DatabaseArguments::default().log_level(self.db.log_level),
+                )?;
+                command.execute(&db)?;
+            }
+            Subcommands::Check(command) => {
+                let args = DatabaseArguments::default()
+                    .log_level(self.db.log_level)
+                    .max_read_transaction_duration(Some(MaxReadTransactionDuration::Unbounded));
+                let db = if command.repair {
+                    open_db(&db_path, args)?
+                } else {
+                    open_db_read_only(&db_path, args)?
+                };
+                let mut factory = ProviderFactory::new(db, self.chain.clone());
+                // The changesets of snapshotted blocks may have been pruned from the database.
+                let snapshots_path = data_dir.snapshots_path();
+                if snapshots_path.exists() {
+                    factory = factory.with_snapshots(snapshots_path, watch::channel(None).1)?;
+                }
+                command.execute(factory)?;
             }
             Subcommands::Version => {
                 let local_db_version = match get_db_version(&db_path) {
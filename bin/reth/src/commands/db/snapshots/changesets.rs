@@ -0,0 +1,111 @@
+use super::{
+    bench::{bench, BenchKind},
+    Command,
+};
+use rand::{seq::SliceRandom, Rng};
+use reth_db::{mdbx::DatabaseArguments, models::AccountBeforeTx, open_db_read_only};
+use reth_interfaces::{db::LogLevel, provider::ProviderResult};
+use reth_primitives::{
+    snapshot::{Compression, Filters},
+    Address, BlockNumber, ChainSpec, SnapshotSegment, StorageEntry,
+};
+use reth_provider::{
+    providers::SnapshotProvider, BlockNumReader, ChangeSetReader, ProviderFactory,
+    TransactionsProviderExt,
+};
+use std::{
+    path::{Path, PathBuf},
+    sync::Arc,
+};
+
+/// Account and storage changes of a block. Only the part of the benchmarked segment is filled.
+type BlockChangeSet = (Vec<AccountBeforeTx>, Vec<(Address, StorageEntry)>);
+
+impl Command {
+    pub(crate) fn bench_changesets_snapshot(
+        &self,
+        db_path: &Path,
+        log_level: Option<LogLevel>,
+        chain: Arc<ChainSpec>,
+        segment: SnapshotSegment,
+        compression: Compression,
+    ) -> eyre::Result<()> {
+        let db_args = DatabaseArguments::default().log_level(log_level);
+
+        let factory = ProviderFactory::new(open_db_read_only(db_path, db_args)?, chain.clone());
+        let provider = factory.provider()?;
+        let tip = provider.last_block_number()?;
+        let block_range =
+            self.block_ranges(tip).first().expect("has been generated before").clone();
+
+        // Changeset snapshots have no filters
+        let filters = Filters::WithoutFilters;
+
+        let mut row_indexes = block_range.clone().collect::<Vec<_>>();
+        let mut rng = rand::thread_rng();
+
+        let tx_range = provider.transaction_range_by_block_range(block_range.clone())?;
+
+        let path: PathBuf = segment
+            .filename_with_configuration(filters, compression, &block_range, &tx_range)
+            .into();
+        let provider = SnapshotProvider::new(PathBuf::default())?;
+        let jar_provider =
+            provider.get_segment_provider_from_block(segment, self.from, Some(&path))?;
+
+        for bench_kind in [BenchKind::Walk, BenchKind::RandomAll] {
+            bench(
+                bench_kind,
+                (open_db_read_only(db_path, db_args)?, chain.clone()),
+                segment,
+                filters,
+                compression,
+                || {
+                    for num in row_indexes.iter() {
+                        block_changeset(&jar_provider, segment, *num)?;
+                    }
+                    Ok(())
+                },
+                |provider| {
+                    for num in row_indexes.iter() {
+                        block_changeset(&provider, segment, *num)?;
+                    }
+                    Ok(())
+                },
+            )?;
+
+            // For random walk
+            row_indexes.shuffle(&mut rng);
+        }
+
+        // BENCHMARK QUERYING A RANDOM CHANGESET BY NUMBER
+        {
+            let num = row_indexes[rng.gen_range(0..row_indexes.len())];
+            bench(
+                BenchKind::RandomOne,
+                (open_db_read_only(db_path, db_args)?, chain.clone()),
+                segment,
+                filters,
+                compression,
+                || Ok(block_changeset(&jar_provider, segment, num)?),
+                |provider| Ok(block_changeset(&provider, segment, num)?),
+            )?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Returns the changes of the block that belong to the changeset segment.
+fn block_changeset(
+    provider: &impl ChangeSetReader,
+    segment: SnapshotSegment,
+    block_number: BlockNumber,
+) -> ProviderResult<BlockChangeSet> {
+    Ok(match segment {
+        SnapshotSegment::AccountChangeSets => {
+            (provider.account_block_changeset(block_number)?, Vec::new())
+        }
+        _ => (Vec::new(), provider.storage_block_changeset(block_number)?),
+    })
+}
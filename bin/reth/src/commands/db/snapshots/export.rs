@@ -0,0 +1,38 @@
+use clap::Parser;
+use reth_db::{mdbx::DatabaseArguments, open_db_read_only};
+use reth_interfaces::db::LogLevel;
+use reth_primitives::ChainSpec;
+use reth_provider::ProviderFactory;
+use std::{
+    path::{Path, PathBuf},
+    sync::Arc,
+};
+use tracing::info;
+
+/// The arguments for the `reth db snapshot export` command
+#[derive(Parser, Debug)]
+pub struct Command {
+    /// Directory to write the snapshot bundle to.
+    path: PathBuf,
+}
+
+impl Command {
+    /// Execute `db snapshot export` command
+    pub fn execute(
+        self,
+        db_path: &Path,
+        snapshots_path: &Path,
+        log_level: Option<LogLevel>,
+        chain: Arc<ChainSpec>,
+    ) -> eyre::Result<()> {
+        let db = open_db_read_only(db_path, DatabaseArguments::default().log_level(log_level))?;
+        let provider_factory = ProviderFactory::new(db, chain);
+
+        info!(target: "reth::cli", path = ?self.path, "Exporting snapshot bundle");
+        let bundle =
+            reth_snapshot::bundle::export_bundle(&provider_factory, snapshots_path, &self.path)?;
+        info!(target: "reth::cli", tip = bundle.tip_number, segments = ?bundle.segments, "Snapshot bundle exported");
+
+        Ok(())
+    }
+}
@@ -0,0 +1,40 @@
+use crate::init::init_genesis;
+use clap::Parser;
+use reth_db::{init_db, mdbx::DatabaseArguments};
+use reth_interfaces::db::LogLevel;
+use reth_primitives::ChainSpec;
+use reth_provider::ProviderFactory;
+use std::{
+    path::{Path, PathBuf},
+    sync::Arc,
+};
+use tracing::info;
+
+/// The arguments for the `reth db snapshot import` command
+#[derive(Parser, Debug)]
+pub struct Command {
+    /// Directory of the snapshot bundle to import.
+    path: PathBuf,
+}
+
+impl Command {
+    /// Execute `db snapshot import` command
+    pub fn execute(
+        self,
+        db_path: &Path,
+        snapshots_path: &Path,
+        log_level: Option<LogLevel>,
+        chain: Arc<ChainSpec>,
+    ) -> eyre::Result<()> {
+        let db = Arc::new(init_db(db_path, DatabaseArguments::default().log_level(log_level))?);
+        init_genesis(db.clone(), chain.clone())?;
+        let provider_factory = ProviderFactory::new(db, chain);
+
+        info!(target: "reth::cli", path = ?self.path, "Importing snapshot bundle");
+        let bundle =
+            reth_snapshot::bundle::import_bundle(&provider_factory, &self.path, snapshots_path)?;
+        info!(target: "reth::cli", tip = bundle.tip_number, segments = ?bundle.segments, "Snapshot bundle imported");
+
+        Ok(())
+    }
+}
@@ -1,4 +1,4 @@
-use clap::{builder::RangedU64ValueParser, Parser};
+use clap::{builder::RangedU64ValueParser, Parser, Subcommand};
 use human_bytes::human_bytes;
 use itertools::Itertools;
 use rayon::iter::{IntoParallelIterator, ParallelIterator};
@@ -23,13 +23,21 @@ use std::{
 };
 
 mod bench;
+mod changesets;
+mod export;
 mod headers;
+mod import;
 mod receipts;
 mod transactions;
+mod verify;
 
 #[derive(Parser, Debug)]
+#[command(args_conflicts_with_subcommands = true)]
 /// Arguments for the `reth db snapshot` command.
 pub struct Command {
+    #[command(subcommand)]
+    command: Option<Subcommands>,
+
     /// Snapshot segments to generate.
     segments: Vec<SnapshotSegment>,
 
@@ -75,14 +83,40 @@ pub struct Command {
     phf: Vec<PerfectHashingFunction>,
 }
 
+#[derive(Subcommand, Debug)]
+/// `reth db snapshot` subcommands
+pub enum Subcommands {
+    /// Checks the snapshots of the data directory against the manifests of their segments and
+    /// reports corrupted files
+    Verify(verify::Command),
+    /// Exports the headers, transactions and receipts snapshots into a bundle that another node
+    /// can import
+    Export(export::Command),
+    /// Imports a snapshot bundle into an empty data directory, so the pipeline continues from the
+    /// bundle tip
+    Import(import::Command),
+}
+
 impl Command {
     /// Execute `db snapshot` command
     pub fn execute(
         self,
         db_path: &Path,
+        snapshots_path: &Path,
         log_level: Option<LogLevel>,
         chain: Arc<ChainSpec>,
     ) -> eyre::Result<()> {
+        match self.command {
+            Some(Subcommands::Verify(command)) => return command.execute(snapshots_path),
+            Some(Subcommands::Export(command)) => {
+                return command.execute(db_path, snapshots_path, log_level, chain)
+            }
+            Some(Subcommands::Import(command)) => {
+                return command.execute(db_path, snapshots_path, log_level, chain)
+            }
+            None => {}
+        }
+
         let all_combinations =
             self.segments.iter().cartesian_product(self.compression.iter()).cartesian_product(
                 if self.phf.is_empty() {
@@ -121,6 +155,18 @@ impl Command {
                             factory.clone(),
                             snap_segments::Receipts::new(*compression, filters),
                         )?,
+                        // Changeset rows are only queried by block number, so they have no
+                        // filters
+                        SnapshotSegment::AccountChangeSets => self
+                            .generate_snapshot::<DatabaseEnv>(
+                                factory.clone(),
+                                snap_segments::AccountChangeSets::new(*compression),
+                            )?,
+                        SnapshotSegment::StorageChangeSets => self
+                            .generate_snapshot::<DatabaseEnv>(
+                                factory.clone(),
+                                snap_segments::StorageChangeSets::new(*compression),
+                            )?,
                     }
                 }
             }
@@ -153,6 +199,14 @@ impl Command {
                         InclusionFilter::Cuckoo,
                         phf,
                     )?,
+                    SnapshotSegment::AccountChangeSets | SnapshotSegment::StorageChangeSets => self
+                        .bench_changesets_snapshot(
+                            db_path,
+                            log_level,
+                            chain.clone(),
+                            *mode,
+                            *compression,
+                        )?,
                 }
             }
         }
@@ -0,0 +1,30 @@
+use clap::Parser;
+use reth_primitives::snapshot::ManifestVerification;
+use reth_provider::providers::SnapshotProvider;
+use std::path::Path;
+
+/// The arguments for the `reth db snapshot verify` command
+#[derive(Parser, Debug)]
+pub struct Command {
+    /// How thoroughly the snapshot files are checked against their manifests.
+    #[arg(long, value_enum, default_value_t = ManifestVerification::Full)]
+    mode: ManifestVerification,
+}
+
+impl Command {
+    /// Execute `db snapshot verify` command
+    pub fn execute(self, snapshots_path: &Path) -> eyre::Result<()> {
+        let corruptions = SnapshotProvider::verify(snapshots_path, self.mode)?;
+
+        if corruptions.is_empty() {
+            println!("All snapshots in {snapshots_path:?} match their manifests.");
+            return Ok(())
+        }
+
+        for corruption in &corruptions {
+            println!("{corruption}");
+        }
+
+        eyre::bail!("found {} corrupted snapshot files", corruptions.len())
+    }
+}
@@ -0,0 +1,140 @@
+//! Command for finding the first transaction of a block with a diverging state root.
+
+use crate::{
+    args::{
+        utils::{chain_help, genesis_value_parser, SUPPORTED_CHAINS},
+        DatabaseArgs,
+    },
+    dirs::{DataDirPath, MaybePlatformPath},
+};
+use clap::Parser;
+use jsonrpsee::http_client::HttpClientBuilder;
+use reth_db::{mdbx::DatabaseArguments, open_db_read_only};
+use reth_node_ethereum::EthEvmConfig;
+use reth_primitives::{BlockHashOrNumber, ChainSpec};
+use reth_provider::{BlockReader, HeaderProvider, ProviderFactory, TransactionVariant};
+use reth_revm::{database::StateProviderDatabase, processor::EVMProcessor};
+use reth_rpc_api::DebugApiClient;
+use std::sync::Arc;
+use tracing::*;
+
+/// `reth debug intermediate-roots` command
+/// This debug routine executes the target block on top of the state of its parent block in the
+/// local database and prints the state root after each transaction. If a reference node is given,
+/// the roots are compared with the roots returned by its `debug_intermediateRoots` method instead,
+/// and the first transaction with a diverging state root is printed.
+#[derive(Debug, Parser)]
+pub struct Command {
+    /// The path to the data dir for all reth files and subdirectories.
+    ///
+    /// Defaults to the OS-specific data directory:
+    ///
+    /// - Linux: `$XDG_DATA_HOME/reth/` or `$HOME/.local/share/reth/`
+    /// - Windows: `{FOLDERID_RoamingAppData}/reth/`
+    /// - macOS: `$HOME/Library/Application Support/reth/`
+    #[arg(long, value_name = "DATA_DIR", verbatim_doc_comment, default_value_t)]
+    datadir: MaybePlatformPath<DataDirPath>,
+
+    /// The chain this node is running.
+    ///
+    /// Possible values are either a built-in chain or the path to a chain specification file.
+    #[arg(
+        long,
+        value_name = "CHAIN_OR_PATH",
+        long_help = chain_help(),
+        default_value = SUPPORTED_CHAINS[0],
+        value_parser = genesis_value_parser
+    )]
+    chain: Arc<ChainSpec>,
+
+    #[clap(flatten)]
+    db: DatabaseArgs,
+
+    /// The number of the block to execute.
+    #[arg(long)]
+    block: u64,
+
+    /// The HTTP RPC endpoint of the reference node to compare the roots with.
+    #[arg(long, value_name = "URL")]
+    rpc_url: Option<String>,
+}
+
+impl Command {
+    /// Execute `debug intermediate-roots` command
+    pub async fn execute(self) -> eyre::Result<()> {
+        if self.block == 0 {
+            eyre::bail!("Genesis block can't be executed")
+        }
+
+        let data_dir = self.datadir.unwrap_or_chain_default(self.chain.chain);
+        let db = open_db_read_only(
+            &data_dir.db_path(),
+            DatabaseArguments::default().log_level(self.db.log_level),
+        )?;
+        let factory = ProviderFactory::new(db, self.chain.clone());
+        let provider = factory.provider()?;
+
+        let block = provider
+            .block_with_senders(
+                BlockHashOrNumber::Number(self.block),
+                TransactionVariant::WithHash,
+            )?
+            .ok_or_else(|| eyre::eyre!("Block {} not found", self.block))?;
+        let total_difficulty = provider
+            .header_td_by_number(self.block)?
+            .ok_or_else(|| eyre::eyre!("Total difficulty of block {} not found", self.block))?;
+
+        info!(target: "reth::cli", block = self.block, "Executing block");
+        let state = factory.history_by_block_number(self.block - 1)?;
+        let mut executor = EVMProcessor::new_with_db(
+            self.chain.clone(),
+            StateProviderDatabase::new(&state),
+            EthEvmConfig::default(),
+        );
+        let roots = executor.intermediate_state_roots(&block, total_difficulty, &state)?;
+
+        let Some(rpc_url) = self.rpc_url else {
+            for (index, root) in roots.iter().enumerate() {
+                info!(
+                    target: "reth::cli",
+                    index,
+                    tx_hash = ?block.body[index].hash(),
+                    ?root,
+                    "Intermediate state root"
+                );
+            }
+            return Ok(())
+        };
+
+        info!(target: "reth::cli", %rpc_url, "Requesting reference roots");
+        let client = HttpClientBuilder::default().build(&rpc_url)?;
+        let expected_roots =
+            DebugApiClient::debug_intermediate_roots(&client, block.header.hash_slow(), None)
+                .await?;
+        if roots.len() != expected_roots.len() {
+            eyre::bail!(
+                "Reference node returned {} roots for a block with {} transactions",
+                expected_roots.len(),
+                roots.len()
+            )
+        }
+
+        match roots.iter().zip(&expected_roots).position(|(root, expected)| root != expected) {
+            Some(index) => {
+                info!(
+                    target: "reth::cli",
+                    index,
+                    tx_hash = ?block.body[index].hash(),
+                    got = ?roots[index],
+                    expected = ?expected_roots[index],
+                    "Found first transaction with diverging state root"
+                );
+            }
+            None => {
+                info!(target: "reth::cli", block = self.block, "All intermediate state roots match")
+            }
+        }
+
+        Ok(())
+    }
+}
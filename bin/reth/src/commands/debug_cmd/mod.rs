@@ -8,6 +8,7 @@ mod build_block;
 pub mod engine_api_store;
 mod execution;
 mod in_memory_merkle;
+mod intermediate_roots;
 mod merkle;
 mod replay_engine;
 
@@ -31,6 +32,8 @@ pub enum Subcommands {
     BuildBlock(build_block::Command),
     /// Debug engine API by replaying stored messages.
     ReplayEngine(replay_engine::Command),
+    /// Compute the state root after each transaction of a block, optionally against a reference.
+    IntermediateRoots(intermediate_roots::Command),
 }
 
 impl Command {
@@ -42,6 +45,7 @@ impl Command {
             Subcommands::InMemoryMerkle(command) => command.execute(ctx).await,
             Subcommands::BuildBlock(command) => command.execute(ctx).await,
             Subcommands::ReplayEngine(command) => command.execute(ctx).await,
+            Subcommands::IntermediateRoots(command) => command.execute().await,
         }
     }
 }
@@ -149,6 +149,22 @@ impl Command {
                         Default::default(),
                     )?;
                 }
+                StageEnum::AddressAppearances => {
+                    tx.clear::<tables::AddressAppearances>()?;
+                    // The stage is optional, removing its checkpoint stops the index from being
+                    // maintained.
+                    tx.delete::<tables::SyncStage>(
+                        StageId::IndexAddressAppearances.to_string(),
+                        None,
+                    )?;
+                }
+                StageEnum::Logs => {
+                    tx.clear::<tables::LogAddressIndex>()?;
+                    tx.clear::<tables::LogTopicIndex>()?;
+                    // The stage is optional, removing its checkpoint stops the index from being
+                    // maintained.
+                    tx.delete::<tables::SyncStage>(StageId::IndexLogs.to_string(), None)?;
+                }
                 StageEnum::TotalDifficulty => {
                     tx.clear::<tables::HeaderTD>()?;
                     tx.put::<tables::SyncStage>(
@@ -23,8 +23,9 @@ use reth_provider::{ProviderFactory, StageCheckpointReader};
 use reth_stages::{
     stages::{
         AccountHashingStage, BodyStage, ExecutionStage, ExecutionStageThresholds,
-        IndexAccountHistoryStage, IndexStorageHistoryStage, MerkleStage, SenderRecoveryStage,
-        StorageHashingStage, TransactionLookupStage,
+        IndexAccountHistoryStage, IndexAddressAppearancesStage, IndexLogsStage,
+        IndexStorageHistoryStage, MerkleStage, SenderRecoveryStage, StorageHashingStage,
+        TransactionLookupStage,
     },
     ExecInput, Stage, StageExt, UnwindInput,
 };
@@ -232,6 +233,10 @@ impl Command {
                 ),
                 StageEnum::AccountHistory => (Box::<IndexAccountHistoryStage>::default(), None),
                 StageEnum::StorageHistory => (Box::<IndexStorageHistoryStage>::default(), None),
+                StageEnum::AddressAppearances => {
+                    (Box::new(IndexAddressAppearancesStage::new(batch_size, None)), None)
+                }
+                StageEnum::Logs => (Box::new(IndexLogsStage::new(batch_size)), None),
                 _ => return Ok(()),
             };
         if let Some(unwind_stage) = &unwind_stage {
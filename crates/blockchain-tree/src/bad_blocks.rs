@@ -0,0 +1,105 @@
+//! Background writer for blocks that failed validation.
+
+use reth_db::database::Database;
+use reth_interfaces::provider::ProviderResult;
+use reth_primitives::BadBlock;
+use reth_provider::{BadBlockWriter, ProviderFactory, MAX_BAD_BLOCKS};
+use std::sync::mpsc::{self, SyncSender, TrySendError};
+use tracing::{debug, warn};
+
+/// Writes blocks that failed validation to the database on a dedicated thread, so that recording
+/// a bad block never waits for a database commit.
+///
+/// All bad blocks that queued up while the previous ones were written are written in a single
+/// transaction. At most [MAX_BAD_BLOCKS] bad blocks are queued, further ones are dropped until the
+/// queue drains.
+#[derive(Debug, Clone)]
+pub struct BadBlocksWriter {
+    sender: SyncSender<BadBlock>,
+}
+
+impl BadBlocksWriter {
+    /// Spawns the thread that writes the bad blocks to the database of the provider factory.
+    ///
+    /// The thread exits once all clones of the writer are dropped.
+    pub fn spawn<DB: Database + 'static>(provider_factory: ProviderFactory<DB>) -> Self {
+        let (sender, receiver) = mpsc::sync_channel::<BadBlock>(MAX_BAD_BLOCKS);
+        std::thread::Builder::new()
+            .name("bad-blocks-writer".to_string())
+            .spawn(move || {
+                while let Ok(bad_block) = receiver.recv() {
+                    let batch = std::iter::once(bad_block)
+                        .chain(receiver.try_iter())
+                        .take(MAX_BAD_BLOCKS)
+                        .collect::<Vec<_>>();
+                    if let Err(err) = write_bad_blocks(&provider_factory, batch) {
+                        warn!(target: "blockchain_tree", ?err, "Failed to record bad blocks");
+                    }
+                }
+            })
+            .expect("failed to spawn bad blocks writer thread");
+        Self { sender }
+    }
+
+    /// Queues the bad block to be written to the database.
+    pub fn insert_bad_block(&self, bad_block: BadBlock) {
+        match self.sender.try_send(bad_block) {
+            Ok(()) => {}
+            Err(TrySendError::Full(bad_block)) => {
+                debug!(target: "blockchain_tree", hash=?bad_block.block.hash, "Bad blocks queue is full, dropping bad block");
+            }
+            Err(TrySendError::Disconnected(bad_block)) => {
+                warn!(target: "blockchain_tree", hash=?bad_block.block.hash, "Bad blocks writer is gone, dropping bad block");
+            }
+        }
+    }
+}
+
+/// Writes the bad blocks to the database in a single transaction.
+fn write_bad_blocks<DB: Database>(
+    provider_factory: &ProviderFactory<DB>,
+    bad_blocks: Vec<BadBlock>,
+) -> ProviderResult<()> {
+    let provider = provider_factory.provider_rw()?;
+    for bad_block in bad_blocks {
+        provider.insert_bad_block(bad_block)?;
+    }
+    provider.commit()?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use reth_interfaces::test_utils::generators::{self, random_block};
+    use reth_provider::{test_utils::create_test_provider_factory, BadBlockReader};
+    use std::time::{Duration, Instant};
+
+    #[test]
+    fn writes_bad_blocks_in_background() {
+        let factory = create_test_provider_factory();
+        let writer = BadBlocksWriter::spawn(factory.clone());
+
+        let mut rng = generators::rng();
+        let bad_blocks = (0..3)
+            .map(|number| BadBlock {
+                block: random_block(&mut rng, number, None, Some(1), None),
+                error: format!("invalid block {number}"),
+            })
+            .collect::<Vec<_>>();
+        for bad_block in &bad_blocks {
+            writer.insert_bad_block(bad_block.clone());
+        }
+
+        let deadline = Instant::now() + Duration::from_secs(10);
+        loop {
+            let stored = factory.provider().unwrap().bad_blocks().unwrap();
+            if stored.len() == bad_blocks.len() {
+                assert_eq!(stored, bad_blocks.into_iter().rev().collect::<Vec<_>>());
+                break
+            }
+            assert!(Instant::now() < deadline, "bad blocks were not written");
+            std::thread::sleep(Duration::from_millis(10));
+        }
+    }
+}
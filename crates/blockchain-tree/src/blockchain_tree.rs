@@ -4,7 +4,8 @@ use crate::{
     canonical_chain::CanonicalChain,
     metrics::{MakeCanonicalAction, MakeCanonicalDurationsRecorder, TreeMetrics},
     state::{BlockChainId, TreeState},
-    AppendableChain, BlockIndices, BlockchainTreeConfig, BundleStateData, TreeExternals,
+    AppendableChain, BadBlocksWriter, BlockIndices, BlockchainTreeConfig, BundleStateData,
+    TreeExternals,
 };
 use reth_db::{database::Database, DatabaseError};
 use reth_interfaces::{
@@ -18,8 +19,8 @@ use reth_interfaces::{
     RethError, RethResult,
 };
 use reth_primitives::{
-    BlockHash, BlockNumHash, BlockNumber, ForkBlock, GotExpected, Hardfork, PruneModes, Receipt,
-    SealedBlock, SealedBlockWithSenders, SealedHeader, U256,
+    BadBlock, BlockHash, BlockNumHash, BlockNumber, ForkBlock, GotExpected, Hardfork, PruneModes,
+    Receipt, SealedBlock, SealedBlockWithSenders, SealedHeader, U256,
 };
 use reth_provider::{
     chain::{ChainSplit, ChainSplitTarget},
@@ -32,7 +33,7 @@ use std::{
     collections::{BTreeMap, HashSet},
     sync::Arc,
 };
-use tracing::{debug, error, info, instrument, trace, warn};
+use tracing::{debug, error, info, instrument, trace};
 
 #[cfg_attr(doc, aquamarine::aquamarine)]
 /// A Tree of chains.
@@ -64,7 +65,9 @@ pub struct BlockchainTree<DB: Database, EF: ExecutorFactory> {
     /// Tracks all the chains, the block indices, and the block buffer.
     state: TreeState,
     /// External components (the database, consensus engine etc.)
-    externals: TreeExternals<DB, EF>,
+    ///
+    /// Shared with detached block validations, which execute without holding on to the tree.
+    externals: Arc<TreeExternals<DB, EF>>,
     /// Tree configuration
     config: BlockchainTreeConfig,
     /// Broadcast channel for canon state changes notifications.
@@ -73,6 +76,8 @@ pub struct BlockchainTree<DB: Database, EF: ExecutorFactory> {
     metrics: TreeMetrics,
     /// Metrics for sync stages.
     sync_metrics_tx: Option<MetricEventsSender>,
+    /// Writes the blocks that failed validation to the database in the background.
+    bad_blocks_writer: Option<BadBlocksWriter>,
     prune_modes: Option<PruneModes>,
 }
 
@@ -106,7 +111,7 @@ impl<DB: Database, EF: ExecutorFactory> BlockchainTree<DB, EF> {
         .unwrap_or_default();
 
         Ok(Self {
-            externals,
+            externals: Arc::new(externals),
             state: TreeState::new(
                 last_finalized_block_number,
                 last_canonical_hashes,
@@ -116,6 +121,7 @@ impl<DB: Database, EF: ExecutorFactory> BlockchainTree<DB, EF> {
             canon_state_notification_sender,
             metrics: Default::default(),
             sync_metrics_tx: None,
+            bad_blocks_writer: None,
             prune_modes,
         })
     }
@@ -126,6 +132,12 @@ impl<DB: Database, EF: ExecutorFactory> BlockchainTree<DB, EF> {
         self
     }
 
+    /// Set the writer that records the blocks that failed validation.
+    pub fn with_bad_blocks_writer(mut self, bad_blocks_writer: BadBlocksWriter) -> Self {
+        self.bad_blocks_writer = Some(bad_blocks_writer);
+        self
+    }
+
     /// Check if the block is known to blockchain tree or database and return its status.
     ///
     /// Function will check:
@@ -635,6 +647,16 @@ impl<DB: Database, EF: ExecutorFactory> BlockchainTree<DB, EF> {
         Ok(())
     }
 
+    /// Records a block that failed validation, so that it can be inspected later.
+    ///
+    /// The block is written to the database in the background by the [BadBlocksWriter], bad
+    /// blocks are not recorded if the tree has none.
+    pub fn insert_bad_block(&self, bad_block: BadBlock) {
+        if let Some(bad_blocks_writer) = &self.bad_blocks_writer {
+            bad_blocks_writer.insert_bad_block(bad_block);
+        }
+    }
+
     /// Validate if block is correct and satisfies all the consensus rules that concern the header
     /// and block body itself.
     fn validate_block(&self, block: &SealedBlockWithSenders) -> Result<(), ConsensusError> {
@@ -726,6 +748,67 @@ impl<DB: Database, EF: ExecutorFactory> BlockchainTree<DB, EF> {
         Ok(InsertPayloadOk::Inserted(status))
     }
 
+    /// Validates the given block and executes it on top of the state of its parent, without
+    /// inserting it into the tree.
+    ///
+    /// The block is executed in a scratch state that is discarded afterwards. The parent of the
+    /// block must be part of the canonical chain or of a sidechain of the tree. The state root of
+    /// the block is always validated.
+    ///
+    /// Returns the state changes and the receipts of the block.
+    pub fn validate_block_detached(
+        &self,
+        block: SealedBlockWithSenders,
+    ) -> Result<BundleStateWithReceipts, InsertBlockErrorKind> {
+        let (parent_header, bundle_state_data, externals) = self.prepare_detached(&block)?;
+        Self::execute_detached(block, &parent_header, bundle_state_data, &externals)
+    }
+
+    /// Validates the given block against the consensus rules and returns the header and the state
+    /// of its parent, together with the externals to execute it with.
+    ///
+    /// The returned values don't borrow the tree, so that the block can be executed with
+    /// [Self::execute_detached] after releasing the lock of a shared tree.
+    pub(crate) fn prepare_detached(
+        &self,
+        block: &SealedBlockWithSenders,
+    ) -> Result<(SealedHeader, BundleStateData, Arc<TreeExternals<DB, EF>>), InsertBlockErrorKind>
+    {
+        self.validate_block(block)?;
+
+        let parent_hash = block.parent_hash;
+        let parent_header = match self.block_by_hash(parent_hash) {
+            Some(parent) => parent.header.clone(),
+            None => self
+                .find_canonical_header(&parent_hash)?
+                .ok_or(BlockchainTreeError::CanonicalChain { block_hash: parent_hash })?,
+        };
+        let bundle_state_data = self
+            .post_state_data(parent_hash)
+            .ok_or(BlockchainTreeError::BlockHashNotFoundInChain { block_hash: parent_hash })?;
+
+        Ok((parent_header, bundle_state_data, Arc::clone(&self.externals)))
+    }
+
+    /// Executes a block prepared by [Self::prepare_detached] on top of the state of its parent.
+    pub(crate) fn execute_detached(
+        block: SealedBlockWithSenders,
+        parent_header: &SealedHeader,
+        bundle_state_data: BundleStateData,
+        externals: &TreeExternals<DB, EF>,
+    ) -> Result<BundleStateWithReceipts, InsertBlockErrorKind> {
+        let (bundle_state, _) = AppendableChain::validate_and_execute(
+            block,
+            parent_header,
+            bundle_state_data,
+            externals,
+            // trie updates of the scratch state are discarded
+            BlockAttachment::HistoricalFork,
+            BlockValidationKind::Exhaustive,
+        )?;
+        Ok(bundle_state)
+    }
+
     /// Finalize blocks up until and including `finalized_block`, and remove them from the tree.
     pub fn finalize_block(&mut self, finalized_block: BlockNumber) {
         // remove blocks
@@ -830,15 +913,18 @@ impl<DB: Database, EF: ExecutorFactory> BlockchainTree<DB, EF> {
         // then try to reinsert them into the tree
         for block in include_blocks.into_iter() {
             // dont fail on error, just ignore the block.
-            let _ = self
-                .try_insert_validated_block(block, BlockValidationKind::SkipStateRootValidation)
-                .map_err(|err| {
-                    debug!(
-                        target: "blockchain_tree", ?err,
-                        "Failed to insert buffered block",
-                    );
-                    err
-                });
+            if let Err(err) = self.try_insert_validated_block(
+                block.clone(),
+                BlockValidationKind::SkipStateRootValidation,
+            ) {
+                debug!(
+                    target: "blockchain_tree", ?err,
+                    "Failed to insert buffered block",
+                );
+                if err.is_invalid_block() {
+                    self.insert_bad_block(BadBlock { block: block.block, error: err.to_string() });
+                }
+            }
         }
     }
 
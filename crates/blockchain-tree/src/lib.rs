@@ -38,6 +38,9 @@ pub use shareable::ShareableBlockchainTree;
 mod bundle;
 pub use bundle::{BundleStateData, BundleStateDataRef};
 
+mod bad_blocks;
+pub use bad_blocks::BadBlocksWriter;
+
 /// Buffer of not executed blocks.
 pub mod block_buffer;
 mod canonical_chain;
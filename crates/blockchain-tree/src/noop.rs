@@ -1,18 +1,19 @@
 use reth_interfaces::{
     blockchain_tree::{
-        error::{BlockchainTreeError, InsertBlockError},
+        error::{BlockchainTreeError, InsertBlockError, InsertBlockErrorKind},
         BlockValidationKind, BlockchainTreeEngine, BlockchainTreeViewer, CanonicalOutcome,
         InsertPayloadOk,
     },
     RethResult,
 };
 use reth_primitives::{
-    BlockHash, BlockNumHash, BlockNumber, Receipt, SealedBlock, SealedBlockWithSenders,
+    BadBlock, BlockHash, BlockNumHash, BlockNumber, Receipt, SealedBlock, SealedBlockWithSenders,
     SealedHeader,
 };
 use reth_provider::{
-    BlockchainTreePendingStateProvider, BundleStateDataProvider, CanonStateNotificationSender,
-    CanonStateNotifications, CanonStateSubscriptions,
+    BlockValidationProvider, BlockchainTreePendingStateProvider, BundleStateDataProvider,
+    BundleStateWithReceipts, CanonStateNotificationSender, CanonStateNotifications,
+    CanonStateSubscriptions,
 };
 use std::collections::{BTreeMap, HashSet};
 
@@ -59,6 +60,10 @@ impl BlockchainTreeEngine for NoopBlockchainTree {
     fn unwind(&self, _unwind_to: BlockNumber) -> RethResult<()> {
         Ok(())
     }
+
+    fn insert_bad_block(&self, _bad_block: BadBlock) -> RethResult<()> {
+        Ok(())
+    }
 }
 
 impl BlockchainTreeViewer for NoopBlockchainTree {
@@ -132,6 +137,15 @@ impl BlockchainTreePendingStateProvider for NoopBlockchainTree {
     }
 }
 
+impl BlockValidationProvider for NoopBlockchainTree {
+    fn validate_block_detached(
+        &self,
+        block: SealedBlockWithSenders,
+    ) -> Result<BundleStateWithReceipts, InsertBlockErrorKind> {
+        Err(BlockchainTreeError::BlockHashNotFoundInChain { block_hash: block.parent_hash }.into())
+    }
+}
+
 impl CanonStateSubscriptions for NoopBlockchainTree {
     fn subscribe_to_canonical_state(&self) -> CanonStateNotifications {
         CanonStateNotificationSender::new(1).subscribe()
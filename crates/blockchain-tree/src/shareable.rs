@@ -5,18 +5,19 @@ use parking_lot::RwLock;
 use reth_db::database::Database;
 use reth_interfaces::{
     blockchain_tree::{
-        error::InsertBlockError, BlockValidationKind, BlockchainTreeEngine, BlockchainTreeViewer,
-        CanonicalOutcome, InsertPayloadOk,
+        error::{InsertBlockError, InsertBlockErrorKind},
+        BlockValidationKind, BlockchainTreeEngine, BlockchainTreeViewer, CanonicalOutcome,
+        InsertPayloadOk,
     },
     RethResult,
 };
 use reth_primitives::{
-    BlockHash, BlockNumHash, BlockNumber, Receipt, SealedBlock, SealedBlockWithSenders,
+    BadBlock, BlockHash, BlockNumHash, BlockNumber, Receipt, SealedBlock, SealedBlockWithSenders,
     SealedHeader,
 };
 use reth_provider::{
-    BlockchainTreePendingStateProvider, BundleStateDataProvider, CanonStateSubscriptions,
-    ExecutorFactory,
+    BlockValidationProvider, BlockchainTreePendingStateProvider, BundleStateDataProvider,
+    BundleStateWithReceipts, CanonStateSubscriptions, ExecutorFactory,
 };
 use std::{
     collections::{BTreeMap, HashSet},
@@ -100,6 +101,12 @@ impl<DB: Database, EF: ExecutorFactory> BlockchainTreeEngine for ShareableBlockc
         tree.update_chains_metrics();
         res
     }
+
+    fn insert_bad_block(&self, bad_block: BadBlock) -> RethResult<()> {
+        trace!(target: "blockchain_tree", hash=?bad_block.block.hash, number=bad_block.block.number, "Inserting bad block");
+        self.tree.read().insert_bad_block(bad_block);
+        Ok(())
+    }
 }
 
 impl<DB: Database, EF: ExecutorFactory> BlockchainTreeViewer for ShareableBlockchainTree<DB, EF> {
@@ -208,6 +215,21 @@ impl<DB: Database, EF: ExecutorFactory> BlockchainTreePendingStateProvider
     }
 }
 
+impl<DB: Database, EF: ExecutorFactory> BlockValidationProvider
+    for ShareableBlockchainTree<DB, EF>
+{
+    fn validate_block_detached(
+        &self,
+        block: SealedBlockWithSenders,
+    ) -> Result<BundleStateWithReceipts, InsertBlockErrorKind> {
+        trace!(target: "blockchain_tree", hash=?block.hash, number=block.number, parent_hash=?block.parent_hash, "Validating detached block");
+        let (parent_header, bundle_state_data, externals) =
+            self.tree.read().prepare_detached(&block)?;
+        // the block is executed without holding the lock, so that it doesn't stall the tree
+        BlockchainTree::execute_detached(block, &parent_header, bundle_state_data, &externals)
+    }
+}
+
 impl<DB: Database, EF: ExecutorFactory> CanonStateSubscriptions
     for ShareableBlockchainTree<DB, EF>
 {
@@ -72,6 +72,10 @@ pub struct StageConfig {
     pub index_account_history: IndexHistoryConfig,
     /// Index Storage History stage configuration.
     pub index_storage_history: IndexHistoryConfig,
+    /// Index Address Appearances stage configuration.
+    pub index_address_appearances: IndexAddressAppearancesConfig,
+    /// Index Logs stage configuration.
+    pub index_logs: IndexLogsConfig,
 }
 
 /// Header stage configuration.
@@ -187,6 +191,9 @@ pub struct ExecutionConfig {
     pub max_cumulative_gas: Option<u64>,
     /// The maximum time spent on blocks processing before the execution stage commits.
     pub max_duration: Option<Duration>,
+    /// Whether the creator of each deployed contract should be indexed by the execution stage and
+    /// the blockchain tree.
+    pub index_contract_creators: bool,
 }
 
 impl Default for ExecutionConfig {
@@ -198,6 +205,7 @@ impl Default for ExecutionConfig {
             max_cumulative_gas: Some(30_000_000 * 50_000),
             // 10 minutes
             max_duration: Some(Duration::from_secs(10 * 60)),
+            index_contract_creators: false,
         }
     }
 }
@@ -262,6 +270,42 @@ impl Default for IndexHistoryConfig {
     }
 }
 
+/// Index Address Appearances stage configuration.
+///
+/// The stage is optional and disabled by default.
+#[derive(Debug, Clone, Copy, Deserialize, PartialEq, Eq, Serialize)]
+#[serde(default)]
+pub struct IndexAddressAppearancesConfig {
+    /// Whether the stage is added to the pipeline.
+    pub enabled: bool,
+    /// The maximum number of blocks to process before committing progress to the database.
+    pub commit_threshold: u64,
+}
+
+impl Default for IndexAddressAppearancesConfig {
+    fn default() -> Self {
+        Self { enabled: false, commit_threshold: 100_000 }
+    }
+}
+
+/// Index Logs stage configuration.
+///
+/// The stage is optional and disabled by default.
+#[derive(Debug, Clone, Copy, Deserialize, PartialEq, Eq, Serialize)]
+#[serde(default)]
+pub struct IndexLogsConfig {
+    /// Whether the stage is added to the pipeline.
+    pub enabled: bool,
+    /// The maximum number of blocks to process before committing progress to the database.
+    pub commit_threshold: u64,
+}
+
+impl Default for IndexLogsConfig {
+    fn default() -> Self {
+        Self { enabled: false, commit_threshold: 100_000 }
+    }
+}
+
 /// Pruning configuration.
 #[derive(Debug, Clone, Deserialize, PartialEq, Serialize)]
 #[serde(default)]
@@ -23,8 +23,8 @@ use reth_interfaces::{
 use reth_node_api::{EngineTypes, PayloadAttributes, PayloadBuilderAttributes};
 use reth_payload_builder::PayloadBuilderHandle;
 use reth_primitives::{
-    constants::EPOCH_SLOTS, stage::StageId, BlockNumHash, BlockNumber, Head, Header, SealedBlock,
-    SealedHeader, B256,
+    constants::EPOCH_SLOTS, stage::StageId, BadBlock, BlockNumHash, BlockNumber, Head, Header,
+    SealedBlock, SealedHeader, B256,
 };
 use reth_provider::{
     BlockIdReader, BlockReader, BlockSource, CanonChainTracker, ChainSpecProvider, ProviderError,
@@ -1326,8 +1326,9 @@ where
             // all of these occurred if the payload is invalid
             let parent_hash = block.parent_hash;
 
-            // keep track of the invalid header
-            self.invalid_headers.insert(block.header);
+            // keep track of the invalid header and the full block
+            self.invalid_headers.insert(block.header.clone());
+            self.record_bad_block(block, &error);
 
             let latest_valid_hash =
                 self.latest_valid_hash_for_invalid_payload(parent_hash, Some(&error));
@@ -1338,6 +1339,15 @@ where
         }
     }
 
+    /// Records a block that failed validation with the given error, so that it can be inspected
+    /// later, e.g. through `debug_getBadBlocks`.
+    fn record_bad_block(&self, block: SealedBlock, error: &InsertBlockErrorKind) {
+        let bad_block = BadBlock { block, error: error.to_string() };
+        if let Err(err) = self.blockchain.insert_bad_block(bad_block) {
+            warn!(target: "consensus::engine", ?err, "Failed to record bad block");
+        }
+    }
+
     /// Attempt to restore the tree with the given block hash.
     ///
     /// This is invoked after a full pipeline to update the tree with the most recent canonical
@@ -1416,7 +1426,8 @@ where
                     let (block, err) = err.split();
                     warn!(target: "consensus::engine", invalid_number=?block.number, invalid_hash=?block.hash, ?err, "Marking block as invalid");
 
-                    self.invalid_headers.insert(block.header);
+                    self.invalid_headers.insert(block.header.clone());
+                    self.record_bad_block(block, &err);
                 }
             }
         }
@@ -1,6 +1,6 @@
 use crate::{blockchain_tree::error::InsertBlockError, RethResult};
 use reth_primitives::{
-    BlockHash, BlockNumHash, BlockNumber, Receipt, SealedBlock, SealedBlockWithSenders,
+    BadBlock, BlockHash, BlockNumHash, BlockNumber, Receipt, SealedBlock, SealedBlockWithSenders,
     SealedHeader,
 };
 use std::collections::{BTreeMap, HashSet};
@@ -98,6 +98,12 @@ pub trait BlockchainTreeEngine: BlockchainTreeViewer + Send + Sync {
 
     /// Unwind tables and put it inside state
     fn unwind(&self, unwind_to: BlockNumber) -> RethResult<()>;
+
+    /// Records a block that failed validation, so that it can be inspected later.
+    ///
+    /// This is called on the engine path, so the block should be persisted without blocking on
+    /// a database commit.
+    fn insert_bad_block(&self, bad_block: BadBlock) -> RethResult<()>;
 }
 
 /// Represents the kind of validation that should be performed when inserting a block.
@@ -37,6 +37,9 @@ pub enum DatabaseError {
     /// Failed to use the specified log level, as it's not available.
     #[error("log level {0:?} is not available")]
     LogLevelUnavailable(LogLevel),
+    /// Failed to back up the database.
+    #[error("failed to back up the database: {0}")]
+    Backup(DatabaseErrorInfo),
 }
 
 /// Common error struct to propagate implementation-specific error information.
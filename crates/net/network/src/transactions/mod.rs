@@ -130,6 +130,9 @@ impl TransactionsHandle {
     }
 
     /// Manually propagate the transaction that belongs to the hash.
+    ///
+    /// Note: transactions that are not allowed to be propagated, like private transactions, are
+    /// never sent to peers.
     pub fn propagate(&self, hash: TxHash) {
         self.send(TransactionsCommand::PropagateHash(hash))
     }
@@ -346,7 +349,12 @@ where
         // This fetches all transaction from the pool, including the blob transactions, which are
         // only ever sent as hashes.
         let propagated = self.propagate_transactions(
-            self.pool.get_all(hashes).into_iter().map(PropagateTransaction::new).collect(),
+            self.pool
+                .get_all(hashes)
+                .into_iter()
+                .filter(|tx| tx.propagate)
+                .map(PropagateTransaction::new)
+                .collect(),
         );
 
         // notify pool so events get fired
@@ -463,7 +471,7 @@ where
             .pool
             .get_all(txs)
             .into_iter()
-            .filter(|tx| !tx.transaction.is_eip4844())
+            .filter(|tx| tx.propagate && !tx.transaction.is_eip4844())
             .map(PropagateTransaction::new);
 
         // Iterate through the transactions to propagate and fill the hashes and full transaction
@@ -505,8 +513,13 @@ where
                 return
             };
 
-            let to_propagate: Vec<PropagateTransaction> =
-                self.pool.get_all(hashes).into_iter().map(PropagateTransaction::new).collect();
+            let to_propagate: Vec<PropagateTransaction> = self
+                .pool
+                .get_all(hashes)
+                .into_iter()
+                .filter(|tx| tx.propagate)
+                .map(PropagateTransaction::new)
+                .collect();
 
             let mut propagated = PropagatedTransactions::default();
 
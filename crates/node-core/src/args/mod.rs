@@ -25,7 +25,7 @@ mod log_args;
 pub use log_args::{ColorMode, LogArgs};
 
 mod secret_key;
-pub use secret_key::{get_secret_key, SecretKeyError};
+pub use secret_key::{get_secret_key, read_secret_key, SecretKeyError};
 
 /// PayloadBuilderArgs struct for configuring the payload builder
 mod payload_builder_args;
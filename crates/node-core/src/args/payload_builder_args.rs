@@ -9,7 +9,7 @@ use clap::{
 use reth_primitives::constants::{
     ETHEREUM_BLOCK_GAS_LIMIT, MAXIMUM_EXTRA_DATA_SIZE, SLOT_DURATION,
 };
-use std::{borrow::Cow, ffi::OsStr, time::Duration};
+use std::{borrow::Cow, ffi::OsStr, path::PathBuf, time::Duration};
 
 /// Parameters for configuring the Payload Builder
 #[derive(Debug, Args, PartialEq)]
@@ -35,6 +35,13 @@ pub struct PayloadBuilderArgs {
     #[arg(long = "builder.max-tasks", default_value = "3", value_parser = RangedU64ValueParser::<usize>::new().range(1..))]
     pub max_payload_tasks: usize,
 
+    /// Path to a file with the hex encoded secret key of the fee recipient, which signs the
+    /// refund payouts of MEV-Share bundles.
+    ///
+    /// Bundles with refunds are only included in blocks if it's set.
+    #[arg(long = "builder.refund-secret-key", value_name = "PATH")]
+    pub refund_secret_key: Option<PathBuf>,
+
     /// By default the pending block equals the latest block
     /// to save resources and not leak txs from the tx-pool,
     /// this flag enables computing of the pending block
@@ -56,6 +63,7 @@ impl Default for PayloadBuilderArgs {
             interval: Duration::from_secs(1),
             deadline: SLOT_DURATION,
             max_payload_tasks: 3,
+            refund_secret_key: None,
             #[cfg(feature = "optimism")]
             compute_pending_block: false,
         }
@@ -18,16 +18,22 @@ use clap::{
 };
 use futures::TryFutureExt;
 use rand::Rng;
+use reth_db::database_backup::DatabaseBackup;
 use reth_network_api::{NetworkInfo, Peers};
 use reth_node_api::{ConfigureEvmEnv, EngineTypes};
 use reth_provider::{
-    AccountReader, BlockReaderIdExt, CanonStateSubscriptions, ChainSpecProvider, ChangeSetReader,
-    EvmEnvProvider, HeaderProvider, StateProviderFactory,
+    AccountReader, AddressAppearancesReader, BadBlockReader, BlockReaderIdExt,
+    BlockValidationProvider, CanonStateSubscriptions, ChainSpecProvider, ChangeSetReader,
+    ContractCreatorReader, EvmEnvProvider, HeaderProvider, LogIndexReader, StateProviderFactory,
 };
 use reth_rpc::{
-    eth::{cache::EthStateCacheConfig, gas_oracle::GasPriceOracleConfig, RPC_DEFAULT_GAS_CAP},
-    JwtError, JwtSecret,
+    eth::{
+        cache::EthStateCacheConfig, gas_oracle::GasPriceOracleConfig, RPC_DEFAULT_ETH_PROOF_WINDOW,
+        RPC_DEFAULT_GAS_CAP,
+    },
+    AdminBackupApi, JwtError, JwtSecret,
 };
+use reth_rpc_api::AdminBackupApiServer;
 use reth_rpc_builder::{
     auth::{AuthServerConfig, AuthServerHandle},
     constants,
@@ -156,6 +162,19 @@ pub struct RpcServerArgs {
     #[arg(long, value_name = "COUNT", default_value_t = constants::DEFAULT_MAX_TRACING_REQUESTS)]
     pub rpc_max_tracing_requests: u32,
 
+    /// Maximum number of blocks that could be traced per `trace_filter` request. (0 = entire
+    /// chain)
+    #[arg(long, value_name = "COUNT", default_value_t = ZeroAsNoneU64::new(constants::DEFAULT_MAX_TRACE_FILTER_BLOCKS))]
+    pub rpc_max_trace_filter_blocks: ZeroAsNoneU64,
+
+    /// Maximum block range of `debug_getModifiedAccountsBy*` requests. (0 = entire chain)
+    #[arg(long, value_name = "COUNT", default_value_t = ZeroAsNoneU64::new(constants::DEFAULT_MAX_MODIFIED_ACCOUNTS_BLOCKS))]
+    pub rpc_max_modified_accounts_blocks: ZeroAsNoneU64,
+
+    /// Maximum block range of `debug_traceChain` subscriptions. (0 = entire chain)
+    #[arg(long, value_name = "COUNT", default_value_t = ZeroAsNoneU64::new(constants::DEFAULT_MAX_TRACE_CHAIN_BLOCKS))]
+    pub rpc_max_trace_chain_blocks: ZeroAsNoneU64,
+
     /// Maximum number of blocks that could be scanned per filter request. (0 = entire chain)
     #[arg(long, value_name = "COUNT", default_value_t = ZeroAsNoneU64::new(constants::DEFAULT_MAX_BLOCKS_PER_FILTER))]
     pub rpc_max_blocks_per_filter: ZeroAsNoneU64,
@@ -174,6 +193,15 @@ pub struct RpcServerArgs {
     )]
     pub rpc_gas_cap: u64,
 
+    /// Maximum number of blocks behind the tip for which `eth_getProof` is served.
+    #[arg(
+        long,
+        alias = "rpc.eth-proof-window",
+        value_name = "COUNT",
+        default_value_t = RPC_DEFAULT_ETH_PROOF_WINDOW
+    )]
+    pub rpc_eth_proof_window: u64,
+
     /// State cache configuration.
     #[clap(flatten)]
     pub rpc_state_cache: RpcStateCacheArgs,
@@ -273,11 +301,14 @@ impl RpcServerArgs {
     /// Returns the handles for the launched regular RPC server(s) (if any) and the server handle
     /// for the auth server that handles the `engine_` API that's accessed by the consensus
     /// layer.
-    pub async fn start_servers<Reth, Engine, Conf, EngineT>(
+    ///
+    /// The auth server also serves the `admin_` methods to back up the given database.
+    pub async fn start_servers<Reth, Engine, Conf, EngineT, DB>(
         &self,
         components: &Reth,
         engine_api: Engine,
         jwt_secret: JwtSecret,
+        db: DB,
         conf: &mut Conf,
     ) -> eyre::Result<RethRpcServerHandles>
     where
@@ -285,6 +316,7 @@ impl RpcServerArgs {
         Engine: EngineApiServer<EngineT>,
         Reth: RethNodeComponents,
         Conf: RethNodeCommandConfig,
+        DB: DatabaseBackup + Send + Sync + 'static,
     {
         let auth_config = self.auth_server_config(jwt_secret)?;
 
@@ -300,6 +332,10 @@ impl RpcServerArgs {
             .with_evm_config(components.evm_config())
             .build_with_auth_server(module_config, engine_api);
 
+        // backups write to the local file system, so they're only served by the auth server
+        let backup_api = AdminBackupApi::new(db, Box::new(components.task_executor()));
+        auth_module.merge_auth_methods(backup_api.into_rpc())?;
+
         let rpc_components = RethRpcComponents {
             registry: &mut registry,
             modules: &mut modules,
@@ -361,6 +397,11 @@ impl RpcServerArgs {
             + EvmEnvProvider
             + ChainSpecProvider
             + ChangeSetReader
+            + AddressAppearancesReader
+            + LogIndexReader
+            + ContractCreatorReader
+            + BadBlockReader
+            + BlockValidationProvider
             + Clone
             + Unpin
             + 'static,
@@ -401,6 +442,7 @@ impl RpcServerArgs {
             + EvmEnvProvider
             + HeaderProvider
             + StateProviderFactory
+            + LogIndexReader
             + Clone
             + Unpin
             + 'static,
@@ -439,9 +481,13 @@ impl RethRpcConfig for RpcServerArgs {
     fn eth_config(&self) -> EthConfig {
         EthConfig::default()
             .max_tracing_requests(self.rpc_max_tracing_requests)
+            .max_trace_filter_blocks(self.rpc_max_trace_filter_blocks.unwrap_or_max())
+            .max_modified_accounts_blocks(self.rpc_max_modified_accounts_blocks.unwrap_or_max())
+            .max_trace_chain_blocks(self.rpc_max_trace_chain_blocks.unwrap_or_max())
             .max_blocks_per_filter(self.rpc_max_blocks_per_filter.unwrap_or_max())
             .max_logs_per_response(self.rpc_max_logs_per_response.unwrap_or_max() as usize)
             .rpc_gas_cap(self.rpc_gas_cap)
+            .eth_proof_window(self.rpc_eth_proof_window)
             .state_cache(self.state_cache_config())
             .gpo_config(self.gas_price_oracle_config())
     }
@@ -580,9 +626,14 @@ impl Default for RpcServerArgs {
             rpc_max_subscriptions_per_connection: RPC_DEFAULT_MAX_SUBS_PER_CONN.into(),
             rpc_max_connections: RPC_DEFAULT_MAX_CONNECTIONS.into(),
             rpc_max_tracing_requests: constants::DEFAULT_MAX_TRACING_REQUESTS,
+            rpc_max_trace_filter_blocks: constants::DEFAULT_MAX_TRACE_FILTER_BLOCKS.into(),
+            rpc_max_modified_accounts_blocks: constants::DEFAULT_MAX_MODIFIED_ACCOUNTS_BLOCKS
+                .into(),
+            rpc_max_trace_chain_blocks: constants::DEFAULT_MAX_TRACE_CHAIN_BLOCKS.into(),
             rpc_max_blocks_per_filter: constants::DEFAULT_MAX_BLOCKS_PER_FILTER.into(),
             rpc_max_logs_per_response: (constants::DEFAULT_MAX_LOGS_PER_RESPONSE as u64).into(),
             rpc_gas_cap: RPC_DEFAULT_GAS_CAP.into(),
+            rpc_eth_proof_window: RPC_DEFAULT_ETH_PROOF_WINDOW,
             gas_price_oracle: GasPriceOracleArgs::default(),
             rpc_state_cache: RpcStateCacheArgs::default(),
         }
@@ -650,6 +701,19 @@ mod tests {
         assert!(args.is_err());
     }
 
+    #[test]
+    fn test_rpc_eth_proof_window() {
+        let args = CommandParser::<RpcServerArgs>::parse_from(["reth"]).args;
+        let config = args.eth_config();
+        assert_eq!(config.eth_proof_window, RPC_DEFAULT_ETH_PROOF_WINDOW);
+
+        let args =
+            CommandParser::<RpcServerArgs>::parse_from(["reth", "--rpc.eth-proof-window", "1000"])
+                .args;
+        let config = args.eth_config();
+        assert_eq!(config.eth_proof_window, 1000);
+    }
+
     #[test]
     fn test_rpc_server_args_parser() {
         let args =
@@ -805,6 +869,34 @@ mod tests {
         assert_eq!(config.max_logs_per_response, Some(200));
     }
 
+    #[test]
+    fn test_trace_filter_limit() {
+        let args = CommandParser::<RpcServerArgs>::parse_from(["reth"]).args;
+        assert_eq!(
+            args.eth_config().max_trace_filter_blocks,
+            constants::DEFAULT_MAX_TRACE_FILTER_BLOCKS
+        );
+
+        let args = CommandParser::<RpcServerArgs>::parse_from([
+            "reth",
+            "--rpc-max-trace-filter-blocks",
+            "0",
+        ])
+        .args;
+        assert_eq!(args.eth_config().max_trace_filter_blocks, u64::MAX);
+    }
+
+    #[test]
+    fn test_modified_accounts_limit() {
+        let args = CommandParser::<RpcServerArgs>::parse_from([
+            "reth",
+            "--rpc-max-modified-accounts-blocks",
+            "100",
+        ])
+        .args;
+        assert_eq!(args.eth_config().max_modified_accounts_blocks, 100);
+    }
+
     #[test]
     fn rpc_server_args_default_sanity_test() {
         let default_args = RpcServerArgs::default();
@@ -28,6 +28,14 @@ pub enum SecretKeyError {
     },
 }
 
+/// Loads an existing [`SecretKey`] from the specified path.
+///
+/// Unlike [`get_secret_key`], this doesn't generate a secret key if no file exists there.
+pub fn read_secret_key(secret_key_path: &Path) -> Result<SecretKey, SecretKeyError> {
+    let contents = fs::read_to_string(secret_key_path)?;
+    Ok(contents.trim().parse::<SecretKey>()?)
+}
+
 /// Attempts to load a [`SecretKey`] from a specified path. If no file exists there, then it
 /// generates a secret key and stores it in the provided path. I/O errors might occur during write
 /// operations in the form of a [`SecretKeyError`]
@@ -49,6 +49,14 @@ pub enum StageEnum {
     ///
     /// Manages historical data related to storage.
     StorageHistory,
+    /// The optional address appearances stage within the pipeline.
+    ///
+    /// Indexes the transactions in which addresses appear.
+    AddressAppearances,
+    /// The optional logs stage within the pipeline.
+    ///
+    /// Indexes the blocks in which addresses and topics were logged.
+    Logs,
     /// The total difficulty stage within the pipeline.
     ///
     /// Handles computations and data related to total difficulty.
@@ -6,8 +6,10 @@ use reth_network_api::{NetworkInfo, Peers};
 use reth_node_api::ConfigureEvmEnv;
 use reth_primitives::ChainSpec;
 use reth_provider::{
-    AccountReader, BlockReaderIdExt, CanonStateSubscriptions, ChainSpecProvider, ChangeSetReader,
-    DatabaseProviderFactory, EvmEnvProvider, StateProviderFactory,
+    AccountReader, AddressAppearancesReader, BadBlockReader, BlockReaderIdExt,
+    BlockValidationProvider, CanonStateSubscriptions, ChainSpecProvider, ChangeSetReader,
+    ContractCreatorReader, DatabaseProviderFactory, EvmEnvProvider, LogIndexReader,
+    StateProviderFactory,
 };
 use reth_rpc_builder::{
     auth::{AuthRpcModule, AuthServerHandle},
@@ -26,6 +28,11 @@ pub trait FullProvider<DB: Database>:
     + EvmEnvProvider
     + ChainSpecProvider
     + ChangeSetReader
+    + AddressAppearancesReader
+    + LogIndexReader
+    + ContractCreatorReader
+    + BadBlockReader
+    + BlockValidationProvider
     + Clone
     + Unpin
     + 'static
@@ -40,6 +47,11 @@ impl<T, DB: Database> FullProvider<DB> for T where
         + EvmEnvProvider
         + ChainSpecProvider
         + ChangeSetReader
+        + AddressAppearancesReader
+        + LogIndexReader
+        + ContractCreatorReader
+        + BadBlockReader
+        + BlockValidationProvider
         + Clone
         + Unpin
         + 'static
@@ -15,7 +15,7 @@ use once_cell::sync::Lazy;
 use reth_auto_seal_consensus::{AutoSealConsensus, MiningMode};
 use reth_beacon_consensus::BeaconConsensus;
 use reth_blockchain_tree::{
-    config::BlockchainTreeConfig, externals::TreeExternals, BlockchainTree,
+    config::BlockchainTreeConfig, externals::TreeExternals, BadBlocksWriter, BlockchainTree,
 };
 use reth_config::{
     config::{PruneConfig, StageConfig},
@@ -53,8 +53,8 @@ use reth_stages::{
     prelude::*,
     stages::{
         AccountHashingStage, ExecutionStage, ExecutionStageThresholds, IndexAccountHistoryStage,
-        IndexStorageHistoryStage, MerkleStage, SenderRecoveryStage, StorageHashingStage,
-        TotalDifficultyStage, TransactionLookupStage,
+        IndexAddressAppearancesStage, IndexLogsStage, IndexStorageHistoryStage, MerkleStage,
+        SenderRecoveryStage, StorageHashingStage, TotalDifficultyStage, TransactionLookupStage,
     },
     MetricEvent,
 };
@@ -413,6 +413,11 @@ impl NodeConfig {
     }
 
     /// Build the blockchain tree
+    ///
+    /// If `index_contract_creators` is set, the creators of the contracts deployed by blocks
+    /// executed in the tree are written to the database on commit. Likewise, the participants of
+    /// internal calls are recorded if `index_call_participants` is set.
+    #[allow(clippy::too_many_arguments)]
     pub fn build_blockchain_tree<DB, EvmConfig>(
         &self,
         provider_factory: ProviderFactory<DB>,
@@ -421,6 +426,8 @@ impl NodeConfig {
         sync_metrics_tx: UnboundedSender<MetricEvent>,
         tree_config: BlockchainTreeConfig,
         evm_config: EvmConfig,
+        index_contract_creators: bool,
+        index_call_participants: bool,
     ) -> eyre::Result<BlockchainTree<DB, EvmProcessorFactory<EvmConfig>>>
     where
         DB: Database + Unpin + Clone + 'static,
@@ -430,14 +437,17 @@ impl NodeConfig {
         let tree_externals = TreeExternals::new(
             provider_factory.clone(),
             consensus.clone(),
-            EvmProcessorFactory::new(self.chain.clone(), evm_config),
+            EvmProcessorFactory::new(self.chain.clone(), evm_config)
+                .with_contract_creations(index_contract_creators)
+                .with_call_participants(index_call_participants),
         );
         let tree = BlockchainTree::new(
             tree_externals,
             tree_config,
             prune_config.clone().map(|config| config.segments),
         )?
-        .with_sync_metrics_tx(sync_metrics_tx.clone());
+        .with_sync_metrics_tx(sync_metrics_tx.clone())
+        .with_bad_blocks_writer(BadBlocksWriter::spawn(provider_factory.clone()));
 
         Ok(tree)
     }
@@ -800,7 +810,10 @@ impl NodeConfig {
             },
         };
 
-        let factory = factory.with_stack_config(stack_config);
+        let factory = factory
+            .with_stack_config(stack_config)
+            .with_contract_creations(stage_config.execution.index_contract_creators)
+            .with_call_participants(stage_config.index_address_appearances.enabled);
 
         let prune_modes = prune_config.map(|prune| prune.segments).unwrap_or_default();
 
@@ -863,7 +876,22 @@ impl NodeConfig {
                 .set(IndexStorageHistoryStage::new(
                     stage_config.index_storage_history.commit_threshold,
                     prune_modes.storage_history,
-                )),
+                ))
+                .add_before(
+                    IndexAddressAppearancesStage::new(
+                        stage_config.index_address_appearances.commit_threshold,
+                        prune_modes.address_appearances,
+                    ),
+                    StageId::Finish,
+                )
+                .disable_if(StageId::IndexAddressAppearances, || {
+                    !stage_config.index_address_appearances.enabled
+                })
+                .add_before(
+                    IndexLogsStage::new(stage_config.index_logs.commit_threshold),
+                    StageId::Finish,
+                )
+                .disable_if(StageId::IndexLogs, || !stage_config.index_logs.enabled),
             )
             .build(provider_factory);
 
@@ -24,24 +24,50 @@ mod builder {
             eip4844::MAX_DATA_GAS_PER_BLOCK, BEACON_NONCE, EMPTY_RECEIPTS, EMPTY_TRANSACTIONS,
         },
         eip4844::calculate_excess_blob_gas,
-        proofs,
+        proofs, public_key_to_address,
         revm::env::tx_env_with_recovered,
-        Block, Header, IntoRecoveredTransaction, Receipt, Receipts, EMPTY_OMMER_ROOT_HASH, U256,
+        sign_message, Address, Block, Header, IntoRecoveredTransaction, Receipt, Receipts,
+        Transaction, TransactionKind, TransactionSigned, TransactionSignedEcRecovered, TxLegacy,
+        B256, EMPTY_OMMER_ROOT_HASH, U256,
     };
-    use reth_provider::{BundleStateWithReceipts, StateProviderFactory};
+    use reth_provider::{BundleStateWithReceipts, ProviderError, StateProviderFactory};
     use reth_revm::database::StateProviderDatabase;
-    use reth_transaction_pool::{BestTransactionsAttributes, TransactionPool};
+    use reth_transaction_pool::{
+        BestTransactionsAttributes, PooledBundle, TransactionPool, SBUNDLE_PAYOUT_MAX_COST,
+    };
     use revm::{
         db::states::bundle_state::BundleRetention,
-        primitives::{EVMError, EnvWithHandlerCfg, InvalidTransaction, ResultAndState},
-        DatabaseCommit, State,
+        primitives::{
+            BlockEnv, CfgEnvWithHandlerCfg, EVMError, EnvWithHandlerCfg, InvalidTransaction,
+            ResultAndState, State as EvmState,
+        },
+        Database, DatabaseCommit, State,
     };
+    use secp256k1::{SecretKey, SECP256K1};
+    use std::{cmp::Reverse, sync::Arc};
     use tracing::{debug, trace, warn};
 
+    /// The maximum number of bundles that are simulated when building a payload.
+    pub const MAX_BUNDLES_PER_BUILD: usize = 100;
+
     /// Ethereum payload builder
     #[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
     #[non_exhaustive]
-    pub struct EthereumPayloadBuilder;
+    pub struct EthereumPayloadBuilder {
+        /// The key of the coinbase that signs the refund payouts of bundles.
+        refund_signer: Option<SecretKey>,
+    }
+
+    impl EthereumPayloadBuilder {
+        /// Sets the key that signs the refund payouts of bundles.
+        ///
+        /// Bundles with refunds are only included in payloads whose fee recipient is the address
+        /// of this key.
+        pub fn set_refund_signer(mut self, refund_signer: Option<SecretKey>) -> Self {
+            self.refund_signer = refund_signer;
+            self
+        }
+    }
 
     // Default implementation of [PayloadBuilder] for unit type
     impl<Pool, Client> PayloadBuilder<Pool, Client> for EthereumPayloadBuilder
@@ -56,7 +82,7 @@ mod builder {
             &self,
             args: BuildArguments<Pool, Client, EthPayloadBuilderAttributes, EthBuiltPayload>,
         ) -> Result<BuildOutcome<EthBuiltPayload>, PayloadBuilderError> {
-            default_ethereum_payload_builder(args)
+            ethereum_payload_builder(args, self.refund_signer.as_ref())
         }
 
         fn build_empty_payload(
@@ -178,6 +204,22 @@ mod builder {
     pub fn default_ethereum_payload_builder<Pool, Client>(
         args: BuildArguments<Pool, Client, EthPayloadBuilderAttributes, EthBuiltPayload>,
     ) -> Result<BuildOutcome<EthBuiltPayload>, PayloadBuilderError>
+    where
+        Client: StateProviderFactory,
+        Pool: TransactionPool,
+    {
+        ethereum_payload_builder(args, None)
+    }
+
+    /// Constructs an Ethereum transaction payload like [default_ethereum_payload_builder], and
+    /// pays out the refunds of bundles with the given key of the fee recipient.
+    ///
+    /// Bundles with refunds are skipped if there's no key for the fee recipient.
+    #[inline]
+    pub fn ethereum_payload_builder<Pool, Client>(
+        args: BuildArguments<Pool, Client, EthPayloadBuilderAttributes, EthBuiltPayload>,
+        refund_signer: Option<&SecretKey>,
+    ) -> Result<BuildOutcome<EthBuiltPayload>, PayloadBuilderError>
     where
         Client: StateProviderFactory,
         Pool: TransactionPool,
@@ -228,6 +270,84 @@ mod builder {
         )?;
 
         let mut receipts = Vec::new();
+
+        // refunds are paid out by the fee recipient
+        let refund_signer = refund_signer.filter(|signer| {
+            public_key_to_address(signer.public_key(SECP256K1)) == initialized_block_env.coinbase
+        });
+
+        // include the most profitable bundles at the top of the block, before any pool transaction
+        let mut bundles = pool.bundle_pool().bundles_for_block(block_number, attributes.timestamp);
+        if !bundles.is_empty() {
+            select_bundles(&mut bundles, base_fee);
+
+            // rank the bundles by their profit when executed in isolation, every bundle is
+            // simulated on top of `db` without modifying it
+            let mut ranked = Vec::with_capacity(bundles.len());
+            for bundle in bundles {
+                if cancel.is_cancelled() {
+                    return Ok(BuildOutcome::Cancelled)
+                }
+                if bundle.has_refunds() && refund_signer.is_none() {
+                    trace!(target: "payload_builder", bundle=?bundle.hash, "skipping bundle with refunds without a refund signer");
+                    continue
+                }
+
+                let mut simulation = State::builder().with_database(&mut db).build();
+                if let Some(executed) = execute_bundle(
+                    &mut simulation,
+                    &bundle,
+                    &initialized_cfg,
+                    &initialized_block_env,
+                    cumulative_gas_used,
+                    block_gas_limit,
+                    refund_signer,
+                )? {
+                    ranked.push((bundle, executed));
+                }
+            }
+            ranked.sort_unstable_by(|(_, a), (_, b)| b.profit.cmp(&a.profit));
+
+            // bundles that conflict with an already included bundle fail to execute and are skipped
+            for (bundle, simulated) in ranked {
+                if cancel.is_cancelled() {
+                    return Ok(BuildOutcome::Cancelled)
+                }
+
+                // bundles are included first, so as long as no bundle has been included the
+                // simulation ran on top of the current state and can be reused
+                let executed = if executed_txs.is_empty() {
+                    simulated
+                } else {
+                    let mut execution = State::builder().with_database(&mut db).build();
+                    let Some(executed) = execute_bundle(
+                        &mut execution,
+                        &bundle,
+                        &initialized_cfg,
+                        &initialized_block_env,
+                        cumulative_gas_used,
+                        block_gas_limit,
+                        refund_signer,
+                    )?
+                    else {
+                        trace!(target: "payload_builder", bundle=?bundle.hash, "skipping bundle that conflicts with included bundles");
+                        continue
+                    };
+                    executed
+                };
+
+                trace!(target: "payload_builder", bundle=?bundle.hash, profit=?executed.profit, "including bundle");
+                for state in executed.states {
+                    db.commit(state);
+                }
+                cumulative_gas_used += executed.gas_used;
+                // the profit of a bundle includes the fees and direct payments to the coinbase
+                total_fees += executed.profit;
+                receipts.extend(executed.receipts.into_iter().map(Some));
+                executed_txs.extend(executed.transactions);
+            }
+        }
+
         while let Some(pool_tx) = best_txs.next() {
             // ensure we still have capacity for this transaction
             if cumulative_gas_used + pool_tx.gas_limit() > block_gas_limit {
@@ -422,4 +542,496 @@ mod builder {
 
         Ok(BuildOutcome::Better { payload, cached_reads })
     }
+
+    /// A bundle that was executed successfully.
+    #[derive(Debug)]
+    struct ExecutedBundle {
+        /// The executed transactions, in order.
+        transactions: Vec<TransactionSigned>,
+        /// The receipts of the executed transactions.
+        receipts: Vec<Receipt>,
+        /// The state changes of the executed transactions, in order.
+        states: Vec<EvmState>,
+        /// The gas used by all transactions of the bundle.
+        gas_used: u64,
+        /// The balance increase of the coinbase.
+        profit: U256,
+    }
+
+    /// Retains the [MAX_BUNDLES_PER_BUILD] bundles with the highest priority fees, which are the
+    /// only bundles that are simulated when building a payload.
+    fn select_bundles(bundles: &mut Vec<Arc<PooledBundle>>, base_fee: u64) {
+        if bundles.len() <= MAX_BUNDLES_PER_BUILD {
+            return
+        }
+        bundles.sort_by_cached_key(|bundle| {
+            Reverse(bundle.transactions.iter().fold(0u128, |fees, tx| {
+                let tip = tx.effective_tip_per_gas(Some(base_fee)).unwrap_or_default();
+                fees.saturating_add(tip.saturating_mul(tx.gas_limit() as u128))
+            }))
+        });
+        bundles.truncate(MAX_BUNDLES_PER_BUILD);
+    }
+
+    /// Executes all transactions of the bundle on top of `db`, in order, and pays out its refunds
+    /// with transactions of the `refund_signer`, which must be the key of the coinbase.
+    ///
+    /// `db` is expected to be a scratch [State] on top of the state of the payload. The state
+    /// changes of the bundle are returned, so they can be committed to the payload if the bundle
+    /// is included.
+    ///
+    /// Returns `None` if the bundle can not be included, because it does not fit into the
+    /// remaining gas of the block, because one of its transactions is invalid or reverts without
+    /// being allowed to or because its refunds exceed the value it generates. In that case `db`
+    /// may contain some of the changes of the bundle and must be discarded by the caller.
+    fn execute_bundle<DB>(
+        db: &mut State<DB>,
+        bundle: &PooledBundle,
+        initialized_cfg: &CfgEnvWithHandlerCfg,
+        initialized_block_env: &BlockEnv,
+        cumulative_gas_used: u64,
+        block_gas_limit: u64,
+        refund_signer: Option<&SecretKey>,
+    ) -> Result<Option<ExecutedBundle>, PayloadBuilderError>
+    where
+        DB: Database<Error = ProviderError>,
+    {
+        let coinbase = initialized_block_env.coinbase;
+        let coinbase_before = db.basic(coinbase)?.map(|acc| acc.balance).unwrap_or_default();
+        let payout_fee = initialized_block_env.basefee * U256::from(SBUNDLE_PAYOUT_MAX_COST);
+
+        let mut gas_used = 0;
+        let mut transactions = Vec::with_capacity(bundle.transactions.len());
+        let mut receipts = Vec::with_capacity(bundle.transactions.len());
+        let mut states = Vec::with_capacity(bundle.transactions.len());
+        // the balance of the coinbase before every transaction, after the refunds of the nested
+        // bundles that end before it
+        let mut coinbase_balances = Vec::with_capacity(bundle.transactions.len() + 1);
+        coinbase_balances.push(coinbase_before);
+        for (idx, tx) in bundle.transactions.iter().enumerate() {
+            if !execute_bundle_transaction(
+                db,
+                bundle,
+                tx,
+                initialized_cfg,
+                initialized_block_env,
+                cumulative_gas_used,
+                block_gas_limit,
+                &mut gas_used,
+                &mut transactions,
+                &mut receipts,
+                &mut states,
+            )? {
+                return Ok(None)
+            }
+            coinbase_balances.push(db.basic(coinbase)?.map(|acc| acc.balance).unwrap_or_default());
+
+            // pay out the refunds of the bundles that end with this transaction, nested bundles
+            // first
+            for refunds in bundle.refunds.iter().filter(|refunds| refunds.span().end == idx + 1) {
+                let Some(refund_signer) = refund_signer else {
+                    trace!(target: "payload_builder", bundle=?bundle.hash, "bundle refunds require a refund signer");
+                    return Ok(None)
+                };
+
+                // the value generated by refunded items is not refundable
+                let refundable_value = refunds
+                    .items
+                    .iter()
+                    .enumerate()
+                    .filter(|(item, _)| !refunds.is_refunded(*item))
+                    .fold(U256::ZERO, |value, (_, item)| {
+                        value +
+                            coinbase_balances[item.end]
+                                .saturating_sub(coinbase_balances[item.start])
+                    });
+                for refund in &refunds.refunds {
+                    let Some(payouts) = refund.payouts(refundable_value, payout_fee) else {
+                        trace!(target: "payload_builder", bundle=?bundle.hash, "bundle refunds exceed refundable value");
+                        return Ok(None)
+                    };
+                    for (recipient, value) in payouts {
+                        let nonce = db.basic(coinbase)?.map(|acc| acc.nonce).unwrap_or_default();
+                        let payout = refund_payout(
+                            refund_signer,
+                            coinbase,
+                            initialized_cfg.chain_id,
+                            nonce,
+                            initialized_block_env.basefee.to(),
+                            recipient,
+                            value,
+                        )?;
+                        if !execute_bundle_transaction(
+                            db,
+                            bundle,
+                            &payout,
+                            initialized_cfg,
+                            initialized_block_env,
+                            cumulative_gas_used,
+                            block_gas_limit,
+                            &mut gas_used,
+                            &mut transactions,
+                            &mut receipts,
+                            &mut states,
+                        )? {
+                            return Ok(None)
+                        }
+                    }
+                }
+
+                *coinbase_balances.last_mut().expect("not empty") =
+                    db.basic(coinbase)?.map(|acc| acc.balance).unwrap_or_default();
+            }
+        }
+
+        let coinbase_after = db.basic(coinbase)?.map(|acc| acc.balance).unwrap_or_default();
+        Ok(Some(ExecutedBundle {
+            transactions,
+            receipts,
+            states,
+            gas_used,
+            profit: coinbase_after.saturating_sub(coinbase_before),
+        }))
+    }
+
+    /// Executes a transaction of the bundle on top of `db` and appends it with its receipt and
+    /// state changes.
+    ///
+    /// Returns `false` if the transaction can not be included, see [execute_bundle].
+    #[allow(clippy::too_many_arguments)]
+    fn execute_bundle_transaction<DB>(
+        db: &mut State<DB>,
+        bundle: &PooledBundle,
+        tx: &TransactionSignedEcRecovered,
+        initialized_cfg: &CfgEnvWithHandlerCfg,
+        initialized_block_env: &BlockEnv,
+        cumulative_gas_used: u64,
+        block_gas_limit: u64,
+        gas_used: &mut u64,
+        transactions: &mut Vec<TransactionSigned>,
+        receipts: &mut Vec<Receipt>,
+        states: &mut Vec<EvmState>,
+    ) -> Result<bool, PayloadBuilderError>
+    where
+        DB: Database<Error = ProviderError>,
+    {
+        if cumulative_gas_used + *gas_used + tx.gas_limit() > block_gas_limit {
+            trace!(target: "payload_builder", bundle=?bundle.hash, tx=?tx.hash, "bundle exceeds block gas limit");
+            return Ok(false)
+        }
+
+        let mut evm = revm::Evm::builder()
+            .with_db(&mut *db)
+            .with_env_with_handler_cfg(EnvWithHandlerCfg::new_with_cfg_env(
+                initialized_cfg.clone(),
+                initialized_block_env.clone(),
+                tx_env_with_recovered(tx),
+            ))
+            .build();
+
+        let ResultAndState { result, state } = match evm.transact() {
+            Ok(res) => res,
+            Err(EVMError::Transaction(err)) => {
+                trace!(target: "payload_builder", bundle=?bundle.hash, tx=?tx.hash, ?err, "bundle contains invalid transaction");
+                return Ok(false)
+            }
+            Err(err) => return Err(PayloadBuilderError::EvmExecutionError(err)),
+        };
+        // drop evm so db is released.
+        drop(evm);
+
+        if !result.is_success() && !bundle.can_revert(&tx.hash) {
+            trace!(target: "payload_builder", bundle=?bundle.hash, tx=?tx.hash, "bundle transaction reverted");
+            return Ok(false)
+        }
+        db.commit(state.clone());
+        states.push(state);
+
+        *gas_used += result.gas_used();
+        receipts.push(Receipt {
+            tx_type: tx.tx_type(),
+            success: result.is_success(),
+            cumulative_gas_used: cumulative_gas_used + *gas_used,
+            logs: result.logs().into_iter().map(Into::into).collect(),
+        });
+        transactions.push(tx.clone().into_signed());
+        Ok(true)
+    }
+
+    /// Signs the transaction that pays out `value` of a refund from the coinbase to the
+    /// `recipient`.
+    ///
+    /// The gas price of the transaction is the base fee, so it doesn't pay a priority fee, and its
+    /// gas limit is the gas reserved for it by the refund.
+    fn refund_payout(
+        refund_signer: &SecretKey,
+        coinbase: Address,
+        chain_id: u64,
+        nonce: u64,
+        base_fee: u64,
+        recipient: Address,
+        value: U256,
+    ) -> Result<TransactionSignedEcRecovered, PayloadBuilderError> {
+        let transaction = Transaction::Legacy(TxLegacy {
+            chain_id: Some(chain_id),
+            nonce,
+            gas_price: base_fee as u128,
+            gas_limit: SBUNDLE_PAYOUT_MAX_COST,
+            to: TransactionKind::Call(recipient),
+            value: value.into(),
+            input: Default::default(),
+        });
+        let signature =
+            sign_message(B256::from(refund_signer.secret_bytes()), transaction.signature_hash())
+                .map_err(|err| PayloadBuilderError::Other(err.to_string().into()))?;
+        Ok(TransactionSignedEcRecovered::from_signed_transaction(
+            TransactionSigned::from_transaction_and_signature(transaction, signature),
+            coinbase,
+        ))
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+        use reth_basic_payload_builder::Cancelled;
+        use reth_payload_builder::{database::CachedReads, PayloadId};
+        use reth_primitives::{Signature, Withdrawals, MAINNET};
+        use reth_provider::test_utils::{ExtendedAccount, MockEthProvider};
+        use reth_transaction_pool::{
+            test_utils::{testing_pool, MockTransaction},
+            BundleRefund, BundleRefunds, TransactionOrigin,
+        };
+        use std::sync::Arc;
+
+        #[tokio::test]
+        async fn includes_bundles_before_pool_transactions() {
+            let client = MockEthProvider::default();
+            let pool = testing_pool();
+
+            let pool_sender = Address::random();
+            let bundle_sender = Address::random();
+            for sender in [pool_sender, bundle_sender] {
+                client.add_account(sender, ExtendedAccount::new(0, U256::from(10u128.pow(18))));
+            }
+
+            // The pool transaction pays a higher fee than the bundle, but bundles are included
+            // first.
+            let pool_tx = MockTransaction::legacy()
+                .with_sender(pool_sender)
+                .with_gas_limit(21_000)
+                .with_gas_price(2_000_000_000);
+            pool.add_transaction(TransactionOrigin::External, pool_tx.clone()).await.unwrap();
+
+            let bundle_tx = TransactionSignedEcRecovered::from_signed_transaction(
+                TransactionSigned::from_transaction_and_signature(
+                    TxLegacy {
+                        gas_price: 1_000_000_000,
+                        gas_limit: 21_000,
+                        to: TransactionKind::Call(Address::random()),
+                        ..Default::default()
+                    }
+                    .into(),
+                    Signature::default(),
+                ),
+                bundle_sender,
+            );
+            pool.bundle_pool()
+                .add_bundle(PooledBundle {
+                    hash: B256::random(),
+                    block_number: 1,
+                    max_block_number: 1,
+                    min_timestamp: None,
+                    max_timestamp: None,
+                    transactions: vec![bundle_tx.clone()],
+                    reverting_tx_hashes: Default::default(),
+                    replacement_uuid: None,
+                    refunds: Vec::new(),
+                    refund_recipients: Vec::new(),
+                })
+                .unwrap();
+
+            let parent_block = Block {
+                header: Header { gas_limit: 30_000_000, ..Default::default() },
+                ..Default::default()
+            }
+            .seal_slow();
+            let attributes = EthPayloadBuilderAttributes {
+                id: PayloadId::new([0; 8]),
+                parent: parent_block.hash,
+                timestamp: 1,
+                suggested_fee_recipient: Address::random(),
+                prev_randao: B256::ZERO,
+                withdrawals: Withdrawals::default(),
+                parent_beacon_block_root: None,
+            };
+            let config = PayloadConfig::new(
+                Arc::new(parent_block),
+                Default::default(),
+                attributes,
+                MAINNET.clone(),
+            );
+
+            let outcome = default_ethereum_payload_builder(BuildArguments::new(
+                client,
+                pool,
+                CachedReads::default(),
+                config,
+                Cancelled::default(),
+                None,
+            ))
+            .unwrap();
+
+            let BuildOutcome::Better { payload, .. } = outcome else {
+                panic!("expected a better payload")
+            };
+            let hashes = payload.block().body.iter().map(|tx| tx.hash).collect::<Vec<_>>();
+            assert_eq!(hashes, vec![bundle_tx.hash, pool_tx.get_hash()]);
+        }
+
+        #[test]
+        fn selects_bundles_with_highest_priority_fees() {
+            let bundle = |gas_price: u128| {
+                let tx = TransactionSignedEcRecovered::from_signed_transaction(
+                    TransactionSigned::from_transaction_and_signature(
+                        TxLegacy { gas_price, gas_limit: 21_000, ..Default::default() }.into(),
+                        Signature::default(),
+                    ),
+                    Address::random(),
+                );
+                Arc::new(PooledBundle {
+                    hash: B256::random(),
+                    block_number: 1,
+                    max_block_number: 1,
+                    min_timestamp: None,
+                    max_timestamp: None,
+                    transactions: vec![tx],
+                    reverting_tx_hashes: Default::default(),
+                    replacement_uuid: None,
+                    refunds: Vec::new(),
+                    refund_recipients: Vec::new(),
+                })
+            };
+
+            // the bundle that pays less than the base fee has no priority fee
+            let base_fee = 1_000_000_000;
+            let cheapest = bundle(1);
+            let mut bundles = vec![cheapest.clone()];
+            bundles.extend(
+                (0..MAX_BUNDLES_PER_BUILD).map(|i| bundle(base_fee as u128 + 1 + i as u128)),
+            );
+
+            select_bundles(&mut bundles, base_fee);
+            assert_eq!(bundles.len(), MAX_BUNDLES_PER_BUILD);
+            assert!(!bundles.iter().any(|bundle| bundle.hash == cheapest.hash));
+        }
+
+        #[tokio::test]
+        async fn pays_out_bundle_refunds() {
+            let client = MockEthProvider::default();
+            let pool = testing_pool();
+
+            let refund_signer = SecretKey::from_slice(&[0x11; 32]).unwrap();
+            let coinbase = public_key_to_address(refund_signer.public_key(SECP256K1));
+            let user = Address::random();
+            let searcher = Address::random();
+            for sender in [user, searcher] {
+                client.add_account(sender, ExtendedAccount::new(0, U256::from(10u128.pow(18))));
+            }
+
+            let transaction = |signer, to, value: u64| {
+                TransactionSignedEcRecovered::from_signed_transaction(
+                    TransactionSigned::from_transaction_and_signature(
+                        TxLegacy {
+                            gas_price: 1_000_000_000,
+                            gas_limit: 21_000,
+                            to: TransactionKind::Call(to),
+                            value: U256::from(value).into(),
+                            ..Default::default()
+                        }
+                        .into(),
+                        Signature::default(),
+                    ),
+                    signer,
+                )
+            };
+            let user_tx = transaction(user, Address::random(), 1);
+            let backrun_tx = transaction(searcher, coinbase, 10u64.pow(17));
+
+            // the user is refunded half of the value generated by the backrun
+            pool.bundle_pool()
+                .add_bundle(PooledBundle {
+                    hash: B256::random(),
+                    block_number: 1,
+                    max_block_number: 1,
+                    min_timestamp: None,
+                    max_timestamp: None,
+                    transactions: vec![user_tx.clone(), backrun_tx.clone()],
+                    reverting_tx_hashes: Default::default(),
+                    replacement_uuid: None,
+                    refunds: vec![BundleRefunds {
+                        items: vec![0..1, 1..2],
+                        refunds: vec![BundleRefund {
+                            item: 0,
+                            percent: 50,
+                            recipients: vec![(user, 100)],
+                        }],
+                    }],
+                    refund_recipients: Vec::new(),
+                })
+                .unwrap();
+
+            let parent_block = Arc::new(
+                Block {
+                    header: Header { gas_limit: 30_000_000, ..Default::default() },
+                    ..Default::default()
+                }
+                .seal_slow(),
+            );
+            let build = |refund_signer: Option<&SecretKey>| {
+                let attributes = EthPayloadBuilderAttributes {
+                    id: PayloadId::new([0; 8]),
+                    parent: parent_block.hash,
+                    timestamp: 1,
+                    suggested_fee_recipient: coinbase,
+                    prev_randao: B256::ZERO,
+                    withdrawals: Withdrawals::default(),
+                    parent_beacon_block_root: None,
+                };
+                let config = PayloadConfig::new(
+                    parent_block.clone(),
+                    Default::default(),
+                    attributes,
+                    MAINNET.clone(),
+                );
+                let outcome = ethereum_payload_builder(
+                    BuildArguments::new(
+                        client.clone(),
+                        pool.clone(),
+                        CachedReads::default(),
+                        config,
+                        Cancelled::default(),
+                        None,
+                    ),
+                    refund_signer,
+                )
+                .unwrap();
+                let BuildOutcome::Better { payload, .. } = outcome else {
+                    panic!("expected a better payload")
+                };
+                payload.block().body.clone()
+            };
+
+            // the bundle is skipped if the refunds can't be paid out
+            assert!(build(None).is_empty());
+
+            let body = build(Some(&refund_signer));
+            assert_eq!(body.len(), 3);
+            assert_eq!(body[0].hash, user_tx.hash);
+            assert_eq!(body[1].hash, backrun_tx.hash);
+            let payout = &body[2];
+            assert_eq!(payout.recover_signer(), Some(coinbase));
+            assert_eq!(payout.to(), Some(user));
+            assert!(U256::from(payout.value()) > U256::ZERO);
+        }
+    }
 }
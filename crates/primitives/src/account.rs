@@ -1,7 +1,7 @@
 use crate::{
     keccak256,
     revm_primitives::{Bytecode as RevmBytecode, BytecodeState, Bytes, JumpMap},
-    GenesisAccount, B256, KECCAK_EMPTY, U256,
+    Address, BlockNumber, GenesisAccount, TxHash, B256, KECCAK_EMPTY, U256,
 };
 use byteorder::{BigEndian, ReadBytesExt};
 use bytes::Buf;
@@ -52,6 +52,19 @@ impl Account {
     }
 }
 
+/// The creation of a contract.
+#[main_codec]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Default)]
+pub struct ContractCreation {
+    /// Number of the block the contract was created in.
+    pub block_number: BlockNumber,
+    /// Hash of the transaction the contract was created in.
+    pub tx_hash: TxHash,
+    /// Address that created the contract. This is the sender of the transaction for top-level
+    /// creations, and the deploying contract for internal ones.
+    pub creator: Address,
+}
+
 /// Bytecode for an account.
 ///
 /// A wrapper around [`revm::primitives::Bytecode`][RevmBytecode] with encoding/decoding support.
@@ -446,6 +446,15 @@ impl std::ops::DerefMut for SealedBlockWithSenders {
     }
 }
 
+/// A block that failed validation, along with the validation error.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct BadBlock {
+    /// The invalid block.
+    pub block: SealedBlock,
+    /// The error the block failed validation with.
+    pub error: String,
+}
+
 /// A response to `GetBlockBodies`, containing bodies if any bodies were found.
 ///
 /// Withdrawals can be optionally included at the end of the RLP encoded message.
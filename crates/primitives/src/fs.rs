@@ -93,6 +93,17 @@ pub enum FsPathError {
         to: PathBuf,
     },
 
+    /// Error variant for failed file copy operation with additional path context.
+    #[error("failed to copy {from:?} to {to:?}: {source}")]
+    Copy {
+        /// The source `io::Error`.
+        source: io::Error,
+        /// The original path.
+        from: PathBuf,
+        /// The target path.
+        to: PathBuf,
+    },
+
     /// Error variant for failed file opening operation with additional path context.
     #[error("failed to open file {path:?}: {source}")]
     Open {
@@ -171,6 +182,11 @@ impl FsPathError {
     pub fn rename(source: io::Error, from: impl Into<PathBuf>, to: impl Into<PathBuf>) -> Self {
         FsPathError::Rename { source, from: from.into(), to: to.into() }
     }
+
+    /// Returns the complementary error variant for [`std::fs::copy`].
+    pub fn copy(source: io::Error, from: impl Into<PathBuf>, to: impl Into<PathBuf>) -> Self {
+        FsPathError::Copy { source, from: from.into(), to: to.into() }
+    }
 }
 
 type Result<T> = std::result::Result<T, FsPathError>;
@@ -225,3 +241,10 @@ pub fn rename(from: impl AsRef<Path>, to: impl AsRef<Path>) -> Result<()> {
     let to = to.as_ref();
     fs::rename(from, to).map_err(|err| FsPathError::rename(err, from, to))
 }
+
+/// Wrapper for `std::fs::copy`
+pub fn copy(from: impl AsRef<Path>, to: impl AsRef<Path>) -> Result<u64> {
+    let from = from.as_ref();
+    let to = to.as_ref();
+    fs::copy(from, to).map_err(|err| FsPathError::copy(err, from, to))
+}
@@ -43,10 +43,10 @@ pub mod transaction;
 pub mod trie;
 mod withdrawal;
 
-pub use account::{Account, Bytecode};
+pub use account::{Account, Bytecode, ContractCreation};
 pub use block::{
-    Block, BlockBody, BlockHashOrNumber, BlockId, BlockNumHash, BlockNumberOrTag, BlockWithSenders,
-    ForkBlock, RpcBlockHash, SealedBlock, SealedBlockWithSenders,
+    BadBlock, Block, BlockBody, BlockHashOrNumber, BlockId, BlockNumHash, BlockNumberOrTag,
+    BlockWithSenders, ForkBlock, RpcBlockHash, SealedBlock, SealedBlockWithSenders,
 };
 pub use chain::{
     AllGenesisFormats, BaseFeeParams, BaseFeeParamsKind, Chain, ChainInfo, ChainSpec,
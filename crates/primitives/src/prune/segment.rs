@@ -23,15 +23,27 @@ pub enum PruneSegment {
     Headers,
     /// Prune segment responsible for the `Transactions` table.
     Transactions,
+    /// Prune segment responsible for the `AddressAppearances` table.
+    AddressAppearances,
+    /// Prune segment responsible for the `AccountChangeSet` table of snapshotted blocks. Unlike
+    /// [PruneSegment::AccountHistory], it keeps the `AccountHistory` indices.
+    AccountChangeSets,
+    /// Prune segment responsible for the `StorageChangeSet` table of snapshotted blocks. Unlike
+    /// [PruneSegment::StorageHistory], it keeps the `StorageHistory` indices.
+    StorageChangeSets,
 }
 
 impl PruneSegment {
     /// Returns minimum number of blocks to left in the database for this segment.
     pub fn min_blocks(&self) -> u64 {
         match self {
-            Self::SenderRecovery | Self::TransactionLookup | Self::Headers | Self::Transactions => {
-                0
-            }
+            Self::SenderRecovery |
+            Self::TransactionLookup |
+            Self::Headers |
+            Self::Transactions |
+            Self::AddressAppearances |
+            Self::AccountChangeSets |
+            Self::StorageChangeSets => 0,
             Self::Receipts | Self::ContractLogs | Self::AccountHistory | Self::StorageHistory => {
                 MINIMUM_PRUNING_DISTANCE
             }
@@ -0,0 +1,58 @@
+use crate::{
+    fs::{self, FsPathError},
+    snapshot::{HighestSnapshots, ManifestEntry, SnapshotSegment},
+    BlockNumber, B256,
+};
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+
+/// Describes a directory of snapshots that can be imported by another node, so it doesn't have to
+/// download and snapshot the covered block range itself.
+///
+/// Next to this description, stored as `bundle.json`, the directory holds the snapshots and the
+/// [`SegmentManifest`](crate::snapshot::SegmentManifest) of every segment, and the blocks jar.
+#[derive(Debug, Clone, Eq, PartialEq, Serialize, Deserialize)]
+pub struct SnapshotBundle {
+    /// Id of the chain the snapshots belong to.
+    pub chain_id: u64,
+    /// Hash of the genesis block of the chain.
+    pub genesis_hash: B256,
+    /// Highest block for which the bundle contains the header and the body.
+    pub tip_number: BlockNumber,
+    /// Hash of the highest block for which the bundle contains the header and the body.
+    pub tip_hash: B256,
+    /// Segments contained in the bundle.
+    pub segments: Vec<SnapshotSegment>,
+    /// Highest snapshotted block of every segment contained in the bundle.
+    pub highest_snapshots: HighestSnapshots,
+    /// The blocks jar, which holds the body indices, ommers and withdrawals of every block up to
+    /// the tip, since those aren't part of any snapshot segment.
+    pub blocks: ManifestEntry,
+}
+
+impl SnapshotBundle {
+    /// Returns the path of the bundle description within the bundle directory.
+    pub fn path(directory: impl AsRef<Path>) -> PathBuf {
+        directory.as_ref().join("bundle.json")
+    }
+
+    /// Returns the file name of the blocks jar of a bundle with the given tip.
+    pub fn blocks_filename(tip_number: BlockNumber) -> String {
+        format!("blocks_0_{tip_number}")
+    }
+
+    /// Reads the bundle description from the bundle directory.
+    pub fn load(directory: impl AsRef<Path>) -> Result<Self, FsPathError> {
+        let path = Self::path(directory);
+        let content = fs::read(&path)?;
+        serde_json::from_slice(&content).map_err(|source| FsPathError::ReadJson { source, path })
+    }
+
+    /// Writes the bundle description to the bundle directory.
+    pub fn save(&self, directory: impl AsRef<Path>) -> Result<(), FsPathError> {
+        let path = Self::path(directory);
+        let content = serde_json::to_vec_pretty(self)
+            .map_err(|source| FsPathError::WriteJson { source, path: path.clone() })?;
+        fs::write(path, content)
+    }
+}
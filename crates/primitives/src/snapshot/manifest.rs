@@ -0,0 +1,315 @@
+use crate::{
+    fs::{self, FsPathError},
+    snapshot::SnapshotSegment,
+    BlockNumber, GotExpected, TxNumber, B256,
+};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::{
+    collections::BTreeMap,
+    fs::File,
+    io,
+    ops::RangeInclusive,
+    path::{Path, PathBuf},
+};
+
+/// How thoroughly the files of a snapshot are checked against their [`SegmentManifest`].
+#[derive(Debug, Copy, Clone, Default, Eq, PartialEq)]
+#[cfg_attr(feature = "clap", derive(clap::ValueEnum))]
+pub enum ManifestVerification {
+    /// Only checks that all files exist and have the recorded size.
+    #[default]
+    Fast,
+    /// Additionally hashes the content of all files. Reads every snapshot file in full.
+    Full,
+}
+
+/// Size and content hash of a snapshot file.
+#[derive(Debug, Copy, Clone, Default, Eq, PartialEq, Serialize, Deserialize)]
+pub struct FileChecksum {
+    /// Size of the file in bytes.
+    pub size: u64,
+    /// SHA-256 hash of the file content.
+    pub sha256: B256,
+}
+
+impl FileChecksum {
+    /// Computes the checksum of the file at the given path.
+    pub fn compute(path: impl AsRef<Path>) -> Result<Self, FsPathError> {
+        let path = path.as_ref();
+        let mut file = File::open(path).map_err(|err| FsPathError::open(err, path))?;
+        let mut hasher = Sha256::new();
+        let size = io::copy(&mut file, &mut hasher).map_err(|err| FsPathError::read(err, path))?;
+        Ok(Self { size, sha256: B256::from_slice(&hasher.finalize()) })
+    }
+}
+
+/// A snapshot of a segment as recorded in its [`SegmentManifest`].
+#[derive(Debug, Clone, Eq, PartialEq, Serialize, Deserialize)]
+pub struct ManifestEntry {
+    /// Block range covered by the snapshot.
+    pub block_range: RangeInclusive<BlockNumber>,
+    /// Transaction range covered by the snapshot.
+    pub tx_range: RangeInclusive<TxNumber>,
+    /// Checksums of the data file and its sibling files, by file name.
+    pub files: BTreeMap<String, FileChecksum>,
+}
+
+impl ManifestEntry {
+    /// Creates a new entry with the checksums of the given files. Only the file names are
+    /// recorded, since all files of a snapshot are expected to be located in the same directory.
+    pub fn new(
+        block_range: RangeInclusive<BlockNumber>,
+        tx_range: RangeInclusive<TxNumber>,
+        files: impl IntoIterator<Item = impl AsRef<Path>>,
+    ) -> Result<Self, FsPathError> {
+        let files = files
+            .into_iter()
+            .map(|path| {
+                let path = path.as_ref();
+                let name = path.file_name().unwrap_or_default().to_string_lossy().into_owned();
+                Ok((name, FileChecksum::compute(path)?))
+            })
+            .collect::<Result<_, FsPathError>>()?;
+
+        Ok(Self { block_range, tx_range, files })
+    }
+
+    /// Checks the files of the entry in the directory and returns every file that doesn't match
+    /// its recorded checksum.
+    pub fn verify(
+        &self,
+        directory: impl AsRef<Path>,
+        verification: ManifestVerification,
+    ) -> Vec<SnapshotCorruption> {
+        self.files
+            .iter()
+            .filter_map(|(name, expected)| {
+                let path = directory.as_ref().join(name);
+                verify_file(&path, expected, verification)
+                    .map(|kind| SnapshotCorruption { path, kind })
+            })
+            .collect()
+    }
+}
+
+/// Records the files of every snapshot of a [`SnapshotSegment`], alongside the block and
+/// transaction ranges they cover, so torn copies and bit-rot can be detected before the snapshots
+/// are queried.
+///
+/// It's stored next to the snapshots as `manifest_{segment}.json`.
+#[derive(Debug, Clone, Eq, PartialEq, Serialize, Deserialize)]
+pub struct SegmentManifest {
+    /// The segment of the snapshots.
+    pub segment: SnapshotSegment,
+    /// The snapshots of the segment, sorted by block range.
+    pub snapshots: Vec<ManifestEntry>,
+}
+
+impl SegmentManifest {
+    /// Creates an empty manifest for the segment.
+    pub fn new(segment: SnapshotSegment) -> Self {
+        Self { segment, snapshots: Vec::new() }
+    }
+
+    /// Returns the path of the manifest of the segment within the snapshots directory.
+    pub fn path(directory: impl AsRef<Path>, segment: SnapshotSegment) -> PathBuf {
+        directory.as_ref().join(format!("manifest_{}.json", segment.as_ref()))
+    }
+
+    /// Reads the manifest of the segment from the snapshots directory, if there is one.
+    pub fn load(
+        directory: impl AsRef<Path>,
+        segment: SnapshotSegment,
+    ) -> Result<Option<Self>, FsPathError> {
+        let path = Self::path(directory, segment);
+        if !path.exists() {
+            return Ok(None)
+        }
+
+        let content = fs::read(&path)?;
+        serde_json::from_slice(&content)
+            .map(Some)
+            .map_err(|source| FsPathError::ReadJson { source, path })
+    }
+
+    /// Writes the manifest to the snapshots directory.
+    ///
+    /// The manifest is written to a temporary file first and then moved into place, so a crash
+    /// never leaves a partially written manifest behind.
+    pub fn save(&self, directory: impl AsRef<Path>) -> Result<(), FsPathError> {
+        let path = Self::path(directory, self.segment);
+        let content = serde_json::to_vec_pretty(self)
+            .map_err(|source| FsPathError::WriteJson { source, path: path.clone() })?;
+
+        let temp_path = path.with_extension("json.tmp");
+        fs::write(&temp_path, content)?;
+        fs::rename(temp_path, path)
+    }
+
+    /// Returns the highest block covered by the recorded snapshots.
+    pub fn highest_block(&self) -> Option<BlockNumber> {
+        self.snapshots.last().map(|entry| *entry.block_range.end())
+    }
+
+    /// Records a snapshot, replacing any previous entry with the same block range start.
+    pub fn insert(&mut self, entry: ManifestEntry) {
+        self.snapshots.retain(|existing| existing.block_range.start() != entry.block_range.start());
+        self.snapshots.push(entry);
+        self.snapshots.sort_by_key(|entry| *entry.block_range.start());
+    }
+
+    /// Returns `true` if the file with the given name is recorded in the manifest.
+    pub fn contains_file(&self, name: &str) -> bool {
+        self.snapshots.iter().any(|entry| entry.files.contains_key(name))
+    }
+
+    /// Returns the snapshot data files of the segment in the directory that aren't recorded in the
+    /// manifest.
+    pub fn unlisted_files(&self, directory: impl AsRef<Path>) -> Result<Vec<PathBuf>, FsPathError> {
+        let mut unlisted = Vec::new();
+        for dir_entry in fs::read_dir(directory.as_ref())?.filter_map(Result::ok) {
+            let name = dir_entry.file_name();
+            let Some((segment, _, _)) = SnapshotSegment::parse_filename(&name) else { continue };
+
+            if segment == self.segment && !self.contains_file(name.to_string_lossy().as_ref()) {
+                unlisted.push(dir_entry.path());
+            }
+        }
+        unlisted.sort();
+        Ok(unlisted)
+    }
+
+    /// Checks the snapshots in the directory against the manifest and returns every file that
+    /// doesn't match it.
+    ///
+    /// Snapshot data files of the segment that aren't recorded in the manifest are reported as
+    /// well, since their content can't be verified.
+    pub fn verify(
+        &self,
+        directory: impl AsRef<Path>,
+        verification: ManifestVerification,
+    ) -> Result<Vec<SnapshotCorruption>, FsPathError> {
+        let directory = directory.as_ref();
+        let mut corruptions = self
+            .snapshots
+            .iter()
+            .flat_map(|entry| entry.verify(directory, verification))
+            .collect::<Vec<_>>();
+
+        corruptions.extend(
+            self.unlisted_files(directory)?
+                .into_iter()
+                .map(|path| SnapshotCorruption { path, kind: CorruptionKind::Unlisted }),
+        );
+
+        Ok(corruptions)
+    }
+}
+
+/// Checks a single file against its recorded checksum.
+fn verify_file(
+    path: &Path,
+    expected: &FileChecksum,
+    verification: ManifestVerification,
+) -> Option<CorruptionKind> {
+    let size = match std::fs::metadata(path) {
+        Ok(metadata) => metadata.len(),
+        Err(err) if err.kind() == io::ErrorKind::NotFound => return Some(CorruptionKind::Missing),
+        Err(err) => return Some(CorruptionKind::Unreadable(err.to_string())),
+    };
+
+    if size != expected.size {
+        return Some(CorruptionKind::Size(GotExpected { got: size, expected: expected.size }))
+    }
+
+    if verification == ManifestVerification::Full {
+        match FileChecksum::compute(path) {
+            Ok(checksum) if checksum.sha256 != expected.sha256 => {
+                return Some(CorruptionKind::Hash(GotExpected {
+                    got: checksum.sha256,
+                    expected: expected.sha256,
+                }))
+            }
+            Ok(_) => {}
+            Err(err) => return Some(CorruptionKind::Unreadable(err.to_string())),
+        }
+    }
+
+    None
+}
+
+/// A snapshot file that doesn't match its [`SegmentManifest`].
+#[derive(Debug, Clone, Eq, PartialEq, thiserror::Error)]
+#[error("snapshot file {path:?} is corrupted: {kind}")]
+pub struct SnapshotCorruption {
+    /// Path of the file.
+    pub path: PathBuf,
+    /// How the file differs from the manifest.
+    pub kind: CorruptionKind,
+}
+
+/// How a snapshot file differs from its [`SegmentManifest`].
+#[derive(Debug, Clone, Eq, PartialEq, thiserror::Error)]
+pub enum CorruptionKind {
+    /// The file is recorded in the manifest, but doesn't exist.
+    #[error("file is missing")]
+    Missing,
+    /// The file is a snapshot of the segment, but isn't recorded in the manifest.
+    #[error("file is not recorded in the manifest")]
+    Unlisted,
+    /// The file can't be read.
+    #[error("file is unreadable: {0}")]
+    Unreadable(String),
+    /// The size of the file differs from the manifest.
+    #[error("file size mismatch: {0}")]
+    Size(GotExpected<u64>),
+    /// The content hash of the file differs from the manifest.
+    #[error("file hash mismatch: {0}")]
+    Hash(GotExpected<B256>),
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn manifest_detects_corruption() {
+        let dir = tempfile::tempdir().unwrap();
+        let segment = SnapshotSegment::Headers;
+        let data_file = dir.path().join(segment.filename(&(0..=9), &(0..=0)));
+        let config_file = data_file.with_extension("conf");
+        std::fs::write(&data_file, b"data").unwrap();
+        std::fs::write(&config_file, b"config").unwrap();
+
+        assert_eq!(SegmentManifest::load(dir.path(), segment).unwrap(), None);
+
+        let mut manifest = SegmentManifest::new(segment);
+        manifest.insert(ManifestEntry::new(0..=9, 0..=0, [&data_file, &config_file]).unwrap());
+        manifest.save(dir.path()).unwrap();
+        assert_eq!(SegmentManifest::load(dir.path(), segment).unwrap(), Some(manifest.clone()));
+
+        for verification in [ManifestVerification::Fast, ManifestVerification::Full] {
+            assert_eq!(manifest.verify(dir.path(), verification).unwrap(), vec![]);
+        }
+
+        // Same size, different content is only noticed by the full verification.
+        std::fs::write(&data_file, b"atad").unwrap();
+        assert_eq!(manifest.verify(dir.path(), ManifestVerification::Fast).unwrap(), vec![]);
+        assert!(matches!(
+            manifest.verify(dir.path(), ManifestVerification::Full).unwrap().as_slice(),
+            [SnapshotCorruption { kind: CorruptionKind::Hash(_), .. }]
+        ));
+
+        std::fs::remove_file(&config_file).unwrap();
+        let unlisted_file = dir.path().join(segment.filename(&(10..=19), &(1..=1)));
+        std::fs::write(&unlisted_file, b"data").unwrap();
+        assert_eq!(
+            manifest.verify(dir.path(), ManifestVerification::Fast).unwrap(),
+            vec![
+                SnapshotCorruption { path: config_file, kind: CorruptionKind::Missing },
+                SnapshotCorruption { path: unlisted_file, kind: CorruptionKind::Unlisted },
+            ]
+        );
+    }
+}
@@ -1,19 +1,27 @@
 //! Snapshot primitives.
 
+mod bundle;
 mod compression;
 mod filters;
+mod manifest;
 mod segment;
 
 use alloy_primitives::BlockNumber;
+pub use bundle::SnapshotBundle;
 pub use compression::Compression;
 pub use filters::{Filters, InclusionFilter, PerfectHashingFunction};
+pub use manifest::{
+    CorruptionKind, FileChecksum, ManifestEntry, ManifestVerification, SegmentManifest,
+    SnapshotCorruption,
+};
 pub use segment::{SegmentConfig, SegmentHeader, SnapshotSegment};
+use serde::{Deserialize, Serialize};
 
 /// Default snapshot block count.
 pub const BLOCKS_PER_SNAPSHOT: u64 = 500_000;
 
 /// Highest snapshotted block numbers, per data part.
-#[derive(Debug, Clone, Copy, Default, Eq, PartialEq)]
+#[derive(Debug, Clone, Copy, Default, Eq, PartialEq, Serialize, Deserialize)]
 pub struct HighestSnapshots {
     /// Highest snapshotted block of headers, inclusive.
     /// If [`None`], no snapshot is available.
@@ -24,6 +32,12 @@ pub struct HighestSnapshots {
     /// Highest snapshotted block of transactions, inclusive.
     /// If [`None`], no snapshot is available.
     pub transactions: Option<BlockNumber>,
+    /// Highest snapshotted block of account changesets, inclusive.
+    /// If [`None`], no snapshot is available.
+    pub account_changesets: Option<BlockNumber>,
+    /// Highest snapshotted block of storage changesets, inclusive.
+    /// If [`None`], no snapshot is available.
+    pub storage_changesets: Option<BlockNumber>,
 }
 
 impl HighestSnapshots {
@@ -33,6 +47,8 @@ impl HighestSnapshots {
             SnapshotSegment::Headers => self.headers,
             SnapshotSegment::Transactions => self.transactions,
             SnapshotSegment::Receipts => self.receipts,
+            SnapshotSegment::AccountChangeSets => self.account_changesets,
+            SnapshotSegment::StorageChangeSets => self.storage_changesets,
         }
     }
 
@@ -42,6 +58,8 @@ impl HighestSnapshots {
             SnapshotSegment::Headers => &mut self.headers,
             SnapshotSegment::Transactions => &mut self.transactions,
             SnapshotSegment::Receipts => &mut self.receipts,
+            SnapshotSegment::AccountChangeSets => &mut self.account_changesets,
+            SnapshotSegment::StorageChangeSets => &mut self.storage_changesets,
         }
     }
 }
@@ -35,6 +35,12 @@ pub enum SnapshotSegment {
     #[strum(serialize = "receipts")]
     /// Snapshot segment responsible for the `Receipts` table.
     Receipts,
+    #[strum(serialize = "account-changesets")]
+    /// Snapshot segment responsible for the `AccountChangeSet` table.
+    AccountChangeSets,
+    #[strum(serialize = "storage-changesets")]
+    /// Snapshot segment responsible for the `StorageChangeSet` table.
+    StorageChangeSets,
 }
 
 impl SnapshotSegment {
@@ -52,6 +58,21 @@ impl SnapshotSegment {
             SnapshotSegment::Headers => default_config,
             SnapshotSegment::Transactions => default_config,
             SnapshotSegment::Receipts => default_config,
+            // Rows are only queried by block number
+            SnapshotSegment::AccountChangeSets | SnapshotSegment::StorageChangeSets => {
+                SegmentConfig { filters: Filters::WithoutFilters, ..default_config }
+            }
+        }
+    }
+
+    /// Returns `true` if the rows of the segment are indexed by block number, and `false` if they
+    /// are indexed by transaction number.
+    pub const fn is_block_based(&self) -> bool {
+        match self {
+            SnapshotSegment::Headers |
+            SnapshotSegment::AccountChangeSets |
+            SnapshotSegment::StorageChangeSets => true,
+            SnapshotSegment::Transactions | SnapshotSegment::Receipts => false,
         }
     }
 
@@ -181,9 +202,10 @@ impl SegmentHeader {
 
     /// Returns the row offset which depends on whether the segment is block or transaction based.
     pub fn start(&self) -> u64 {
-        match self.segment {
-            SnapshotSegment::Headers => self.block_start(),
-            SnapshotSegment::Transactions | SnapshotSegment::Receipts => self.tx_start(),
+        if self.segment.is_block_based() {
+            self.block_start()
+        } else {
+            self.tx_start()
         }
     }
 }
@@ -212,6 +234,13 @@ mod tests {
                 "snapshot_receipts_30_300_110_1000",
                 None,
             ),
+            (
+                SnapshotSegment::StorageChangeSets,
+                30..=300,
+                110..=1000,
+                "snapshot_storage-changesets_30_300_110_1000",
+                None,
+            ),
             (
                 SnapshotSegment::Transactions,
                 1_123_233..=11_223_233,
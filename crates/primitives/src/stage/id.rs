@@ -27,6 +27,10 @@ pub enum StageId {
     IndexStorageHistory,
     /// Index account history stage in the process.
     IndexAccountHistory,
+    /// Optional stage indexing the transactions in which addresses appear.
+    IndexAddressAppearances,
+    /// Optional stage indexing the blocks in which addresses and topics were logged.
+    IndexLogs,
     /// Finish stage in the process.
     Finish,
     /// Other custom stage with a provided string identifier.
@@ -51,6 +55,12 @@ impl StageId {
         StageId::Finish,
     ];
 
+    /// Optional stages that are not part of the default pipeline.
+    ///
+    /// Their checkpoints are only kept up to date once they have been written for the first time,
+    /// i.e. once the stage was enabled.
+    pub const OPTIONAL: [StageId; 2] = [StageId::IndexAddressAppearances, StageId::IndexLogs];
+
     /// Return stage id formatted as string.
     pub fn as_str(&self) -> &str {
         match self {
@@ -66,6 +76,8 @@ impl StageId {
             StageId::TransactionLookup => "TransactionLookup",
             StageId::IndexAccountHistory => "IndexAccountHistory",
             StageId::IndexStorageHistory => "IndexStorageHistory",
+            StageId::IndexAddressAppearances => "IndexAddressAppearances",
+            StageId::IndexLogs => "IndexLogs",
             StageId::Finish => "Finish",
             StageId::Other(s) => s,
         }
@@ -106,6 +118,8 @@ mod tests {
         assert_eq!(StageId::IndexAccountHistory.to_string(), "IndexAccountHistory");
         assert_eq!(StageId::IndexStorageHistory.to_string(), "IndexStorageHistory");
         assert_eq!(StageId::TransactionLookup.to_string(), "TransactionLookup");
+        assert_eq!(StageId::IndexAddressAppearances.to_string(), "IndexAddressAppearances");
+        assert_eq!(StageId::IndexLogs.to_string(), "IndexLogs");
         assert_eq!(StageId::Finish.to_string(), "Finish");
 
         assert_eq!(StageId::Other("Foo").to_string(), "Foo");
@@ -6,7 +6,7 @@ use crate::{
     Metrics, PrunerError, PrunerEvent,
 };
 use reth_db::database::Database;
-use reth_primitives::{BlockNumber, PruneMode, PruneProgress, PruneSegment};
+use reth_primitives::{BlockNumber, PruneMode, PruneProgress};
 use reth_provider::{ProviderFactory, PruneCheckpointReader};
 use reth_snapshot::HighestSnapshotsTracker;
 use reth_tokio_util::EventListeners;
@@ -20,6 +20,9 @@ pub type PrunerResult = Result<PruneProgress, PrunerError>;
 /// The pruner type itself with the result of [Pruner::run]
 pub type PrunerWithResult<DB> = (Pruner<DB>, PrunerResult);
 
+/// Creates a segment that prunes with the given mode.
+type SegmentFactory<DB> = fn(PruneMode) -> Box<dyn Segment<DB>>;
+
 /// Pruning routine. Main pruning logic happens in [Pruner::run].
 #[derive(Debug)]
 pub struct Pruner<DB> {
@@ -152,51 +155,38 @@ impl<DB: Database> Pruner<DB> {
         }
 
         if let Some(snapshots) = highest_snapshots {
-            if let (Some(to_block), true) = (snapshots.headers, delete_limit > 0) {
-                let prune_mode = PruneMode::Before(to_block + 1);
-                trace!(
-                    target: "pruner",
-                    prune_segment = ?PruneSegment::Headers,
-                    %to_block,
-                    ?prune_mode,
-                    "Got target block to prune"
-                );
-
-                let segment_start = Instant::now();
-                let segment = segments::Headers::new(prune_mode);
-                let previous_checkpoint = provider.get_prune_checkpoint(PruneSegment::Headers)?;
-                let output = segment
-                    .prune(&provider, PruneInput { previous_checkpoint, to_block, delete_limit })?;
-                if let Some(checkpoint) = output.checkpoint {
-                    segment
-                        .save_checkpoint(&provider, checkpoint.as_prune_checkpoint(prune_mode))?;
+            // Snapshotted data is pruned up to the highest snapshotted block of its segment.
+            // Changesets are pruned without their history indices, so that historical state is
+            // still looked up in the snapshots.
+            let snapshotted_segments: [(Option<BlockNumber>, SegmentFactory<DB>); 4] = [
+                (snapshots.headers, |mode| Box::new(segments::Headers::new(mode))),
+                (snapshots.transactions, |mode| Box::new(segments::Transactions::new(mode))),
+                (snapshots.account_changesets, |mode| {
+                    Box::new(segments::AccountChangeSets::new(mode))
+                }),
+                (snapshots.storage_changesets, |mode| {
+                    Box::new(segments::StorageChangeSets::new(mode))
+                }),
+            ];
+
+            for (to_block, segment) in snapshotted_segments {
+                if delete_limit == 0 {
+                    break
                 }
-                self.metrics
-                    .get_prune_segment_metrics(PruneSegment::Headers)
-                    .duration_seconds
-                    .record(segment_start.elapsed());
-
-                done = done && output.done;
-                delete_limit = delete_limit.saturating_sub(output.pruned);
-                stats.insert(
-                    PruneSegment::Headers,
-                    (PruneProgress::from_done(output.done), output.pruned),
-                );
-            }
 
-            if let (Some(to_block), true) = (snapshots.transactions, delete_limit > 0) {
+                let Some(to_block) = to_block else { continue };
                 let prune_mode = PruneMode::Before(to_block + 1);
+                let segment = segment(prune_mode);
                 trace!(
                     target: "pruner",
-                    prune_segment = ?PruneSegment::Transactions,
+                    prune_segment = ?segment.segment(),
                     %to_block,
                     ?prune_mode,
                     "Got target block to prune"
                 );
 
                 let segment_start = Instant::now();
-                let segment = segments::Transactions::new(prune_mode);
-                let previous_checkpoint = provider.get_prune_checkpoint(PruneSegment::Headers)?;
+                let previous_checkpoint = provider.get_prune_checkpoint(segment.segment())?;
                 let output = segment
                     .prune(&provider, PruneInput { previous_checkpoint, to_block, delete_limit })?;
                 if let Some(checkpoint) = output.checkpoint {
@@ -204,14 +194,14 @@ impl<DB: Database> Pruner<DB> {
                         .save_checkpoint(&provider, checkpoint.as_prune_checkpoint(prune_mode))?;
                 }
                 self.metrics
-                    .get_prune_segment_metrics(PruneSegment::Transactions)
+                    .get_prune_segment_metrics(segment.segment())
                     .duration_seconds
                     .record(segment_start.elapsed());
 
                 done = done && output.done;
                 delete_limit = delete_limit.saturating_sub(output.pruned);
                 stats.insert(
-                    PruneSegment::Transactions,
+                    segment.segment(),
                     (PruneProgress::from_done(output.done), output.pruned),
                 );
             }
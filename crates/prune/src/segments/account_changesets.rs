@@ -0,0 +1,153 @@
+use crate::{
+    segments::{PruneInput, PruneOutput, PruneOutputCheckpoint, Segment},
+    PrunerError,
+};
+use reth_db::{database::Database, tables};
+use reth_primitives::{PruneMode, PruneSegment};
+use reth_provider::DatabaseProviderRW;
+use tracing::{instrument, trace};
+
+/// Prunes the account changesets of snapshotted blocks, keeping the `AccountHistory` indices, so
+/// that historical reads are answered from the snapshots.
+#[derive(Debug)]
+pub struct AccountChangeSets {
+    mode: PruneMode,
+}
+
+impl AccountChangeSets {
+    pub fn new(mode: PruneMode) -> Self {
+        Self { mode }
+    }
+}
+
+impl<DB: Database> Segment<DB> for AccountChangeSets {
+    fn segment(&self) -> PruneSegment {
+        PruneSegment::AccountChangeSets
+    }
+
+    fn mode(&self) -> Option<PruneMode> {
+        Some(self.mode)
+    }
+
+    #[instrument(level = "trace", target = "pruner", skip(self, provider), ret)]
+    fn prune(
+        &self,
+        provider: &DatabaseProviderRW<DB>,
+        input: PruneInput,
+    ) -> Result<PruneOutput, PrunerError> {
+        let range = match input.get_next_block_range() {
+            Some(range) => range,
+            None => {
+                trace!(target: "pruner", "No account changesets to prune");
+                return Ok(PruneOutput::done())
+            }
+        };
+        let range_end = *range.end();
+
+        let mut last_pruned_block = None;
+        let (pruned, done) = provider.prune_table_with_range::<tables::AccountChangeSet>(
+            range,
+            input.delete_limit,
+            |_| false,
+            |row| last_pruned_block = Some(row.0),
+        )?;
+        trace!(target: "pruner", %pruned, %done, "Pruned account changesets");
+
+        let last_pruned_block = last_pruned_block
+            // If there's more account changesets to prune, set the checkpoint block number to
+            // previous, so we could finish pruning its account changesets on the next run.
+            .map(|block_number| if done { block_number } else { block_number.saturating_sub(1) })
+            .unwrap_or(range_end);
+
+        Ok(PruneOutput {
+            done,
+            pruned,
+            checkpoint: Some(PruneOutputCheckpoint {
+                block_number: Some(last_pruned_block),
+                tx_number: None,
+            }),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::segments::{AccountChangeSets, PruneInput, PruneOutput, Segment};
+    use assert_matches::assert_matches;
+    use reth_db::{database::Database, tables};
+    use reth_interfaces::{
+        provider::ProviderResult,
+        test_utils::{
+            generators,
+            generators::{random_block_range, random_changeset_range, random_eoa_account_range},
+        },
+    };
+    use reth_primitives::{Account, Address, PruneMode, B256};
+    use reth_provider::{AccountReader, ProviderFactory};
+    use reth_snapshot::segments::{self as snapshot_segments, Segment as _};
+    use reth_stages::test_utils::TestStageDB;
+    use tokio::sync::watch;
+
+    /// Returns the accounts after block 0, which are read from the changesets of block 1.
+    fn accounts_after_genesis<DB: Database>(
+        factory: &ProviderFactory<DB>,
+        addresses: &[Address],
+    ) -> ProviderResult<Vec<Option<Account>>> {
+        let state = factory.history_by_block_number(0)?;
+        addresses.iter().map(|address| state.basic_account(*address)).collect()
+    }
+
+    #[test]
+    fn prune_snapshotted() {
+        let db = TestStageDB::default();
+        let mut rng = generators::rng();
+
+        let blocks = random_block_range(&mut rng, 0..=3, B256::ZERO, 0..1);
+        db.insert_blocks(blocks.iter(), None).expect("insert blocks");
+
+        let accounts = random_eoa_account_range(&mut rng, 0..3);
+        let addresses = accounts.iter().map(|(address, _)| *address).collect::<Vec<_>>();
+        let (changesets, _) = random_changeset_range(
+            &mut rng,
+            blocks.iter(),
+            accounts.into_iter().map(|(address, account)| (address, (account, Vec::new()))),
+            0..0,
+            0..0,
+        );
+        db.insert_changesets(changesets.clone(), None).expect("insert changesets");
+        db.insert_history(changesets.clone(), None).expect("insert history");
+        let expected = accounts_after_genesis(&db.factory, &addresses).unwrap();
+
+        let snapshots = tempfile::tempdir().unwrap();
+        snapshot_segments::AccountChangeSets::default()
+            .snapshot(&db.factory.provider().unwrap(), snapshots.path(), 0..=1)
+            .expect("snapshot changesets");
+
+        let history = db.table::<tables::AccountHistory>().unwrap();
+        let segment = AccountChangeSets::new(PruneMode::Before(2));
+        let input = PruneInput { previous_checkpoint: None, to_block: 1, delete_limit: 100 };
+        let provider = db.factory.provider_rw().unwrap();
+        let result = segment.prune(&provider, input).unwrap();
+        assert_matches!(
+            result,
+            PruneOutput { done: true, pruned, checkpoint: Some(_) }
+                if pruned == changesets[..2].iter().flatten().count()
+        );
+        provider.commit().expect("commit");
+
+        let changesets_left = db.table::<tables::AccountChangeSet>().unwrap();
+        assert_eq!(changesets_left.len(), changesets[2..].iter().flatten().count());
+        assert!(changesets_left.iter().all(|(block_number, _)| *block_number >= 2));
+        // Indices are kept
+        assert_eq!(db.table::<tables::AccountHistory>().unwrap(), history);
+
+        // Historical reads need the snapshotted changesets
+        assert!(accounts_after_genesis(&db.factory, &addresses).is_err());
+        let factory = db
+            .factory
+            .clone()
+            .with_snapshots(snapshots.path().to_path_buf(), watch::channel(None).1)
+            .unwrap();
+        assert_eq!(accounts_after_genesis(&factory, &addresses).unwrap(), expected);
+    }
+}
@@ -0,0 +1,165 @@
+use crate::{
+    segments::{
+        history::prune_history_indices, PruneInput, PruneOutput, PruneOutputCheckpoint, Segment,
+    },
+    PrunerError,
+};
+use reth_db::{database::Database, models::ShardedKey, tables};
+use reth_primitives::{PruneMode, PruneSegment};
+use reth_provider::DatabaseProviderRW;
+use tracing::{instrument, trace};
+
+#[derive(Debug)]
+pub struct AddressAppearances {
+    mode: PruneMode,
+}
+
+impl AddressAppearances {
+    pub fn new(mode: PruneMode) -> Self {
+        Self { mode }
+    }
+}
+
+impl<DB: Database> Segment<DB> for AddressAppearances {
+    fn segment(&self) -> PruneSegment {
+        PruneSegment::AddressAppearances
+    }
+
+    fn mode(&self) -> Option<PruneMode> {
+        Some(self.mode)
+    }
+
+    #[instrument(level = "trace", target = "pruner", skip(self, provider), ret)]
+    fn prune(
+        &self,
+        provider: &DatabaseProviderRW<DB>,
+        input: PruneInput,
+    ) -> Result<PruneOutput, PrunerError> {
+        let tx_range = match input.get_next_tx_num_range(provider)? {
+            Some(range) => range,
+            None => {
+                trace!(target: "pruner", "No address appearances to prune");
+                return Ok(PruneOutput::done())
+            }
+        };
+        let tx_range_end = *tx_range.end();
+
+        // The index shards are keyed by transaction numbers, so the same logic as for the
+        // block-based history indices applies.
+        let (processed, pruned) = prune_history_indices::<DB, tables::AddressAppearances, _>(
+            provider,
+            tx_range_end,
+            |a, b| a.key == b.key,
+            |key| ShardedKey::last(key.key),
+        )?;
+        trace!(target: "pruner", %processed, %pruned, "Pruned address appearances");
+
+        // The recorded internal calls are only read when indexing, so they go along with the
+        // index.
+        let (pruned_calls, _) = provider.prune_table_with_range::<tables::CallParticipants>(
+            tx_range,
+            usize::MAX,
+            |_| false,
+            |_| {},
+        )?;
+        trace!(target: "pruner", pruned = %pruned_calls, "Pruned call participants");
+
+        Ok(PruneOutput {
+            done: true,
+            pruned: pruned + pruned_calls,
+            checkpoint: Some(PruneOutputCheckpoint {
+                block_number: Some(input.to_block),
+                tx_number: Some(tx_range_end),
+            }),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::segments::{AddressAppearances, PruneInput, PruneOutput, Segment};
+    use assert_matches::assert_matches;
+    use reth_db::{tables, transaction::DbTxMut};
+    use reth_interfaces::test_utils::{generators, generators::random_block_range};
+    use reth_primitives::{Address, BlockNumber, PruneCheckpoint, PruneMode, PruneSegment, B256};
+    use reth_provider::{AddressAppearancesReader, HistoryWriter, PruneCheckpointReader};
+    use reth_stages::test_utils::TestStageDB;
+
+    #[test]
+    fn prune() {
+        let db = TestStageDB::default();
+        let mut rng = generators::rng();
+
+        let blocks = random_block_range(&mut rng, 1..=100, B256::ZERO, 1..3);
+        db.insert_blocks(blocks.iter(), None).expect("insert blocks");
+        let tx_count = blocks.iter().map(|block| block.body.len() as u64).sum::<u64>();
+        db.commit(|tx| {
+            for tx_number in 0..tx_count {
+                tx.put::<tables::CallParticipants>(tx_number, Address::with_last_byte(1))?;
+            }
+            Ok(())
+        })
+        .expect("insert call participants");
+
+        let provider = db.factory.provider_rw().unwrap();
+        let appearances = provider.address_appearances_with_range(1..=100).unwrap();
+        provider.insert_address_appearances_index(appearances).unwrap();
+        provider.commit().expect("commit");
+
+        let to_block: BlockNumber = 50;
+        let prune_mode = PruneMode::Before(to_block + 1);
+        let input = PruneInput {
+            previous_checkpoint: db
+                .factory
+                .provider()
+                .unwrap()
+                .get_prune_checkpoint(PruneSegment::AddressAppearances)
+                .unwrap(),
+            to_block,
+            delete_limit: usize::MAX,
+        };
+        let segment = AddressAppearances::new(prune_mode);
+
+        let provider = db.factory.provider_rw().unwrap();
+        let result = segment.prune(&provider, input).unwrap();
+        assert_matches!(result, PruneOutput { done: true, checkpoint: Some(_), .. });
+        segment
+            .save_checkpoint(&provider, result.checkpoint.unwrap().as_prune_checkpoint(prune_mode))
+            .unwrap();
+        provider.commit().expect("commit");
+
+        let last_pruned_tx_number = blocks
+            .iter()
+            .take(to_block as usize)
+            .map(|block| block.body.len() as u64)
+            .sum::<u64>()
+            .checked_sub(1)
+            .unwrap();
+
+        // Only transactions after the pruned one are left in the index.
+        assert!(db
+            .table::<tables::AddressAppearances>()
+            .unwrap()
+            .into_iter()
+            .flat_map(|(_, list)| list.iter(0).collect::<Vec<_>>())
+            .all(|tx_number| tx_number as u64 > last_pruned_tx_number));
+        assert!(db
+            .table::<tables::CallParticipants>()
+            .unwrap()
+            .into_iter()
+            .all(|(tx_number, _)| tx_number > last_pruned_tx_number));
+
+        assert_eq!(
+            db.factory
+                .provider()
+                .unwrap()
+                .get_prune_checkpoint(PruneSegment::AddressAppearances)
+                .unwrap(),
+            Some(PruneCheckpoint {
+                block_number: Some(to_block),
+                tx_number: Some(last_pruned_tx_number),
+                prune_mode
+            })
+        );
+    }
+}
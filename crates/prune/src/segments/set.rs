@@ -1,6 +1,6 @@
 use crate::segments::{
-    AccountHistory, Receipts, ReceiptsByLogs, Segment, SenderRecovery, StorageHistory,
-    TransactionLookup,
+    AccountHistory, AddressAppearances, Receipts, ReceiptsByLogs, Segment, SenderRecovery,
+    StorageHistory, TransactionLookup,
 };
 use reth_db::database::Database;
 use reth_primitives::PruneModes;
@@ -45,6 +45,7 @@ impl<DB: Database> SegmentSet<DB> {
             receipts,
             account_history,
             storage_history,
+            address_appearances,
             receipts_log_filter,
         } = prune_modes;
 
@@ -64,6 +65,8 @@ impl<DB: Database> SegmentSet<DB> {
             .segment_opt(account_history.map(AccountHistory::new))
             // Storage history
             .segment_opt(storage_history.map(StorageHistory::new))
+            // Address appearances
+            .segment_opt(address_appearances.map(AddressAppearances::new))
     }
 }
 
@@ -0,0 +1,164 @@
+use crate::{
+    segments::{PruneInput, PruneOutput, PruneOutputCheckpoint, Segment},
+    PrunerError,
+};
+use reth_db::{database::Database, models::BlockNumberAddress, tables};
+use reth_primitives::{PruneMode, PruneSegment};
+use reth_provider::DatabaseProviderRW;
+use tracing::{instrument, trace};
+
+/// Prunes the storage changesets of snapshotted blocks, keeping the `StorageHistory` indices, so
+/// that historical reads are answered from the snapshots.
+#[derive(Debug)]
+pub struct StorageChangeSets {
+    mode: PruneMode,
+}
+
+impl StorageChangeSets {
+    pub fn new(mode: PruneMode) -> Self {
+        Self { mode }
+    }
+}
+
+impl<DB: Database> Segment<DB> for StorageChangeSets {
+    fn segment(&self) -> PruneSegment {
+        PruneSegment::StorageChangeSets
+    }
+
+    fn mode(&self) -> Option<PruneMode> {
+        Some(self.mode)
+    }
+
+    #[instrument(level = "trace", target = "pruner", skip(self, provider), ret)]
+    fn prune(
+        &self,
+        provider: &DatabaseProviderRW<DB>,
+        input: PruneInput,
+    ) -> Result<PruneOutput, PrunerError> {
+        let range = match input.get_next_block_range() {
+            Some(range) => range,
+            None => {
+                trace!(target: "pruner", "No storage changesets to prune");
+                return Ok(PruneOutput::done())
+            }
+        };
+        let range_end = *range.end();
+
+        let mut last_pruned_block = None;
+        let (pruned, done) = provider.prune_table_with_range::<tables::StorageChangeSet>(
+            BlockNumberAddress::range(range),
+            input.delete_limit,
+            |_| false,
+            |row| last_pruned_block = Some(row.0.block_number()),
+        )?;
+        trace!(target: "pruner", %pruned, %done, "Pruned storage changesets");
+
+        let last_pruned_block = last_pruned_block
+            // If there's more storage changesets to prune, set the checkpoint block number to
+            // previous, so we could finish pruning its storage changesets on the next run.
+            .map(|block_number| if done { block_number } else { block_number.saturating_sub(1) })
+            .unwrap_or(range_end);
+
+        Ok(PruneOutput {
+            done,
+            pruned,
+            checkpoint: Some(PruneOutputCheckpoint {
+                block_number: Some(last_pruned_block),
+                tx_number: None,
+            }),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::segments::{PruneInput, PruneOutput, Segment, StorageChangeSets};
+    use assert_matches::assert_matches;
+    use reth_db::{database::Database, tables};
+    use reth_interfaces::{
+        provider::ProviderResult,
+        test_utils::{
+            generators,
+            generators::{
+                random_block_range, random_changeset_range, random_eoa_account_range, ChangeSet,
+            },
+        },
+    };
+    use reth_primitives::{Address, PruneMode, StorageKey, StorageValue, B256};
+    use reth_provider::{ProviderFactory, StateProvider};
+    use reth_snapshot::segments::{self as snapshot_segments, Segment as _};
+    use reth_stages::test_utils::TestStageDB;
+    use tokio::sync::watch;
+
+    /// Returns the storage slots after block 0, which are read from the changesets of block 1.
+    fn storage_after_genesis<DB: Database>(
+        factory: &ProviderFactory<DB>,
+        slots: &[(Address, StorageKey)],
+    ) -> ProviderResult<Vec<Option<StorageValue>>> {
+        let state = factory.history_by_block_number(0)?;
+        slots.iter().map(|(address, key)| state.storage(*address, *key)).collect()
+    }
+
+    #[test]
+    fn prune_snapshotted() {
+        let db = TestStageDB::default();
+        let mut rng = generators::rng();
+
+        let blocks = random_block_range(&mut rng, 0..=3, B256::ZERO, 0..1);
+        db.insert_blocks(blocks.iter(), None).expect("insert blocks");
+
+        let accounts = random_eoa_account_range(&mut rng, 0..3);
+        let (changesets, _) = random_changeset_range(
+            &mut rng,
+            blocks.iter(),
+            accounts.into_iter().map(|(address, account)| (address, (account, Vec::new()))),
+            1..2,
+            0..256,
+        );
+        db.insert_changesets(changesets.clone(), None).expect("insert changesets");
+        db.insert_history(changesets.clone(), None).expect("insert history");
+
+        let storage_changes = |blocks: &[ChangeSet]| {
+            blocks
+                .iter()
+                .flatten()
+                .flat_map(|(address, _, entries)| entries.iter().map(|entry| (*address, entry.key)))
+                .collect::<Vec<_>>()
+        };
+        let slots = storage_changes(&changesets[1..2]);
+        assert!(!slots.is_empty());
+        let expected = storage_after_genesis(&db.factory, &slots).unwrap();
+
+        let snapshots = tempfile::tempdir().unwrap();
+        snapshot_segments::StorageChangeSets::default()
+            .snapshot(&db.factory.provider().unwrap(), snapshots.path(), 0..=1)
+            .expect("snapshot changesets");
+
+        let history = db.table::<tables::StorageHistory>().unwrap();
+        let segment = StorageChangeSets::new(PruneMode::Before(2));
+        let input = PruneInput { previous_checkpoint: None, to_block: 1, delete_limit: 100 };
+        let provider = db.factory.provider_rw().unwrap();
+        let result = segment.prune(&provider, input).unwrap();
+        assert_matches!(
+            result,
+            PruneOutput { done: true, pruned, checkpoint: Some(_) }
+                if pruned == storage_changes(&changesets[..2]).len()
+        );
+        provider.commit().expect("commit");
+
+        let changesets_left = db.table::<tables::StorageChangeSet>().unwrap();
+        assert_eq!(changesets_left.len(), storage_changes(&changesets[2..]).len());
+        assert!(changesets_left.iter().all(|(key, _)| key.block_number() >= 2));
+        // Indices are kept
+        assert_eq!(db.table::<tables::StorageHistory>().unwrap(), history);
+
+        // Historical reads need the snapshotted changesets
+        assert!(storage_after_genesis(&db.factory, &slots).is_err());
+        let factory = db
+            .factory
+            .clone()
+            .with_snapshots(snapshots.path().to_path_buf(), watch::channel(None).1)
+            .unwrap();
+        assert_eq!(storage_after_genesis(&factory, &slots).unwrap(), expected);
+    }
+}
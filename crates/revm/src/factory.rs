@@ -5,7 +5,7 @@ use crate::{
 };
 use reth_node_api::ConfigureEvmEnv;
 use reth_primitives::ChainSpec;
-use reth_provider::{ExecutorFactory, PrunableBlockExecutor, StateProvider};
+use reth_provider::{BlockExecutor, ExecutorFactory, PrunableBlockExecutor, StateProvider};
 use std::sync::Arc;
 
 /// Factory for creating [EVMProcessor].
@@ -13,6 +13,10 @@ use std::sync::Arc;
 pub struct EvmProcessorFactory<EvmConfig> {
     chain_spec: Arc<ChainSpec>,
     stack: Option<InspectorStack>,
+    /// Whether generated executors record the contracts created by executed transactions.
+    record_contract_creations: bool,
+    /// Whether generated executors record the accounts taking part in internal calls.
+    record_call_participants: bool,
     /// Type that defines how the produced EVM should be configured.
     evm_config: EvmConfig,
 }
@@ -20,7 +24,13 @@ pub struct EvmProcessorFactory<EvmConfig> {
 impl<EvmConfig> EvmProcessorFactory<EvmConfig> {
     /// Create new factory
     pub fn new(chain_spec: Arc<ChainSpec>, evm_config: EvmConfig) -> Self {
-        Self { chain_spec, stack: None, evm_config }
+        Self {
+            chain_spec,
+            stack: None,
+            record_contract_creations: false,
+            record_call_participants: false,
+            evm_config,
+        }
     }
 
     /// Sets the inspector stack for all generated executors.
@@ -34,6 +44,23 @@ impl<EvmConfig> EvmProcessorFactory<EvmConfig> {
         self.stack = Some(InspectorStack::new(config));
         self
     }
+
+    /// Enables or disables recording of contract creations for all generated executors.
+    ///
+    /// See [BlockExecutor::set_record_contract_creations].
+    pub fn with_contract_creations(mut self, record: bool) -> Self {
+        self.record_contract_creations = record;
+        self
+    }
+
+    /// Enables or disables recording of the accounts taking part in internal calls for all
+    /// generated executors.
+    ///
+    /// See [BlockExecutor::set_record_call_participants].
+    pub fn with_call_participants(mut self, record: bool) -> Self {
+        self.record_call_participants = record;
+        self
+    }
 }
 
 impl<EvmConfig> ExecutorFactory for EvmProcessorFactory<EvmConfig>
@@ -53,6 +80,8 @@ where
         if let Some(ref stack) = self.stack {
             evm.set_stack(stack.clone());
         }
+        evm.set_record_contract_creations(self.record_contract_creations);
+        evm.set_record_call_participants(self.record_call_participants);
         evm
     }
 
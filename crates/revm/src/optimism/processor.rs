@@ -6,7 +6,7 @@ use reth_node_api::ConfigureEvmEnv;
 use reth_primitives::{revm_primitives::ResultAndState, BlockWithSenders, Hardfork, Receipt, U256};
 use reth_provider::{BlockExecutor, BlockExecutorStats, BundleStateWithReceipts};
 use revm::DatabaseCommit;
-use std::time::Instant;
+use std::{collections::BTreeMap, time::Instant};
 use tracing::{debug, trace};
 
 impl<'a, EvmConfig> BlockExecutor for EVMProcessor<'a, EvmConfig>
@@ -110,7 +110,8 @@ where
                 .map_err(|_| BlockExecutionError::ProviderError)?;
 
             // Execute transaction.
-            let ResultAndState { result, state } = self.transact(transaction, *sender)?;
+            let ResultAndState { result, state } =
+                self.transact_and_record(block.number, transaction, *sender)?;
             trace!(
                 target: "evm",
                 ?transaction, ?result, ?state,
@@ -158,6 +159,8 @@ where
             receipts,
             self.first_block.unwrap_or_default(),
         )
+        .with_contract_creations(self.take_contract_creations())
+        .with_call_participants(self.take_call_participants())
     }
 
     fn stats(&self) -> BlockExecutorStats {
@@ -167,4 +170,12 @@ where
     fn size_hint(&self) -> Option<usize> {
         Some(self.evm.context.evm.db.bundle_size_hint())
     }
+
+    fn set_record_contract_creations(&mut self, record: bool) {
+        self.contract_creations = record.then(Vec::new);
+    }
+
+    fn set_record_call_participants(&mut self, record: bool) {
+        self.call_participants = record.then(BTreeMap::new);
+    }
 }
@@ -3,35 +3,40 @@ use crate::{
     eth_dao_fork::{DAO_HARDFORK_BENEFICIARY, DAO_HARDKFORK_ACCOUNTS},
     stack::{InspectorStack, InspectorStackConfig},
     state_change::{apply_beacon_root_contract_call, post_block_balance_increments},
+    tracing::{types::CallKind, TracingInspector, TracingInspectorConfig},
+};
+use reth_interfaces::{
+    executor::{BlockExecutionError, BlockValidationError},
+    RethResult,
 };
-use reth_interfaces::executor::{BlockExecutionError, BlockValidationError};
 use reth_node_api::ConfigureEvmEnv;
 use reth_primitives::{
-    Address, Block, BlockNumber, BlockWithSenders, Bloom, ChainSpec, GotExpected, Hardfork, Header,
-    PruneMode, PruneModes, PruneSegmentError, Receipt, ReceiptWithBloom, Receipts,
-    TransactionSigned, Withdrawals, B256, MINIMUM_PRUNING_DISTANCE, U256,
+    Address, Block, BlockNumber, BlockWithSenders, Bloom, ChainSpec, ContractCreation, GotExpected,
+    Hardfork, Header, PruneMode, PruneModes, PruneSegmentError, Receipt, ReceiptWithBloom,
+    Receipts, TransactionSigned, Withdrawals, B256, MINIMUM_PRUNING_DISTANCE, U256,
 };
 use reth_provider::{
-    BlockExecutor, BlockExecutorStats, ProviderError, PrunableBlockExecutor, StateProvider,
+    BlockExecutor, BlockExecutorStats, BundleStateWithReceipts, ProviderError,
+    PrunableBlockExecutor, StateProvider, StateRootProvider,
 };
 use revm::{
     db::{states::bundle_state::BundleRetention, EmptyDBTyped, StateDBBox},
     inspector_handle_register,
     interpreter::Host,
-    primitives::{CfgEnvWithHandlerCfg, ResultAndState},
-    Evm, State, StateBuilder,
+    primitives::{CfgEnvWithHandlerCfg, EnvWithHandlerCfg, ResultAndState},
+    DatabaseCommit, Evm, State, StateBuilder,
+};
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    sync::Arc,
+    time::Instant,
 };
-use std::{sync::Arc, time::Instant};
 
 #[cfg(feature = "optimism")]
 use reth_primitives::revm::env::fill_op_tx_env;
 #[cfg(not(feature = "optimism"))]
 use reth_primitives::revm::env::fill_tx_env;
 
-#[cfg(not(feature = "optimism"))]
-use reth_provider::BundleStateWithReceipts;
-#[cfg(not(feature = "optimism"))]
-use revm::DatabaseCommit;
 #[cfg(not(feature = "optimism"))]
 use tracing::{debug, trace};
 
@@ -77,6 +82,11 @@ pub struct EVMProcessor<'a, EvmConfig> {
     pruning_address_filter: Option<(u64, Vec<Address>)>,
     /// Execution stats
     pub(crate) stats: BlockExecutorStats,
+    /// Contracts created by the executed transactions, if recording is enabled.
+    pub(crate) contract_creations: Option<Vec<(Address, ContractCreation)>>,
+    /// Accounts taking part in the internal calls of the executed transactions, listed by
+    /// transaction index for each block, if recording is enabled.
+    pub(crate) call_participants: Option<BTreeMap<BlockNumber, Vec<Vec<Address>>>>,
     /// The type that is able to configure the EVM environment.
     _evm_config: EvmConfig,
 }
@@ -113,6 +123,8 @@ where
             prune_modes: PruneModes::none(),
             pruning_address_filter: None,
             stats: BlockExecutorStats::default(),
+            contract_creations: None,
+            call_participants: None,
             _evm_config: evm_config,
         }
     }
@@ -150,6 +162,8 @@ where
             prune_modes: PruneModes::none(),
             pruning_address_filter: None,
             stats: BlockExecutorStats::default(),
+            contract_creations: None,
+            call_participants: None,
             _evm_config: evm_config,
         }
     }
@@ -256,16 +270,7 @@ where
         transaction: &TransactionSigned,
         sender: Address,
     ) -> Result<ResultAndState, BlockExecutionError> {
-        // Fill revm structure.
-        #[cfg(not(feature = "optimism"))]
-        fill_tx_env(self.evm.tx_mut(), transaction, sender);
-
-        #[cfg(feature = "optimism")]
-        {
-            let mut envelope_buf = Vec::with_capacity(transaction.length_without_header());
-            transaction.encode_enveloped(&mut envelope_buf);
-            fill_op_tx_env(self.evm.tx_mut(), transaction, sender, envelope_buf.into());
-        }
+        self.fill_tx_env(transaction, sender);
 
         let hash = transaction.hash();
         let should_inspect = self.evm.context.external.should_inspect(self.evm.env(), hash);
@@ -288,6 +293,98 @@ where
         out.map_err(move |e| BlockValidationError::EVM { hash, error: e.into() }.into())
     }
 
+    /// Runs a single transaction like [Self::transact], and records the accounts taking part in
+    /// its internal calls and the contracts it creates if recording is enabled.
+    ///
+    /// Recorded transactions are traced in place of the configured inspector stack.
+    pub(crate) fn transact_and_record(
+        &mut self,
+        block_number: BlockNumber,
+        transaction: &TransactionSigned,
+        sender: Address,
+    ) -> Result<ResultAndState, BlockExecutionError> {
+        if self.call_participants.is_none() && self.contract_creations.is_none() {
+            return self.transact(transaction, sender)
+        }
+
+        self.fill_tx_env(transaction, sender);
+        #[allow(unused_mut)]
+        let mut cfg = CfgEnvWithHandlerCfg::new(self.evm.cfg().clone(), self.evm.spec_id());
+        #[cfg(feature = "optimism")]
+        {
+            cfg.handler_cfg.is_optimism = self.chain_spec.is_optimism();
+        }
+        let env = EnvWithHandlerCfg::new_with_cfg_env(
+            cfg,
+            self.evm.block().clone(),
+            self.evm.tx().clone(),
+        );
+
+        let hash = transaction.hash();
+        let mut inspector = TracingInspector::new(TracingInspectorConfig::none());
+        let out = {
+            let mut evm = Evm::builder()
+                .with_db(self.db_mut())
+                .with_external_context(&mut inspector)
+                .with_env_with_handler_cfg(env)
+                .append_handler_register(inspector_handle_register)
+                .build();
+            evm.transact().map_err(move |e| BlockValidationError::EVM { hash, error: e.into() })?
+        };
+
+        let nodes = inspector.get_traces().nodes();
+        if let Some(call_participants) = &mut self.call_participants {
+            // The first frame is the transaction itself, whose sender and recipient are known.
+            let participants = nodes
+                .iter()
+                .skip(1)
+                .flat_map(|node| [Some(node.trace.address), node.trace.selfdestruct_refund_target])
+                .flatten()
+                .collect::<BTreeSet<_>>();
+            call_participants
+                .entry(block_number)
+                .or_default()
+                .push(participants.into_iter().collect());
+        }
+        if let Some(contract_creations) = &mut self.contract_creations {
+            // The creator of a contract is the caller of the frame that created it. Contracts of
+            // reverted frames are not created in the resulting state.
+            contract_creations.extend(
+                nodes
+                    .iter()
+                    .filter(|node| {
+                        matches!(node.trace.kind, CallKind::Create | CallKind::Create2) &&
+                            out.state
+                                .get(&node.trace.address)
+                                .is_some_and(|account| account.is_created())
+                    })
+                    .map(|node| {
+                        let creation = ContractCreation {
+                            block_number,
+                            tx_hash: hash,
+                            creator: node.trace.caller,
+                        };
+                        (node.trace.address, creation)
+                    }),
+            );
+        }
+
+        Ok(out)
+    }
+
+    /// Fills the transaction environment of the EVM with the given transaction.
+    fn fill_tx_env(&mut self, transaction: &TransactionSigned, sender: Address) {
+        #[cfg(not(feature = "optimism"))]
+        fill_tx_env(self.evm.tx_mut(), transaction, sender);
+
+        #[cfg(feature = "optimism")]
+        {
+            let mut envelope_buf = Vec::with_capacity(transaction.length_without_header());
+            transaction.encode_enveloped(&mut envelope_buf);
+            fill_op_tx_env(self.evm.tx_mut(), transaction, sender, envelope_buf.into());
+        }
+    }
+
     /// Execute the block, verify gas usage and apply post-block state changes.
     pub(crate) fn execute_inner(
         &mut self,
@@ -336,6 +433,48 @@ where
         Ok(receipts)
     }
 
+    /// Executes the transactions of the block and returns the state root after each transaction.
+    ///
+    /// The roots are calculated by the given [StateRootProvider], which is expected to provide the
+    /// state this executor was created with. The changes of the pre-block beacon root contract
+    /// call are included in every root, while post-block changes such as block rewards and
+    /// withdrawals are not included in any of them.
+    pub fn intermediate_state_roots<SP: StateRootProvider>(
+        &mut self,
+        block: &BlockWithSenders,
+        total_difficulty: U256,
+        state_root_provider: SP,
+    ) -> RethResult<Vec<B256>> {
+        self.init_env(&block.header, total_difficulty);
+        self.apply_beacon_root_contract_call(block)?;
+
+        let mut roots = Vec::with_capacity(block.body.len());
+        for (sender, transaction) in block.transactions_with_sender() {
+            let ResultAndState { state, .. } = self.transact(transaction, *sender)?;
+            self.db_mut().commit(state);
+            self.db_mut().merge_transitions(BundleRetention::PlainState);
+
+            let bundle_state = BundleStateWithReceipts::new(
+                self.db_mut().bundle_state.clone(),
+                Receipts::new(),
+                block.number,
+            );
+            roots.push(state_root_provider.state_root(&bundle_state)?);
+        }
+
+        Ok(roots)
+    }
+
+    /// Returns the contracts recorded since the last call.
+    pub(crate) fn take_contract_creations(&mut self) -> Vec<(Address, ContractCreation)> {
+        self.contract_creations.as_mut().map(std::mem::take).unwrap_or_default()
+    }
+
+    /// Returns the accounts taking part in internal calls recorded since the last call.
+    pub(crate) fn take_call_participants(&mut self) -> BTreeMap<BlockNumber, Vec<Vec<Address>>> {
+        self.call_participants.as_mut().map(std::mem::take).unwrap_or_default()
+    }
+
     /// Save receipts to the executor.
     pub fn save_receipts(&mut self, receipts: Vec<Receipt>) -> Result<(), BlockExecutionError> {
         let mut receipts = receipts.into_iter().map(Option::Some).collect();
@@ -468,7 +607,8 @@ where
                 .into())
             }
             // Execute transaction.
-            let ResultAndState { result, state } = self.transact(transaction, *sender)?;
+            let ResultAndState { result, state } =
+                self.transact_and_record(block.number, transaction, *sender)?;
             trace!(
                 target: "evm",
                 ?transaction, ?result, ?state,
@@ -506,6 +646,8 @@ where
             receipts,
             self.first_block.unwrap_or_default(),
         )
+        .with_contract_creations(self.take_contract_creations())
+        .with_call_participants(self.take_call_participants())
     }
 
     fn stats(&self) -> BlockExecutorStats {
@@ -515,6 +657,14 @@ where
     fn size_hint(&self) -> Option<usize> {
         Some(self.evm.context.evm.db.bundle_size_hint())
     }
+
+    fn set_record_contract_creations(&mut self, record: bool) {
+        self.contract_creations = record.then(Vec::new);
+    }
+
+    fn set_record_call_participants(&mut self, record: bool) {
+        self.call_participants = record.then(BTreeMap::new);
+    }
 }
 
 impl<'a, EvmConfig> PrunableBlockExecutor for EVMProcessor<'a, EvmConfig>
@@ -573,15 +723,17 @@ mod tests {
     use reth_node_ethereum::EthEvmConfig;
     use reth_primitives::{
         bytes,
-        constants::{BEACON_ROOTS_ADDRESS, SYSTEM_ADDRESS},
+        constants::{BEACON_ROOTS_ADDRESS, KECCAK_EMPTY, SYSTEM_ADDRESS},
         keccak256,
         trie::AccountProof,
-        Account, Bytecode, Bytes, ChainSpecBuilder, ForkCondition, StorageKey, MAINNET,
+        Account, Bytecode, Bytes, ChainSpecBuilder, ForkCondition, Signature, StorageKey,
+        Transaction, TransactionKind, TxLegacy, MAINNET,
     };
     use reth_provider::{
-        AccountReader, BlockHashReader, BundleStateWithReceipts, StateRootProvider,
+        AccountReader, BlockHashReader, BundleStateWithReceipts, StateRangeProvider,
+        StateRootProvider,
     };
-    use reth_trie::updates::TrieUpdates;
+    use reth_trie::{updates::TrieUpdates, AccountRange, HashedPostState, StorageRange};
     use revm::{Database, TransitionState};
     use std::collections::HashMap;
 
@@ -650,6 +802,28 @@ mod tests {
         }
     }
 
+    impl StateRangeProvider for StateProviderTest {
+        fn account_range(
+            &self,
+            _post_state: &HashedPostState,
+            _start: B256,
+            _limit: usize,
+            _storage_limit: usize,
+        ) -> ProviderResult<AccountRange> {
+            unimplemented!("state range iteration is not supported")
+        }
+
+        fn storage_range(
+            &self,
+            _post_state: &HashedPostState,
+            _hashed_address: B256,
+            _start: B256,
+            _limit: usize,
+        ) -> ProviderResult<StorageRange> {
+            unimplemented!("state range iteration is not supported")
+        }
+    }
+
     impl StateProvider for StateProviderTest {
         fn storage(
             &self,
@@ -1061,4 +1235,174 @@ mod tests {
             .unwrap();
         assert_eq!(parent_beacon_block_root_storage, U256::from(0x69));
     }
+
+    #[test]
+    fn record_internal_contract_creations() {
+        let sender = Address::with_last_byte(1);
+        let factory = Address::with_last_byte(2);
+
+        let mut db = StateProviderTest::default();
+        // CREATE(0, 22, 10) with init code CREATE2(0, 0, 0, 0), then CREATE2(0, 0, 0, 0)
+        db.insert_account(
+            factory,
+            Account { nonce: 1, ..Default::default() },
+            Some(bytes!("696000600060006000f500600052600a60166000f06000600060006000f500")),
+            HashMap::new(),
+        );
+
+        let mut executor = EVMProcessor::new_with_db(
+            MAINNET.clone(),
+            StateProviderDatabase::new(db),
+            EthEvmConfig::default(),
+        );
+        executor.set_record_contract_creations(true);
+
+        let block_number = 15_000_000;
+        let transaction = TransactionSigned::from_transaction_and_signature(
+            Transaction::Legacy(TxLegacy {
+                gas_limit: 500_000,
+                to: TransactionKind::Call(factory),
+                ..Default::default()
+            }),
+            Signature::default(),
+        );
+        let tx_hash = transaction.hash();
+        let block = BlockWithSenders {
+            block: Block {
+                header: Header { number: block_number, gas_limit: 1_000_000, ..Header::default() },
+                body: vec![transaction],
+                ommers: vec![],
+                withdrawals: None,
+            },
+            senders: vec![sender],
+        };
+        executor.execute_transactions(&block, U256::ZERO).unwrap();
+
+        // The factory deploys a contract with CREATE, which deploys another one with CREATE2, and
+        // then deploys one with CREATE2 itself.
+        let created_first = factory.create(1);
+        let created_nested = created_first.create2(B256::ZERO, KECCAK_EMPTY);
+        let created_second = factory.create2(B256::ZERO, KECCAK_EMPTY);
+        assert_eq!(
+            executor.take_contract_creations(),
+            vec![
+                (created_first, ContractCreation { block_number, tx_hash, creator: factory }),
+                (
+                    created_nested,
+                    ContractCreation { block_number, tx_hash, creator: created_first }
+                ),
+                (created_second, ContractCreation { block_number, tx_hash, creator: factory }),
+            ]
+        );
+    }
+
+    #[test]
+    fn record_call_participants() {
+        let sender = Address::with_last_byte(1);
+        let caller = Address::with_last_byte(2);
+        let callee = Address::with_last_byte(3);
+
+        let mut db = StateProviderTest::default();
+        // CALL(0x1000, callee, 0, 0, 0, 0, 0)
+        db.insert_account(
+            caller,
+            Account::default(),
+            Some(bytes!(
+                "60006000600060006000730000000000000000000000000000000000000003611000f100"
+            )),
+            HashMap::new(),
+        );
+        db.insert_account(callee, Account::default(), Some(bytes!("00")), HashMap::new());
+
+        let mut executor = EVMProcessor::new_with_db(
+            MAINNET.clone(),
+            StateProviderDatabase::new(db),
+            EthEvmConfig::default(),
+        );
+        executor.set_record_call_participants(true);
+
+        let block = BlockWithSenders {
+            block: Block {
+                header: Header { number: 1, gas_limit: 1_000_000, ..Header::default() },
+                body: vec![TransactionSigned::from_transaction_and_signature(
+                    Transaction::Legacy(TxLegacy {
+                        gas_limit: 100_000,
+                        to: TransactionKind::Call(caller),
+                        ..Default::default()
+                    }),
+                    Signature::default(),
+                )],
+                ommers: vec![],
+                withdrawals: None,
+            },
+            senders: vec![sender],
+        };
+        executor.execute_transactions(&block, U256::ZERO).unwrap();
+
+        assert_eq!(executor.take_call_participants(), BTreeMap::from([(1, vec![vec![callee]])]));
+    }
+
+    #[test]
+    fn intermediate_state_roots() {
+        let sender = Address::with_last_byte(1);
+        let recipient = Address::with_last_byte(2);
+
+        let mut db = StateProviderTest::default();
+        db.insert_account(
+            sender,
+            Account { balance: U256::from(10), ..Default::default() },
+            None,
+            HashMap::new(),
+        );
+
+        let mut executor = EVMProcessor::new_with_db(
+            MAINNET.clone(),
+            StateProviderDatabase::new(db),
+            EthEvmConfig::default(),
+        );
+
+        let transfer = |nonce: u64, value: u64| {
+            TransactionSigned::from_transaction_and_signature(
+                Transaction::Legacy(TxLegacy {
+                    nonce,
+                    gas_limit: 21_000,
+                    to: TransactionKind::Call(recipient),
+                    value: U256::from(value).into(),
+                    ..Default::default()
+                }),
+                Signature::default(),
+            )
+        };
+        let block = BlockWithSenders {
+            block: Block {
+                header: Header { number: 1, gas_limit: 1_000_000, ..Header::default() },
+                body: vec![transfer(0, 1), transfer(1, 2)],
+                ommers: vec![],
+                withdrawals: None,
+            },
+            senders: vec![sender, sender],
+        };
+
+        /// Uses the balance of the recipient as state root, to check which changes are included.
+        struct RecipientBalanceRoot(Address);
+
+        impl StateRootProvider for RecipientBalanceRoot {
+            fn state_root(&self, bundle_state: &BundleStateWithReceipts) -> ProviderResult<B256> {
+                let balance = bundle_state.account(&self.0).flatten().unwrap_or_default().balance;
+                Ok(balance.into())
+            }
+
+            fn state_root_with_updates(
+                &self,
+                _bundle_state: &BundleStateWithReceipts,
+            ) -> ProviderResult<(B256, TrieUpdates)> {
+                unimplemented!("state root computation is not supported")
+            }
+        }
+
+        let roots = executor
+            .intermediate_state_roots(&block, U256::ZERO, RecipientBalanceRoot(recipient))
+            .unwrap();
+        assert_eq!(roots, vec![B256::with_last_byte(1), B256::with_last_byte(3)]);
+    }
 }
@@ -1,6 +1,7 @@
 use jsonrpsee::{core::RpcResult, proc_macros::rpc};
 use reth_primitives::NodeRecord;
-use reth_rpc_types::{NodeInfo, PeerInfo};
+use reth_rpc_types::{DatabaseBackupStatus, NodeInfo, PeerInfo};
+use std::path::PathBuf;
 
 /// Admin namespace rpc interface that gives access to several non-standard RPC methods.
 #[cfg_attr(not(feature = "client"), rpc(server, namespace = "admin"))]
@@ -47,3 +48,27 @@ pub trait AdminApi {
     #[method(name = "nodeInfo")]
     async fn node_info(&self) -> RpcResult<NodeInfo>;
 }
+
+/// Admin namespace rpc interface for maintenance of the node's database.
+///
+/// These methods access the local file system, so they're only served by the authenticated server.
+#[cfg_attr(not(feature = "client"), rpc(server, namespace = "admin"))]
+#[cfg_attr(feature = "client", rpc(server, client, namespace = "admin"))]
+#[async_trait::async_trait]
+pub trait AdminBackupApi {
+    /// Starts a consistent backup of the database into the given directory, which must not contain
+    /// a database yet. If `compact` is set, free pages are omitted from the backup.
+    ///
+    /// Returns immediately, the progress can be followed with `admin_databaseBackupStatus`. Fails
+    /// if another backup is still running.
+    #[method(name = "backupDatabase")]
+    async fn backup_database(
+        &self,
+        path: PathBuf,
+        compact: bool,
+    ) -> RpcResult<DatabaseBackupStatus>;
+
+    /// Returns the status of the most recent database backup, if one has been started.
+    #[method(name = "databaseBackupStatus")]
+    async fn database_backup_status(&self) -> RpcResult<Option<DatabaseBackupStatus>>;
+}
@@ -64,8 +64,8 @@ pub trait EthBundleApi {
     /// The `eth_cancelPrivateTransaction` method stops private transactions from being
     /// submitted for future blocks.
     ///
-    /// A transaction can only be cancelled if the request is signed by the same key as the
-    /// eth_sendPrivateTransaction call submitting the transaction in first place.
+    /// A transaction can only be cancelled if the request is signed by the sender of the
+    /// transaction.
     #[method(name = "cancelPrivateTransaction")]
     async fn cancel_private_transaction(
         &self,
@@ -1,5 +1,5 @@
 use jsonrpsee::{core::RpcResult, proc_macros::rpc};
-use reth_primitives::{Address, BlockId, BlockNumberOrTag, TxHash, B256};
+use reth_primitives::{Address, BlockId, BlockNumberOrTag, Bytes, TxHash, B256};
 use reth_rpc_types::{
     BlockDetails, ContractCreator, InternalOperation, OtsBlockTransactions, TraceEntry,
     Transaction, TransactionsWithReceipts,
@@ -23,17 +23,20 @@ pub trait Otterscan {
     #[method(name = "getInternalOperations")]
     async fn get_internal_operations(&self, tx_hash: TxHash) -> RpcResult<Vec<InternalOperation>>;
 
-    /// Given a transaction hash, returns its raw revert reason.
+    /// Given a transaction hash, returns its raw revert reason, or empty bytes if the transaction
+    /// succeeded.
     #[method(name = "getTransactionError")]
-    async fn get_transaction_error(&self, tx_hash: TxHash) -> RpcResult<String>;
+    async fn get_transaction_error(&self, tx_hash: TxHash) -> RpcResult<Bytes>;
 
     /// Extract all variations of calls, contract creation and self-destructs and returns a call
     /// tree.
     #[method(name = "traceTransaction")]
-    async fn trace_transaction(&self, tx_hash: TxHash) -> RpcResult<TraceEntry>;
+    async fn trace_transaction(&self, tx_hash: TxHash) -> RpcResult<Vec<TraceEntry>>;
 
     /// Tailor-made and expanded version of eth_getBlockByNumber for block details page in
     /// Otterscan.
+    ///
+    /// The total fees are zero if the receipts of the block were pruned.
     #[method(name = "getBlockDetails")]
     async fn get_block_details(
         &self,
@@ -41,6 +44,8 @@ pub trait Otterscan {
     ) -> RpcResult<Option<BlockDetails>>;
 
     /// Tailor-made and expanded version of eth_getBlockByHash for block details page in Otterscan.
+    ///
+    /// The total fees are zero if the receipts of the block were pruned.
     #[method(name = "getBlockDetailsByHash")]
     async fn get_block_details_by_hash(&self, block_hash: B256) -> RpcResult<Option<BlockDetails>>;
 
@@ -54,6 +59,8 @@ pub trait Otterscan {
     ) -> RpcResult<OtsBlockTransactions>;
 
     /// Gets paginated inbound/outbound transaction calls for a certain address.
+    ///
+    /// Internal calls to the address are only found if the address emitted a log in them.
     #[method(name = "searchTransactionsBefore")]
     async fn search_transactions_before(
         &self,
@@ -63,6 +70,8 @@ pub trait Otterscan {
     ) -> RpcResult<TransactionsWithReceipts>;
 
     /// Gets paginated inbound/outbound transaction calls for a certain address.
+    ///
+    /// Internal calls to the address are only found if the address emitted a log in them.
     #[method(name = "searchTransactionsAfter")]
     async fn search_transactions_after(
         &self,
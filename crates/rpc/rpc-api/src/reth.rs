@@ -1,5 +1,6 @@
 use jsonrpsee::{core::RpcResult, proc_macros::rpc};
 use reth_primitives::{Address, BlockId, U256};
+use reth_rpc_types::{trace::parity::StateDiff, BlockStateDiff, ChainNotification};
 use std::collections::HashMap;
 
 /// Reth API namespace for reth-specific methods
@@ -12,4 +13,34 @@ pub trait RethApi {
         &self,
         block_id: BlockId,
     ) -> RpcResult<HashMap<Address, U256>>;
+
+    /// Returns the nonce, balance, code and changed storage slots of every account touched by a
+    /// block, before and after the block.
+    #[method(name = "getStateDiffInBlock")]
+    async fn reth_get_state_diff_in_block(&self, block_id: BlockId) -> RpcResult<StateDiff>;
+
+    /// Creates a subscription that returns the state diff of every new canonical block.
+    #[subscription(
+        name = "subscribeStateDiffs",
+        unsubscribe = "unsubscribeStateDiffs",
+        item = BlockStateDiff
+    )]
+    async fn reth_subscribe_state_diffs(&self) -> jsonrpsee::core::SubscriptionResult;
+
+    /// Creates a subscription that returns every commit and reorg of the canonical chain.
+    ///
+    /// If `include_receipts` is `true`, the receipts of the reverted and committed blocks are
+    /// included.
+    ///
+    /// The subscription is closed with an error if the subscriber falls so far behind that
+    /// notifications are dropped.
+    #[subscription(
+        name = "subscribeChainNotifications",
+        unsubscribe = "unsubscribeChainNotifications",
+        item = ChainNotification
+    )]
+    async fn reth_subscribe_chain_notifications(
+        &self,
+        include_receipts: Option<bool>,
+    ) -> jsonrpsee::core::SubscriptionResult;
 }
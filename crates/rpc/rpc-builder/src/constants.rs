@@ -22,6 +22,15 @@ pub const DEFAULT_MAX_LOGS_PER_RESPONSE: usize = 20_000;
 /// The default maximum number of concurrently executed tracing calls
 pub const DEFAULT_MAX_TRACING_REQUESTS: u32 = 25;
 
+/// The default maximum block range allowed in `trace_filter`
+pub const DEFAULT_MAX_TRACE_FILTER_BLOCKS: u64 = 10_000;
+
+/// The default maximum block range allowed in `debug_getModifiedAccountsBy*`
+pub const DEFAULT_MAX_MODIFIED_ACCOUNTS_BLOCKS: u64 = 1_000;
+
+/// The default maximum block range allowed in `debug_traceChain`
+pub const DEFAULT_MAX_TRACE_CHAIN_BLOCKS: u64 = 1_000;
+
 /// The default IPC endpoint
 #[cfg(windows)]
 pub const DEFAULT_IPC_ENDPOINT: &str = r"\\.\pipe\reth.ipc";
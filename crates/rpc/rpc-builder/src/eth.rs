@@ -1,11 +1,13 @@
 use crate::constants::{
-    DEFAULT_MAX_BLOCKS_PER_FILTER, DEFAULT_MAX_LOGS_PER_RESPONSE, DEFAULT_MAX_TRACING_REQUESTS,
+    DEFAULT_MAX_BLOCKS_PER_FILTER, DEFAULT_MAX_LOGS_PER_RESPONSE,
+    DEFAULT_MAX_MODIFIED_ACCOUNTS_BLOCKS, DEFAULT_MAX_TRACE_CHAIN_BLOCKS,
+    DEFAULT_MAX_TRACE_FILTER_BLOCKS, DEFAULT_MAX_TRACING_REQUESTS,
 };
 use reth_rpc::{
     eth::{
         cache::{EthStateCache, EthStateCacheConfig},
         gas_oracle::GasPriceOracleConfig,
-        EthFilterConfig, FeeHistoryCacheConfig, RPC_DEFAULT_GAS_CAP,
+        EthFilterConfig, FeeHistoryCacheConfig, RPC_DEFAULT_ETH_PROOF_WINDOW, RPC_DEFAULT_GAS_CAP,
     },
     BlockingTaskPool, EthApi, EthFilter, EthPubSub,
 };
@@ -35,6 +37,12 @@ pub struct EthConfig {
     pub gas_oracle: GasPriceOracleConfig,
     /// The maximum number of tracing calls that can be executed in concurrently.
     pub max_tracing_requests: u32,
+    /// Maximum number of blocks that could be traced per `trace_filter` request.
+    pub max_trace_filter_blocks: u64,
+    /// Maximum block range of `debug_getModifiedAccountsBy*` requests.
+    pub max_modified_accounts_blocks: u64,
+    /// Maximum block range of `debug_traceChain` subscriptions.
+    pub max_trace_chain_blocks: u64,
     /// Maximum number of blocks that could be scanned per filter request in `eth_getLogs` calls.
     pub max_blocks_per_filter: u64,
     /// Maximum number of logs that can be returned in a single response in `eth_getLogs` calls.
@@ -43,6 +51,10 @@ pub struct EthConfig {
     ///
     /// Defaults to [RPC_DEFAULT_GAS_CAP]
     pub rpc_gas_cap: u64,
+    /// Maximum number of blocks behind the tip for which `eth_getProof` is served.
+    ///
+    /// Defaults to [RPC_DEFAULT_ETH_PROOF_WINDOW]
+    pub eth_proof_window: u64,
     ///
     /// Sets TTL for stale filters
     pub stale_filter_ttl: std::time::Duration,
@@ -69,9 +81,13 @@ impl Default for EthConfig {
             cache: EthStateCacheConfig::default(),
             gas_oracle: GasPriceOracleConfig::default(),
             max_tracing_requests: DEFAULT_MAX_TRACING_REQUESTS,
+            max_trace_filter_blocks: DEFAULT_MAX_TRACE_FILTER_BLOCKS,
+            max_modified_accounts_blocks: DEFAULT_MAX_MODIFIED_ACCOUNTS_BLOCKS,
+            max_trace_chain_blocks: DEFAULT_MAX_TRACE_CHAIN_BLOCKS,
             max_blocks_per_filter: DEFAULT_MAX_BLOCKS_PER_FILTER,
             max_logs_per_response: DEFAULT_MAX_LOGS_PER_RESPONSE,
             rpc_gas_cap: RPC_DEFAULT_GAS_CAP.into(),
+            eth_proof_window: RPC_DEFAULT_ETH_PROOF_WINDOW,
             stale_filter_ttl: DEFAULT_STALE_FILTER_TTL,
             fee_history_cache: FeeHistoryCacheConfig::default(),
         }
@@ -97,6 +113,24 @@ impl EthConfig {
         self
     }
 
+    /// Configures the maximum block length to trace per `trace_filter` request
+    pub fn max_trace_filter_blocks(mut self, max_blocks: u64) -> Self {
+        self.max_trace_filter_blocks = max_blocks;
+        self
+    }
+
+    /// Configures the maximum block range of `debug_getModifiedAccountsBy*` requests
+    pub fn max_modified_accounts_blocks(mut self, max_blocks: u64) -> Self {
+        self.max_modified_accounts_blocks = max_blocks;
+        self
+    }
+
+    /// Configures the maximum block range of `debug_traceChain` subscriptions
+    pub fn max_trace_chain_blocks(mut self, max_blocks: u64) -> Self {
+        self.max_trace_chain_blocks = max_blocks;
+        self
+    }
+
     /// Configures the maximum block length to scan per `eth_getLogs` request
     pub fn max_blocks_per_filter(mut self, max_blocks: u64) -> Self {
         self.max_blocks_per_filter = max_blocks;
@@ -114,4 +148,10 @@ impl EthConfig {
         self.rpc_gas_cap = rpc_gas_cap;
         self
     }
+
+    /// Configures the maximum number of blocks behind the tip for which `eth_getProof` is served
+    pub fn eth_proof_window(mut self, eth_proof_window: u64) -> Self {
+        self.eth_proof_window = eth_proof_window;
+        self
+    }
 }
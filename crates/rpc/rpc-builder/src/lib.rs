@@ -20,8 +20,9 @@
 //! use reth_network_api::{NetworkInfo, Peers};
 //! use reth_node_api::ConfigureEvmEnv;
 //! use reth_provider::{
-//!     AccountReader, BlockReaderIdExt, CanonStateSubscriptions, ChainSpecProvider,
-//!     ChangeSetReader, EvmEnvProvider, StateProviderFactory,
+//!     AccountReader, AddressAppearancesReader, BadBlockReader, BlockReaderIdExt,
+//!     BlockValidationProvider, CanonStateSubscriptions, ChainSpecProvider, ChangeSetReader,
+//!     ContractCreatorReader, EvmEnvProvider, LogIndexReader, StateProviderFactory,
 //! };
 //! use reth_rpc_builder::{
 //!     RethRpcModule, RpcModuleBuilder, RpcServerConfig, ServerBuilder, TransportRpcModuleConfig,
@@ -39,6 +40,11 @@
 //!         + BlockReaderIdExt
 //!         + ChainSpecProvider
 //!         + ChangeSetReader
+//!         + AddressAppearancesReader
+//!         + LogIndexReader
+//!         + ContractCreatorReader
+//!         + BadBlockReader
+//!         + BlockValidationProvider
 //!         + StateProviderFactory
 //!         + EvmEnvProvider
 //!         + Clone
@@ -80,8 +86,9 @@
 //! use reth_network_api::{NetworkInfo, Peers};
 //! use reth_node_api::{ConfigureEvmEnv, EngineTypes};
 //! use reth_provider::{
-//!     AccountReader, BlockReaderIdExt, CanonStateSubscriptions, ChainSpecProvider,
-//!     ChangeSetReader, EvmEnvProvider, StateProviderFactory,
+//!     AccountReader, AddressAppearancesReader, BadBlockReader, BlockReaderIdExt,
+//!     BlockValidationProvider, CanonStateSubscriptions, ChainSpecProvider, ChangeSetReader,
+//!     ContractCreatorReader, EvmEnvProvider, LogIndexReader, StateProviderFactory,
 //! };
 //! use reth_rpc::JwtSecret;
 //! use reth_rpc_api::EngineApiServer;
@@ -104,6 +111,11 @@
 //!         + BlockReaderIdExt
 //!         + ChainSpecProvider
 //!         + ChangeSetReader
+//!         + AddressAppearancesReader
+//!         + LogIndexReader
+//!         + ContractCreatorReader
+//!         + BadBlockReader
+//!         + BlockValidationProvider
 //!         + StateProviderFactory
 //!         + EvmEnvProvider
 //!         + Clone
@@ -184,8 +196,9 @@ use reth_ipc::server::IpcServer;
 pub use reth_ipc::server::{Builder as IpcServerBuilder, Endpoint};
 use reth_network_api::{noop::NoopNetwork, NetworkInfo, Peers};
 use reth_provider::{
-    AccountReader, BlockReader, BlockReaderIdExt, CanonStateSubscriptions, ChainSpecProvider,
-    ChangeSetReader, EvmEnvProvider, StateProviderFactory,
+    AccountReader, AddressAppearancesReader, BadBlockReader, BlockReader, BlockReaderIdExt,
+    BlockValidationProvider, CanonStateSubscriptions, ChainSpecProvider, ChangeSetReader,
+    ContractCreatorReader, EvmEnvProvider, LogIndexReader, StateProviderFactory,
 };
 use reth_rpc::{
     eth::{
@@ -196,7 +209,7 @@ use reth_rpc::{
     },
     AdminApi, AuthLayer, BlockingTaskGuard, BlockingTaskPool, Claims, DebugApi, EngineEthApi,
     EthApi, EthFilter, EthPubSub, EthSubscriptionIdProvider, JwtAuthValidator, JwtSecret, NetApi,
-    OtterscanApi, RPCApi, RethApi, TraceApi, TxPoolApi, Web3Api,
+    OtterscanApi, RPCApi, RethApi, TraceApi, TracerRegistry, TxPoolApi, ValidationApi, Web3Api,
 };
 use reth_rpc_api::{servers::*, EngineApiServer};
 use reth_tasks::{TaskSpawner, TokioTaskExecutor};
@@ -241,6 +254,11 @@ where
         + EvmEnvProvider
         + ChainSpecProvider
         + ChangeSetReader
+        + AddressAppearancesReader
+        + LogIndexReader
+        + ContractCreatorReader
+        + BadBlockReader
+        + BlockValidationProvider
         + Clone
         + Unpin
         + 'static,
@@ -432,6 +450,11 @@ where
         + EvmEnvProvider
         + ChainSpecProvider
         + ChangeSetReader
+        + AddressAppearancesReader
+        + LogIndexReader
+        + ContractCreatorReader
+        + BadBlockReader
+        + BlockValidationProvider
         + Clone
         + Unpin
         + 'static,
@@ -728,6 +751,11 @@ impl RpcModuleSelection {
             + EvmEnvProvider
             + ChainSpecProvider
             + ChangeSetReader
+            + AddressAppearancesReader
+            + LogIndexReader
+            + ContractCreatorReader
+            + BadBlockReader
+            + BlockValidationProvider
             + Clone
             + Unpin
             + 'static,
@@ -856,6 +884,18 @@ pub enum RethRpcModule {
     /// This is separate from [RethRpcModule::Eth] because it is a non standardized call that
     /// should be opt-in.
     EthCallBundle,
+    /// Non-standard `eth_` bundle calls: `eth_sendBundle`, `eth_callBundle` and
+    /// `eth_cancelBundle`
+    ///
+    /// Submitted bundles are added to the bundle pool of the transaction pool. This includes
+    /// `eth_callBundle`, so [RethRpcModule::EthCallBundle] is ignored if this is selected.
+    EthBundle,
+    /// `flashbots_` module, for validating block submissions of builders
+    Flashbots,
+    /// `mev_` module, for simulating and submitting MEV-Share bundles
+    ///
+    /// Submitted bundles are added to the bundle pool of the transaction pool.
+    Mev,
 }
 
 // === impl RethRpcModule ===
@@ -900,6 +940,9 @@ impl FromStr for RethRpcModule {
             "reth" => RethRpcModule::Reth,
             "ots" => RethRpcModule::Ots,
             "eth-call-bundle" | "eth_callBundle" => RethRpcModule::EthCallBundle,
+            "eth-bundle" => RethRpcModule::EthBundle,
+            "flashbots" => RethRpcModule::Flashbots,
+            "mev" => RethRpcModule::Mev,
             _ => return Err(ParseError::VariantNotFound),
         })
     }
@@ -943,6 +986,8 @@ pub struct RethModuleRegistry<Provider, Pool, Network, Tasks, Events, EvmConfig>
     eth: Option<EthHandlers<Provider, Pool, Network, Events, EvmConfig>>,
     /// to put trace calls behind semaphore
     blocking_pool_guard: BlockingTaskGuard,
+    /// Custom tracers of the `debug` namespace
+    tracers: TracerRegistry,
     /// Contains the [Methods] of a module
     modules: HashMap<RethRpcModule, Methods>,
 }
@@ -971,6 +1016,7 @@ impl<Provider, Pool, Network, Tasks, Events, EvmConfig>
             executor,
             modules: Default::default(),
             blocking_pool_guard: BlockingTaskGuard::new(config.eth.max_tracing_requests),
+            tracers: TracerRegistry::default(),
             config,
             events,
         }
@@ -996,6 +1042,14 @@ impl<Provider, Pool, Network, Tasks, Events, EvmConfig>
         &self.provider
     }
 
+    /// Returns the registry of custom `debug` tracers.
+    ///
+    /// Tracers can be registered at any time, they are available to all `debug` handlers of this
+    /// registry, including the handlers that were already created.
+    pub fn tracers(&self) -> &TracerRegistry {
+        &self.tracers
+    }
+
     /// Returns all installed methods
     pub fn methods(&self) -> Vec<Methods> {
         self.modules.values().cloned().collect()
@@ -1050,6 +1104,11 @@ where
         + EvmEnvProvider
         + ChainSpecProvider
         + ChangeSetReader
+        + AddressAppearancesReader
+        + LogIndexReader
+        + ContractCreatorReader
+        + BadBlockReader
+        + BlockValidationProvider
         + Clone
         + Unpin
         + 'static,
@@ -1151,6 +1210,36 @@ where
         self
     }
 
+    /// Register Flashbots Namespace
+    pub fn register_flashbots(&mut self) -> &mut Self {
+        let validation_api = self.validation_api();
+        self.modules.insert(RethRpcModule::Flashbots, validation_api.into_rpc().into());
+        self
+    }
+
+    /// Register the non-standard `eth_` bundle calls
+    ///
+    /// # Panics
+    ///
+    /// If called outside of the tokio runtime. See also [Self::eth_api]
+    pub fn register_eth_bundle(&mut self) -> &mut Self {
+        let bundle_api = self.bundle_api();
+        self.modules
+            .insert(RethRpcModule::EthBundle, EthBundleApiServer::into_rpc(bundle_api).into());
+        self
+    }
+
+    /// Register Mev Namespace
+    ///
+    /// # Panics
+    ///
+    /// If called outside of the tokio runtime. See also [Self::eth_api]
+    pub fn register_mev(&mut self) -> &mut Self {
+        let bundle_api = self.bundle_api();
+        self.modules.insert(RethRpcModule::Mev, MevApiServer::into_rpc(bundle_api).into());
+        self
+    }
+
     /// Helper function to create a [RpcModule] if it's not `None`
     fn maybe_module(&mut self, config: Option<&RpcModuleSelection>) -> Option<RpcModule<()>> {
         let config = config?;
@@ -1185,12 +1274,17 @@ where
             api: eth_api,
             filter: eth_filter,
             pubsub: eth_pubsub,
-            cache: _,
+            cache: eth_cache,
             blocking_task_pool: _,
         } = self.with_eth(|eth| eth.clone());
 
         // Create a copy, so we can list out all the methods for rpc_ api
-        let namespaces: Vec<_> = namespaces.collect();
+        let mut namespaces: Vec<_> = namespaces.collect();
+
+        // the full bundle api also serves `eth_callBundle`
+        if namespaces.contains(&RethRpcModule::EthBundle) {
+            namespaces.retain(|namespace| *namespace != RethRpcModule::EthCallBundle);
+        }
         namespaces
             .iter()
             .copied()
@@ -1205,6 +1299,9 @@ where
                             self.provider.clone(),
                             eth_api.clone(),
                             self.blocking_pool_guard.clone(),
+                            self.tracers.clone(),
+                            self.config.eth.max_modified_accounts_blocks,
+                            self.config.eth.max_trace_chain_blocks,
                         )
                         .into_rpc()
                         .into(),
@@ -1223,6 +1320,7 @@ where
                             self.provider.clone(),
                             eth_api.clone(),
                             self.blocking_pool_guard.clone(),
+                            self.config.eth.max_trace_filter_blocks,
                         )
                         .into_rpc()
                         .into(),
@@ -1238,17 +1336,46 @@ where
                         )
                         .into_rpc()
                         .into(),
-                        RethRpcModule::Ots => OtterscanApi::new(eth_api.clone()).into_rpc().into(),
-                        RethRpcModule::Reth => {
-                            RethApi::new(self.provider.clone(), Box::new(self.executor.clone()))
-                                .into_rpc()
-                                .into()
-                        }
+                        RethRpcModule::Ots => OtterscanApi::new(
+                            self.provider.clone(),
+                            eth_api.clone(),
+                            eth_cache.clone(),
+                        )
+                        .into_rpc()
+                        .into(),
+                        RethRpcModule::Reth => RethApi::new(
+                            self.provider.clone(),
+                            Box::new(self.executor.clone()),
+                            self.events.clone(),
+                        )
+                        .into_rpc()
+                        .into(),
                         RethRpcModule::EthCallBundle => {
-                            EthBundle::new(eth_api.clone(), self.blocking_pool_guard.clone())
-                                .into_rpc()
-                                .into()
+                            EthCallBundleApiServer::into_rpc(EthBundle::new(
+                                eth_api.clone(),
+                                self.pool.bundle_pool(),
+                                self.blocking_pool_guard.clone(),
+                            ))
+                            .into()
                         }
+                        RethRpcModule::Flashbots => ValidationApi::new(
+                            self.provider.clone(),
+                            Box::new(self.executor.clone()),
+                        )
+                        .into_rpc()
+                        .into(),
+                        RethRpcModule::EthBundle => EthBundleApiServer::into_rpc(EthBundle::new(
+                            eth_api.clone(),
+                            self.pool.bundle_pool(),
+                            self.blocking_pool_guard.clone(),
+                        ))
+                        .into(),
+                        RethRpcModule::Mev => MevApiServer::into_rpc(EthBundle::new(
+                            eth_api.clone(),
+                            self.pool.bundle_pool(),
+                            self.blocking_pool_guard.clone(),
+                        ))
+                        .into(),
                     })
                     .clone()
             })
@@ -1317,6 +1444,7 @@ where
                 cache.clone(),
                 gas_oracle,
                 self.config.eth.rpc_gas_cap,
+                self.config.eth.eth_proof_window,
                 executor.clone(),
                 blocking_task_pool.clone(),
                 fee_history_cache,
@@ -1328,7 +1456,8 @@ where
                 cache.clone(),
                 self.config.eth.filter_config(),
                 executor.clone(),
-            );
+            )
+            .with_reorg_tracking(self.events.canonical_state_stream());
 
             let pubsub = EthPubSub::with_spawner(
                 self.provider.clone(),
@@ -1371,7 +1500,12 @@ where
     /// If called outside of the tokio runtime. See also [Self::eth_api]
     pub fn trace_api(&mut self) -> TraceApi<Provider, EthApi<Provider, Pool, Network, EvmConfig>> {
         let eth = self.eth_handlers();
-        TraceApi::new(self.provider.clone(), eth.api, self.blocking_pool_guard.clone())
+        TraceApi::new(
+            self.provider.clone(),
+            eth.api,
+            self.blocking_pool_guard.clone(),
+            self.config.eth.max_trace_filter_blocks,
+        )
     }
 
     /// Instantiates [EthBundle] Api
@@ -1381,7 +1515,7 @@ where
     /// If called outside of the tokio runtime. See also [Self::eth_api]
     pub fn bundle_api(&mut self) -> EthBundle<EthApi<Provider, Pool, Network, EvmConfig>> {
         let eth_api = self.eth_api();
-        EthBundle::new(eth_api, self.blocking_pool_guard.clone())
+        EthBundle::new(eth_api, self.pool.bundle_pool(), self.blocking_pool_guard.clone())
     }
 
     /// Instantiates OtterscanApi
@@ -1389,9 +1523,11 @@ where
     /// # Panics
     ///
     /// If called outside of the tokio runtime. See also [Self::eth_api]
-    pub fn otterscan_api(&mut self) -> OtterscanApi<EthApi<Provider, Pool, Network, EvmConfig>> {
-        let eth_api = self.eth_api();
-        OtterscanApi::new(eth_api)
+    pub fn otterscan_api(
+        &mut self,
+    ) -> OtterscanApi<Provider, EthApi<Provider, Pool, Network, EvmConfig>> {
+        let eth = self.eth_handlers();
+        OtterscanApi::new(self.provider.clone(), eth.api, eth.cache)
     }
 
     /// Instantiates DebugApi
@@ -1401,7 +1537,14 @@ where
     /// If called outside of the tokio runtime. See also [Self::eth_api]
     pub fn debug_api(&mut self) -> DebugApi<Provider, EthApi<Provider, Pool, Network, EvmConfig>> {
         let eth_api = self.eth_api();
-        DebugApi::new(self.provider.clone(), eth_api, self.blocking_pool_guard.clone())
+        DebugApi::new(
+            self.provider.clone(),
+            eth_api,
+            self.blocking_pool_guard.clone(),
+            self.tracers.clone(),
+            self.config.eth.max_modified_accounts_blocks,
+            self.config.eth.max_trace_chain_blocks,
+        )
     }
 
     /// Instantiates NetApi
@@ -1415,8 +1558,13 @@ where
     }
 
     /// Instantiates RethApi
-    pub fn reth_api(&mut self) -> RethApi<Provider> {
-        RethApi::new(self.provider.clone(), Box::new(self.executor.clone()))
+    pub fn reth_api(&mut self) -> RethApi<Provider, Events> {
+        RethApi::new(self.provider.clone(), Box::new(self.executor.clone()), self.events.clone())
+    }
+
+    /// Instantiates ValidationApi
+    pub fn validation_api(&mut self) -> ValidationApi<Provider> {
+        ValidationApi::new(self.provider.clone(), Box::new(self.executor.clone()))
     }
 }
 
@@ -2379,6 +2527,9 @@ mod tests {
                 "rpc" => RethRpcModule::Rpc,
                 "ots" => RethRpcModule::Ots,
                 "reth" => RethRpcModule::Reth,
+                "eth-bundle" => RethRpcModule::EthBundle,
+                "flashbots" => RethRpcModule::Flashbots,
+                "mev" => RethRpcModule::Mev,
             );
     }
 
@@ -4,6 +4,7 @@ use serde::{Deserialize, Serialize};
 use std::{
     collections::BTreeMap,
     net::{IpAddr, SocketAddr},
+    path::PathBuf,
 };
 
 /// Represents the `admin_nodeInfo` response, which can be queried for all the information
@@ -89,6 +90,38 @@ pub struct EthProtocolInfo {
     pub genesis: B256,
 }
 
+/// Represents the `admin_databaseBackupStatus` response, which describes the progress of the
+/// most recent database backup.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DatabaseBackupStatus {
+    /// Directory the backup is written to.
+    pub path: PathBuf,
+    /// Whether free pages are omitted from the backup.
+    pub compact: bool,
+    /// Number of bytes written to the backup so far.
+    pub copied_bytes: u64,
+    /// Number of bytes in use by the database when the backup was started.
+    pub total_bytes: u64,
+    /// State of the backup.
+    pub state: DatabaseBackupState,
+    /// The error the backup failed with, if any.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+/// State of a database backup.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum DatabaseBackupState {
+    /// The backup is being written.
+    Running,
+    /// The backup has been written completely.
+    Finished,
+    /// The backup has been aborted because of an error.
+    Failed,
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -0,0 +1,117 @@
+use crate::RichBlock;
+use alloy_primitives::{Address, Bytes, B256, U256};
+use serde::{Deserialize, Serialize};
+use serde_with::{serde_as, DisplayFromStr};
+use std::collections::BTreeMap;
+
+/// Response of the `debug_storageRangeAt` RPC method.
+#[derive(Clone, Debug, Default, Eq, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StorageRangeResult {
+    /// The storage slots in the range, keyed by the hashed slot.
+    pub storage: BTreeMap<B256, StorageRangeEntry>,
+    /// The hashed slot following the last one in the range, if any.
+    pub next_key: Option<B256>,
+}
+
+/// A storage slot returned by the `debug_storageRangeAt` RPC method.
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub struct StorageRangeEntry {
+    /// The preimage of the hashed slot, if known.
+    pub key: Option<B256>,
+    /// The value of the slot.
+    pub value: B256,
+}
+
+/// Response of the `debug_accountRange` RPC method.
+#[derive(Clone, Debug, Default, Eq, PartialEq, Serialize, Deserialize)]
+pub struct AccountRangeResult {
+    /// The state root of the block.
+    pub root: B256,
+    /// The accounts in the range, keyed by their address or, if the address is unknown, by
+    /// `pre(<hashed address>)`.
+    pub accounts: BTreeMap<String, DumpAccount>,
+    /// The hashed address following the last account in the range, if any.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub next: Option<B256>,
+}
+
+/// An account returned by the `debug_accountRange` RPC method.
+#[serde_as]
+#[derive(Clone, Debug, Default, Eq, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DumpAccount {
+    /// The balance of the account, as a decimal string.
+    #[serde_as(as = "DisplayFromStr")]
+    pub balance: U256,
+    /// The nonce of the account.
+    pub nonce: u64,
+    /// The storage root of the account.
+    pub root: B256,
+    /// The code hash of the account.
+    pub code_hash: B256,
+    /// The code of the account, unless omitted.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub code: Option<Bytes>,
+    /// The non-zero storage slots of the account keyed by the hashed slot, unless omitted.
+    ///
+    /// Values are hex encoded without leading zero bytes.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub storage: Option<BTreeMap<B256, String>>,
+    /// The address of the account, if known.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub address: Option<Address>,
+    /// The hashed address of the account.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub key: Option<B256>,
+}
+
+/// A block that failed validation, as returned by the `debug_getBadBlocks` RPC method.
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub struct BadBlockResult {
+    /// The hash of the block.
+    pub hash: B256,
+    /// The block, with full transactions if their senders could be recovered.
+    pub block: RichBlock,
+    /// The RLP encoded block.
+    pub rlp: Bytes,
+    /// The error the block failed validation with.
+    pub error: String,
+}
+
+/// Subscription kind of the `debug_subscribe` RPC method.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum DebugSubscriptionKind {
+    /// Traces every block of a range of the canonical chain, see `debug_traceChain`.
+    TraceChain,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn serde_storage_range_result() {
+        let s = r#"{"storage":{"0x0000000000000000000000000000000000000000000000000000000000000001":{"key":null,"value":"0x000000000000000000000000000000000000000000000000000000000000002a"}},"nextKey":null}"#;
+        let result: StorageRangeResult = serde_json::from_str(s).unwrap();
+        assert_eq!(result.storage[&B256::with_last_byte(1)].value, B256::with_last_byte(42));
+        assert_eq!(serde_json::to_string(&result).unwrap(), s);
+    }
+
+    #[test]
+    fn serde_account_range_result() {
+        let s = r#"{"root":"0x0000000000000000000000000000000000000000000000000000000000000000","accounts":{"pre(0x0000000000000000000000000000000000000000000000000000000000000001)":{"balance":"42","nonce":1,"root":"0x0000000000000000000000000000000000000000000000000000000000000002","codeHash":"0x0000000000000000000000000000000000000000000000000000000000000003","key":"0x0000000000000000000000000000000000000000000000000000000000000001"}}}"#;
+        let result: AccountRangeResult = serde_json::from_str(s).unwrap();
+        let account = result.accounts.values().next().unwrap();
+        assert_eq!(account.balance, U256::from(42));
+        assert_eq!(result.next, None);
+        assert_eq!(serde_json::to_string(&result).unwrap(), s);
+    }
+
+    #[test]
+    fn serde_debug_subscription_kind() {
+        let kind: DebugSubscriptionKind = serde_json::from_str(r#""traceChain""#).unwrap();
+        assert_eq!(kind, DebugSubscriptionKind::TraceChain);
+    }
+}
@@ -11,13 +11,16 @@
 
 mod admin;
 pub mod beacon;
+mod debug;
 mod eth;
 mod mev;
 mod net;
 mod otterscan;
 mod peer;
 pub mod relay;
+mod reth;
 mod rpc;
+mod simulate;
 
 // re-export for convenience
 pub use alloy_rpc_types::serde_helpers;
@@ -38,8 +41,11 @@ pub use eth::{
 };
 
 pub use admin::*;
+pub use debug::*;
 pub use mev::*;
 pub use net::*;
 pub use otterscan::*;
 pub use peer::*;
+pub use reth::*;
 pub use rpc::*;
+pub use simulate::*;
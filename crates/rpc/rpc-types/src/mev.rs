@@ -78,6 +78,11 @@ pub enum BundleItem {
         /// If true, the transaction can revert without the bundle being considered invalid.
         can_revert: bool,
     },
+    /// A nested bundle request.
+    Bundle {
+        /// A bundle request of type SendBundleRequest
+        bundle: Box<SendBundleRequest>,
+    },
 }
 
 /// Requirements for the bundle to be included in the block.
@@ -394,6 +399,13 @@ impl SendBundleRequest {
 pub struct CancelBundleRequest {
     /// Bundle hash of the bundle to be canceled
     pub bundle_hash: String,
+    /// Signature over the `bundle_hash` string by the signer of the first transaction of the
+    /// bundle, as returned by `eth_sign`.
+    ///
+    /// Like for [CancelPrivateTransactionRequest], this takes the place of the
+    /// `X-Flashbots-Signature` header and proves that the cancellation is requested by the
+    /// submitter of the bundle.
+    pub signature: Bytes,
 }
 
 /// Request for `eth_sendPrivateTransaction`
@@ -435,6 +447,13 @@ impl PrivateTransactionPreferences {
 pub struct CancelPrivateTransactionRequest {
     /// Transaction hash of the transaction to be canceled
     pub tx_hash: B256,
+    /// Signature over the transaction hash by the sender of the transaction, as returned by
+    /// `eth_sign`.
+    ///
+    /// This proves that the cancellation is requested by the sender. It is the signature that
+    /// Flashbots expects in the `X-Flashbots-Signature` header, which is not available to the
+    /// handler, so it is part of the body instead.
+    pub signature: Bytes,
 }
 
 // TODO(@optimiz-r): Revisit after <https://github.com/flashbots/flashbots-docs/issues/424> is closed.
@@ -781,6 +800,51 @@ mod tests {
         assert!(res.is_ok());
     }
 
+    #[test]
+    fn can_deserialize_nested_bundle() {
+        let str = r#"
+        {
+            "version": "v0.1",
+            "inclusion": {
+                "block": "0x1",
+                "maxBlock": "0x3"
+            },
+            "body": [
+                {
+                    "hash": "0x2c5a3a2b1da2d0dbd4357e1c5b73ae8c4e2a2ba1e2a7a06ef2e2d0f1ac2d9c4e"
+                },
+                {
+                    "bundle": {
+                        "version": "v0.1",
+                        "inclusion": {
+                            "block": "0x1"
+                        },
+                        "body": [{
+                            "tx": "0x02f86b0180843b9aca00852ecc889a0082520894c87037874aed04e51c29f582394217a0a2b89d808080c080a0a463985c616dd8ee17d7ef9112af4e6e06a27b071525b42182fe7b0b5c8b4925a00af5ca177ffef2ff28449292505d41be578bebb77110dfc09361d2fb56998260",
+                            "canRevert": true
+                        }]
+                    }
+                }
+            ],
+            "validity": {
+                "refund": [
+                    {
+                        "bodyIdx": 0,
+                        "percent": 90
+                    }
+                ]
+            }
+        }
+        "#;
+        let bundle: SendBundleRequest = serde_json::from_str(str).unwrap();
+        assert_eq!(bundle.inclusion.max_block_number(), Some(3));
+        assert!(matches!(bundle.bundle_body[0], BundleItem::Hash { .. }));
+        let BundleItem::Bundle { bundle: nested } = &bundle.bundle_body[1] else {
+            panic!("expected nested bundle")
+        };
+        assert!(matches!(nested.bundle_body[0], BundleItem::Tx { can_revert: true, .. }));
+    }
+
     #[test]
     fn can_serialize_complex() {
         let str = r#"
@@ -853,6 +917,33 @@ mod tests {
         assert_eq!(actual, expected);
     }
 
+    #[test]
+    fn can_deserialize_cancel_private_transaction() {
+        let tx_hash = B256::repeat_byte(0x01);
+        let unsigned = serde_json::json!({ "txHash": tx_hash });
+        assert!(serde_json::from_value::<CancelPrivateTransactionRequest>(unsigned).is_err());
+
+        let signed = serde_json::json!({ "txHash": tx_hash, "signature": "0x1234" });
+        let request: CancelPrivateTransactionRequest =
+            serde_json::from_value(signed.clone()).unwrap();
+        let signature = Bytes::from_static(&[0x12, 0x34]);
+        assert_eq!(request, CancelPrivateTransactionRequest { tx_hash, signature });
+        assert_eq!(serde_json::to_value(&request).unwrap(), signed);
+    }
+
+    #[test]
+    fn can_deserialize_cancel_bundle() {
+        let bundle_hash = "e4b5ad11-5d6d-4c5f-a1ab-1ab5fc3fa6e1".to_string();
+        let unsigned = serde_json::json!({ "bundleHash": bundle_hash });
+        assert!(serde_json::from_value::<CancelBundleRequest>(unsigned).is_err());
+
+        let signed = serde_json::json!({ "bundleHash": bundle_hash, "signature": "0x1234" });
+        let request: CancelBundleRequest = serde_json::from_value(signed.clone()).unwrap();
+        let signature = Bytes::from_static(&[0x12, 0x34]);
+        assert_eq!(request, CancelBundleRequest { bundle_hash, signature });
+        assert_eq!(serde_json::to_value(&request).unwrap(), signed);
+    }
+
     #[test]
     fn can_deserialize_privacy_hint() {
         let hint = PrivacyHint {
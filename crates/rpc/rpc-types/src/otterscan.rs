@@ -1,9 +1,12 @@
 use crate::{Block, BlockTransactions, Rich, Transaction, TransactionReceipt};
 use alloy_primitives::{Address, Bytes, U256};
-use serde::{Deserialize, Serialize};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
 
 /// Operation type enum for `InternalOperation` struct
-#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+///
+/// Serialized as its numeric value, as expected by Otterscan.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[repr(u8)]
 pub enum OperationType {
     /// Operation Transfer
     OpTransfer = 0,
@@ -15,67 +18,117 @@ pub enum OperationType {
     OpCreate2 = 3,
 }
 
+impl Serialize for OperationType {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_u8(*self as u8)
+    }
+}
+
+impl<'de> Deserialize<'de> for OperationType {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        match u8::deserialize(deserializer)? {
+            0 => Ok(Self::OpTransfer),
+            1 => Ok(Self::OpSelfDestruct),
+            2 => Ok(Self::OpCreate),
+            3 => Ok(Self::OpCreate2),
+            other => Err(serde::de::Error::custom(format!("invalid operation type {other}"))),
+        }
+    }
+}
+
 /// Custom struct for otterscan `getInternalOperations` RPC response
 #[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
 pub struct InternalOperation {
-    r#type: OperationType,
-    from: Address,
-    to: Address,
-    value: u128,
+    /// The type of the operation.
+    pub r#type: OperationType,
+    /// The address the value is transferred from.
+    pub from: Address,
+    /// The address the value is transferred to, or the created contract.
+    pub to: Address,
+    /// The transferred value.
+    pub value: U256,
 }
 
 /// Custom struct for otterscan `traceTransaction` RPC response
 #[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
 pub struct TraceEntry {
-    r#type: String,
-    depth: u32,
-    from: Address,
-    to: Address,
-    value: u128,
-    input: Bytes,
+    /// The type of the frame, e.g. `CALL`, `CREATE2` or `SELFDESTRUCT`.
+    pub r#type: String,
+    /// The depth of the frame, starting at zero for the transaction itself.
+    pub depth: u32,
+    /// The caller.
+    pub from: Address,
+    /// The callee, or the created contract.
+    pub to: Address,
+    /// The value sent along with the frame.
+    pub value: U256,
+    /// The input data.
+    pub input: Bytes,
+    /// The output data.
+    pub output: Bytes,
 }
 
 /// Internal issuance struct for `BlockDetails` struct
 #[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize, Default)]
 #[serde(rename_all = "camelCase")]
 pub struct InternalIssuance {
-    block_reward: U256,
-    uncle_reward: U256,
-    issuance: U256,
+    /// The reward of the block, including the rewards for the inclusion of uncles.
+    pub block_reward: U256,
+    /// The sum of the rewards of the uncles.
+    pub uncle_reward: U256,
+    /// The total issuance of the block.
+    pub issuance: U256,
 }
 
 /// Custom `Block` struct that includes transaction count for Otterscan responses
 #[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct OtsBlock {
+    /// The block.
     #[serde(flatten)]
-    block: Block,
-    transaction_count: usize,
+    pub block: Block,
+    /// The number of transactions in the block.
+    pub transaction_count: usize,
 }
 
 /// Custom struct for otterscan `getBlockDetails` RPC response
 #[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct BlockDetails {
-    block: OtsBlock,
-    issuance: InternalIssuance,
-    total_fees: U256,
+    /// The block.
+    pub block: OtsBlock,
+    /// The issuance of the block.
+    pub issuance: InternalIssuance,
+    /// The sum of the fees paid by the transactions of the block.
+    pub total_fees: U256,
 }
 
 /// Custom transaction receipt struct for otterscan `OtsBlockTransactions` struct
 #[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct OtsTransactionReceipt {
+    /// The transaction receipt.
     #[serde(flatten)]
-    receipt: TransactionReceipt,
-    timestamp: u64,
+    pub receipt: TransactionReceipt,
+    /// The timestamp of the block the transaction was included in.
+    pub timestamp: u64,
 }
 
 /// Custom struct for otterscan `getBlockTransactions` RPC response
+///
+/// The block only contains the transactions of the requested page, with their input truncated to
+/// the function selector, and the receipts are stripped of their logs.
 #[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
 pub struct OtsBlockTransactions {
-    fullblock: OtsBlock,
-    receipts: Vec<OtsTransactionReceipt>,
+    /// The block with the transactions of the page.
+    pub fullblock: OtsBlock,
+    /// The receipts of the transactions of the page.
+    pub receipts: Vec<OtsTransactionReceipt>,
+    /// The issuance of the block.
+    pub issuance: InternalIssuance,
+    /// The sum of the fees paid by all transactions of the block.
+    pub total_fees: U256,
 }
 
 /// Custom struct for otterscan `searchTransactionsAfter`and `searchTransactionsBefore` RPC
@@ -83,17 +136,23 @@ pub struct OtsBlockTransactions {
 #[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct TransactionsWithReceipts {
-    txs: Vec<Transaction>,
-    receipts: Vec<OtsTransactionReceipt>,
-    first_page: bool,
-    last_page: bool,
+    /// The transactions of the page, newest first.
+    pub txs: Vec<Transaction>,
+    /// The receipts of the transactions, in the same order.
+    pub receipts: Vec<OtsTransactionReceipt>,
+    /// Whether this page contains the most recent transactions of the address.
+    pub first_page: bool,
+    /// Whether this page contains the oldest transactions of the address.
+    pub last_page: bool,
 }
 
 /// Custom struct for otterscan `getContractCreator` RPC responses
 #[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
 pub struct ContractCreator {
-    tx: Transaction,
-    creator: Address,
+    /// The transaction the contract was created in.
+    pub tx: Transaction,
+    /// The address that created the contract.
+    pub creator: Address,
 }
 
 impl From<Block> for OtsBlock {
@@ -108,12 +167,36 @@ impl From<Block> for OtsBlock {
     }
 }
 
+impl BlockDetails {
+    /// Creates the details of the given block.
+    pub fn new(rich_block: Rich<Block>, issuance: InternalIssuance, total_fees: U256) -> Self {
+        Self { block: rich_block.inner.into(), issuance, total_fees }
+    }
+}
+
 impl From<Rich<Block>> for BlockDetails {
     fn from(rich_block: Rich<Block>) -> Self {
-        Self {
-            block: rich_block.inner.into(),
-            issuance: Default::default(),
-            total_fees: U256::default(),
-        }
+        Self::new(rich_block, Default::default(), U256::default())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn serde_internal_operation() {
+        let s = r#"{"type":1,"from":"0x0000000000000000000000000000000000000001","to":"0x0000000000000000000000000000000000000002","value":"0x2a"}"#;
+        let operation: InternalOperation = serde_json::from_str(s).unwrap();
+        assert_eq!(operation.r#type, OperationType::OpSelfDestruct);
+        assert_eq!(operation.value, U256::from(42));
+        assert_eq!(serde_json::to_string(&operation).unwrap(), s);
+    }
+
+    #[test]
+    fn serde_trace_entry() {
+        let s = r#"{"type":"CALL","depth":1,"from":"0x0000000000000000000000000000000000000001","to":"0x0000000000000000000000000000000000000002","value":"0x0","input":"0x12345678","output":"0x"}"#;
+        let entry: TraceEntry = serde_json::from_str(s).unwrap();
+        assert_eq!(serde_json::to_string(&entry).unwrap(), s);
     }
 }
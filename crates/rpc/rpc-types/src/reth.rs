@@ -0,0 +1,70 @@
+use crate::{trace::parity::StateDiff, TransactionReceipt};
+use alloy_primitives::B256;
+use serde::{Deserialize, Serialize};
+
+/// The state diff of a block, as returned by the `reth_subscribeStateDiffs` subscription.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BlockStateDiff {
+    /// The number of the block.
+    pub block_number: u64,
+    /// The hash of the block.
+    pub block_hash: B256,
+    /// The nonce, balance, code and changed storage slots of every account touched by the block,
+    /// before and after the block.
+    pub state_diff: StateDiff,
+}
+
+/// A change of the canonical chain, as returned by the `reth_subscribeChainNotifications`
+/// subscription.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "camelCase")]
+pub enum ChainNotification {
+    /// New blocks were appended to the canonical chain.
+    Commit {
+        /// The new canonical blocks, in ascending order.
+        committed: Vec<NotifiedBlock>,
+    },
+    /// The canonical chain was reorged.
+    ///
+    /// The reverted blocks should be rolled back, starting with the highest one, before the
+    /// committed blocks are applied.
+    Reorg {
+        /// The blocks that are no longer canonical, in ascending order.
+        reverted: Vec<NotifiedBlock>,
+        /// The new canonical blocks, in ascending order.
+        committed: Vec<NotifiedBlock>,
+    },
+}
+
+/// A block of a [ChainNotification].
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct NotifiedBlock {
+    /// The number of the block.
+    pub number: u64,
+    /// The hash of the block.
+    pub hash: B256,
+    /// The hash of the parent of the block.
+    pub parent_hash: B256,
+    /// The receipts of the transactions of the block, if they were requested.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub receipts: Option<Vec<TransactionReceipt>>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn serde_chain_notification() {
+        let s = r#"{"type":"reorg","reverted":[{"number":1,"hash":"0x0000000000000000000000000000000000000000000000000000000000000001","parentHash":"0x0000000000000000000000000000000000000000000000000000000000000000"}],"committed":[{"number":1,"hash":"0x0000000000000000000000000000000000000000000000000000000000000002","parentHash":"0x0000000000000000000000000000000000000000000000000000000000000000","receipts":[]}]}"#;
+        let notification: ChainNotification = serde_json::from_str(s).unwrap();
+        let ChainNotification::Reorg { reverted, committed } = &notification else {
+            panic!("expected reorg")
+        };
+        assert_eq!(reverted[0].receipts, None);
+        assert_eq!(committed[0].receipts, Some(Vec::new()));
+        assert_eq!(serde_json::to_string(&notification).unwrap(), s);
+    }
+}
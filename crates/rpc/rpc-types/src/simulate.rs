@@ -0,0 +1,95 @@
+//! Types for the `eth_simulateV1` RPC method.
+
+use crate::{state::StateOverride, Block, BlockOverrides, CallRequest, Log};
+use alloy_primitives::{Bytes, U64};
+use serde::{Deserialize, Serialize};
+
+/// The maximum number of blocks that can be simulated by a single `eth_simulateV1` call.
+pub const MAX_SIMULATE_BLOCKS: u64 = 256;
+
+/// Request of the `eth_simulateV1` RPC method.
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SimulatePayload {
+    /// The blocks to simulate, chained on top of each other.
+    pub block_state_calls: Vec<SimBlock>,
+    /// Whether to report ether transfers as `Transfer` logs of the
+    /// `0xEeeeeEeeeEeEeeEeEeEeeEEEeeeeEeeeeeeeEEeE` pseudo token.
+    #[serde(default)]
+    pub trace_transfers: bool,
+    /// Whether to execute the calls with the same checks as regular transactions, e.g. nonce,
+    /// balance and base fee checks.
+    #[serde(default)]
+    pub validation: bool,
+    /// Whether to return full transaction objects instead of transaction hashes.
+    #[serde(default)]
+    pub return_full_transactions: bool,
+}
+
+/// A block to simulate as part of an `eth_simulateV1` call.
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SimBlock {
+    /// Overrides of the header fields of the simulated block.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub block_overrides: Option<BlockOverrides>,
+    /// Overrides of the state, applied before the first call of the block.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub state_overrides: Option<StateOverride>,
+    /// The calls to execute in the block.
+    #[serde(default)]
+    pub calls: Vec<CallRequest>,
+}
+
+/// A simulated block returned by the `eth_simulateV1` RPC method.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SimulatedBlock {
+    /// The simulated block.
+    #[serde(flatten)]
+    pub inner: Block,
+    /// The results of the calls of the block.
+    pub calls: Vec<SimCallResult>,
+}
+
+/// The result of a call of a simulated block.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SimCallResult {
+    /// The data returned by the call.
+    pub return_data: Bytes,
+    /// The logs emitted by the call.
+    pub logs: Vec<Log>,
+    /// The gas used by the call.
+    pub gas_used: U64,
+    /// `1` if the call succeeded, `0` otherwise.
+    pub status: U64,
+    /// The error of the call, if it failed.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error: Option<SimulateError>,
+}
+
+/// The error of a failed call of a simulated block.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SimulateError {
+    /// The error code, `3` if the call reverted and `-32015` if the execution halted.
+    pub code: i32,
+    /// The error message.
+    pub message: String,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn serde_simulate_payload() {
+        let s = r#"{"blockStateCalls":[{"blockOverrides":{"number":"0x2"},"calls":[{"from":"0x0000000000000000000000000000000000000001","to":"0x0000000000000000000000000000000000000002","value":"0x1"}]},{}],"traceTransfers":true}"#;
+        let payload: SimulatePayload = serde_json::from_str(s).unwrap();
+        assert_eq!(payload.block_state_calls.len(), 2);
+        assert_eq!(payload.block_state_calls[0].calls.len(), 1);
+        assert!(payload.block_state_calls[1].calls.is_empty());
+        assert!(payload.trace_transfers);
+        assert!(!payload.validation);
+        assert!(!payload.return_full_transactions);
+    }
+}
@@ -1,10 +1,22 @@
-use crate::result::ToRpcResult;
+use crate::result::{invalid_params_rpc_err, ToRpcResult};
 use async_trait::async_trait;
 use jsonrpsee::core::RpcResult;
+use parking_lot::Mutex;
+use reth_db::database_backup::DatabaseBackup;
 use reth_network_api::{NetworkInfo, PeerKind, Peers};
 use reth_primitives::NodeRecord;
-use reth_rpc_api::AdminApiServer;
-use reth_rpc_types::{NodeInfo, PeerEthProtocolInfo, PeerInfo, PeerNetworkInfo, PeerProtocolsInfo};
+use reth_rpc_api::{AdminApiServer, AdminBackupApiServer};
+use reth_rpc_types::{
+    DatabaseBackupState, DatabaseBackupStatus, NodeInfo, PeerEthProtocolInfo, PeerInfo,
+    PeerNetworkInfo, PeerProtocolsInfo,
+};
+use reth_tasks::TaskSpawner;
+use std::{
+    panic::{self, AssertUnwindSafe},
+    path::PathBuf,
+    sync::Arc,
+};
+use tracing::{error, info};
 
 /// `admin` API implementation.
 ///
@@ -101,3 +113,149 @@ impl<N> std::fmt::Debug for AdminApi<N> {
         f.debug_struct("AdminApi").finish_non_exhaustive()
     }
 }
+
+/// `admin` API implementation for backing up the database.
+///
+/// This type provides the functionality for handling `admin_backupDatabase` and
+/// `admin_databaseBackupStatus` requests.
+pub struct AdminBackupApi<DB> {
+    inner: Arc<AdminBackupApiInner<DB>>,
+}
+
+impl<DB> AdminBackupApi<DB> {
+    /// Creates a new instance of `AdminBackupApi`.
+    pub fn new(db: DB, task_spawner: Box<dyn TaskSpawner>) -> Self {
+        let inner = AdminBackupApiInner { db, task_spawner, status: Mutex::new(None) };
+        Self { inner: Arc::new(inner) }
+    }
+}
+
+impl<DB> Clone for AdminBackupApi<DB> {
+    fn clone(&self) -> Self {
+        Self { inner: Arc::clone(&self.inner) }
+    }
+}
+
+#[async_trait]
+impl<DB> AdminBackupApiServer for AdminBackupApi<DB>
+where
+    DB: DatabaseBackup + Send + Sync + 'static,
+{
+    /// Handler for `admin_backupDatabase`
+    async fn backup_database(
+        &self,
+        path: PathBuf,
+        compact: bool,
+    ) -> RpcResult<DatabaseBackupStatus> {
+        let status = {
+            let mut current = self.inner.status.lock();
+            if current.as_ref().is_some_and(|status| status.state == DatabaseBackupState::Running) {
+                return Err(invalid_params_rpc_err("a database backup is already running").into())
+            }
+
+            let status = DatabaseBackupStatus {
+                path: path.clone(),
+                compact,
+                copied_bytes: 0,
+                total_bytes: 0,
+                state: DatabaseBackupState::Running,
+                error: None,
+            };
+            *current = Some(status.clone());
+            status
+        };
+
+        let this = self.clone();
+        self.inner.task_spawner.spawn_blocking(Box::pin(async move {
+            info!(target: "rpc::admin", ?path, compact, "Starting database backup");
+
+            // A panicking backup is caught, so that its status doesn't stay `Running` forever.
+            let result = panic::catch_unwind(AssertUnwindSafe(|| {
+                this.inner.db.backup(&path, compact, &mut |progress| {
+                    if let Some(status) = this.inner.status.lock().as_mut() {
+                        status.copied_bytes = progress.copied_bytes;
+                        status.total_bytes = progress.total_bytes;
+                    }
+                })
+            }))
+            .map_or_else(
+                |_| Err("database backup panicked".to_string()),
+                |result| result.map_err(|err| err.to_string()),
+            );
+
+            let mut status = this.inner.status.lock();
+            let Some(status) = status.as_mut() else { return };
+            match result {
+                Ok(()) => {
+                    info!(target: "rpc::admin", ?path, "Database backup finished");
+                    status.state = DatabaseBackupState::Finished;
+                }
+                Err(err) => {
+                    error!(target: "rpc::admin", ?path, %err, "Database backup failed");
+                    status.state = DatabaseBackupState::Failed;
+                    status.error = Some(err);
+                }
+            }
+        }));
+
+        Ok(status)
+    }
+
+    /// Handler for `admin_databaseBackupStatus`
+    async fn database_backup_status(&self) -> RpcResult<Option<DatabaseBackupStatus>> {
+        Ok(self.inner.status.lock().clone())
+    }
+}
+
+impl<DB> std::fmt::Debug for AdminBackupApi<DB> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("AdminBackupApi").finish_non_exhaustive()
+    }
+}
+
+/// Container type for `AdminBackupApi`
+struct AdminBackupApiInner<DB> {
+    /// The database to back up
+    db: DB,
+    /// The type that can spawn the blocking backup task
+    task_spawner: Box<dyn TaskSpawner>,
+    /// Status of the most recent backup
+    status: Mutex<Option<DatabaseBackupStatus>>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use reth_db::{database_backup::BackupProgress, DatabaseError};
+    use reth_tasks::TokioTaskExecutor;
+    use std::{path::Path, time::Duration};
+
+    struct PanickingDb;
+
+    impl DatabaseBackup for PanickingDb {
+        fn backup(
+            &self,
+            _path: &Path,
+            _compact: bool,
+            _on_progress: &mut dyn FnMut(BackupProgress),
+        ) -> Result<(), DatabaseError> {
+            panic!("backup failed")
+        }
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn panicking_backup_fails() {
+        let api = AdminBackupApi::new(PanickingDb, Box::<TokioTaskExecutor>::default());
+        api.backup_database(PathBuf::from("backup"), false).await.unwrap();
+
+        let status = loop {
+            let status = api.database_backup_status().await.unwrap().unwrap();
+            if status.state != DatabaseBackupState::Running {
+                break status
+            }
+            tokio::time::sleep(Duration::from_millis(10)).await;
+        };
+        assert_eq!(status.state, DatabaseBackupState::Failed);
+        assert_eq!(status.error.as_deref(), Some("database backup panicked"));
+    }
+}
@@ -8,37 +8,63 @@ use crate::{
         EthTransactions, TransactionSource,
     },
     result::{internal_rpc_err, ToRpcResult},
+    tracers::{TracerRegistry, TransactionContext},
     BlockingTaskGuard, EthApiSpec,
 };
 use alloy_rlp::{Decodable, Encodable};
 use async_trait::async_trait;
-use jsonrpsee::core::RpcResult;
+use futures::{FutureExt, Stream, StreamExt};
+use jsonrpsee::{core::RpcResult, server::SubscriptionMessage, PendingSubscriptionSink};
 use reth_primitives::{
-    revm::env::tx_env_with_recovered, Address, Block, BlockId, BlockNumberOrTag, Bytes,
-    TransactionSignedEcRecovered, Withdrawals, B256,
+    hex, keccak256, revm::env::tx_env_with_recovered, Address, BadBlock, Block, BlockId,
+    BlockNumber, BlockNumberOrTag, BlockWithSenders, Bytes, Hardfork, SealedBlockWithSenders,
+    TransactionSignedEcRecovered, Withdrawals, B256, U256,
 };
 use reth_provider::{
-    BlockReaderIdExt, ChainSpecProvider, HeaderProvider, StateProviderBox, TransactionVariant,
+    AccountReader, BadBlockReader, BlockNumReader, BlockReaderIdExt, ChainSpecProvider,
+    ChangeSetReader, HeaderProvider, StateProvider, StateProviderBox, StateProviderFactory,
+    StateRangeProvider, TransactionVariant,
+};
+use reth_revm::{
+    database::{StateProviderDatabase, SubState},
+    eth_dao_fork::{DAO_HARDFORK_BENEFICIARY, DAO_HARDKFORK_ACCOUNTS},
+    state_change::{apply_beacon_root_contract_call, post_block_balance_increments},
 };
-use reth_revm::database::{StateProviderDatabase, SubState};
 use reth_rpc_api::DebugApiServer;
 use reth_rpc_types::{
     trace::geth::{
         BlockTraceResult, FourByteFrame, GethDebugBuiltInTracerType, GethDebugTracerType,
         GethDebugTracingCallOptions, GethDebugTracingOptions, GethTrace, NoopFrame, TraceResult,
     },
-    BlockError, Bundle, CallRequest, RichBlock, StateContext,
+    AccountRangeResult, BadBlockResult, BlockError, Bundle, CallRequest, DebugSubscriptionKind,
+    DumpAccount, StateContext, StorageRangeEntry, StorageRangeResult,
 };
+use reth_rpc_types_compat::block::{from_block_full, from_block_with_tx_hashes};
+use reth_trie::{HashedPostState, HashedStorage};
 use revm::{
-    db::CacheDB,
+    db::{AccountState, CacheDB},
     primitives::{db::DatabaseCommit, BlockEnv, CfgEnvWithHandlerCfg, Env, EnvWithHandlerCfg},
+    Database,
 };
-use revm_inspectors::tracing::{
-    js::{JsInspector, TransactionContext},
-    FourByteInspector, TracingInspector, TracingInspectorConfig,
+#[cfg(feature = "js-tracer")]
+use revm_inspectors::tracing::js::JsInspector;
+use revm_inspectors::tracing::{FourByteInspector, TracingInspector, TracingInspectorConfig};
+use std::{
+    collections::{BTreeMap, HashMap, HashSet},
+    sync::Arc,
 };
-use std::sync::Arc;
-use tokio::sync::{AcquireError, OwnedSemaphorePermit};
+use tokio::sync::{mpsc, AcquireError, OwnedSemaphorePermit};
+use tokio_stream::wrappers::ReceiverStream;
+
+/// The maximum number of accounts returned by `debug_accountRange`, same as geth.
+const ACCOUNT_RANGE_MAX_RESULTS: u64 = 256;
+
+/// The maximum number of storage slots returned per account by `debug_accountRange`.
+const ACCOUNT_RANGE_MAX_STORAGE_SLOTS: usize = 256;
+
+/// The number of blocks a `debug_traceChain` subscription loads ahead of the traced block, and
+/// traces ahead of the subscriber.
+const TRACE_CHAIN_BUFFER: usize = 4;
 
 /// `debug` API implementation.
 ///
@@ -51,8 +77,27 @@ pub struct DebugApi<Provider, Eth> {
 
 impl<Provider, Eth> DebugApi<Provider, Eth> {
     /// Create a new instance of the [DebugApi]
-    pub fn new(provider: Provider, eth: Eth, blocking_task_guard: BlockingTaskGuard) -> Self {
-        let inner = Arc::new(DebugApiInner { provider, eth_api: eth, blocking_task_guard });
+    ///
+    /// The `tracers` are looked up by the name given in the `tracer` field of the tracing options
+    /// before falling back to the JavaScript tracer. `max_modified_accounts_blocks` and
+    /// `max_trace_chain_blocks` are the maximum block ranges a `debug_getModifiedAccountsBy*`
+    /// request and a `debug_traceChain` subscription may span.
+    pub fn new(
+        provider: Provider,
+        eth: Eth,
+        blocking_task_guard: BlockingTaskGuard,
+        tracers: TracerRegistry,
+        max_modified_accounts_blocks: u64,
+        max_trace_chain_blocks: u64,
+    ) -> Self {
+        let inner = Arc::new(DebugApiInner {
+            provider,
+            eth_api: eth,
+            blocking_task_guard,
+            tracers,
+            max_modified_accounts_blocks,
+            max_trace_chain_blocks,
+        });
         Self { inner }
     }
 }
@@ -61,7 +106,13 @@ impl<Provider, Eth> DebugApi<Provider, Eth> {
 
 impl<Provider, Eth> DebugApi<Provider, Eth>
 where
-    Provider: BlockReaderIdExt + HeaderProvider + ChainSpecProvider + 'static,
+    Provider: BlockReaderIdExt
+        + HeaderProvider
+        + ChainSpecProvider
+        + StateProviderFactory
+        + ChangeSetReader
+        + BadBlockReader
+        + 'static,
     Eth: EthTransactions + 'static,
 {
     /// Acquires a permit to execute a tracing call.
@@ -69,6 +120,16 @@ where
         self.inner.blocking_task_guard.clone().acquire_owned().await
     }
 
+    /// Returns an error if the hashed state at the given block would have to be reverted further
+    /// than the proof window of the eth API allows, same as `eth_getProof`.
+    fn ensure_within_proof_window(&self, block_number: BlockNumber) -> EthResult<()> {
+        let best_number = self.inner.provider.best_block_number()?;
+        if best_number.saturating_sub(block_number) > self.inner.eth_api.eth_proof_window() {
+            return Err(EthApiError::ExceedsMaxProofWindow)
+        }
+        Ok(())
+    }
+
     /// Trace the entire block asynchronously
     async fn trace_block_with(
         &self,
@@ -83,49 +144,66 @@ where
         self.inner
             .eth_api
             .spawn_with_state_at_block(at, move |state| {
-                let block_hash = at.as_block_hash();
-                let mut results = Vec::with_capacity(transactions.len());
                 let mut db = CacheDB::new(StateProviderDatabase::new(state));
-                let mut transactions = transactions.into_iter().enumerate().peekable();
-                while let Some((index, tx)) = transactions.next() {
-                    let tx_hash = tx.hash;
-                    let tx = tx_env_with_recovered(&tx);
-                    let env = EnvWithHandlerCfg::new(
-                        Env::boxed(cfg.cfg_env.clone(), block_env.clone(), tx),
-                        cfg.handler_cfg.spec_id,
-                    );
-                    let (result, state_changes) = this
-                        .trace_transaction(
-                            opts.clone(),
-                            env,
-                            &mut db,
-                            Some(TransactionContext {
-                                block_hash,
-                                tx_hash: Some(tx_hash),
-                                tx_index: Some(index),
-                            }),
-                        )
-                        .map_err(|err| {
-                            results.push(TraceResult::Error {
-                                error: err.to_string(),
-                                tx_hash: Some(tx_hash),
-                            });
-                            err
-                        })?;
-
-                    results.push(TraceResult::Success { result, tx_hash: Some(tx_hash) });
-                    if transactions.peek().is_some() {
-                        // need to apply the state changes of this transaction before executing the
-                        // next transaction
-                        db.commit(state_changes)
-                    }
-                }
-
-                Ok(results)
+                this.trace_transactions(
+                    &mut db,
+                    at.as_block_hash(),
+                    transactions,
+                    &cfg,
+                    &block_env,
+                    &opts,
+                )
             })
             .await
     }
 
+    /// Traces the transactions in order on top of the given state, and commits the state changes
+    /// of every transaction to it.
+    fn trace_transactions(
+        &self,
+        db: &mut SubState<StateProviderBox>,
+        block_hash: Option<B256>,
+        transactions: Vec<TransactionSignedEcRecovered>,
+        cfg: &CfgEnvWithHandlerCfg,
+        block_env: &BlockEnv,
+        opts: &GethDebugTracingOptions,
+    ) -> EthResult<Vec<TraceResult>> {
+        let mut results = Vec::with_capacity(transactions.len());
+        for (index, tx) in transactions.into_iter().enumerate() {
+            let tx_hash = tx.hash;
+            let tx = tx_env_with_recovered(&tx);
+            let env = EnvWithHandlerCfg::new(
+                Env::boxed(cfg.cfg_env.clone(), block_env.clone(), tx),
+                cfg.handler_cfg.spec_id,
+            );
+            let (result, state_changes) = self
+                .trace_transaction(
+                    opts.clone(),
+                    env,
+                    db,
+                    Some(TransactionContext {
+                        block_hash,
+                        tx_hash: Some(tx_hash),
+                        tx_index: Some(index),
+                    }),
+                )
+                .map_err(|err| {
+                    results.push(TraceResult::Error {
+                        error: err.to_string(),
+                        tx_hash: Some(tx_hash),
+                    });
+                    err
+                })?;
+
+            results.push(TraceResult::Success { result, tx_hash: Some(tx_hash) });
+            // need to apply the state changes of this transaction before executing the next
+            // transaction
+            db.commit(state_changes)
+        }
+
+        Ok(results)
+    }
+
     /// Replays the given block and returns the trace of each transaction.
     ///
     /// This expects a rlp encoded block
@@ -138,7 +216,32 @@ where
     ) -> EthResult<Vec<TraceResult>> {
         let block =
             Block::decode(&mut rlp_block.as_ref()).map_err(BlockError::RlpDecodeRawBlock)?;
+        self.trace_unsealed_block(block, opts).await
+    }
+
+    /// Replays a block that failed validation and returns the trace of each transaction.
+    ///
+    /// The block is looked up in the store of recent bad blocks.
+    pub async fn debug_trace_bad_block(
+        &self,
+        block_hash: B256,
+        opts: GethDebugTracingOptions,
+    ) -> EthResult<Vec<TraceResult>> {
+        let bad_block = self
+            .inner
+            .provider
+            .bad_block(block_hash)?
+            .ok_or_else(|| EthApiError::UnknownBlockNumber)?;
+        self.trace_unsealed_block(bad_block.block.unseal(), opts).await
+    }
 
+    /// Replays the given block, which is not necessarily part of the chain, on top of its parent
+    /// block and returns the trace of each transaction.
+    async fn trace_unsealed_block(
+        &self,
+        block: Block,
+        opts: GethDebugTracingOptions,
+    ) -> EthResult<Vec<TraceResult>> {
         let (cfg, block_env) = self.inner.eth_api.evm_env_for_raw_block(&block.header).await?;
         // we trace on top the block's parent block
         let parent = block.parent_hash;
@@ -180,9 +283,18 @@ where
             .block_hash_for_id(block_id)?
             .ok_or_else(|| EthApiError::UnknownBlockNumber)?;
 
+        self.trace_block_by_hash(block_hash, opts).await
+    }
+
+    /// Replays the block with the given hash and returns the trace of each transaction.
+    async fn trace_block_by_hash(
+        &self,
+        block_hash: B256,
+        opts: GethDebugTracingOptions,
+    ) -> EthResult<Vec<TraceResult>> {
         let ((cfg, block_env, _), block) = futures::try_join!(
             self.inner.eth_api.evm_env_at(block_hash.into()),
-            self.inner.eth_api.block_by_id_with_senders(block_id),
+            self.inner.eth_api.block_by_id_with_senders(block_hash.into()),
         )?;
 
         let block = block.ok_or_else(|| EthApiError::UnknownBlockNumber)?;
@@ -200,6 +312,221 @@ where
         .await
     }
 
+    /// Traces every block of the canonical chain in the `(start_exclusive, end_inclusive]` range.
+    ///
+    /// The blocks are executed in ascending order on top of the state of the start block, which is
+    /// carried forward from block to block, and the returned stream yields the traces of every
+    /// block as soon as it was traced. The next blocks are loaded while a block is traced. The
+    /// whole range is traced with a single tracing permit.
+    ///
+    /// The hashes of the blocks are resolved once, so that a reorg while the chain is traced can't
+    /// mix the blocks of two chains. The stream yields an error for a block that was reorged out,
+    /// after which it ends.
+    pub fn debug_trace_chain(
+        &self,
+        start_exclusive: BlockNumberOrTag,
+        end_inclusive: BlockNumberOrTag,
+        opts: GethDebugTracingOptions,
+    ) -> EthResult<impl Stream<Item = EthResult<BlockTraceResult>> + Send + 'static> {
+        let provider = &self.inner.provider;
+        let start = provider
+            .convert_block_number(start_exclusive)?
+            .ok_or_else(|| EthApiError::UnknownBlockNumber)?;
+        let end = provider
+            .convert_block_number(end_inclusive)?
+            .ok_or_else(|| EthApiError::UnknownBlockNumber)?;
+        if start >= end {
+            return Err(EthApiError::InvalidParams(format!(
+                "end block ({end}) must be greater than start block ({start})"
+            )))
+        }
+        let max_blocks = self.inner.max_trace_chain_blocks;
+        if end - start > max_blocks {
+            return Err(EthApiError::InvalidParams(format!(
+                "Block range too large; currently limited to {max_blocks} blocks"
+            )))
+        }
+        if end > provider.best_block_number()? {
+            return Err(EthApiError::UnknownBlockNumber)
+        }
+
+        let hashes = provider.canonical_hashes_range(start + 1, end + 1)?;
+        if hashes.len() as u64 != end - start {
+            return Err(EthApiError::UnknownBlockNumber)
+        }
+
+        let (blocks_tx, mut blocks_rx) = mpsc::channel(TRACE_CHAIN_BUFFER);
+        let (results_tx, results_rx) = mpsc::channel(TRACE_CHAIN_BUFFER);
+
+        let this = self.clone();
+        let load_blocks = async move {
+            for hash in hashes {
+                let block = this.load_trace_chain_block(hash).await;
+                let failed = block.is_err();
+                if blocks_tx.send(block).await.is_err() || failed {
+                    break
+                }
+            }
+        };
+
+        let this = self.clone();
+        let trace_blocks = async move {
+            let _permit = this.acquire_trace_permit().await;
+            let tracer = this.clone();
+            let results = results_tx.clone();
+            let traced = this
+                .inner
+                .eth_api
+                .spawn_with_state_at_block(BlockNumberOrTag::Number(start).into(), move |state| {
+                    let mut db = CacheDB::new(StateProviderDatabase::new(state));
+                    while let Some(block) = blocks_rx.blocking_recv() {
+                        let result =
+                            block.and_then(|block| tracer.trace_chain_block(&mut db, block, &opts));
+                        let failed = result.is_err();
+                        if results.blocking_send(result).is_err() || failed {
+                            break
+                        }
+                    }
+                    Ok(())
+                })
+                .await;
+            if let Err(err) = traced {
+                let _ = results_tx.send(Err(err)).await;
+            }
+        };
+
+        // The blocks are loaded and traced while the stream is polled, and the stream ends once
+        // both are done and all results were yielded.
+        let work = futures::future::join(load_blocks, trace_blocks);
+        let stream = futures::stream::select(
+            ReceiverStream::new(results_rx).map(Some),
+            work.into_stream().map(|_| None),
+        )
+        .filter_map(futures::future::ready);
+
+        Ok(stream)
+    }
+
+    /// Loads a block of a `debug_traceChain` range, along with its environment.
+    async fn load_trace_chain_block(&self, block_hash: B256) -> EthResult<TraceChainBlock> {
+        let ((cfg, block_env, _), block) = futures::try_join!(
+            self.inner.eth_api.evm_env_at(block_hash.into()),
+            self.inner.eth_api.block_by_id_with_senders(block_hash.into()),
+        )?;
+        let block = block.ok_or_else(|| EthApiError::UnknownBlockNumber)?;
+        let total_difficulty = self
+            .inner
+            .provider
+            .header_td_by_number(block.number)?
+            .ok_or_else(|| EthApiError::UnknownBlockNumber)?;
+        Ok(TraceChainBlock { block, cfg, block_env, total_difficulty })
+    }
+
+    /// Traces a block of a `debug_traceChain` range on top of the state of its parent, and applies
+    /// all state changes of the block to that state: the beacon root contract call, the
+    /// transactions, the DAO hardfork, block rewards and withdrawals.
+    fn trace_chain_block(
+        &self,
+        db: &mut SubState<StateProviderBox>,
+        block: TraceChainBlock,
+        opts: &GethDebugTracingOptions,
+    ) -> EthResult<BlockTraceResult> {
+        let TraceChainBlock { block, cfg, block_env, total_difficulty } = block;
+        let chain_spec = self.inner.provider.chain_spec();
+
+        let mut evm_pre_block = revm::Evm::builder()
+            .with_db(&mut *db)
+            .with_env_with_handler_cfg(EnvWithHandlerCfg::new_with_cfg_env(
+                cfg.clone(),
+                block_env.clone(),
+                Default::default(),
+            ))
+            .build();
+        apply_beacon_root_contract_call(
+            &chain_spec,
+            block.timestamp,
+            block.number,
+            block.parent_beacon_block_root,
+            &mut evm_pre_block,
+        )
+        .map_err(|err| EthApiError::Internal(err.into()))?;
+        drop(evm_pre_block);
+
+        let mut balance_increments = post_block_balance_increments(
+            &chain_spec,
+            block.number,
+            block.difficulty,
+            block.beneficiary,
+            block.timestamp,
+            total_difficulty,
+            &block.ommers,
+            block.withdrawals.as_ref().map(Withdrawals::as_ref),
+        );
+        let (number, hash) = (block.number, block.hash);
+        let traces = self.trace_transactions(
+            db,
+            Some(hash),
+            block.into_transactions_ecrecovered().collect(),
+            &cfg,
+            &block_env,
+            opts,
+        )?;
+
+        if chain_spec.fork(Hardfork::Dao).transitions_at_block(number) {
+            let mut drained_balance = U256::ZERO;
+            for address in DAO_HARDKFORK_ACCOUNTS {
+                let mut info = db.basic(address)?.unwrap_or_default();
+                drained_balance += std::mem::take(&mut info.balance);
+                db.insert_account_info(address, info);
+            }
+            *balance_increments.entry(DAO_HARDFORK_BENEFICIARY).or_default() +=
+                drained_balance.to::<u128>();
+        }
+        for (address, increment) in balance_increments {
+            let mut info = db.basic(address)?.unwrap_or_default();
+            info.balance += U256::from(increment);
+            db.insert_account_info(address, info);
+        }
+
+        Ok(BlockTraceResult { block: U256::from(number), hash, traces })
+    }
+
+    /// Returns the recent blocks that failed validation, the most recent first.
+    pub fn bad_blocks(&self) -> EthResult<Vec<BadBlockResult>> {
+        let bad_blocks = self.inner.provider.bad_blocks()?;
+
+        let mut results = Vec::with_capacity(bad_blocks.len());
+        for BadBlock { block, error } in bad_blocks {
+            let hash = block.hash();
+            // the parent of a bad block is not necessarily known
+            let total_difficulty =
+                self.inner.provider.header_td(&block.parent_hash)?.unwrap_or_default() +
+                    block.difficulty;
+
+            let block = block.unseal();
+            let mut rlp = Vec::new();
+            block.encode(&mut rlp);
+
+            // the block may be invalid because of a transaction with an invalid signature
+            let rich_block = match block.senders() {
+                Some(senders) => from_block_full(
+                    BlockWithSenders { block, senders },
+                    total_difficulty,
+                    Some(hash),
+                )?,
+                None => from_block_with_tx_hashes(
+                    BlockWithSenders { block, senders: Vec::new() },
+                    total_difficulty,
+                    Some(hash),
+                ),
+            };
+
+            results.push(BadBlockResult { hash, block: rich_block.into(), rlp: rlp.into(), error });
+        }
+
+        Ok(results)
+    }
+
     /// Trace the transaction according to the provided options.
     ///
     /// Ref: <https://geth.ethereum.org/docs/developers/evm-tracing/built-in-tracers>
@@ -343,14 +670,18 @@ where
 
                     let (_, _, at) = self.inner.eth_api.evm_env_at(at).await?;
 
-                    let res = self
+                    let this = self.clone();
+                    let (res, _) = self
                         .inner
                         .eth_api
-                        .spawn_with_call_at(call, at, overrides, move |db, env| {
-                            let mut inspector = JsInspector::new(code, config)?;
-                            let (res, _, db) =
-                                inspect_and_return_db(db, env.clone(), &mut inspector)?;
-                            Ok(inspector.json_result(res, &env, &db)?)
+                        .spawn_with_call_at(call, at, overrides, move |mut db, env| {
+                            this.trace_with_named_tracer(
+                                code,
+                                config,
+                                env,
+                                &mut db,
+                                TransactionContext::default(),
+                            )
                         })
                         .await?;
 
@@ -552,15 +883,13 @@ where
                 },
                 GethDebugTracerType::JsTracer(code) => {
                     let config = tracer_config.into_json();
-                    let mut inspector = JsInspector::with_transaction_context(
+                    let (result, state) = self.trace_with_named_tracer(
                         code,
                         config,
+                        env,
+                        db,
                         transaction_context.unwrap_or_default(),
                     )?;
-                    let (res, env, db) = inspect_and_return_db(db, env, &mut inspector)?;
-
-                    let state = res.state.clone();
-                    let result = inspector.json_result(res, &env, db)?;
                     Ok((GethTrace::JS(result), state))
                 }
             }
@@ -578,12 +907,332 @@ where
 
         Ok((frame.into(), res.state))
     }
+
+    /// Executes the transaction with the custom tracer registered under the given name.
+    ///
+    /// If no tracer is registered under the name, the name is treated as the code of a JavaScript
+    /// tracer.
+    ///
+    /// Caution: this is blocking and should be performed on a blocking task.
+    fn trace_with_named_tracer(
+        &self,
+        name: String,
+        config: serde_json::Value,
+        env: EnvWithHandlerCfg,
+        db: &mut SubState<StateProviderBox>,
+        transaction_context: TransactionContext,
+    ) -> EthResult<(serde_json::Value, revm_primitives::State)> {
+        if let Some(tracer) = self.inner.tracers.get(&name) {
+            return tracer.trace(config, env, db, transaction_context)
+        }
+        trace_with_js_tracer(name, config, env, db, transaction_context)
+    }
+
+    /// Executes the block with the given hash and returns the state root after each transaction.
+    ///
+    /// Post-block changes, such as block rewards and withdrawals, are not included in any of the
+    /// roots.
+    pub async fn debug_intermediate_roots(&self, block_hash: B256) -> EthResult<Vec<B256>> {
+        let block = self
+            .inner
+            .eth_api
+            .block_by_id_with_senders(block_hash.into())
+            .await?
+            .ok_or(EthApiError::UnknownBlockNumber)?;
+        self.inner.eth_api.intermediate_state_roots(block).await
+    }
+
+    /// Returns at most `max_results` accounts of the state at the given block, starting at the
+    /// `start` hashed address.
+    ///
+    /// Since reth does not store the preimages of hashed addresses, accounts are always keyed by
+    /// their hashed address and `incompletes` must be set. At most
+    /// [ACCOUNT_RANGE_MAX_STORAGE_SLOTS] storage slots are returned per account, the rest can be
+    /// paged through with `debug_storageRangeAt`.
+    ///
+    /// The range is read from the hashed state, so only blocks within the proof window are served.
+    pub async fn debug_account_range(
+        &self,
+        block_number: BlockNumberOrTag,
+        start: Bytes,
+        max_results: u64,
+        nocode: bool,
+        nostorage: bool,
+        incompletes: bool,
+    ) -> EthResult<AccountRangeResult> {
+        if !incompletes {
+            return Err(EthApiError::InvalidParams(
+                "address preimages are not stored, incompletes must be set".to_string(),
+            ))
+        }
+        if start.len() > B256::len_bytes() {
+            return Err(EthApiError::InvalidParams(format!(
+                "start key too long: {} bytes",
+                start.len()
+            )))
+        }
+        // the start key is a prefix of the hashed address
+        let mut start_key = B256::ZERO;
+        start_key[..start.len()].copy_from_slice(&start);
+
+        let limit = if max_results == 0 || max_results > ACCOUNT_RANGE_MAX_RESULTS {
+            ACCOUNT_RANGE_MAX_RESULTS
+        } else {
+            max_results
+        } as usize;
+
+        let header = self
+            .inner
+            .provider
+            .sealed_header_by_number_or_tag(block_number)?
+            .ok_or(EthApiError::UnknownBlockNumber)?;
+        self.ensure_within_proof_window(header.number)?;
+        let root = header.state_root;
+
+        self.inner
+            .eth_api
+            .spawn_with_state_at_block(header.hash().into(), move |state| {
+                let storage_limit = if nostorage { 0 } else { ACCOUNT_RANGE_MAX_STORAGE_SLOTS };
+                let range = state.account_range(
+                    &HashedPostState::default(),
+                    start_key,
+                    limit,
+                    storage_limit,
+                )?;
+
+                let mut accounts = BTreeMap::new();
+                for entry in range.accounts {
+                    let code = match entry.account.bytecode_hash {
+                        Some(code_hash) if !nocode => state
+                            .bytecode_by_hash(code_hash)?
+                            .map(|code| code.original_bytes())
+                            .filter(|code| !code.is_empty()),
+                        _ => None,
+                    };
+                    let storage = (!nostorage).then(|| {
+                        entry
+                            .storage
+                            .into_iter()
+                            .map(|(hashed_slot, value)| {
+                                (hashed_slot, hex::encode(value.to_be_bytes_trimmed_vec()))
+                            })
+                            .collect()
+                    });
+                    let account = DumpAccount {
+                        balance: entry.account.balance,
+                        nonce: entry.account.nonce,
+                        root: entry.storage_root,
+                        code_hash: entry.account.get_bytecode_hash(),
+                        code,
+                        storage,
+                        address: None,
+                        key: Some(entry.hashed_address),
+                    };
+                    accounts.insert(format!("pre({})", entry.hashed_address), account);
+                }
+
+                Ok(AccountRangeResult { root, accounts, next: range.next })
+            })
+            .await
+    }
+
+    /// Returns at most `max_result` storage slots of the given account, starting at the
+    /// `key_start` hashed slot, in the state after the first `tx_idx` transactions of the given
+    /// block have been executed.
+    ///
+    /// Preimages of hashed slots are only known for the slots accessed by the replayed
+    /// transactions. The range is read from the hashed state, so only blocks within the proof
+    /// window are served.
+    pub async fn debug_storage_range_at(
+        &self,
+        block_hash: B256,
+        tx_idx: usize,
+        contract_address: Address,
+        key_start: B256,
+        max_result: u64,
+    ) -> EthResult<StorageRangeResult> {
+        let ((cfg, block_env, _), block) = futures::try_join!(
+            self.inner.eth_api.evm_env_at(block_hash.into()),
+            self.inner.eth_api.block_by_id_with_senders(block_hash.into()),
+        )?;
+        let block = block.ok_or(EthApiError::UnknownBlockNumber)?;
+        self.ensure_within_proof_window(block.number.saturating_sub(1))?;
+
+        // same as geth, the index must point to a transaction of the block unless the block is
+        // empty
+        if tx_idx >= block.body.len() && tx_idx > 0 {
+            return Err(EthApiError::InvalidParams(format!(
+                "transaction index {tx_idx} out of range for block {block_hash}"
+            )))
+        }
+
+        // we need the state that points to the beginning of the block, which is the state at the
+        // parent block
+        self.inner
+            .eth_api
+            .spawn_with_state_at_block(block.parent_hash.into(), move |state| {
+                let mut db = CacheDB::new(StateProviderDatabase::new(state));
+
+                // replay all transactions prior to the targeted one
+                for tx in block.into_transactions_ecrecovered().take(tx_idx) {
+                    let tx = tx_env_with_recovered(&tx);
+                    let env = EnvWithHandlerCfg::new(
+                        Env::boxed(cfg.cfg_env.clone(), block_env.clone(), tx),
+                        cfg.handler_cfg.spec_id,
+                    );
+                    let (res, _) = transact(&mut db, env)?;
+                    db.commit(res.state);
+                }
+
+                // apply the storage changes of the replayed transactions on top of the state of
+                // the parent block
+                let mut preimages = HashMap::new();
+                let mut post_state = HashedPostState::default();
+                if let Some(account) = db.accounts.get(&contract_address) {
+                    let wiped = matches!(
+                        account.account_state,
+                        AccountState::StorageCleared | AccountState::NotExisting
+                    );
+                    let storage = account.storage.iter().map(|(slot, value)| {
+                        let slot = B256::from(*slot);
+                        let hashed_slot = keccak256(slot);
+                        preimages.insert(hashed_slot, slot);
+                        (hashed_slot, *value)
+                    });
+                    post_state.storages.insert(
+                        keccak256(contract_address),
+                        HashedStorage::from_iter(wiped, storage),
+                    );
+                }
+
+                let range = db.db.state().storage_range(
+                    &post_state,
+                    keccak256(contract_address),
+                    key_start,
+                    max_result as usize,
+                )?;
+
+                let storage = range
+                    .slots
+                    .into_iter()
+                    .map(|(hashed_slot, value)| {
+                        let entry = StorageRangeEntry {
+                            key: preimages.get(&hashed_slot).copied(),
+                            value: value.into(),
+                        };
+                        (hashed_slot, entry)
+                    })
+                    .collect();
+
+                Ok(StorageRangeResult { storage, next_key: range.next })
+            })
+            .await
+    }
+
+    /// Returns all accounts whose nonce, balance, code hash or storage changed between the `start`
+    /// and `end` blocks.
+    ///
+    /// If no end block is given, the accounts modified in the `start` block are returned.
+    pub async fn debug_get_modified_accounts_by_number(
+        &self,
+        start: BlockNumber,
+        end: Option<BlockNumber>,
+    ) -> EthResult<Vec<Address>> {
+        let (start, end) = match end {
+            Some(end) => (start, end),
+            None if start == 0 => {
+                return Err(EthApiError::InvalidParams(
+                    "genesis block has no parent block".to_string(),
+                ))
+            }
+            None => (start - 1, start),
+        };
+        if start >= end {
+            return Err(EthApiError::InvalidParams(format!(
+                "start block height ({start}) must be less than end block height ({end})"
+            )))
+        }
+        // all changesets of the range are read
+        let max_blocks = self.inner.max_modified_accounts_blocks;
+        if end - start > max_blocks {
+            return Err(EthApiError::InvalidParams(format!(
+                "Block range too large; currently limited to {max_blocks} blocks"
+            )))
+        }
+
+        let this = self.clone();
+        self.inner
+            .eth_api
+            .spawn_with_state_at_block(BlockNumberOrTag::Number(start).into(), move |start_state| {
+                let provider = &this.inner.provider;
+                let end_state = provider.history_by_block_number(end)?;
+
+                // collect all accounts and storage slots that were touched in the range
+                let mut candidates = BTreeMap::<Address, HashSet<B256>>::new();
+                for block_number in start + 1..=end {
+                    for change in provider.account_block_changeset(block_number)? {
+                        candidates.entry(change.address).or_default();
+                    }
+                    for (address, entry) in provider.storage_block_changeset(block_number)? {
+                        candidates.entry(address).or_default().insert(entry.key);
+                    }
+                }
+
+                // only keep the accounts whose state actually differs between the two blocks
+                let mut modified = Vec::new();
+                for (address, slots) in candidates {
+                    let before = start_state.basic_account(address)?.unwrap_or_default();
+                    let after = end_state.basic_account(address)?.unwrap_or_default();
+                    let mut changed = before.nonce != after.nonce ||
+                        before.balance != after.balance ||
+                        before.get_bytecode_hash() != after.get_bytecode_hash();
+                    for slot in slots {
+                        if changed {
+                            break
+                        }
+                        changed = start_state.storage(address, slot)?.unwrap_or_default() !=
+                            end_state.storage(address, slot)?.unwrap_or_default();
+                    }
+                    if changed {
+                        modified.push(address);
+                    }
+                }
+
+                Ok(modified)
+            })
+            .await
+    }
+
+    /// Returns all accounts whose nonce, balance, code hash or storage changed between the blocks
+    /// with the given hashes.
+    ///
+    /// See also [Self::debug_get_modified_accounts_by_number]
+    pub async fn debug_get_modified_accounts_by_hash(
+        &self,
+        start_hash: B256,
+        end_hash: Option<B256>,
+    ) -> EthResult<Vec<Address>> {
+        let start =
+            self.inner.provider.block_number(start_hash)?.ok_or(EthApiError::UnknownBlockNumber)?;
+        let end = end_hash
+            .map(|end_hash| {
+                self.inner.provider.block_number(end_hash)?.ok_or(EthApiError::UnknownBlockNumber)
+            })
+            .transpose()?;
+        self.debug_get_modified_accounts_by_number(start, end).await
+    }
 }
 
 #[async_trait]
 impl<Provider, Eth> DebugApiServer for DebugApi<Provider, Eth>
 where
-    Provider: BlockReaderIdExt + HeaderProvider + ChainSpecProvider + 'static,
+    Provider: BlockReaderIdExt
+        + HeaderProvider
+        + ChainSpecProvider
+        + StateProviderFactory
+        + ChangeSetReader
+        + BadBlockReader
+        + 'static,
     Eth: EthApiSpec + 'static,
 {
     /// Handler for `debug_getRawHeader`
@@ -665,17 +1314,52 @@ where
     }
 
     /// Handler for `debug_getBadBlocks`
-    async fn bad_blocks(&self) -> RpcResult<Vec<RichBlock>> {
-        Err(internal_rpc_err("unimplemented"))
+    async fn bad_blocks(&self) -> RpcResult<Vec<BadBlockResult>> {
+        Ok(DebugApi::bad_blocks(self)?)
     }
 
-    /// Handler for `debug_traceChain`
+    /// Handler for `debug_subscribe("traceChain")`
     async fn debug_trace_chain(
         &self,
-        _start_exclusive: BlockNumberOrTag,
-        _end_inclusive: BlockNumberOrTag,
-    ) -> RpcResult<Vec<BlockTraceResult>> {
-        Err(internal_rpc_err("unimplemented"))
+        pending: PendingSubscriptionSink,
+        _kind: DebugSubscriptionKind,
+        start_exclusive: BlockNumberOrTag,
+        end_inclusive: BlockNumberOrTag,
+        opts: Option<GethDebugTracingOptions>,
+    ) -> jsonrpsee::core::SubscriptionResult {
+        let mut stream = match DebugApi::debug_trace_chain(
+            self,
+            start_exclusive,
+            end_inclusive,
+            opts.unwrap_or_default(),
+        ) {
+            Ok(stream) => Box::pin(stream),
+            Err(err) => {
+                pending.reject(err).await;
+                return Ok(())
+            }
+        };
+
+        let sink = pending.accept().await?;
+        // Subscriptions run on their own task, which sends the error of a block that can't be
+        // traced before closing the subscription. The blocks themselves are traced on the blocking
+        // tasks of the eth API.
+        loop {
+            tokio::select! {
+                _ = sink.closed() => {
+                    // connection dropped
+                    return Ok(())
+                },
+                maybe_result = stream.next() => {
+                    // all blocks traced
+                    let Some(result) = maybe_result else { return Ok(()) };
+                    let msg = SubscriptionMessage::from_json(&result?)?;
+                    if sink.send(msg).await.is_err() {
+                        return Ok(())
+                    }
+                }
+            }
+        }
     }
 
     /// Handler for `debug_traceBlock`
@@ -744,16 +1428,26 @@ where
         Ok(())
     }
 
+    /// Handler for `debug_accountRange`
     async fn debug_account_range(
         &self,
-        _block_number: BlockNumberOrTag,
-        _start: Bytes,
-        _max_results: u64,
-        _nocode: bool,
-        _nostorage: bool,
-        _incompletes: bool,
-    ) -> RpcResult<()> {
-        Ok(())
+        block_number: BlockNumberOrTag,
+        start: Bytes,
+        max_results: u64,
+        nocode: bool,
+        nostorage: bool,
+        incompletes: bool,
+    ) -> RpcResult<AccountRangeResult> {
+        Ok(DebugApi::debug_account_range(
+            self,
+            block_number,
+            start,
+            max_results,
+            nocode,
+            nostorage,
+            incompletes,
+        )
+        .await?)
     }
 
     async fn debug_block_profile(&self, _file: String, _seconds: u64) -> RpcResult<()> {
@@ -808,32 +1502,36 @@ where
         Ok(())
     }
 
+    /// Handler for `debug_getModifiedAccountsByHash`
     async fn debug_get_modified_accounts_by_hash(
         &self,
-        _start_hash: B256,
-        _end_hash: B256,
-    ) -> RpcResult<()> {
-        Ok(())
+        start_hash: B256,
+        end_hash: Option<B256>,
+    ) -> RpcResult<Vec<Address>> {
+        Ok(DebugApi::debug_get_modified_accounts_by_hash(self, start_hash, end_hash).await?)
     }
 
+    /// Handler for `debug_getModifiedAccountsByNumber`
     async fn debug_get_modified_accounts_by_number(
         &self,
-        _start_number: u64,
-        _end_number: u64,
-    ) -> RpcResult<()> {
-        Ok(())
+        start_number: u64,
+        end_number: Option<u64>,
+    ) -> RpcResult<Vec<Address>> {
+        Ok(DebugApi::debug_get_modified_accounts_by_number(self, start_number, end_number).await?)
     }
 
     async fn debug_go_trace(&self, _file: String, _seconds: u64) -> RpcResult<()> {
         Ok(())
     }
 
+    /// Handler for `debug_intermediateRoots`
     async fn debug_intermediate_roots(
         &self,
-        _block_hash: B256,
+        block_hash: B256,
         _opts: Option<GethDebugTracingCallOptions>,
-    ) -> RpcResult<()> {
-        Ok(())
+    ) -> RpcResult<Vec<B256>> {
+        let _permit = self.acquire_trace_permit().await;
+        Ok(DebugApi::debug_intermediate_roots(self, block_hash).await?)
     }
 
     async fn debug_mem_stats(&self) -> RpcResult<()> {
@@ -912,23 +1610,35 @@ where
         Ok(())
     }
 
+    /// Handler for `debug_storageRangeAt`
     async fn debug_storage_range_at(
         &self,
-        _block_hash: B256,
-        _tx_idx: usize,
-        _contract_address: Address,
-        _key_start: B256,
-        _max_result: u64,
-    ) -> RpcResult<()> {
-        Ok(())
+        block_hash: B256,
+        tx_idx: usize,
+        contract_address: Address,
+        key_start: B256,
+        max_result: u64,
+    ) -> RpcResult<StorageRangeResult> {
+        let _permit = self.acquire_trace_permit().await;
+        Ok(DebugApi::debug_storage_range_at(
+            self,
+            block_hash,
+            tx_idx,
+            contract_address,
+            key_start,
+            max_result,
+        )
+        .await?)
     }
 
+    /// Handler for `debug_traceBadBlock`
     async fn debug_trace_bad_block(
         &self,
-        _block_hash: B256,
-        _opts: Option<GethDebugTracingCallOptions>,
-    ) -> RpcResult<()> {
-        Ok(())
+        block_hash: B256,
+        opts: Option<GethDebugTracingOptions>,
+    ) -> RpcResult<Vec<TraceResult>> {
+        let _permit = self.acquire_trace_permit().await;
+        Ok(DebugApi::debug_trace_bad_block(self, block_hash, opts.unwrap_or_default()).await?)
     }
 
     async fn debug_verbosity(&self, _level: usize) -> RpcResult<()> {
@@ -964,6 +1674,14 @@ impl<Provider, Eth> Clone for DebugApi<Provider, Eth> {
     }
 }
 
+/// A block of a `debug_traceChain` range, along with its environment.
+struct TraceChainBlock {
+    block: SealedBlockWithSenders,
+    cfg: CfgEnvWithHandlerCfg,
+    block_env: BlockEnv,
+    total_difficulty: U256,
+}
+
 struct DebugApiInner<Provider, Eth> {
     /// The provider that can interact with the chain.
     provider: Provider,
@@ -971,4 +1689,187 @@ struct DebugApiInner<Provider, Eth> {
     eth_api: Eth,
     // restrict the number of concurrent calls to blocking calls
     blocking_task_guard: BlockingTaskGuard,
+    /// Custom tracers that can be selected by name
+    tracers: TracerRegistry,
+    /// Maximum block range of a `debug_getModifiedAccountsBy*` request
+    max_modified_accounts_blocks: u64,
+    /// Maximum block range of a `debug_traceChain` subscription
+    max_trace_chain_blocks: u64,
+}
+
+/// Executes the transaction with the given JavaScript tracer.
+#[cfg(feature = "js-tracer")]
+fn trace_with_js_tracer(
+    code: String,
+    config: serde_json::Value,
+    env: EnvWithHandlerCfg,
+    db: &mut SubState<StateProviderBox>,
+    transaction_context: TransactionContext,
+) -> EthResult<(serde_json::Value, revm_primitives::State)> {
+    let mut inspector =
+        JsInspector::with_transaction_context(code, config, transaction_context.into())?;
+    let (res, env, db) = inspect_and_return_db(db, env, &mut inspector)?;
+
+    let state = res.state.clone();
+    let result = inspector.json_result(res, &env, db)?;
+    Ok((result, state))
+}
+
+/// JavaScript tracers are only available with the `js-tracer` feature.
+#[cfg(not(feature = "js-tracer"))]
+fn trace_with_js_tracer(
+    _code: String,
+    _config: serde_json::Value,
+    _env: EnvWithHandlerCfg,
+    _db: &mut SubState<StateProviderBox>,
+    _transaction_context: TransactionContext,
+) -> EthResult<(serde_json::Value, revm_primitives::State)> {
+    Err(EthApiError::Unsupported("unknown tracer, JavaScript tracers are not enabled"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        eth::{
+            cache::EthStateCache, gas_oracle::GasPriceOracle, FeeHistoryCache,
+            FeeHistoryCacheConfig, RPC_DEFAULT_ETH_PROOF_WINDOW,
+        },
+        tracers::CustomTracer,
+        BlockingTaskPool, EthApi,
+    };
+    use reth_interfaces::test_utils::{generators, generators::random_signed_tx};
+    use reth_network_api::noop::NoopNetwork;
+    use reth_node_ethereum::EthEvmConfig;
+    use reth_primitives::{constants::ETHEREUM_BLOCK_GAS_LIMIT, Header};
+    use reth_provider::test_utils::MockEthProvider;
+    use reth_transaction_pool::test_utils::{testing_pool, TestPool};
+    use serde_json::json;
+
+    fn eth_api(
+        provider: &MockEthProvider,
+    ) -> EthApi<MockEthProvider, TestPool, NoopNetwork, EthEvmConfig> {
+        let evm_config = EthEvmConfig::default();
+        let cache = EthStateCache::spawn(provider.clone(), Default::default(), evm_config);
+        let fee_history_cache =
+            FeeHistoryCache::new(cache.clone(), FeeHistoryCacheConfig::default());
+        EthApi::new(
+            provider.clone(),
+            testing_pool(),
+            NoopNetwork::default(),
+            cache.clone(),
+            GasPriceOracle::new(provider.clone(), Default::default(), cache),
+            ETHEREUM_BLOCK_GAS_LIMIT,
+            RPC_DEFAULT_ETH_PROOF_WINDOW,
+            BlockingTaskPool::build().expect("failed to build tracing pool"),
+            fee_history_cache,
+            evm_config,
+        )
+    }
+
+    /// Returns its config and the context of the traced transaction.
+    struct ContextTracer;
+
+    impl CustomTracer for ContextTracer {
+        fn trace(
+            &self,
+            config: serde_json::Value,
+            _env: EnvWithHandlerCfg,
+            _db: &mut SubState<StateProviderBox>,
+            context: TransactionContext,
+        ) -> EthResult<(serde_json::Value, revm_primitives::State)> {
+            let trace = json!({
+                "config": config,
+                "blockHash": context.block_hash,
+                "txIndex": context.tx_index,
+                "txHash": context.tx_hash,
+            });
+            Ok((trace, Default::default()))
+        }
+    }
+
+    #[tokio::test]
+    async fn trace_transaction_with_custom_tracer() {
+        let mut rng = generators::rng();
+        let provider = MockEthProvider::default();
+        let block_hash = B256::with_last_byte(1);
+        let transaction = random_signed_tx(&mut rng);
+        let tx_hash = transaction.hash;
+        provider.add_block(
+            block_hash,
+            Block {
+                header: Header { number: 1, ..Default::default() },
+                body: vec![transaction],
+                ..Default::default()
+            },
+        );
+
+        let tracers = TracerRegistry::default();
+        let api = DebugApi::new(
+            provider.clone(),
+            eth_api(&provider),
+            BlockingTaskGuard::new(1),
+            tracers.clone(),
+            1,
+            1,
+        );
+        // tracers can be registered after the api was created
+        tracers.register("contextTracer", ContextTracer);
+
+        let opts = serde_json::from_value(json!({
+            "tracer": "contextTracer",
+            "tracerConfig": { "depth": 1 },
+        }))
+        .unwrap();
+        let trace =
+            DebugApiServer::debug_trace_transaction(&api, tx_hash, Some(opts)).await.unwrap();
+        let GethTrace::JS(trace) = trace else { panic!("expected a JSON trace, got {trace:?}") };
+        assert_eq!(
+            trace,
+            json!({
+                "config": { "depth": 1 },
+                "blockHash": block_hash,
+                "txIndex": 0,
+                "txHash": tx_hash,
+            })
+        );
+    }
+
+    #[tokio::test]
+    async fn modified_accounts_range_is_limited() {
+        let provider = MockEthProvider::default();
+        let api = DebugApi::new(
+            provider.clone(),
+            eth_api(&provider),
+            BlockingTaskGuard::new(1),
+            TracerRegistry::default(),
+            10,
+            10,
+        );
+
+        let err = api.debug_get_modified_accounts_by_number(5, Some(16)).await.unwrap_err();
+        assert!(matches!(err, EthApiError::InvalidParams(_)), "unexpected error: {err:?}");
+    }
+
+    #[tokio::test]
+    async fn trace_chain_range_is_limited() {
+        let provider = MockEthProvider::default();
+        let api = DebugApi::new(
+            provider.clone(),
+            eth_api(&provider),
+            BlockingTaskGuard::new(1),
+            TracerRegistry::default(),
+            10,
+            10,
+        );
+
+        let Err(err) = api.debug_trace_chain(
+            BlockNumberOrTag::Number(5),
+            BlockNumberOrTag::Number(16),
+            Default::default(),
+        ) else {
+            panic!("expected the range to be rejected")
+        };
+        assert!(matches!(err, EthApiError::InvalidParams(_)), "unexpected error: {err:?}");
+    }
 }
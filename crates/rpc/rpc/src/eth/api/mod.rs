@@ -42,15 +42,18 @@ mod call;
 pub(crate) mod fee_history;
 mod fees;
 #[cfg(feature = "optimism")]
-mod optimism;
+pub(crate) mod optimism;
 mod pending_block;
 mod server;
 mod sign;
+mod simulate;
 mod state;
-mod transactions;
+pub(crate) mod transactions;
 
 use crate::BlockingTaskPool;
-pub use transactions::{EthTransactions, TransactionSource};
+pub use transactions::{
+    EthTransactions, TransactionSource, DEFAULT_PRIVATE_TRANSACTION_BLOCK_RANGE,
+};
 
 /// `Eth` API trait.
 ///
@@ -102,6 +105,7 @@ where
         eth_cache: EthStateCache,
         gas_oracle: GasPriceOracle<Provider>,
         gas_cap: impl Into<GasCap>,
+        eth_proof_window: u64,
         blocking_task_pool: BlockingTaskPool,
         fee_history_cache: FeeHistoryCache,
         evm_config: EvmConfig,
@@ -113,6 +117,7 @@ where
             eth_cache,
             gas_oracle,
             gas_cap.into().into(),
+            eth_proof_window,
             Box::<TokioTaskExecutor>::default(),
             blocking_task_pool,
             fee_history_cache,
@@ -129,6 +134,7 @@ where
         eth_cache: EthStateCache,
         gas_oracle: GasPriceOracle<Provider>,
         gas_cap: u64,
+        eth_proof_window: u64,
         task_spawner: Box<dyn TaskSpawner>,
         blocking_task_pool: BlockingTaskPool,
         fee_history_cache: FeeHistoryCache,
@@ -150,6 +156,7 @@ where
             eth_cache,
             gas_oracle,
             gas_cap,
+            eth_proof_window,
             starting_block: U256::from(latest_block),
             task_spawner,
             pending_block: Default::default(),
@@ -198,6 +205,11 @@ where
         self.inner.gas_cap
     }
 
+    /// Returns the maximum number of blocks behind the tip for which `eth_getProof` is served
+    pub fn eth_proof_window(&self) -> u64 {
+        self.inner.eth_proof_window
+    }
+
     /// Returns the inner `Provider`
     pub fn provider(&self) -> &Provider {
         &self.inner.provider
@@ -435,6 +447,12 @@ where
 /// more complex calls.
 pub const RPC_DEFAULT_GAS_CAP: GasCap = GasCap(50_000_000);
 
+/// The default maximum number of blocks behind the tip for which `eth_getProof` is served.
+///
+/// Proofs for historical blocks are generated by reverting the hashed state down to the requested
+/// block, so the cost grows with the distance to the tip. The default covers finalized blocks.
+pub const RPC_DEFAULT_ETH_PROOF_WINDOW: u64 = 128;
+
 /// The wrapper type for gas limit
 #[derive(Debug, Clone, Copy)]
 pub struct GasCap(u64);
@@ -473,6 +491,8 @@ struct EthApiInner<Provider, Pool, Network, EvmConfig> {
     gas_oracle: GasPriceOracle<Provider>,
     /// Maximum gas limit for `eth_call` and call tracing RPC methods.
     gas_cap: u64,
+    /// Maximum number of blocks behind the tip for which `eth_getProof` is served.
+    eth_proof_window: u64,
     /// The block number at which the node started
     starting_block: U256,
     /// The type that can spawn tasks which would otherwise block.
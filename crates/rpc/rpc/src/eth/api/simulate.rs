@@ -0,0 +1,694 @@
+//! Contains the implementation of `eth_simulateV1`, which executes calls in a sequence of simulated
+//! blocks.
+
+use crate::{
+    eth::{
+        error::{EthApiError, EthResult, RevertError, RpcInvalidTransactionError},
+        revm_utils::{
+            apply_state_overrides, build_call_evm_env, inspect, prepare_call_env, transact,
+            EvmOverrides,
+        },
+        EthTransactions,
+    },
+    EthApi,
+};
+use reth_network_api::NetworkInfo;
+use reth_node_api::ConfigureEvmEnv;
+use reth_primitives::{
+    address, b256,
+    constants::{BEACON_NONCE, EMPTY_WITHDRAWALS},
+    proofs, AccessList, AccessListItem, Address, Block, BlockId, BlockNumberOrTag,
+    BlockWithSenders, Bytes, Hardfork, Header, Log, Receipt, Receipts, Signature, Transaction,
+    TransactionKind, TransactionSigned, TxEip1559, TxEip2930, TxEip4844, TxLegacy, Withdrawals,
+    B256, EMPTY_OMMER_ROOT_HASH, U256, U64,
+};
+use reth_provider::{
+    BlockReaderIdExt, BundleStateWithReceipts, ChainSpecProvider, EvmEnvProvider, ProviderError,
+    StateProviderFactory, StateRootProvider,
+};
+use reth_revm::{
+    database::StateProviderDatabase,
+    tracing::{
+        types::{CallKind, CallTraceNode, LogCallOrder},
+        TracingInspector, TracingInspectorConfig,
+    },
+};
+use reth_rpc_types::{
+    BlockOverrides, SimBlock, SimCallResult, SimulateError, SimulatePayload, SimulatedBlock,
+    MAX_SIMULATE_BLOCKS,
+};
+use reth_rpc_types_compat::block::from_block;
+use reth_transaction_pool::TransactionPool;
+use revm::{
+    db::{
+        states::{AccountStatus, BundleState},
+        AccountState, CacheDB,
+    },
+    primitives::{AccountInfo, BlockEnv, ExecutionResult, HashMap, TransactTo, TxEnv},
+    Database, DatabaseCommit,
+};
+
+/// The number of seconds between two simulated blocks, unless the timestamp is overridden.
+const SIMULATED_BLOCK_TIME: u64 = 12;
+
+/// The address of the pseudo token that emits the `Transfer` logs of ether transfers.
+const TRANSFER_LOG_EMITTER: Address = address!("EeeeeEeeeEeEeeEeEeEeeEEEeeeeEeeeeeeeEEeE");
+
+/// The topic of the `Transfer(address,address,uint256)` event.
+const TRANSFER_EVENT_TOPIC: B256 =
+    b256!("ddf252ad1be2c89b69c2b068fc378daa952ba7f163c4a11628f55a4df523b3ef");
+
+/// The error code of a reverted simulated call.
+const REVERTED_CALL_ERROR_CODE: i32 = 3;
+
+/// The error code of a simulated call whose execution halted.
+const HALTED_CALL_ERROR_CODE: i32 = -32015;
+
+impl<Provider, Pool, Network, EvmConfig> EthApi<Provider, Pool, Network, EvmConfig>
+where
+    Pool: TransactionPool + Clone + 'static,
+    Provider:
+        BlockReaderIdExt + ChainSpecProvider + StateProviderFactory + EvmEnvProvider + 'static,
+    Network: NetworkInfo + Send + Sync + 'static,
+    EvmConfig: ConfigureEvmEnv + 'static,
+{
+    /// Executes the calls of a sequence of simulated blocks on top of the state at the given
+    /// [BlockId] (`eth_simulateV1`).
+    ///
+    /// Every simulated block is executed on top of the state of the previous one, after applying
+    /// its own block and state overrides. Gaps between the block numbers are filled with empty
+    /// blocks.
+    ///
+    /// Computing the state root of a simulated block requires reverting the history from the tip
+    /// to the target block, so the state roots are only computed in validation mode or if full
+    /// transactions are requested, and are zero otherwise.
+    pub async fn simulate_v1(
+        &self,
+        payload: SimulatePayload,
+        block_number: Option<BlockId>,
+    ) -> EthResult<Vec<SimulatedBlock>> {
+        let SimulatePayload {
+            block_state_calls,
+            trace_transfers,
+            validation,
+            return_full_transactions,
+        } = payload;
+        if block_state_calls.is_empty() {
+            return Err(EthApiError::InvalidParams(String::from("blocks are empty.")))
+        }
+
+        let target_block = block_number.unwrap_or(BlockId::Number(BlockNumberOrTag::Latest));
+        let (cfg, _, at) = self.evm_env_at(target_block).await?;
+        let parent =
+            self.provider().sealed_header_by_id(at)?.ok_or(EthApiError::UnknownBlockNumber)?;
+        let total_difficulty = self
+            .provider()
+            .header_td_by_number(parent.number)?
+            .ok_or(EthApiError::UnknownBlockNumber)?;
+        let blocks = fill_block_gaps(parent.number, block_state_calls)?;
+
+        let chain_spec = self.provider().chain_spec();
+        let gas_cap = self.inner.gas_cap;
+        let compute_state_root = validation || return_full_transactions;
+
+        self.spawn_with_state_at_block(at, move |state| {
+            let mut db = CacheDB::new(StateProviderDatabase::new(state));
+            let mut parent = parent;
+            let mut total_difficulty = total_difficulty;
+            let mut results = Vec::with_capacity(blocks.len());
+
+            for SimBlock { block_overrides, state_overrides, calls } in blocks {
+                // the number of the block was already resolved when filling the gaps
+                let BlockOverrides {
+                    number: _,
+                    difficulty,
+                    time,
+                    gas_limit,
+                    coinbase,
+                    random,
+                    base_fee,
+                    block_hash,
+                } = block_overrides.unwrap_or_default();
+
+                let number = parent.number + 1;
+                let timestamp =
+                    time.map(|time| time.to()).unwrap_or(parent.timestamp + SIMULATED_BLOCK_TIME);
+                if timestamp <= parent.timestamp {
+                    return Err(EthApiError::InvalidParams(format!(
+                        "timestamp of block {number} must be greater than {}",
+                        parent.timestamp
+                    )))
+                }
+
+                let is_shanghai = chain_spec.is_shanghai_active_at_timestamp(timestamp);
+                let is_cancun = chain_spec.is_cancun_active_at_timestamp(timestamp);
+                let base_fee_per_gas =
+                    chain_spec.fork(Hardfork::London).active_at_block(number).then(|| {
+                        match base_fee {
+                            Some(base_fee) => base_fee.saturating_to(),
+                            // the base fee is only enforced in validation mode
+                            None if validation => parent
+                                .next_block_base_fee(chain_spec.base_fee_params(timestamp))
+                                .unwrap_or_default(),
+                            None => 0,
+                        }
+                    });
+
+                let mut header = Header {
+                    parent_hash: parent.hash(),
+                    ommers_hash: EMPTY_OMMER_ROOT_HASH,
+                    beneficiary: coinbase.unwrap_or(parent.beneficiary),
+                    withdrawals_root: is_shanghai.then_some(EMPTY_WITHDRAWALS),
+                    difficulty: difficulty.unwrap_or_default(),
+                    number,
+                    gas_limit: gas_limit
+                        .map(|gas_limit| gas_limit.to())
+                        .unwrap_or(parent.gas_limit),
+                    timestamp,
+                    mix_hash: random.unwrap_or_default(),
+                    nonce: BEACON_NONCE,
+                    base_fee_per_gas,
+                    excess_blob_gas: is_cancun
+                        .then(|| parent.next_block_excess_blob_gas().unwrap_or_default()),
+                    parent_beacon_block_root: is_cancun.then_some(B256::ZERO),
+                    ..Default::default()
+                };
+                total_difficulty += header.difficulty;
+
+                let mut cfg = cfg.clone();
+                let mut block_env = BlockEnv::default();
+                EvmConfig::fill_cfg_and_block_env(
+                    &mut cfg,
+                    &mut block_env,
+                    &chain_spec,
+                    &header,
+                    total_difficulty,
+                );
+
+                if let Some(block_hashes) = block_hash {
+                    // override block hashes
+                    db.block_hashes
+                        .extend(block_hashes.into_iter().map(|(num, hash)| (U256::from(num), hash)))
+                }
+                if let Some(state_overrides) = state_overrides {
+                    apply_state_overrides(state_overrides, &mut db)?;
+                }
+
+                let mut transactions = Vec::with_capacity(calls.len());
+                let mut senders = Vec::with_capacity(calls.len());
+                let mut receipts = Vec::with_capacity(calls.len());
+                let mut call_results = Vec::with_capacity(calls.len());
+                let mut cumulative_gas_used = 0;
+                let mut blob_gas_used = 0;
+
+                for mut call in calls {
+                    let remaining_gas = header.gas_limit - cumulative_gas_used;
+                    let gas = *call.gas.get_or_insert(U256::from(remaining_gas.min(gas_cap)));
+                    if gas > U256::from(remaining_gas) {
+                        return Err(EthApiError::InvalidParams(format!(
+                            "gas limit of call {} of block {number} exceeds the remaining block \
+                             gas {remaining_gas}",
+                            call_results.len()
+                        )))
+                    }
+
+                    // the nonce is required to build the simulated transaction
+                    if call.nonce.is_none() {
+                        let from = call.from.unwrap_or_default();
+                        let nonce =
+                            db.basic(from)?.map(|account| account.nonce).unwrap_or_default();
+                        call.nonce = Some(U64::from(nonce));
+                    }
+
+                    let env = if validation {
+                        build_call_evm_env(cfg.clone(), block_env.clone(), call)?
+                    } else {
+                        prepare_call_env(
+                            cfg.clone(),
+                            block_env.clone(),
+                            call,
+                            gas_cap,
+                            &mut db,
+                            EvmOverrides::default(),
+                        )?
+                    };
+                    let sender = env.tx.caller;
+                    let transaction = simulated_transaction(&env.tx, cfg.chain_id);
+
+                    let (res, logs) = if trace_transfers {
+                        let mut inspector = TracingInspector::new(
+                            TracingInspectorConfig::default_parity().set_record_logs(true),
+                        );
+                        let (res, _) = inspect(&mut db, env, &mut inspector)?;
+                        let logs = with_transfer_logs(
+                            inspector.get_traces().nodes(),
+                            res.result.logs().into_iter().map(Into::into).collect(),
+                        );
+                        (res, logs)
+                    } else {
+                        let (res, _) = transact(&mut db, env)?;
+                        let logs = res.result.logs().into_iter().map(Into::into).collect();
+                        (res, logs)
+                    };
+                    db.commit(res.state);
+
+                    let gas_used = res.result.gas_used();
+                    cumulative_gas_used += gas_used;
+                    blob_gas_used += transaction.blob_gas_used().unwrap_or_default();
+
+                    receipts.push(Some(Receipt {
+                        tx_type: transaction.tx_type(),
+                        success: res.result.is_success(),
+                        cumulative_gas_used,
+                        logs: logs.clone(),
+                        #[cfg(feature = "optimism")]
+                        deposit_nonce: None,
+                        #[cfg(feature = "optimism")]
+                        deposit_receipt_version: None,
+                    }));
+
+                    let status = U64::from(res.result.is_success() as u8);
+                    let (return_data, error) = match res.result {
+                        ExecutionResult::Success { output, .. } => (output.into_data(), None),
+                        ExecutionResult::Revert { output, .. } => {
+                            let message = RpcInvalidTransactionError::Revert(RevertError::new(
+                                output.clone(),
+                            ))
+                            .to_string();
+                            (
+                                output,
+                                Some(SimulateError { code: REVERTED_CALL_ERROR_CODE, message }),
+                            )
+                        }
+                        ExecutionResult::Halt { reason, gas_used } => {
+                            let message =
+                                RpcInvalidTransactionError::halt(reason, gas_used).to_string();
+                            (
+                                Bytes::new(),
+                                Some(SimulateError { code: HALTED_CALL_ERROR_CODE, message }),
+                            )
+                        }
+                    };
+                    call_results.push((return_data, logs, U64::from(gas_used), status, error));
+
+                    transactions.push(transaction);
+                    senders.push(sender);
+                }
+
+                let bundle_state = if compute_state_root {
+                    cache_db_bundle_state(&db)
+                } else {
+                    BundleState::default()
+                };
+                let bundle = BundleStateWithReceipts::new(
+                    bundle_state,
+                    Receipts::from_vec(vec![receipts]),
+                    number,
+                );
+                if compute_state_root {
+                    header.state_root = db
+                        .db
+                        .state()
+                        .state_root(&bundle)
+                        .map_err(|err| state_root_error(number, err))?;
+                }
+                header.receipts_root = bundle
+                    .receipts_root_slow(
+                        number,
+                        #[cfg(feature = "optimism")]
+                        chain_spec.as_ref(),
+                        #[cfg(feature = "optimism")]
+                        timestamp,
+                    )
+                    .expect("Block is present");
+                header.logs_bloom = bundle.block_logs_bloom(number).expect("Block is present");
+                header.transactions_root = proofs::calculate_transaction_root(&transactions);
+                header.gas_used = cumulative_gas_used;
+                header.blob_gas_used = is_cancun.then_some(blob_gas_used);
+
+                let header = header.seal_slow();
+                let block_hash = header.hash();
+                // make the hash of the block available to the following blocks
+                db.block_hashes.insert(U256::from(number), block_hash);
+
+                let mut log_index = 0;
+                let calls = call_results
+                    .into_iter()
+                    .zip(&transactions)
+                    .enumerate()
+                    .map(|(index, ((return_data, logs, gas_used, status, error), transaction))| {
+                        let logs = logs
+                            .into_iter()
+                            .map(|log| {
+                                let log = reth_rpc_types::Log {
+                                    address: log.address,
+                                    topics: log.topics,
+                                    data: log.data,
+                                    block_hash: Some(block_hash),
+                                    block_number: Some(U256::from(number)),
+                                    transaction_hash: Some(transaction.hash()),
+                                    transaction_index: Some(U256::from(index)),
+                                    log_index: Some(U256::from(log_index)),
+                                    removed: false,
+                                };
+                                log_index += 1;
+                                log
+                            })
+                            .collect();
+                        SimCallResult { return_data, logs, gas_used, status, error }
+                    })
+                    .collect();
+
+                let block = BlockWithSenders {
+                    block: Block {
+                        header: header.clone().unseal(),
+                        body: transactions,
+                        ommers: Vec::new(),
+                        withdrawals: is_shanghai.then(Withdrawals::default),
+                    },
+                    senders,
+                };
+                let inner = from_block(
+                    block,
+                    total_difficulty,
+                    return_full_transactions.into(),
+                    Some(block_hash),
+                )?;
+                results.push(SimulatedBlock { inner, calls });
+
+                parent = header;
+            }
+
+            Ok(results)
+        })
+        .await
+    }
+}
+
+/// Converts an error that occurred while computing the state root of the simulated block with the
+/// given number, naming pruning if the history that is needed for it is unavailable.
+fn state_root_error(number: u64, err: ProviderError) -> EthApiError {
+    match err {
+        ProviderError::StateAtBlockPruned(pruned) => EthApiError::InvalidParams(format!(
+            "state root of simulated block {number} is unavailable, the history of block \
+             {pruned} is pruned; disable validation and full transactions to skip it"
+        )),
+        err => err.into(),
+    }
+}
+
+/// Fills the gaps between the simulated blocks with empty blocks, so that the numbers of the blocks
+/// are consecutive and start at the block following `parent_number`.
+///
+/// Returns an error if the block numbers are not increasing or if there are more than
+/// [MAX_SIMULATE_BLOCKS] blocks.
+fn fill_block_gaps(parent_number: u64, blocks: Vec<SimBlock>) -> EthResult<Vec<SimBlock>> {
+    let too_many_blocks = || {
+        EthApiError::InvalidParams(format!(
+            "too many blocks, at most {MAX_SIMULATE_BLOCKS} allowed"
+        ))
+    };
+
+    let mut filled = Vec::with_capacity(blocks.len());
+    let mut next_number = parent_number + 1;
+    for block in blocks {
+        if let Some(number) = block.block_overrides.as_ref().and_then(|overrides| overrides.number)
+        {
+            let number = number.saturating_to::<u64>();
+            if number < next_number {
+                return Err(EthApiError::InvalidParams(format!(
+                    "block number {number} must be at least {next_number}"
+                )))
+            }
+
+            let gap = number - next_number;
+            if filled.len() as u64 + gap >= MAX_SIMULATE_BLOCKS {
+                return Err(too_many_blocks())
+            }
+            filled.extend((0..gap).map(|_| SimBlock::default()));
+            next_number = number;
+        }
+
+        if filled.len() as u64 == MAX_SIMULATE_BLOCKS {
+            return Err(too_many_blocks())
+        }
+        filled.push(block);
+        next_number += 1;
+    }
+
+    Ok(filled)
+}
+
+/// Builds the transaction of a simulated call from its [TxEnv].
+///
+/// The transaction type is derived from the fee fields of the call, the transaction is signed with
+/// an empty signature.
+fn simulated_transaction(tx: &TxEnv, chain_id: u64) -> TransactionSigned {
+    let to = match tx.transact_to {
+        TransactTo::Call(to) => TransactionKind::Call(to),
+        TransactTo::Create(_) => TransactionKind::Create,
+    };
+    let access_list = AccessList(
+        tx.access_list
+            .iter()
+            .map(|(address, storage_keys)| AccessListItem {
+                address: *address,
+                storage_keys: storage_keys.iter().map(|key| B256::from(*key)).collect(),
+            })
+            .collect(),
+    );
+    let nonce = tx.nonce.unwrap_or_default();
+    let gas_price = tx.gas_price.saturating_to();
+
+    let transaction = if !tx.blob_hashes.is_empty() {
+        Transaction::Eip4844(TxEip4844 {
+            chain_id,
+            nonce,
+            gas_limit: tx.gas_limit,
+            max_fee_per_gas: gas_price,
+            max_priority_fee_per_gas: tx.gas_priority_fee.unwrap_or_default().saturating_to(),
+            to,
+            value: tx.value.into(),
+            access_list,
+            blob_versioned_hashes: tx.blob_hashes.clone(),
+            max_fee_per_blob_gas: tx.max_fee_per_blob_gas.unwrap_or_default().saturating_to(),
+            input: tx.data.clone(),
+        })
+    } else if let Some(max_priority_fee_per_gas) = tx.gas_priority_fee {
+        Transaction::Eip1559(TxEip1559 {
+            chain_id,
+            nonce,
+            gas_limit: tx.gas_limit,
+            max_fee_per_gas: gas_price,
+            max_priority_fee_per_gas: max_priority_fee_per_gas.saturating_to(),
+            to,
+            value: tx.value.into(),
+            access_list,
+            input: tx.data.clone(),
+        })
+    } else if !access_list.0.is_empty() {
+        Transaction::Eip2930(TxEip2930 {
+            chain_id,
+            nonce,
+            gas_price,
+            gas_limit: tx.gas_limit,
+            to,
+            value: tx.value.into(),
+            access_list,
+            input: tx.data.clone(),
+        })
+    } else {
+        Transaction::Legacy(TxLegacy {
+            chain_id: Some(chain_id),
+            nonce,
+            gas_price,
+            gas_limit: tx.gas_limit,
+            to,
+            value: tx.value.into(),
+            input: tx.data.clone(),
+        })
+    };
+
+    TransactionSigned::from_transaction_and_signature(transaction, Signature::default())
+}
+
+/// Inserts a `Transfer` log of the [TRANSFER_LOG_EMITTER] pseudo token for every ether transfer of
+/// the recorded call frames into the logs emitted by the call, in execution order.
+///
+/// Frames that reverted, or whose parent reverted, neither transfer ether nor emit logs.
+fn with_transfer_logs(nodes: &[CallTraceNode], logs: Vec<Log>) -> Vec<Log> {
+    fn visit(
+        nodes: &[CallTraceNode],
+        idx: usize,
+        success: bool,
+        logs: &mut impl Iterator<Item = Log>,
+        out: &mut Vec<Log>,
+    ) {
+        let node = &nodes[idx];
+        let success = success && node.trace.success;
+        if success &&
+            node.trace.value > U256::ZERO &&
+            matches!(node.trace.kind, CallKind::Call | CallKind::Create | CallKind::Create2)
+        {
+            out.push(Log {
+                address: TRANSFER_LOG_EMITTER,
+                topics: vec![
+                    TRANSFER_EVENT_TOPIC,
+                    node.trace.caller.into_word(),
+                    node.trace.address.into_word(),
+                ],
+                data: Bytes::from(node.trace.value.to_be_bytes::<32>()),
+            });
+        }
+
+        for order in &node.ordering {
+            match order {
+                LogCallOrder::Log(_) => {
+                    if success {
+                        out.extend(logs.next());
+                    }
+                }
+                LogCallOrder::Call(child) => {
+                    visit(nodes, node.children[*child], success, logs, out)
+                }
+            }
+        }
+    }
+
+    let mut out = Vec::with_capacity(logs.len());
+    if !nodes.is_empty() {
+        visit(nodes, 0, true, &mut logs.into_iter(), &mut out);
+    }
+    out
+}
+
+/// Converts the accounts cached by the [CacheDB] into a [BundleState], so that the state root of
+/// the simulated state can be calculated on top of the underlying database.
+///
+/// The storage of accounts that were cleared or don't exist is wiped.
+fn cache_db_bundle_state<DB>(db: &CacheDB<DB>) -> BundleState {
+    let mut bundle = BundleState::new(
+        db.accounts.iter().map(|(address, account)| {
+            let storage: HashMap<_, _> =
+                account.storage.iter().map(|(slot, value)| (*slot, (U256::ZERO, *value))).collect();
+            (*address, None, account.info(), storage)
+        }),
+        Vec::<Vec<(Address, Option<Option<AccountInfo>>, Vec<(U256, U256)>)>>::new(),
+        db.contracts.iter().map(|(hash, code)| (*hash, code.clone())),
+    );
+
+    for (address, account) in &db.accounts {
+        if matches!(account.account_state, AccountState::StorageCleared | AccountState::NotExisting)
+        {
+            if let Some(bundle_account) = bundle.state.get_mut(address) {
+                bundle_account.status = AccountStatus::DestroyedChanged;
+            }
+        }
+    }
+
+    bundle
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use reth_revm::tracing::types::CallTrace;
+
+    fn node(
+        idx: usize,
+        children: Vec<usize>,
+        ordering: Vec<LogCallOrder>,
+        trace: CallTrace,
+    ) -> CallTraceNode {
+        CallTraceNode {
+            idx,
+            parent: (idx > 0).then_some(0),
+            children,
+            ordering,
+            trace,
+            ..Default::default()
+        }
+    }
+
+    fn log(byte: u8) -> Log {
+        Log { address: Address::with_last_byte(byte), ..Default::default() }
+    }
+
+    #[test]
+    fn transfer_logs_in_execution_order() {
+        let (a, b, c) =
+            (Address::with_last_byte(1), Address::with_last_byte(2), Address::with_last_byte(3));
+        // `a` sends 1 wei to `b`, which emits a log, sends 2 wei to `c` in a call that reverts and
+        // emits another log.
+        let nodes = vec![
+            node(
+                0,
+                vec![1],
+                vec![LogCallOrder::Log(0), LogCallOrder::Call(0), LogCallOrder::Log(1)],
+                CallTrace {
+                    kind: CallKind::Call,
+                    caller: a,
+                    address: b,
+                    value: U256::from(1),
+                    success: true,
+                    ..Default::default()
+                },
+            ),
+            node(
+                1,
+                vec![],
+                vec![LogCallOrder::Log(0)],
+                CallTrace {
+                    depth: 1,
+                    kind: CallKind::Call,
+                    caller: b,
+                    address: c,
+                    value: U256::from(2),
+                    success: false,
+                    ..Default::default()
+                },
+            ),
+        ];
+
+        let logs = with_transfer_logs(&nodes, vec![log(10), log(11)]);
+        assert_eq!(logs.len(), 3);
+        assert_eq!(logs[0].address, TRANSFER_LOG_EMITTER);
+        assert_eq!(logs[0].topics, vec![TRANSFER_EVENT_TOPIC, a.into_word(), b.into_word()]);
+        assert_eq!(logs[0].data, Bytes::from(U256::from(1).to_be_bytes::<32>()));
+        assert_eq!(logs[1], log(10));
+        assert_eq!(logs[2], log(11));
+    }
+
+    #[test]
+    fn fill_simulated_block_gaps() {
+        let block = |number: Option<u64>| SimBlock {
+            block_overrides: number.map(|number| BlockOverrides {
+                number: Some(U256::from(number)),
+                ..Default::default()
+            }),
+            ..Default::default()
+        };
+
+        let blocks = fill_block_gaps(10, vec![block(None), block(Some(14)), block(None)]).unwrap();
+        assert_eq!(blocks.len(), 5);
+        assert_eq!(
+            blocks[3].block_overrides.as_ref().and_then(|overrides| overrides.number),
+            Some(U256::from(14))
+        );
+
+        assert!(fill_block_gaps(10, vec![block(Some(12)), block(Some(12))]).is_err());
+        assert!(fill_block_gaps(10, vec![block(Some(11 + MAX_SIMULATE_BLOCKS))]).is_err());
+        assert!(fill_block_gaps(10, vec![block(Some(10 + MAX_SIMULATE_BLOCKS))]).is_ok());
+    }
+
+    #[test]
+    fn state_root_error_names_pruning() {
+        let err = state_root_error(12, ProviderError::StateAtBlockPruned(10));
+        assert!(
+            matches!(&err, EthApiError::InvalidParams(msg) if msg.contains("block 10 is pruned"))
+        );
+
+        let err = state_root_error(12, ProviderError::BestBlockNotFound);
+        assert!(matches!(err, EthApiError::UnknownBlockNumber));
+    }
+}
@@ -24,10 +24,12 @@ use reth_primitives::{
     TransactionMeta, TransactionSigned, TransactionSignedEcRecovered, B256, U128, U256, U64,
 };
 use reth_provider::{
-    BlockReaderIdExt, ChainSpecProvider, EvmEnvProvider, StateProviderBox, StateProviderFactory,
+    BlockNumReader, BlockReaderIdExt, ChainSpecProvider, EvmEnvProvider, HeaderProvider,
+    StateProviderBox, StateProviderFactory,
 };
 use reth_revm::{
     database::StateProviderDatabase,
+    processor::EVMProcessor,
     tracing::{TracingInspector, TracingInspectorConfig},
 };
 use reth_rpc_types::{
@@ -58,6 +60,12 @@ use revm::L1BlockInfo;
 #[cfg(feature = "optimism")]
 use std::ops::Div;
 
+/// Number of blocks a private transaction is kept in the pool for if the request does not specify
+/// a max block number.
+///
+/// This matches the Flashbots default for `eth_sendPrivateTransaction`.
+pub const DEFAULT_PRIVATE_TRANSACTION_BLOCK_RANGE: u64 = 25;
+
 /// Helper alias type for the state's [CacheDB]
 pub(crate) type StateCacheDB = CacheDB<StateProviderDatabase<StateProviderBox>>;
 
@@ -87,6 +95,10 @@ pub trait EthTransactions: Send + Sync {
     /// Returns default gas limit to use for `eth_call` and tracing RPC methods.
     fn call_gas_limit(&self) -> u64;
 
+    /// Returns the maximum number of blocks behind the tip for which the hashed state is reverted,
+    /// as `eth_getProof` does.
+    fn eth_proof_window(&self) -> u64;
+
     /// Returns the state at the given [BlockId]
     fn state_at(&self, at: BlockId) -> EthResult<StateProviderBox>;
 
@@ -118,6 +130,12 @@ pub trait EthTransactions: Send + Sync {
         at: &Header,
     ) -> EthResult<(CfgEnvWithHandlerCfg, BlockEnv)>;
 
+    /// Returns the base fee of a child of the block with the given [BlockId] that has the given
+    /// timestamp.
+    ///
+    /// Returns `None` if the block has no base fee, i.e. it precedes London.
+    fn next_block_base_fee(&self, parent: BlockId, timestamp: u64) -> EthResult<Option<u64>>;
+
     /// Get all transactions in the block with the given hash.
     ///
     /// Returns `None` if block does not exist.
@@ -181,6 +199,25 @@ pub trait EthTransactions: Send + Sync {
     /// Returns the hash of the signed transaction.
     async fn send_transaction(&self, request: TransactionRequest) -> EthResult<B256>;
 
+    /// Decodes and recovers the transaction and submits it to the pool as a private transaction.
+    ///
+    /// Private transactions are never propagated to the network. The transaction is dropped
+    /// from the pool once the block with `max_block_number` has been mined, which defaults to
+    /// [DEFAULT_PRIVATE_TRANSACTION_BLOCK_RANGE] blocks from the current tip.
+    ///
+    /// Returns the hash of the transaction.
+    async fn send_private_raw_transaction(
+        &self,
+        tx: Bytes,
+        max_block_number: Option<u64>,
+    ) -> EthResult<B256>;
+
+    /// Removes the private transaction with the given hash from the pool.
+    ///
+    /// Returns `false` if there is no such private transaction, and
+    /// [Unauthorized](EthApiError::Unauthorized) if the transaction was not sent by `sender`.
+    fn cancel_private_transaction(&self, hash: B256, sender: Address) -> EthResult<bool>;
+
     /// Prepares the state and env for the given [CallRequest] at the given [BlockId] and executes
     /// the closure on a new task returning the result of the closure.
     ///
@@ -331,6 +368,14 @@ pub trait EthTransactions: Send + Sync {
             + Send
             + 'static,
         R: Send + 'static;
+
+    /// Executes the given block on top of the state of its parent block and returns the state
+    /// root after each transaction.
+    ///
+    /// The roots are computed by reverting the hashed state to the parent block, so only blocks
+    /// within the [proof window](EthTransactions::eth_proof_window) are served.
+    async fn intermediate_state_roots(&self, block: SealedBlockWithSenders)
+        -> EthResult<Vec<B256>>;
 }
 
 #[async_trait]
@@ -347,6 +392,10 @@ where
         self.inner.gas_cap
     }
 
+    fn eth_proof_window(&self) -> u64 {
+        self.inner.eth_proof_window
+    }
+
     fn state_at(&self, at: BlockId) -> EthResult<StateProviderBox> {
         self.state_at_block_id(at)
     }
@@ -402,6 +451,12 @@ where
         Ok((cfg, block_env))
     }
 
+    fn next_block_base_fee(&self, parent: BlockId, timestamp: u64) -> EthResult<Option<u64>> {
+        let parent =
+            self.provider().sealed_header_by_id(parent)?.ok_or(EthApiError::UnknownBlockNumber)?;
+        Ok(parent.next_block_base_fee(self.provider().chain_spec().base_fee_params(timestamp)))
+    }
+
     async fn transactions_by_block(
         &self,
         block: B256,
@@ -550,6 +605,43 @@ where
         Ok(hash)
     }
 
+    async fn send_private_raw_transaction(
+        &self,
+        tx: Bytes,
+        max_block_number: Option<u64>,
+    ) -> EthResult<B256> {
+        let tip = self.provider().best_block_number()?;
+        let max_block_number =
+            max_block_number.unwrap_or(tip + DEFAULT_PRIVATE_TRANSACTION_BLOCK_RANGE);
+        if max_block_number <= tip {
+            return Err(EthApiError::InvalidParams(format!(
+                "max block number {max_block_number} is not after the current block {tip}"
+            )))
+        }
+
+        let recovered = recover_raw_transaction(tx)?;
+        let pool_transaction = <Pool::Transaction>::from_recovered_pooled_transaction(recovered);
+
+        let hash =
+            self.pool().add_private_transaction(pool_transaction, Some(max_block_number)).await?;
+
+        Ok(hash)
+    }
+
+    fn cancel_private_transaction(&self, hash: B256, sender: Address) -> EthResult<bool> {
+        match self.pool().get(&hash) {
+            Some(tx) if tx.origin.is_private() => {
+                if tx.sender() != sender {
+                    return Err(EthApiError::Unauthorized(
+                        "cancellation is not signed by the transaction sender",
+                    ))
+                }
+                Ok(!self.pool().remove_transactions(vec![hash]).is_empty())
+            }
+            _ => Ok(false),
+        }
+    }
+
     async fn send_transaction(&self, mut request: TransactionRequest) -> EthResult<B256> {
         let from = match request.from {
             Some(from) => from,
@@ -901,6 +993,39 @@ where
         .await
         .map(Some)
     }
+
+    async fn intermediate_state_roots(
+        &self,
+        block: SealedBlockWithSenders,
+    ) -> EthResult<Vec<B256>> {
+        let parent_number = block.number.saturating_sub(1);
+        if self.provider().best_block_number()?.saturating_sub(parent_number) >
+            self.inner.eth_proof_window
+        {
+            return Err(EthApiError::ExceedsMaxProofWindow)
+        }
+
+        let parent_td = self
+            .provider()
+            .header_td(&block.parent_hash)?
+            .ok_or(EthApiError::UnknownBlockNumber)?;
+        let total_difficulty = parent_td + block.difficulty;
+        let chain_spec = self.provider().chain_spec();
+        let evm_config = self.inner.evm_config.clone();
+
+        // the block is executed on top of the state of its parent block, and computing a state root
+        // after every transaction is as expensive as tracing
+        self.spawn_tracing_task_with(move |this| {
+            let state = this.state_at(block.parent_hash.into())?;
+            let mut executor = EVMProcessor::new_with_db(
+                chain_spec,
+                StateProviderDatabase::new(&state),
+                evm_config,
+            );
+            Ok(executor.intermediate_state_roots(&block.unseal(), total_difficulty, &state)?)
+        })
+        .await
+    }
 }
 
 // === impl EthApi ===
@@ -1305,7 +1430,7 @@ mod tests {
     use crate::{
         eth::{
             cache::EthStateCache, gas_oracle::GasPriceOracle, FeeHistoryCache,
-            FeeHistoryCacheConfig,
+            FeeHistoryCacheConfig, RPC_DEFAULT_ETH_PROOF_WINDOW,
         },
         BlockingTaskPool, EthApi,
     };
@@ -1333,6 +1458,7 @@ mod tests {
             cache.clone(),
             GasPriceOracle::new(noop_provider, Default::default(), cache.clone()),
             ETHEREUM_BLOCK_GAS_LIMIT,
+            RPC_DEFAULT_ETH_PROOF_WINDOW,
             BlockingTaskPool::build().expect("failed to build tracing pool"),
             fee_history_cache,
             evm_config,
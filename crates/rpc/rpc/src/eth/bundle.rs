@@ -11,19 +11,45 @@ use crate::{
 };
 use jsonrpsee::core::RpcResult;
 use reth_primitives::{
-    keccak256,
+    eip191_hash_message, keccak256,
     revm_primitives::db::{DatabaseCommit, DatabaseRef},
-    U256,
+    Address, BlockId, BlockNumberOrTag, Bytes, Signature, TransactionSignedEcRecovered, B256, U256,
+    U64,
 };
 use reth_revm::database::StateProviderDatabase;
-use reth_rpc_api::EthCallBundleApiServer;
-use reth_rpc_types::{EthCallBundle, EthCallBundleResponse, EthCallBundleTransactionResult};
+use reth_rpc_api::{EthBundleApiServer, EthCallBundleApiServer, MevApiServer};
+use reth_rpc_types::{
+    BundleItem, CancelBundleRequest, CancelPrivateTransactionRequest, EthBundleHash, EthCallBundle,
+    EthCallBundleResponse, EthCallBundleTransactionResult, EthSendBundle, Log,
+    PrivateTransactionRequest, Refund, RefundConfig, SendBundleRequest, SendBundleResponse,
+    SimBundleLogs, SimBundleOverrides, SimBundleResponse,
+};
+use reth_transaction_pool::{
+    BundlePool, BundlePoolError, BundleRefund, BundleRefunds, PooledBundle, SBUNDLE_PAYOUT_MAX_COST,
+};
 use revm::{
     db::CacheDB,
-    primitives::{ResultAndState, TxEnv},
+    primitives::{EVMError, ResultAndState, TxEnv},
 };
 use revm_primitives::EnvWithHandlerCfg;
-use std::sync::Arc;
+use std::{
+    collections::HashSet,
+    ops::{Range, RangeInclusive},
+    sync::Arc,
+    time::{Duration, Instant},
+};
+
+/// The maximum depth of nested bundles in a `mev_sendBundle` or `mev_simBundle` request.
+pub const MAX_NESTED_BUNDLE_DEPTH: usize = 5;
+
+/// The maximum number of items in the body of a single, possibly nested, MEV-Share bundle.
+pub const MAX_BUNDLE_BODY_SIZE: usize = 50;
+
+/// The default timeout of a `mev_simBundle` call.
+pub const DEFAULT_SIM_BUNDLE_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// The maximum timeout of a `mev_simBundle` call.
+pub const MAX_SIM_BUNDLE_TIMEOUT: Duration = Duration::from_secs(30);
 
 /// `Eth` bundle implementation.
 pub struct EthBundle<Eth> {
@@ -33,8 +59,12 @@ pub struct EthBundle<Eth> {
 
 impl<Eth> EthBundle<Eth> {
     /// Create a new `EthBundle` instance.
-    pub fn new(eth_api: Eth, blocking_task_guard: BlockingTaskGuard) -> Self {
-        Self { inner: Arc::new(EthBundleInner { eth_api, blocking_task_guard }) }
+    pub fn new(
+        eth_api: Eth,
+        bundle_pool: BundlePool,
+        blocking_task_guard: BlockingTaskGuard,
+    ) -> Self {
+        Self { inner: Arc::new(EthBundleInner { eth_api, bundle_pool, blocking_task_guard }) }
     }
 }
 
@@ -176,6 +206,328 @@ where
             })
             .await
     }
+
+    /// Adds a bundle to the local bundle pool, from where it is picked up by the payload builder
+    /// when building the target block of the bundle.
+    ///
+    /// A bundle with a replacement UUID replaces the bundle that was previously submitted with the
+    /// same UUID.
+    pub async fn send_bundle(&self, bundle: EthSendBundle) -> EthResult<EthBundleHash> {
+        let EthSendBundle {
+            txs,
+            block_number,
+            min_timestamp,
+            max_timestamp,
+            reverting_tx_hashes,
+            replacement_uuid,
+        } = bundle;
+        if txs.is_empty() {
+            return Err(EthBundleError::EmptyBundleTransactions.into())
+        }
+        let block_number = block_number.to::<u64>();
+        if block_number == 0 {
+            return Err(EthBundleError::BundleMissingBlockNumber.into())
+        }
+
+        let transactions = txs
+            .into_iter()
+            .map(|tx| recover_raw_transaction(tx).map(|tx| tx.into_ecrecovered_transaction()))
+            .collect::<Result<Vec<_>, _>>()?;
+        let mut hash_bytes = Vec::with_capacity(32 * transactions.len());
+        for tx in &transactions {
+            hash_bytes.extend_from_slice(tx.hash().as_slice());
+        }
+
+        let bundle_hash = self
+            .inner
+            .bundle_pool
+            .add_bundle(PooledBundle {
+                hash: keccak256(&hash_bytes),
+                block_number,
+                max_block_number: block_number,
+                min_timestamp,
+                max_timestamp,
+                transactions,
+                reverting_tx_hashes: reverting_tx_hashes.into_iter().collect(),
+                replacement_uuid,
+                refunds: Vec::new(),
+                refund_recipients: Vec::new(),
+            })
+            .map_err(EthBundleError::BundlePool)?;
+        Ok(EthBundleHash { bundle_hash })
+    }
+
+    /// Removes a bundle from the local bundle pool.
+    ///
+    /// The bundle is identified by its replacement UUID or, if no bundle was submitted with that
+    /// UUID, by its hash. Cancelling an unknown bundle is not an error.
+    ///
+    /// The request must be signed by the signer of the bundle, see [PooledBundle::signer],
+    /// requests of other signers are rejected.
+    pub async fn cancel_bundle(&self, request: CancelBundleRequest) -> EthResult<()> {
+        let CancelBundleRequest { bundle_hash, signature } = request;
+        let signer = recover_cancellation_signer(bundle_hash.as_bytes(), &signature)
+            .ok_or(EthBundleError::InvalidCancellationSignature)?;
+        let bundle_pool = &self.inner.bundle_pool;
+        if bundle_pool
+            .remove_by_replacement_uuid(&bundle_hash, signer)
+            .map_err(EthBundleError::BundlePool)?
+            .is_none()
+        {
+            if let Ok(hash) = bundle_hash.parse::<B256>() {
+                if bundle_pool.get(&hash).is_some_and(|bundle| bundle.signer() != signer) {
+                    return Err(EthApiError::Unauthorized(
+                        "cancellation is not signed by the bundle signer",
+                    ))
+                }
+                bundle_pool.remove(&hash);
+            }
+        }
+        Ok(())
+    }
+
+    /// Removes a private transaction from the pool.
+    ///
+    /// The request must be signed by the sender of the transaction, requests of other signers are
+    /// rejected. Returns `false` if there is no such private transaction.
+    pub fn cancel_private_transaction(
+        &self,
+        request: CancelPrivateTransactionRequest,
+    ) -> EthResult<bool> {
+        let CancelPrivateTransactionRequest { tx_hash, signature } = request;
+        let signer = recover_cancellation_signer(tx_hash, &signature)
+            .ok_or(EthBundleError::InvalidCancellationSignature)?;
+        self.inner.eth_api.cancel_private_transaction(tx_hash, signer)
+    }
+
+    /// Adds a MEV-Share bundle to the local bundle pool, from where it is picked up by the payload
+    /// builder.
+    ///
+    /// Bundles that are referenced by hash must already be in the pool, their transactions are
+    /// placed in front of the transactions of the new bundle. Referenced bundles stay in the pool
+    /// until they are included or expire, the payload builder skips bundles that conflict with an
+    /// included one.
+    ///
+    /// Refunds are paid out by the payload builder the same way they are simulated by
+    /// [`mev_simBundle`](Self::sim_mev_bundle).
+    pub async fn send_mev_bundle(
+        &self,
+        request: SendBundleRequest,
+    ) -> EthResult<SendBundleResponse> {
+        let bundle = self.parse_mev_bundle(request, 0)?;
+        let inclusion = bundle.inclusion();
+        if inclusion.is_empty() {
+            return Err(EthBundleError::InvalidInclusion.into())
+        }
+
+        let hash = bundle.hash;
+        let refund_recipients = bundle.refund_recipients();
+        let mut transactions = Vec::new();
+        let mut reverting_tx_hashes = HashSet::new();
+        let mut refunds = Vec::new();
+        bundle.flatten_into(&mut transactions, &mut reverting_tx_hashes, &mut refunds);
+
+        let bundle_hash = self
+            .inner
+            .bundle_pool
+            .add_bundle(PooledBundle {
+                hash,
+                block_number: *inclusion.start(),
+                max_block_number: *inclusion.end(),
+                min_timestamp: None,
+                max_timestamp: None,
+                transactions,
+                reverting_tx_hashes,
+                replacement_uuid: None,
+                refunds,
+                refund_recipients,
+            })
+            .map_err(EthBundleError::BundlePool)?;
+
+        Ok(SendBundleResponse { bundle_hash })
+    }
+
+    /// Simulates a MEV-Share bundle, including its nested bundles and refunds, on top of the
+    /// parent block of the overrides.
+    ///
+    /// If the bundle can not be included in the simulated block, e.g. because a transaction that
+    /// is not allowed to revert reverted or because the refunds exceed the refundable value, an
+    /// unsuccessful [SimBundleResponse] is returned.
+    pub async fn sim_mev_bundle(
+        &self,
+        request: SendBundleRequest,
+        overrides: SimBundleOverrides,
+    ) -> EthResult<SimBundleResponse> {
+        let bundle = self.parse_mev_bundle(request, 0)?;
+        let SimBundleOverrides {
+            parent_block,
+            block_number,
+            coinbase,
+            timestamp,
+            gas_limit,
+            base_fee,
+            timeout,
+        } = overrides;
+        let timeout = timeout
+            .map(|timeout| Duration::from_secs(timeout.to()))
+            .unwrap_or(DEFAULT_SIM_BUNDLE_TIMEOUT)
+            .min(MAX_SIM_BUNDLE_TIMEOUT);
+
+        let parent_block = parent_block.unwrap_or(BlockId::Number(BlockNumberOrTag::Latest));
+        let (cfg, mut block_env, at) = self.inner.eth_api.evm_env_at(parent_block).await?;
+        let state_block = block_env.number.to::<u64>();
+
+        // derive the header of the simulated block from its parent
+        block_env.number =
+            U256::from(block_number.map(|number| number.to()).unwrap_or(state_block + 1));
+        if let Some(timestamp) = timestamp {
+            block_env.timestamp = U256::from(timestamp.to::<u64>());
+        } else {
+            block_env.timestamp += U256::from(12);
+        }
+        if let Some(coinbase) = coinbase {
+            block_env.coinbase = coinbase;
+        }
+        if let Some(gas_limit) = gas_limit {
+            block_env.gas_limit = U256::from(gas_limit.to::<u64>());
+        }
+        match base_fee {
+            Some(base_fee) => block_env.basefee = U256::from(base_fee.to::<u64>()),
+            None => {
+                // the env is the one of the parent block, but the simulated block is its child
+                if let Some(base_fee) =
+                    self.inner.eth_api.next_block_base_fee(at, block_env.timestamp.to())?
+                {
+                    block_env.basefee = U256::from(base_fee);
+                }
+            }
+        }
+
+        let _permit = self.inner.blocking_task_guard.clone().acquire_owned().await;
+        self.inner
+            .eth_api
+            .spawn_with_state_at_block(at, move |state| {
+                let mut ctx = SimBundleContext {
+                    block_number: block_env.number.to(),
+                    coinbase: block_env.coinbase,
+                    basefee: block_env.basefee,
+                    block_gas_limit: block_env.gas_limit.saturating_to(),
+                    deadline: Instant::now() + timeout,
+                    cumulative_gas_used: 0,
+                    tx_index: 0,
+                    log_index: 0,
+                };
+                let env = EnvWithHandlerCfg::new_with_cfg_env(cfg, block_env, TxEnv::default());
+                let db = CacheDB::new(StateProviderDatabase::new(state));
+                let mut evm =
+                    revm::Evm::builder().with_db(db).with_env_with_handler_cfg(env).build();
+
+                let response = match execute_mev_bundle(&mut evm, &bundle, &mut ctx) {
+                    Ok(SimBundleOutcome { profit, refundable_value, gas_used, logs }) => {
+                        let mev_gas_price =
+                            profit.checked_div(U256::from(gas_used)).unwrap_or_default();
+                        SimBundleResponse {
+                            success: true,
+                            error: None,
+                            state_block: U64::from(state_block),
+                            mev_gas_price: U64::from(mev_gas_price.saturating_to::<u64>()),
+                            profit: U64::from(profit.saturating_to::<u64>()),
+                            refundable_value: U64::from(refundable_value.saturating_to::<u64>()),
+                            gas_used: U64::from(gas_used),
+                            logs: Some(logs),
+                        }
+                    }
+                    Err(SimBundleError::Bundle(err)) => SimBundleResponse {
+                        success: false,
+                        error: Some(err.to_string()),
+                        state_block: U64::from(state_block),
+                        mev_gas_price: U64::ZERO,
+                        profit: U64::ZERO,
+                        refundable_value: U64::ZERO,
+                        gas_used: U64::ZERO,
+                        logs: None,
+                    },
+                    Err(SimBundleError::Eth(err)) => return Err(err),
+                };
+
+                Ok(response)
+            })
+            .await
+    }
+
+    /// Validates the given MEV-Share bundle request and resolves all of its items.
+    ///
+    /// Transactions are recovered and bundles that are referenced by hash are looked up in the
+    /// bundle pool.
+    fn parse_mev_bundle(&self, request: SendBundleRequest, depth: usize) -> EthResult<MevBundle> {
+        if depth > MAX_NESTED_BUNDLE_DEPTH {
+            return Err(EthBundleError::MaxNestedBundleDepth.into())
+        }
+
+        let SendBundleRequest { inclusion, bundle_body, validity, .. } = request;
+        if bundle_body.is_empty() {
+            return Err(EthBundleError::EmptyBundleTransactions.into())
+        }
+        if bundle_body.len() > MAX_BUNDLE_BODY_SIZE {
+            return Err(EthBundleError::BundleTooLarge.into())
+        }
+
+        let block_number = inclusion.block_number();
+        if block_number == 0 {
+            return Err(EthBundleError::BundleMissingBlockNumber.into())
+        }
+        let max_block_number = inclusion.max_block_number().unwrap_or(block_number);
+        if max_block_number < block_number {
+            return Err(EthBundleError::InvalidInclusion.into())
+        }
+
+        let validity = validity.unwrap_or_default();
+        let refunds = validity.refund.unwrap_or_default();
+        let refund_config = validity.refund_config.unwrap_or_default();
+        if let Some(refund) =
+            refunds.iter().find(|refund| refund.body_idx >= bundle_body.len() as u64)
+        {
+            return Err(EthBundleError::InvalidRefundIndex(refund.body_idx).into())
+        }
+        if exceeds_full_percent(refunds.iter().map(|refund| refund.percent)) ||
+            exceeds_full_percent(refund_config.iter().map(|config| config.percent))
+        {
+            return Err(EthBundleError::InvalidRefundPercent.into())
+        }
+
+        let mut body = Vec::with_capacity(bundle_body.len());
+        let mut hash_bytes = Vec::with_capacity(32 * bundle_body.len());
+        for item in bundle_body {
+            let item = match item {
+                BundleItem::Tx { tx, can_revert } => {
+                    let transaction = recover_raw_transaction(tx)?.into_ecrecovered_transaction();
+                    MevBundleItem::Tx { transaction, can_revert }
+                }
+                BundleItem::Hash { hash } => {
+                    let pooled = self
+                        .inner
+                        .bundle_pool
+                        .get(&hash)
+                        .ok_or(EthBundleError::UnmatchedBundle(hash))?;
+                    MevBundleItem::Bundle(MevBundle::from_pooled(&pooled))
+                }
+                BundleItem::Bundle { bundle } => {
+                    MevBundleItem::Bundle(self.parse_mev_bundle(*bundle, depth + 1)?)
+                }
+            };
+            hash_bytes.extend_from_slice(item.hash().as_slice());
+            body.push(item);
+        }
+
+        Ok(MevBundle {
+            hash: keccak256(&hash_bytes),
+            block_number,
+            max_block_number,
+            body,
+            refunds,
+            refund_config,
+        })
+    }
 }
 
 #[async_trait::async_trait]
@@ -188,13 +540,73 @@ where
     }
 }
 
+#[async_trait::async_trait]
+impl<Eth> EthBundleApiServer for EthBundle<Eth>
+where
+    Eth: EthTransactions + 'static,
+{
+    async fn send_bundle(&self, bundle: EthSendBundle) -> RpcResult<EthBundleHash> {
+        Ok(EthBundle::send_bundle(self, bundle).await?)
+    }
+
+    async fn call_bundle(&self, request: EthCallBundle) -> RpcResult<EthCallBundleResponse> {
+        Ok(EthBundle::call_bundle(self, request).await?)
+    }
+
+    async fn cancel_bundle(&self, request: CancelBundleRequest) -> RpcResult<()> {
+        Ok(EthBundle::cancel_bundle(self, request).await?)
+    }
+
+    async fn send_private_transaction(
+        &self,
+        request: PrivateTransactionRequest,
+    ) -> RpcResult<B256> {
+        let PrivateTransactionRequest { tx, max_block_number, .. } = request;
+        Ok(self
+            .inner
+            .eth_api
+            .send_private_raw_transaction(tx, max_block_number.map(|num| num.to()))
+            .await?)
+    }
+
+    async fn send_private_raw_transaction(&self, bytes: Bytes) -> RpcResult<B256> {
+        Ok(self.inner.eth_api.send_private_raw_transaction(bytes, None).await?)
+    }
+
+    async fn cancel_private_transaction(
+        &self,
+        request: CancelPrivateTransactionRequest,
+    ) -> RpcResult<bool> {
+        Ok(EthBundle::cancel_private_transaction(self, request)?)
+    }
+}
+
+#[async_trait::async_trait]
+impl<Eth> MevApiServer for EthBundle<Eth>
+where
+    Eth: EthTransactions + 'static,
+{
+    async fn send_bundle(&self, request: SendBundleRequest) -> RpcResult<SendBundleResponse> {
+        Ok(EthBundle::send_mev_bundle(self, request).await?)
+    }
+
+    async fn sim_bundle(
+        &self,
+        bundle: SendBundleRequest,
+        sim_overrides: SimBundleOverrides,
+    ) -> RpcResult<SimBundleResponse> {
+        Ok(EthBundle::sim_mev_bundle(self, bundle, sim_overrides).await?)
+    }
+}
+
 /// Container type for  `EthBundle` internals
 #[derive(Debug)]
 struct EthBundleInner<Eth> {
     /// Access to commonly used code of the `eth` namespace
     eth_api: Eth,
+    /// Bundles submitted via `mev_sendBundle`.
+    bundle_pool: BundlePool,
     // restrict the number of concurrent tracing calls.
-    #[allow(dead_code)]
     blocking_task_guard: BlockingTaskGuard,
 }
 
@@ -219,4 +631,875 @@ pub enum EthBundleError {
     /// Thrown if the bundle does not contain a block number, or block number is 0.
     #[error("bundle missing blockNumber")]
     BundleMissingBlockNumber,
+    /// Thrown if the max block of the bundle is lower than its block.
+    #[error("bundle maxBlock is lower than block")]
+    InvalidInclusion,
+    /// Thrown if bundles are nested deeper than [MAX_NESTED_BUNDLE_DEPTH].
+    #[error("bundle nesting exceeds max depth of {}", MAX_NESTED_BUNDLE_DEPTH)]
+    MaxNestedBundleDepth,
+    /// Thrown if the body of a bundle has more than [MAX_BUNDLE_BODY_SIZE] items.
+    #[error("bundle body exceeds max size of {}", MAX_BUNDLE_BODY_SIZE)]
+    BundleTooLarge,
+    /// Thrown if a refund references an item that is not part of the bundle body.
+    #[error("refund bodyIdx {0} out of range")]
+    InvalidRefundIndex(u64),
+    /// Thrown if the refund percentages of a bundle add up to more than 100.
+    #[error("refund percentages exceed 100")]
+    InvalidRefundPercent,
+    /// Thrown if a bundle is referenced by a hash that is not in the bundle pool.
+    #[error("unmatched bundle {0}")]
+    UnmatchedBundle(B256),
+    /// Thrown if the bundle can not be included in the simulated block.
+    #[error("bundle is not valid for block {0}")]
+    BlockOutOfRange(u64),
+    /// Thrown if the bundle exceeds the gas limit of the simulated block.
+    #[error("bundle exceeds block gas limit")]
+    GasLimitExceeded,
+    /// Thrown if a transaction of the bundle is invalid.
+    #[error("bundle transaction {0} is invalid: {1}")]
+    InvalidTransaction(B256, RpcInvalidTransactionError),
+    /// Thrown if a transaction of the bundle reverted that is not allowed to revert.
+    #[error("bundle transaction {0} reverted")]
+    TransactionReverted(B256),
+    /// Thrown if the refunds of the bundle exceed its refundable value.
+    #[error("bundle refunds exceed refundable value")]
+    NegativeProfit,
+    /// Thrown if the simulation of the bundle exceeded its timeout.
+    #[error("bundle simulation timed out")]
+    SimulationTimeout,
+    /// Thrown if the signature of a private transaction cancellation can't be recovered.
+    #[error("invalid cancellation signature")]
+    InvalidCancellationSignature,
+    /// Thrown if the bundle could not be added to the bundle pool.
+    #[error(transparent)]
+    BundlePool(#[from] BundlePoolError),
+}
+
+impl From<EthBundleError> for EthApiError {
+    fn from(err: EthBundleError) -> Self {
+        match err {
+            EthBundleError::BundlePool(BundlePoolError::UnauthorizedReplacement { .. }) => {
+                EthApiError::Unauthorized("replacement uuid belongs to another signer")
+            }
+            err => EthApiError::InvalidParams(err.to_string()),
+        }
+    }
+}
+
+/// A MEV-Share bundle with all transactions recovered and all referenced bundles resolved.
+#[derive(Debug)]
+struct MevBundle {
+    /// Hash of the bundle, the hash of the concatenated hashes of its body items.
+    hash: B256,
+    /// The first block the bundle is valid for.
+    block_number: u64,
+    /// The last block the bundle is valid for.
+    max_block_number: u64,
+    /// The items of the bundle, in execution order.
+    body: Vec<MevBundleItem>,
+    /// Refunds for the items of the body.
+    refunds: Vec<Refund>,
+    /// Recipients of refunds if this bundle is refunded by an enclosing bundle.
+    refund_config: Vec<RefundConfig>,
+}
+
+impl MevBundle {
+    /// Creates a bundle from a bundle of the pool.
+    ///
+    /// The pool only keeps the nesting of bundles with refunds, the transactions of other nested
+    /// bundles become items of the bundle they are part of.
+    fn from_pooled(bundle: &PooledBundle) -> Self {
+        let levels = bundle.refunds.iter().collect::<Vec<_>>();
+        let span = 0..bundle.transactions.len();
+        Self {
+            hash: bundle.hash,
+            ..Self::nested_from_pooled(bundle, span, &levels, &bundle.refund_recipients)
+        }
+    }
+
+    /// Creates the bundle that consists of the given transactions of a pooled bundle.
+    ///
+    /// The `levels` are the refund constraints of the bundle and of all bundles nested in it, in
+    /// the order of [PooledBundle::refunds].
+    fn nested_from_pooled(
+        pooled: &PooledBundle,
+        span: Range<usize>,
+        levels: &[&BundleRefunds],
+        refund_recipients: &[(Address, u64)],
+    ) -> Self {
+        // the refunds of a bundle come after the ones of the bundles nested in it
+        let (own, nested) = match levels.split_last() {
+            Some((level, nested)) if level.span() == span => (Some(*level), nested),
+            _ => (None, levels),
+        };
+
+        let mut body = Vec::new();
+        let mut refunds = Vec::new();
+        if let Some(level) = own {
+            for (idx, item) in level.items.iter().enumerate() {
+                let recipients = level
+                    .refunds
+                    .iter()
+                    .find(|refund| refund.item == idx)
+                    .map_or(&[][..], |refund| &refund.recipients[..]);
+                body.push(MevBundleItem::from_pooled(pooled, item.clone(), nested, recipients));
+            }
+            refunds = level
+                .refunds
+                .iter()
+                .map(|refund| Refund { body_idx: refund.item as u64, percent: refund.percent })
+                .collect();
+        } else {
+            let mut start = span.start;
+            while start < span.end {
+                // the outermost nested bundle that starts with the transaction, if any
+                let end = nested
+                    .iter()
+                    .map(|level| level.span())
+                    .filter(|nested| nested.start == start)
+                    .map(|nested| nested.end)
+                    .max()
+                    .unwrap_or(start + 1);
+                body.push(MevBundleItem::from_pooled(pooled, start..end, nested, &[]));
+                start = end;
+            }
+        }
+
+        let mut hash_bytes = Vec::with_capacity(32 * body.len());
+        for item in &body {
+            hash_bytes.extend_from_slice(item.hash().as_slice());
+        }
+        Self {
+            hash: keccak256(&hash_bytes),
+            block_number: pooled.block_number,
+            max_block_number: pooled.max_block_number,
+            body,
+            refunds,
+            refund_config: refund_recipients
+                .iter()
+                .map(|(address, percent)| RefundConfig { address: *address, percent: *percent })
+                .collect(),
+        }
+    }
+
+    /// Returns the recipients of a refund of the bundle, with their share in percent.
+    ///
+    /// A bundle is refunded according to its refund config, or to the recipients of its first
+    /// item if it has none.
+    fn refund_recipients(&self) -> Vec<(Address, u64)> {
+        if self.refund_config.is_empty() {
+            self.body[0].refund_recipients()
+        } else {
+            self.refund_config.iter().map(|config| (config.address, config.percent)).collect()
+        }
+    }
+
+    /// Returns the refunds of the items of the bundle.
+    fn item_refunds(&self) -> Vec<BundleRefund> {
+        self.refunds
+            .iter()
+            .map(|refund| BundleRefund {
+                item: refund.body_idx as usize,
+                percent: refund.percent,
+                recipients: self.body[refund.body_idx as usize].refund_recipients(),
+            })
+            .collect()
+    }
+
+    /// Returns the blocks both the bundle and all of its nested bundles can be included in.
+    fn inclusion(&self) -> RangeInclusive<u64> {
+        self.body.iter().fold(self.block_number..=self.max_block_number, |range, item| match item {
+            MevBundleItem::Tx { .. } => range,
+            MevBundleItem::Bundle(bundle) => {
+                let nested = bundle.inclusion();
+                *range.start().max(nested.start())..=*range.end().min(nested.end())
+            }
+        })
+    }
+
+    /// Returns `true` if the bundle can be included in the block with the given number.
+    fn is_valid_for_block(&self, block_number: u64) -> bool {
+        (self.block_number..=self.max_block_number).contains(&block_number)
+    }
+
+    /// Appends the transactions of the bundle and all nested bundles, in execution order, and
+    /// collects the hashes of the transactions that are allowed to revert and the refund
+    /// constraints of the bundles, see [PooledBundle::refunds].
+    fn flatten_into(
+        self,
+        transactions: &mut Vec<TransactionSignedEcRecovered>,
+        reverting_tx_hashes: &mut HashSet<B256>,
+        refunds: &mut Vec<BundleRefunds>,
+    ) {
+        let item_refunds = self.item_refunds();
+        let mut items = Vec::with_capacity(self.body.len());
+        for item in self.body {
+            let start = transactions.len();
+            match item {
+                MevBundleItem::Tx { transaction, can_revert } => {
+                    if can_revert {
+                        reverting_tx_hashes.insert(transaction.hash());
+                    }
+                    transactions.push(transaction);
+                }
+                MevBundleItem::Bundle(bundle) => {
+                    bundle.flatten_into(transactions, reverting_tx_hashes, refunds)
+                }
+            }
+            items.push(start..transactions.len());
+        }
+        if !item_refunds.is_empty() {
+            refunds.push(BundleRefunds { items, refunds: item_refunds });
+        }
+    }
+}
+
+/// An item of the body of a [MevBundle].
+#[derive(Debug)]
+enum MevBundleItem {
+    /// A signed transaction.
+    Tx {
+        /// The transaction with its recovered signer.
+        transaction: TransactionSignedEcRecovered,
+        /// Whether the transaction is allowed to revert.
+        can_revert: bool,
+    },
+    /// A nested bundle.
+    Bundle(MevBundle),
+}
+
+impl MevBundleItem {
+    /// Returns the hash of the transaction or bundle.
+    fn hash(&self) -> B256 {
+        match self {
+            MevBundleItem::Tx { transaction, .. } => transaction.hash(),
+            MevBundleItem::Bundle(bundle) => bundle.hash,
+        }
+    }
+
+    /// Creates the item that consists of the given transactions of a pooled bundle, see
+    /// [MevBundle::nested_from_pooled].
+    fn from_pooled(
+        pooled: &PooledBundle,
+        span: Range<usize>,
+        levels: &[&BundleRefunds],
+        refund_recipients: &[(Address, u64)],
+    ) -> Self {
+        let nested = levels
+            .iter()
+            .copied()
+            .filter(|level| {
+                let nested = level.span();
+                span.start <= nested.start && nested.end <= span.end
+            })
+            .collect::<Vec<_>>();
+        if nested.is_empty() && span.len() == 1 {
+            let transaction = &pooled.transactions[span.start];
+            // a transaction is refunded to its signer, other recipients need an enclosing bundle
+            if refund_recipients.is_empty() || refund_recipients == [(transaction.signer(), 100)] {
+                let can_revert = pooled.can_revert(&transaction.hash());
+                return MevBundleItem::Tx { transaction: transaction.clone(), can_revert }
+            }
+        }
+        MevBundleItem::Bundle(MevBundle::nested_from_pooled(
+            pooled,
+            span,
+            &nested,
+            refund_recipients,
+        ))
+    }
+
+    /// Returns the recipients of a refund of this item, with their share in percent.
+    ///
+    /// A transaction is refunded to its signer, see [MevBundle::refund_recipients] for bundles.
+    fn refund_recipients(&self) -> Vec<(Address, u64)> {
+        match self {
+            MevBundleItem::Tx { transaction, .. } => vec![(transaction.signer(), 100)],
+            MevBundleItem::Bundle(bundle) => bundle.refund_recipients(),
+        }
+    }
+}
+
+/// Block level state of a `mev_simBundle` call, shared by all nested bundles.
+#[derive(Debug)]
+struct SimBundleContext {
+    /// Number of the simulated block.
+    block_number: u64,
+    /// Beneficiary of the simulated block.
+    coinbase: Address,
+    /// Base fee of the simulated block.
+    basefee: U256,
+    /// Gas limit of the simulated block.
+    block_gas_limit: u64,
+    /// Deadline of the simulation.
+    deadline: Instant,
+    /// Gas used by all transactions and refund payouts so far.
+    cumulative_gas_used: u64,
+    /// Index of the next transaction in the simulated block.
+    tx_index: u64,
+    /// Index of the next log in the simulated block.
+    log_index: u64,
+}
+
+/// Result of a successful simulation of a [MevBundle].
+#[derive(Debug, Default)]
+struct SimBundleOutcome {
+    /// Balance increase of the coinbase, after refunds.
+    profit: U256,
+    /// Balance increase of the coinbase caused by the items that are not refunded.
+    refundable_value: U256,
+    /// Gas used by the transactions and refund payouts of the bundle.
+    gas_used: u64,
+    /// Logs of the items of the bundle.
+    logs: Vec<SimBundleLogs>,
+}
+
+/// Reasons a `mev_simBundle` call can fail.
+#[derive(Debug)]
+enum SimBundleError {
+    /// The bundle can not be included in the simulated block.
+    Bundle(EthBundleError),
+    /// Any other error, e.g. a database error.
+    Eth(EthApiError),
+}
+
+impl From<EthBundleError> for SimBundleError {
+    fn from(err: EthBundleError) -> Self {
+        SimBundleError::Bundle(err)
+    }
+}
+
+impl From<EthApiError> for SimBundleError {
+    fn from(err: EthApiError) -> Self {
+        SimBundleError::Eth(err)
+    }
+}
+
+/// Executes the given bundle, including all nested bundles, and pays out its refunds.
+fn execute_mev_bundle<DB>(
+    evm: &mut revm::Evm<'_, (), CacheDB<DB>>,
+    bundle: &MevBundle,
+    ctx: &mut SimBundleContext,
+) -> Result<SimBundleOutcome, SimBundleError>
+where
+    DB: DatabaseRef,
+    EthApiError: From<<DB as DatabaseRef>::Error>,
+{
+    if !bundle.is_valid_for_block(ctx.block_number) {
+        return Err(EthBundleError::BlockOutOfRange(ctx.block_number).into())
+    }
+
+    let mut outcome = SimBundleOutcome::default();
+    let coinbase_before = coinbase_balance(&evm.context.evm.db, ctx.coinbase)?;
+
+    for (idx, item) in bundle.body.iter().enumerate() {
+        let item_coinbase_before = coinbase_balance(&evm.context.evm.db, ctx.coinbase)?;
+
+        match item {
+            MevBundleItem::Tx { transaction, can_revert } => {
+                if Instant::now() > ctx.deadline {
+                    return Err(EthBundleError::SimulationTimeout.into())
+                }
+                if ctx.cumulative_gas_used + transaction.gas_limit() > ctx.block_gas_limit {
+                    return Err(EthBundleError::GasLimitExceeded.into())
+                }
+
+                let hash = transaction.hash();
+                transaction.try_fill_tx_env(evm.tx_mut())?;
+                let ResultAndState { result, state } = evm.transact().map_err(|err| match err {
+                    EVMError::Transaction(err) => {
+                        SimBundleError::Bundle(EthBundleError::InvalidTransaction(hash, err.into()))
+                    }
+                    err => SimBundleError::Eth(err.into()),
+                })?;
+                if !result.is_success() && !can_revert {
+                    return Err(EthBundleError::TransactionReverted(hash).into())
+                }
+
+                let gas_used = result.gas_used();
+                ctx.cumulative_gas_used += gas_used;
+                outcome.gas_used += gas_used;
+
+                let logs: Vec<reth_primitives::Log> =
+                    result.logs().into_iter().map(Into::into).collect();
+                let tx_logs = logs
+                    .into_iter()
+                    .map(|log| {
+                        let log = Log {
+                            address: log.address,
+                            topics: log.topics,
+                            data: log.data,
+                            block_hash: None,
+                            block_number: Some(U256::from(ctx.block_number)),
+                            transaction_hash: Some(hash),
+                            transaction_index: Some(U256::from(ctx.tx_index)),
+                            log_index: Some(U256::from(ctx.log_index)),
+                            removed: false,
+                        };
+                        ctx.log_index += 1;
+                        log
+                    })
+                    .collect();
+                ctx.tx_index += 1;
+                outcome.logs.push(SimBundleLogs { tx_logs: Some(tx_logs), bundle_logs: None });
+
+                evm.context.evm.db.commit(state);
+            }
+            MevBundleItem::Bundle(inner) => {
+                let inner = execute_mev_bundle(evm, inner, ctx)?;
+                outcome.gas_used += inner.gas_used;
+                outcome.logs.push(SimBundleLogs { tx_logs: None, bundle_logs: Some(inner.logs) });
+            }
+        }
+
+        // the value generated by refunded items is not refundable
+        if !bundle.refunds.iter().any(|refund| refund.body_idx == idx as u64) {
+            let item_coinbase_after = coinbase_balance(&evm.context.evm.db, ctx.coinbase)?;
+            outcome.refundable_value += item_coinbase_after.saturating_sub(item_coinbase_before);
+        }
+    }
+
+    // pay out the refunds, every payout is a transfer from the coinbase that has to be paid for by
+    // the builder
+    let payout_fee = ctx.basefee * U256::from(SBUNDLE_PAYOUT_MAX_COST);
+    for refund in bundle.item_refunds() {
+        let payouts = refund
+            .payouts(outcome.refundable_value, payout_fee)
+            .ok_or(EthBundleError::NegativeProfit)?;
+
+        for (recipient, value) in payouts {
+            pay_refund(&mut evm.context.evm.db, ctx.coinbase, recipient, value, payout_fee)?;
+            ctx.cumulative_gas_used += SBUNDLE_PAYOUT_MAX_COST;
+            outcome.gas_used += SBUNDLE_PAYOUT_MAX_COST;
+        }
+    }
+
+    let coinbase_after = coinbase_balance(&evm.context.evm.db, ctx.coinbase)?;
+    outcome.profit = coinbase_after.saturating_sub(coinbase_before);
+
+    Ok(outcome)
+}
+
+/// Returns the current balance of the coinbase.
+fn coinbase_balance<DB>(db: &CacheDB<DB>, coinbase: Address) -> EthResult<U256>
+where
+    DB: DatabaseRef,
+    EthApiError: From<<DB as DatabaseRef>::Error>,
+{
+    Ok(DatabaseRef::basic_ref(db, coinbase)?.map(|acc| acc.balance).unwrap_or_default())
+}
+
+/// Transfers the refund `value` from the coinbase to the `recipient` and charges the coinbase
+/// the `fee` of the payout transaction.
+fn pay_refund<DB>(
+    db: &mut CacheDB<DB>,
+    coinbase: Address,
+    recipient: Address,
+    value: U256,
+    fee: U256,
+) -> EthResult<()>
+where
+    DB: DatabaseRef,
+    EthApiError: From<<DB as DatabaseRef>::Error>,
+{
+    let mut coinbase_info = DatabaseRef::basic_ref(db, coinbase)?.unwrap_or_default();
+    coinbase_info.balance = coinbase_info.balance.saturating_sub(value + fee);
+    db.insert_account_info(coinbase, coinbase_info);
+
+    let mut recipient_info = DatabaseRef::basic_ref(db, recipient)?.unwrap_or_default();
+    recipient_info.balance += value;
+    db.insert_account_info(recipient, recipient_info);
+
+    Ok(())
+}
+
+/// Returns `true` if the given percentages add up to more than 100.
+fn exceeds_full_percent(percents: impl IntoIterator<Item = u64>) -> bool {
+    percents.into_iter().fold(0u64, |sum, percent| sum.saturating_add(percent)) > 100
+}
+
+/// Recovers the signer of an `eth_sign` signature over the given message, e.g. a transaction
+/// hash.
+///
+/// The recovery id may be encoded either as `0`/`1` or as `27`/`28`.
+fn recover_cancellation_signer(message: impl AsRef<[u8]>, signature: &[u8]) -> Option<Address> {
+    let signature: &[u8; 65] = signature.try_into().ok()?;
+    let odd_y_parity = match signature[64] {
+        0 | 27 => false,
+        1 | 28 => true,
+        _ => return None,
+    };
+    let signature = Signature {
+        r: U256::from_be_slice(&signature[..32]),
+        s: U256::from_be_slice(&signature[32..64]),
+        odd_y_parity,
+    };
+    signature.recover_signer(eip191_hash_message(message))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        eth::{
+            cache::EthStateCache, gas_oracle::GasPriceOracle, FeeHistoryCache,
+            FeeHistoryCacheConfig, RPC_DEFAULT_ETH_PROOF_WINDOW,
+        },
+        BlockingTaskPool, EthApi,
+    };
+    use reth_interfaces::test_utils::generators::{self, generate_keys, sign_tx_with_key_pair};
+    use reth_network_api::noop::NoopNetwork;
+    use reth_node_ethereum::EthEvmConfig;
+    use reth_primitives::{
+        constants::ETHEREUM_BLOCK_GAS_LIMIT, sign_message, Transaction, TransactionKind, TxLegacy,
+    };
+    use reth_provider::test_utils::{ExtendedAccount, MockEthProvider, NoopProvider};
+    use reth_transaction_pool::{
+        test_utils::{testing_pool, TestPool},
+        TransactionPool,
+    };
+    use revm::primitives::{BlockEnv, CfgEnv, CfgEnvWithHandlerCfg, SpecId};
+
+    const BASEFEE: u64 = 10;
+    const GAS_PRICE: u128 = 20;
+    const TRANSFER_GAS: u64 = 21_000;
+    /// Priority fee the coinbase earns for a transfer.
+    const TRANSFER_TIP: u64 = TRANSFER_GAS * (GAS_PRICE as u64 - BASEFEE);
+
+    fn transfer(nonce: u64, to: Address, value: u64) -> Transaction {
+        Transaction::Legacy(TxLegacy {
+            chain_id: Some(1),
+            nonce,
+            gas_price: GAS_PRICE,
+            gas_limit: TRANSFER_GAS,
+            to: TransactionKind::Call(to),
+            value: U256::from(value).into(),
+            input: Bytes::default(),
+        })
+    }
+
+    fn tx_item(transaction: TransactionSignedEcRecovered) -> MevBundleItem {
+        MevBundleItem::Tx { transaction, can_revert: false }
+    }
+
+    fn bundle(body: Vec<MevBundleItem>, refunds: Vec<Refund>) -> MevBundle {
+        MevBundle {
+            hash: B256::random(),
+            block_number: 1,
+            max_block_number: 1,
+            body,
+            refunds,
+            refund_config: Vec::new(),
+        }
+    }
+
+    type TestEvm = revm::Evm<'static, (), CacheDB<StateProviderDatabase<MockEthProvider>>>;
+
+    /// Simulates the bundle in block 1 on top of a state in which all given accounts are funded.
+    fn simulate(
+        bundle: &MevBundle,
+        funded: &[Address],
+    ) -> (Result<SimBundleOutcome, SimBundleError>, TestEvm) {
+        let provider = MockEthProvider::default();
+        for address in funded {
+            provider
+                .add_account(*address, ExtendedAccount::new(0, U256::from(1_000_000_000_000u64)));
+        }
+
+        let coinbase = Address::repeat_byte(0xcb);
+        let block_env = BlockEnv {
+            number: U256::from(1),
+            coinbase,
+            basefee: U256::from(BASEFEE),
+            gas_limit: U256::from(30_000_000),
+            ..Default::default()
+        };
+        let cfg = CfgEnvWithHandlerCfg::new(CfgEnv::default(), SpecId::SHANGHAI);
+        let env = EnvWithHandlerCfg::new_with_cfg_env(cfg, block_env, TxEnv::default());
+        let db = CacheDB::new(StateProviderDatabase::new(provider));
+        let mut evm = revm::Evm::builder().with_db(db).with_env_with_handler_cfg(env).build();
+
+        let mut ctx = SimBundleContext {
+            block_number: 1,
+            coinbase,
+            basefee: U256::from(BASEFEE),
+            block_gas_limit: 30_000_000,
+            deadline: Instant::now() + DEFAULT_SIM_BUNDLE_TIMEOUT,
+            cumulative_gas_used: 0,
+            tx_index: 0,
+            log_index: 0,
+        };
+        let outcome = execute_mev_bundle(&mut evm, bundle, &mut ctx);
+        (outcome, evm)
+    }
+
+    #[test]
+    fn sim_pays_out_refunds() {
+        let [user, searcher] = generate_keys(&mut generators::rng(), 2)[..] else { unreachable!() };
+        let coinbase = Address::repeat_byte(0xcb);
+        let coinbase_payment = 1_000_000_000;
+
+        let user_tx = sign_tx_with_key_pair(user, transfer(0, Address::random(), 1))
+            .into_ecrecovered()
+            .unwrap();
+        let user_address = user_tx.signer();
+        let searcher_tx = sign_tx_with_key_pair(searcher, transfer(0, coinbase, coinbase_payment))
+            .into_ecrecovered()
+            .unwrap();
+        let searcher_address = searcher_tx.signer();
+
+        // the user is refunded half of the value generated by the backrun
+        let bundle = bundle(
+            vec![tx_item(user_tx), tx_item(searcher_tx)],
+            vec![Refund { body_idx: 0, percent: 50 }],
+        );
+        let (outcome, evm) = simulate(&bundle, &[user_address, searcher_address]);
+        let outcome = outcome.unwrap();
+
+        let payout_fee = BASEFEE * SBUNDLE_PAYOUT_MAX_COST;
+        let refundable_value = coinbase_payment + TRANSFER_TIP;
+        let refund = refundable_value / 2 - payout_fee;
+        assert_eq!(outcome.refundable_value, U256::from(refundable_value));
+        assert_eq!(outcome.gas_used, 2 * TRANSFER_GAS + SBUNDLE_PAYOUT_MAX_COST);
+        assert_eq!(
+            outcome.profit,
+            U256::from(TRANSFER_TIP + refundable_value - refund - payout_fee)
+        );
+
+        let user_balance =
+            DatabaseRef::basic_ref(&evm.context.evm.db, user_address).unwrap().unwrap().balance;
+        let user_cost = 1 + TRANSFER_GAS * GAS_PRICE as u64;
+        assert_eq!(user_balance, U256::from(1_000_000_000_000u64 - user_cost + refund));
+    }
+
+    #[test]
+    fn sim_rejects_refunds_exceeding_refundable_value() {
+        let [user, searcher] = generate_keys(&mut generators::rng(), 2)[..] else { unreachable!() };
+        let user_tx = sign_tx_with_key_pair(user, transfer(0, Address::random(), 1))
+            .into_ecrecovered()
+            .unwrap();
+        let searcher_tx = sign_tx_with_key_pair(searcher, transfer(0, Address::random(), 1))
+            .into_ecrecovered()
+            .unwrap();
+        let funded = [user_tx.signer(), searcher_tx.signer()];
+
+        // the backrun only pays its priority fee, which doesn't cover the payout transaction
+        let bundle = bundle(
+            vec![tx_item(user_tx), tx_item(searcher_tx)],
+            vec![Refund { body_idx: 0, percent: 100 }],
+        );
+        let (outcome, _) = simulate(&bundle, &funded);
+        assert!(matches!(outcome, Err(SimBundleError::Bundle(EthBundleError::NegativeProfit))));
+    }
+
+    #[test]
+    fn sim_rejects_reverts_and_blocks_out_of_range() {
+        let [sender] = generate_keys(&mut generators::rng(), 1)[..] else { unreachable!() };
+        let tx = sign_tx_with_key_pair(sender, transfer(0, Address::random(), 1))
+            .into_ecrecovered()
+            .unwrap();
+        let sender_address = tx.signer();
+
+        let mut out_of_range = bundle(vec![tx_item(tx.clone())], Vec::new());
+        out_of_range.block_number = 2;
+        out_of_range.max_block_number = 3;
+        let (outcome, _) = simulate(&out_of_range, &[sender_address]);
+        assert!(matches!(outcome, Err(SimBundleError::Bundle(EthBundleError::BlockOutOfRange(1)))));
+
+        // the sender can't pay for the transaction
+        let (outcome, _) = simulate(&bundle(vec![tx_item(tx)], Vec::new()), &[]);
+        assert!(matches!(
+            outcome,
+            Err(SimBundleError::Bundle(EthBundleError::InvalidTransaction(..)))
+        ));
+    }
+
+    #[test]
+    fn nested_bundle_inclusion() {
+        let tx = generators::random_signed_tx(&mut generators::rng()).into_ecrecovered().unwrap();
+
+        let mut pooled = bundle(vec![tx_item(tx.clone())], Vec::new());
+        pooled.max_block_number = 5;
+
+        let mut nested = bundle(vec![tx_item(tx.clone())], Vec::new());
+        nested.block_number = 2;
+        nested.max_block_number = 9;
+        let mut outer = bundle(
+            vec![MevBundleItem::Bundle(pooled), MevBundleItem::Bundle(nested), tx_item(tx)],
+            Vec::new(),
+        );
+        outer.max_block_number = 10;
+
+        assert_eq!(outer.inclusion(), 2..=5);
+    }
+
+    #[test]
+    fn pooled_bundles_keep_refunds() {
+        let [user, searcher] = generate_keys(&mut generators::rng(), 2)[..] else { unreachable!() };
+        let coinbase = Address::repeat_byte(0xcb);
+        let refund_recipient = Address::random();
+
+        let user_tx = sign_tx_with_key_pair(user, transfer(0, Address::random(), 1))
+            .into_ecrecovered()
+            .unwrap();
+        let backrun_tx = sign_tx_with_key_pair(searcher, transfer(0, coinbase, 1_000_000_000))
+            .into_ecrecovered()
+            .unwrap();
+        let other_tx = sign_tx_with_key_pair(searcher, transfer(1, Address::random(), 1))
+            .into_ecrecovered()
+            .unwrap();
+        let funded = [user_tx.signer(), backrun_tx.signer()];
+
+        // a backrun that refunds half of its value to the user, nested in a bundle without refunds
+        let sent_bundle = || {
+            let mut user_bundle = bundle(vec![tx_item(user_tx.clone())], Vec::new());
+            user_bundle.refund_config =
+                vec![RefundConfig { address: refund_recipient, percent: 100 }];
+            let backrun = bundle(
+                vec![MevBundleItem::Bundle(user_bundle), tx_item(backrun_tx.clone())],
+                vec![Refund { body_idx: 0, percent: 50 }],
+            );
+            bundle(vec![MevBundleItem::Bundle(backrun), tx_item(other_tx.clone())], Vec::new())
+        };
+
+        let sent = sent_bundle();
+        let refund_recipients = sent.refund_recipients();
+        assert_eq!(refund_recipients, vec![(refund_recipient, 100)]);
+        let mut transactions = Vec::new();
+        let mut refunds = Vec::new();
+        sent.flatten_into(&mut transactions, &mut HashSet::new(), &mut refunds);
+        assert_eq!(refunds.len(), 1);
+        assert_eq!(refunds[0].items, vec![0..1, 1..2]);
+        let pooled = PooledBundle {
+            hash: B256::random(),
+            block_number: 1,
+            max_block_number: 1,
+            min_timestamp: None,
+            max_timestamp: None,
+            transactions,
+            reverting_tx_hashes: HashSet::new(),
+            replacement_uuid: None,
+            refunds,
+            refund_recipients,
+        };
+
+        // the bundle resolved from the pool is simulated like the bundle that was sent
+        let (expected, expected_evm) = simulate(&sent_bundle(), &funded);
+        let (outcome, evm) = simulate(&MevBundle::from_pooled(&pooled), &funded);
+        let (expected, outcome) = (expected.unwrap(), outcome.unwrap());
+        assert_eq!(outcome.refundable_value, expected.refundable_value);
+        assert_eq!(outcome.profit, expected.profit);
+        assert_eq!(outcome.gas_used, expected.gas_used);
+
+        let refunded = |evm: &TestEvm| {
+            DatabaseRef::basic_ref(&evm.context.evm.db, refund_recipient)
+                .unwrap()
+                .map(|account| account.balance)
+        };
+        assert!(refunded(&evm).is_some());
+        assert_eq!(refunded(&evm), refunded(&expected_evm));
+    }
+
+    #[test]
+    fn recovers_cancellation_signer() {
+        let tx_hash = B256::repeat_byte(0x01);
+        let message_hash = eip191_hash_message(tx_hash);
+        let signature = sign_message(B256::repeat_byte(0x46), message_hash).unwrap();
+        let signer = signature.recover_signer(message_hash).unwrap();
+
+        let mut raw = signature.to_bytes();
+        assert_eq!(recover_cancellation_signer(tx_hash, &raw), Some(signer));
+
+        raw[64] -= 27;
+        assert_eq!(recover_cancellation_signer(tx_hash, &raw), Some(signer));
+
+        raw[64] = 2;
+        assert_eq!(recover_cancellation_signer(tx_hash, &raw), None);
+        assert_eq!(recover_cancellation_signer(tx_hash, &raw[..64]), None);
+    }
+
+    fn eth_api(pool: TestPool) -> EthApi<NoopProvider, TestPool, NoopNetwork, EthEvmConfig> {
+        let provider = NoopProvider::default();
+        let evm_config = EthEvmConfig::default();
+        let cache = EthStateCache::spawn(provider, Default::default(), evm_config);
+        let fee_history_cache =
+            FeeHistoryCache::new(cache.clone(), FeeHistoryCacheConfig::default());
+        EthApi::new(
+            provider,
+            pool,
+            NoopNetwork::default(),
+            cache.clone(),
+            GasPriceOracle::new(provider, Default::default(), cache),
+            ETHEREUM_BLOCK_GAS_LIMIT,
+            RPC_DEFAULT_ETH_PROOF_WINDOW,
+            BlockingTaskPool::build().expect("failed to build tracing pool"),
+            fee_history_cache,
+            evm_config,
+        )
+    }
+
+    #[tokio::test]
+    async fn only_sender_can_cancel_private_transaction() {
+        let [sender, other] = generate_keys(&mut generators::rng(), 2)[..] else { unreachable!() };
+        let tx = sign_tx_with_key_pair(sender, transfer(0, Address::random(), 1));
+
+        let pool = testing_pool();
+        let eth_api = eth_api(pool.clone());
+        let tx_hash =
+            eth_api.send_private_raw_transaction(tx.envelope_encoded(), None).await.unwrap();
+        let bundle = EthBundle::new(eth_api, BundlePool::default(), BlockingTaskGuard::new(1));
+
+        let cancel = |secret: [u8; 32]| {
+            let signature = sign_message(B256::from(secret), eip191_hash_message(tx_hash)).unwrap();
+            CancelPrivateTransactionRequest {
+                tx_hash,
+                signature: Bytes::copy_from_slice(&signature.to_bytes()),
+            }
+        };
+
+        let res = bundle.cancel_private_transaction(cancel(other.secret_bytes()));
+        assert!(matches!(res, Err(EthApiError::Unauthorized(_))));
+        assert!(pool.get(&tx_hash).is_some());
+
+        assert!(bundle.cancel_private_transaction(cancel(sender.secret_bytes())).unwrap());
+        assert!(pool.get(&tx_hash).is_none());
+    }
+
+    #[tokio::test]
+    async fn only_signer_can_replace_and_cancel_bundle() {
+        let [sender, other] = generate_keys(&mut generators::rng(), 2)[..] else { unreachable!() };
+        let bundle_pool = BundlePool::default();
+        let bundle =
+            EthBundle::new(eth_api(testing_pool()), bundle_pool.clone(), BlockingTaskGuard::new(1));
+        let uuid = "e4b5ad11-5d6d-4c5f-a1ab-1ab5fc3fa6e1".to_string();
+        let send = |key, nonce| {
+            let tx = sign_tx_with_key_pair(key, transfer(nonce, Address::random(), 1));
+            bundle.send_bundle(EthSendBundle {
+                txs: vec![tx.envelope_encoded()],
+                block_number: U64::from(1),
+                replacement_uuid: Some(uuid.clone()),
+                ..Default::default()
+            })
+        };
+        let cancel = |secret: [u8; 32]| {
+            let signature =
+                sign_message(B256::from(secret), eip191_hash_message(uuid.as_bytes())).unwrap();
+            CancelBundleRequest {
+                bundle_hash: uuid.clone(),
+                signature: Bytes::copy_from_slice(&signature.to_bytes()),
+            }
+        };
+
+        let EthBundleHash { bundle_hash } = send(sender, 0).await.unwrap();
+        let res = send(other, 0).await;
+        assert!(matches!(res, Err(EthApiError::Unauthorized(_))));
+        assert!(bundle_pool.contains(&bundle_hash));
+
+        let res = bundle.cancel_bundle(cancel(other.secret_bytes())).await;
+        assert!(matches!(res, Err(EthApiError::Unauthorized(_))));
+        assert!(bundle_pool.contains(&bundle_hash));
+
+        bundle.cancel_bundle(cancel(sender.secret_bytes())).await.unwrap();
+        assert!(bundle_pool.is_empty());
+    }
 }
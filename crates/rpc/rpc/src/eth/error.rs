@@ -8,6 +8,7 @@ use jsonrpsee::{
 };
 use reth_interfaces::RethError;
 use reth_primitives::{revm_primitives::InvalidHeader, Address, Bytes, U256};
+#[cfg(feature = "js-tracer")]
 use reth_revm::tracing::js::JsInspectorError;
 use reth_rpc_types::{error::EthRpcErrorCode, BlockError, CallInputError};
 use reth_transaction_pool::error::{
@@ -20,6 +21,10 @@ use std::time::Duration;
 /// Result alias
 pub type EthResult<T> = Result<T, EthApiError>;
 
+/// Error code of requests whose signer is not authorized to perform them, see
+/// [EIP-1193](https://eips.ethereum.org/EIPS/eip-1193#provider-errors).
+pub const UNAUTHORIZED_CODE: i32 = 4100;
+
 /// Errors that can occur when interacting with the `eth_` namespace
 #[derive(Debug, thiserror::Error)]
 pub enum EthApiError {
@@ -48,6 +53,9 @@ pub enum EthApiError {
     /// When an invalid block range is provided
     #[error("invalid block range")]
     InvalidBlockRange,
+    /// Thrown when the target block for proof generation exceeds the maximum configured window.
+    #[error("distance to target block exceeds maximum proof window")]
+    ExceedsMaxProofWindow,
     /// An internal error where prevrandao is not set in the evm's environment
     #[error("prevrandao not in the EVM's environment after merge")]
     PrevrandaoNotSet,
@@ -82,6 +90,9 @@ pub enum EthApiError {
     /// General purpose error for invalid params
     #[error("{0}")]
     InvalidParams(String),
+    /// Thrown when the signer of a request is not allowed to perform it
+    #[error("{0}")]
+    Unauthorized(&'static str),
     /// When the tracer config does not match the tracer
     #[error("invalid tracer config")]
     InvalidTracerConfig,
@@ -143,6 +154,7 @@ impl From<EthApiError> for ErrorObject<'static> {
             EthApiError::InvalidTransactionSignature |
             EthApiError::EmptyRawTransactionData |
             EthApiError::InvalidBlockRange |
+            EthApiError::ExceedsMaxProofWindow |
             EthApiError::ConflictingFeeFieldsInRequest |
             EthApiError::Signing(_) |
             EthApiError::BothStateAndStateDiffInOverride(_) |
@@ -164,6 +176,7 @@ impl From<EthApiError> for ErrorObject<'static> {
             EthApiError::Unsupported(msg) => internal_rpc_err(msg),
             EthApiError::InternalJsTracerError(msg) => internal_rpc_err(msg),
             EthApiError::InvalidParams(msg) => invalid_params_rpc_err(msg),
+            EthApiError::Unauthorized(msg) => rpc_error_with_code(UNAUTHORIZED_CODE, msg),
             EthApiError::InvalidRewardPercentiles => internal_rpc_err(error.to_string()),
             err @ EthApiError::ExecutionTimedOut(_) => {
                 rpc_error_with_code(CALL_EXECUTION_FAILED_CODE, err.to_string())
@@ -188,6 +201,7 @@ impl From<EthApiError> for RpcError {
         RpcError::Call(error.into())
     }
 }
+#[cfg(feature = "js-tracer")]
 impl From<JsInspectorError> for EthApiError {
     fn from(error: JsInspectorError) -> Self {
         match error {
@@ -10,9 +10,13 @@ use crate::{
 use core::fmt;
 
 use async_trait::async_trait;
+use futures::{Stream, StreamExt};
 use jsonrpsee::{core::RpcResult, server::IdProvider};
 use reth_primitives::{IntoRecoveredTransaction, TxHash};
-use reth_provider::{BlockIdReader, BlockReader, EvmEnvProvider, ProviderError};
+use reth_provider::{
+    chain::BlockReceipts, BlockIdReader, BlockReader, CanonStateNotification, EvmEnvProvider,
+    LogIndexReader, ProviderError,
+};
 use reth_rpc_api::EthFilterApiServer;
 use reth_rpc_types::{
     BlockNumHash, Filter, FilterBlockOption, FilterChanges, FilterId, FilteredParams, Log,
@@ -21,7 +25,7 @@ use reth_rpc_types::{
 use reth_tasks::TaskSpawner;
 use reth_transaction_pool::{NewSubpoolTransactionStream, PoolTransaction, TransactionPool};
 use std::{
-    collections::HashMap,
+    collections::{BTreeSet, HashMap},
     iter::StepBy,
     ops::RangeInclusive,
     sync::Arc,
@@ -91,11 +95,66 @@ where
         eth_filter
     }
 
+    /// Makes polling log filters aware of reorgs of the canonical chain.
+    ///
+    /// This spawns a task that listens to the given [CanonStateNotification]s. Logs of reverted
+    /// blocks that were already returned to the client are returned again with `removed: true`
+    /// on the next poll, followed by the logs of the new canonical blocks.
+    pub fn with_reorg_tracking<St>(self, canon_state_notifications: St) -> Self
+    where
+        St: Stream<Item = CanonStateNotification> + Send + Unpin + 'static,
+    {
+        let this = self.clone();
+        self.inner.task_spawner.clone().spawn_critical(
+            "eth-filters_reorg-tracking",
+            Box::pin(async move {
+                this.watch_reorgs(canon_state_notifications).await;
+            }),
+        );
+        self
+    }
+
     /// Returns all currently active filters
     pub fn active_filters(&self) -> &ActiveFilters {
         &self.inner.active_filters
     }
 
+    /// Endless future that buffers the logs of reverted blocks for all active log filters.
+    async fn watch_reorgs<St>(&self, mut canon_state_notifications: St)
+    where
+        St: Stream<Item = CanonStateNotification> + Unpin,
+    {
+        while let Some(notification) = canon_state_notifications.next().await {
+            if let Some(reverted) = notification.reverted() {
+                self.on_reverted_chain(
+                    reverted.first().number,
+                    &reverted.receipts_with_attachment(),
+                )
+                .await;
+            }
+        }
+    }
+
+    /// Buffers the logs of the reverted blocks for all log filters that already returned them and
+    /// rewinds the filters to the first reverted block.
+    async fn on_reverted_chain(&self, first_reverted_block: u64, receipts: &[BlockReceipts]) {
+        let mut filters = self.active_filters().inner.lock().await;
+        for (id, filter) in filters.iter_mut() {
+            let FilterKind::Log(ref log_filter) = filter.kind else { continue };
+            if filter.block <= first_reverted_block {
+                // none of the reverted blocks have been returned yet
+                continue
+            }
+
+            let params = FilteredParams::new(Some(*log_filter.clone()));
+            filter.removed_logs.extend(removed_logs(&params, receipts, filter.block));
+
+            trace!(target: "rpc::eth::filter", ?id, from=filter.block, to=first_reverted_block, "rewinding filter");
+            // the new canonical blocks need to be returned again
+            filter.block = first_reverted_block;
+        }
+    }
+
     /// Endless future that [Self::clear_stale_filters] every `stale_filter_ttl` interval.
     async fn watch_and_clear_stale_filters(&self) {
         let mut interval = tokio::time::interval(self.inner.stale_filter_ttl);
@@ -124,7 +183,7 @@ where
 
 impl<Provider, Pool> EthFilter<Provider, Pool>
 where
-    Provider: BlockReader + BlockIdReader + EvmEnvProvider + 'static,
+    Provider: BlockReader + BlockIdReader + EvmEnvProvider + LogIndexReader + 'static,
     Pool: TransactionPool + 'static,
     <Pool as TransactionPool>::Transaction: 'static,
 {
@@ -135,13 +194,20 @@ where
 
         // start_block is the block from which we should start fetching changes, the next block from
         // the last time changes were polled, in other words the best block at last poll + 1
-        let (start_block, kind) = {
+        let (start_block, kind, removed_logs) = {
             let mut filters = self.inner.active_filters.inner.lock().await;
             let filter = filters.get_mut(&id).ok_or(FilterError::FilterNotFound(id))?;
 
+            // logs of reverted blocks are always returned first
+            let removed_logs = std::mem::take(&mut filter.removed_logs);
+
             if filter.block > best_number {
                 // no new blocks since the last poll
-                return Ok(FilterChanges::Empty)
+                if removed_logs.is_empty() {
+                    return Ok(FilterChanges::Empty)
+                }
+                filter.last_poll_timestamp = Instant::now();
+                return Ok(FilterChanges::Logs(removed_logs))
             }
 
             // update filter
@@ -151,7 +217,7 @@ where
             std::mem::swap(&mut filter.block, &mut block);
             filter.last_poll_timestamp = Instant::now();
 
-            (block, filter.kind.clone())
+            (block, filter.kind.clone(), removed_logs)
         };
 
         match kind {
@@ -188,10 +254,12 @@ where
                     }
                 };
 
-                let logs = self
-                    .inner
-                    .get_logs_in_block_range(&filter, from_block_number, to_block_number)
-                    .await?;
+                let mut logs = removed_logs;
+                logs.extend(
+                    self.inner
+                        .get_logs_in_block_range(&filter, from_block_number, to_block_number)
+                        .await?,
+                );
                 Ok(FilterChanges::Logs(logs))
             }
         }
@@ -223,7 +291,7 @@ where
 #[async_trait]
 impl<Provider, Pool> EthFilterApiServer for EthFilter<Provider, Pool>
 where
-    Provider: BlockReader + BlockIdReader + EvmEnvProvider + 'static,
+    Provider: BlockReader + BlockIdReader + EvmEnvProvider + LogIndexReader + 'static,
     Pool: TransactionPool + 'static,
 {
     /// Handler for `eth_newFilter`
@@ -342,7 +410,7 @@ struct EthFilterInner<Provider, Pool> {
 
 impl<Provider, Pool> EthFilterInner<Provider, Pool>
 where
-    Provider: BlockReader + BlockIdReader + EvmEnvProvider + 'static,
+    Provider: BlockReader + BlockIdReader + EvmEnvProvider + LogIndexReader + 'static,
     Pool: TransactionPool + 'static,
 {
     /// Returns logs matching given filter object.
@@ -398,6 +466,7 @@ where
                 block: last_poll_block_number,
                 last_poll_timestamp: Instant::now(),
                 kind,
+                removed_logs: Vec::new(),
             },
         );
         Ok(id)
@@ -416,13 +485,51 @@ where
     ) -> Result<Vec<Log>, FilterError> {
         trace!(target: "rpc::eth::filter", from=from_block, to=to_block, ?filter, "finding logs in range");
 
-        if to_block - from_block > self.max_blocks_per_filter {
-            return Err(FilterError::QueryExceedsMaxBlocks(self.max_blocks_per_filter))
-        }
-
         let mut all_logs = Vec::new();
         let filter_params = FilteredParams::new(Some(filter.clone()));
 
+        // size check but only if range is multiple blocks, so we always return all logs of a
+        // single block
+        let is_multi_block_range = from_block != to_block;
+
+        // blocks covered by the log index are looked up in the index, only the remaining blocks
+        // are scanned using the header blooms
+        let mut scan_from_block = from_block;
+        if let Some((blocks, indexed_to_block)) =
+            self.indexed_candidate_blocks(filter, from_block, to_block)?
+        {
+            for block_number in blocks {
+                let block_hash = self
+                    .provider
+                    .block_hash(block_number)?
+                    .ok_or(ProviderError::BlockNotFound(block_number.into()))?;
+
+                if let Some(receipts) = self.eth_cache.get_receipts(block_hash).await? {
+                    append_matching_block_logs(
+                        &mut all_logs,
+                        &self.provider,
+                        &filter_params,
+                        BlockNumHash::new(block_number, block_hash),
+                        &receipts,
+                        false,
+                    )?;
+
+                    if is_multi_block_range && all_logs.len() > self.max_logs_per_response {
+                        return Err(FilterError::QueryExceedsMaxResults(self.max_logs_per_response))
+                    }
+                }
+            }
+
+            if indexed_to_block >= to_block {
+                return Ok(all_logs)
+            }
+            scan_from_block = indexed_to_block + 1;
+        }
+
+        if to_block - scan_from_block > self.max_blocks_per_filter {
+            return Err(FilterError::QueryExceedsMaxBlocks(self.max_blocks_per_filter))
+        }
+
         // derive bloom filters from filter input
         let address_filter = FilteredParams::address_filter(&filter.address);
         let topics_filter = FilteredParams::topics_filter(&filter.topics);
@@ -430,7 +537,7 @@ where
         // loop over the range of new blocks and check logs if the filter matches the log's bloom
         // filter
         for (from, to) in
-            BlockRangeInclusiveIter::new(from_block..=to_block, self.max_headers_range)
+            BlockRangeInclusiveIter::new(scan_from_block..=to_block, self.max_headers_range)
         {
             let headers = self.provider.headers_range(from..=to)?;
 
@@ -459,9 +566,6 @@ where
                             false,
                         )?;
 
-                        // size check but only if range is multiple blocks, so we always return all
-                        // logs of a single block
-                        let is_multi_block_range = from_block != to_block;
                         if is_multi_block_range && all_logs.len() > self.max_logs_per_response {
                             return Err(FilterError::QueryExceedsMaxResults(
                                 self.max_logs_per_response,
@@ -474,6 +578,52 @@ where
 
         Ok(all_logs)
     }
+
+    /// Returns the blocks of the given _inclusive_ range that may contain logs matching the filter
+    /// according to the log index, in ascending order, and the last block of the range that is
+    /// covered by the index.
+    ///
+    /// The blocks of every address and of every topic position are unioned, and the resulting
+    /// sets are intersected.
+    ///
+    /// Returns `None` if the filter does not constrain addresses or topics, or if the index is not
+    /// maintained or does not cover the start of the range.
+    fn indexed_candidate_blocks(
+        &self,
+        filter: &Filter,
+        from_block: u64,
+        to_block: u64,
+    ) -> Result<Option<(Vec<u64>, u64)>, FilterError> {
+        if filter.address.is_empty() && filter.topics.iter().all(|topics| topics.is_empty()) {
+            return Ok(None)
+        }
+        let Some(indexed_block) = self.provider.log_index_highest_block()? else { return Ok(None) };
+        if indexed_block < from_block {
+            return Ok(None)
+        }
+        let range = from_block..=indexed_block.min(to_block);
+
+        let mut candidates: Option<BTreeSet<u64>> = None;
+        if !filter.address.is_empty() {
+            let mut blocks = BTreeSet::new();
+            for address in filter.address.iter() {
+                blocks.extend(self.provider.log_address_blocks(*address, range.clone())?);
+            }
+            candidates = Some(blocks);
+        }
+        for topics in filter.topics.iter().filter(|topics| !topics.is_empty()) {
+            let mut blocks = BTreeSet::new();
+            for topic in topics.iter() {
+                blocks.extend(self.provider.log_topic_blocks(*topic, range.clone())?);
+            }
+            candidates = Some(match candidates {
+                Some(candidates) => candidates.intersection(&blocks).copied().collect(),
+                None => blocks,
+            });
+        }
+
+        Ok(candidates.map(|blocks| (blocks.into_iter().collect(), *range.end())))
+    }
 }
 
 /// Config for the filter
@@ -541,6 +691,29 @@ struct ActiveFilter {
     last_poll_timestamp: Instant,
     /// What kind of filter it is.
     kind: FilterKind,
+    /// Logs of reverted blocks that were already returned, to be returned with `removed: true`
+    /// on the next poll.
+    removed_logs: Vec<Log>,
+}
+
+/// Returns the matching logs of all reverted blocks before the given block, marked as removed.
+fn removed_logs(
+    filter: &FilteredParams,
+    receipts: &[BlockReceipts],
+    before_block: u64,
+) -> Vec<Log> {
+    receipts
+        .iter()
+        .filter(|block_receipts| block_receipts.block.number < before_block)
+        .flat_map(|block_receipts| {
+            logs_utils::matching_block_logs_with_tx_hashes(
+                filter,
+                block_receipts.block,
+                block_receipts.tx_receipts.iter().map(|(tx, receipt)| (*tx, receipt)),
+                true,
+            )
+        })
+        .collect()
 }
 
 /// A receiver for pending transactions that returns all new transactions since the last poll.
@@ -714,6 +887,7 @@ impl Iterator for BlockRangeInclusiveIter {
 mod tests {
     use super::*;
     use rand::{thread_rng, Rng};
+    use reth_primitives::{Address, BlockNumHash, Receipt, B256};
 
     #[test]
     fn test_block_range_iter() {
@@ -737,4 +911,37 @@ mod tests {
             assert_eq!(end, *range.end());
         }
     }
+
+    #[test]
+    fn test_removed_logs() {
+        let address = Address::random();
+        let receipt = |address| Receipt {
+            logs: vec![reth_primitives::Log { address, ..Default::default() }],
+            ..Default::default()
+        };
+        let receipts = vec![
+            BlockReceipts {
+                block: BlockNumHash::new(5, B256::random()),
+                tx_receipts: vec![
+                    (B256::random(), receipt(address)),
+                    (B256::random(), receipt(Address::random())),
+                ],
+            },
+            BlockReceipts {
+                block: BlockNumHash::new(6, B256::random()),
+                tx_receipts: vec![(B256::random(), receipt(address))],
+            },
+        ];
+        let filter = FilteredParams::new(Some(Filter::new().address(address)));
+
+        // only the first block was returned to the client
+        let logs = removed_logs(&filter, &receipts, 6);
+        assert_eq!(logs.len(), 1);
+        assert!(logs[0].removed);
+        assert_eq!(logs[0].block_hash, Some(receipts[0].block.hash));
+        assert_eq!(logs[0].transaction_hash, Some(receipts[0].tx_receipts[0].0));
+
+        assert_eq!(removed_logs(&filter, &receipts, 7).len(), 2);
+        assert!(removed_logs(&filter, &receipts, 5).is_empty());
+    }
 }
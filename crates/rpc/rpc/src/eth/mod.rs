@@ -13,9 +13,13 @@ pub mod revm_utils;
 mod signer;
 pub(crate) mod utils;
 
+#[cfg(feature = "optimism")]
+pub(crate) use api::optimism::OptimismTxMeta;
+pub(crate) use api::transactions::build_transaction_receipt_with_block_receipts;
 pub use api::{
     fee_history::{fee_history_cache_new_blocks_task, FeeHistoryCache, FeeHistoryCacheConfig},
-    EthApi, EthApiSpec, EthTransactions, TransactionSource, RPC_DEFAULT_GAS_CAP,
+    EthApi, EthApiSpec, EthTransactions, TransactionSource, RPC_DEFAULT_ETH_PROOF_WINDOW,
+    RPC_DEFAULT_GAS_CAP,
 };
 
 pub use bundle::EthBundle;
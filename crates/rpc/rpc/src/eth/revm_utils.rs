@@ -136,7 +136,7 @@ where
 }
 
 /// Executes the [EnvWithHandlerCfg] against the given [Database] without committing state changes.
-pub(crate) fn inspect<DB, I>(
+pub fn inspect<DB, I>(
     db: DB,
     env: EnvWithHandlerCfg,
     inspector: I,
@@ -34,9 +34,11 @@ mod otterscan;
 mod reth;
 mod rpc;
 mod trace;
+mod tracers;
 mod txpool;
+mod validation;
 mod web3;
-pub use admin::AdminApi;
+pub use admin::{AdminApi, AdminBackupApi};
 pub use blocking_pool::{BlockingTaskGuard, BlockingTaskPool};
 pub use debug::DebugApi;
 pub use engine::{EngineApi, EngineEthApi};
@@ -47,7 +49,9 @@ pub use otterscan::OtterscanApi;
 pub use reth::RethApi;
 pub use rpc::RPCApi;
 pub use trace::TraceApi;
+pub use tracers::{CustomTracer, TracerRegistry, TransactionContext};
 pub use txpool::TxPoolApi;
+pub use validation::{ValidationApi, ValidationApiError, ValidationApiResult};
 pub use web3::Web3Api;
 pub mod blocking_pool;
 pub mod result;
@@ -1,32 +1,314 @@
-use crate::result::internal_rpc_err;
+use crate::eth::{
+    build_transaction_receipt_with_block_receipts,
+    cache::EthStateCache,
+    error::{EthApiError, EthResult},
+    EthTransactions,
+};
 use async_trait::async_trait;
 use jsonrpsee::core::RpcResult;
-use reth_primitives::{Address, BlockId, BlockNumberOrTag, TxHash, B256};
+use reth_consensus_common::calc::{base_block_reward, block_reward, ommer_reward};
+use reth_primitives::{
+    Address, BlockHashOrNumber, BlockId, BlockNumberOrTag, Bloom, Bytes, ChainSpec,
+    ContractCreation, Receipt, SealedBlock, SealedBlockWithSenders, TransactionMeta, TxHash,
+    TxNumber, B256, U256,
+};
+use reth_provider::{
+    AccountReader, AddressAppearancesReader, BlockIdReader, BlockNumReader, BlockReader,
+    BlockReaderIdExt, ChainSpecProvider, ContractCreatorReader, HeaderProvider,
+    StateProviderFactory, TransactionVariant, TransactionsProvider,
+};
+use reth_revm::tracing::{
+    types::{CallKind, CallTrace, CallTraceNode},
+    TracingInspectorConfig,
+};
 use reth_rpc_api::{EthApiServer, OtterscanServer};
 use reth_rpc_types::{
-    BlockDetails, ContractCreator, InternalOperation, OtsBlockTransactions, TraceEntry,
+    BlockDetails, BlockTransactions, BlockTransactionsKind, ContractCreator, InternalIssuance,
+    InternalOperation, OperationType, OtsBlockTransactions, OtsTransactionReceipt, TraceEntry,
     Transaction, TransactionsWithReceipts,
 };
+use reth_rpc_types_compat::block::from_block;
+use revm::primitives::{db::DatabaseRef, ExecutionResult};
+use std::{
+    collections::{hash_map::Entry, HashMap},
+    sync::Arc,
+};
+
+#[cfg(feature = "optimism")]
+use crate::eth::OptimismTxMeta;
 
 const API_LEVEL: u64 = 8;
 
 /// Otterscan API.
 #[derive(Debug)]
-pub struct OtterscanApi<Eth> {
+pub struct OtterscanApi<Provider, Eth> {
+    provider: Provider,
     eth: Eth,
+    eth_cache: EthStateCache,
 }
 
-impl<Eth> OtterscanApi<Eth> {
+impl<Provider, Eth> OtterscanApi<Provider, Eth> {
     /// Creates a new instance of `Otterscan`.
-    pub fn new(eth: Eth) -> Self {
-        Self { eth }
+    pub fn new(provider: Provider, eth: Eth, eth_cache: EthStateCache) -> Self {
+        Self { provider, eth, eth_cache }
+    }
+}
+
+impl<Provider, Eth> OtterscanApi<Provider, Eth>
+where
+    Provider: BlockReaderIdExt
+        + ChainSpecProvider
+        + AddressAppearancesReader
+        + ContractCreatorReader
+        + StateProviderFactory
+        + 'static,
+    Eth: EthApiServer + EthTransactions + 'static,
+{
+    /// Walks the address appearances index starting at `tx_number`, in descending order if
+    /// `ascending` is false.
+    ///
+    /// At least `page_size` transactions are collected if available, and the block of the last
+    /// collected transaction is always completed, so that a page never ends in the middle of a
+    /// block. Returns the collected transaction numbers and whether there are more appearances.
+    fn collect_appearances(
+        &self,
+        address: Address,
+        tx_number: TxNumber,
+        page_size: usize,
+        ascending: bool,
+    ) -> EthResult<(Vec<TxNumber>, bool)> {
+        let mut appearances = Vec::new();
+        let mut last_block = None;
+        let mut cursor = tx_number;
+        loop {
+            let batch = if ascending {
+                self.provider.address_appearances_after(address, cursor, page_size.max(1))?
+            } else {
+                self.provider.address_appearances_before(address, cursor, page_size.max(1))?
+            };
+            if batch.is_empty() {
+                return Ok((appearances, false))
+            }
+
+            for tx_number in batch {
+                let block_number = self
+                    .provider
+                    .transaction_block(tx_number)?
+                    .ok_or(EthApiError::UnknownBlockOrTxIndex)?;
+                if appearances.len() >= page_size && last_block != Some(block_number) {
+                    return Ok((appearances, true))
+                }
+
+                last_block = Some(block_number);
+                appearances.push(tx_number);
+                cursor = if ascending { tx_number + 1 } else { tx_number };
+            }
+        }
+    }
+
+    /// Fetches the RPC transactions and receipts for the given transaction numbers.
+    async fn transactions_with_receipts(
+        &self,
+        tx_numbers: Vec<TxNumber>,
+    ) -> RpcResult<(Vec<Transaction>, Vec<OtsTransactionReceipt>)> {
+        let mut txs = Vec::with_capacity(tx_numbers.len());
+        let mut receipts = Vec::with_capacity(tx_numbers.len());
+        for tx_number in tx_numbers {
+            let hash = self
+                .provider
+                .transaction_by_id(tx_number)
+                .map_err(EthApiError::from)?
+                .ok_or(EthApiError::TransactionNotFound)?
+                .hash();
+            let tx = EthApiServer::transaction_by_hash(&self.eth, hash)
+                .await?
+                .ok_or(EthApiError::TransactionNotFound)?;
+            let receipt = EthApiServer::transaction_receipt(&self.eth, hash)
+                .await?
+                .ok_or(EthApiError::TransactionNotFound)?;
+            let timestamp = receipt
+                .block_number
+                .map(|number| self.provider.header_by_number(number.to::<u64>()))
+                .transpose()
+                .map_err(EthApiError::from)?
+                .flatten()
+                .ok_or(EthApiError::UnknownBlockNumber)?
+                .timestamp;
+
+            txs.push(tx);
+            receipts.push(OtsTransactionReceipt { receipt, timestamp });
+        }
+        Ok((txs, receipts))
+    }
+
+    /// Returns the block in which the nonce of the sender was incremented past `nonce`, or
+    /// `None` if the sender hasn't sent a transaction with that nonce yet.
+    fn find_block_by_sender_nonce(&self, sender: Address, nonce: u64) -> EthResult<Option<u64>> {
+        let nonce_after = |block_number| -> EthResult<u64> {
+            Ok(self
+                .provider
+                .history_by_block_number(block_number)?
+                .account_nonce(sender)?
+                .unwrap_or_default())
+        };
+
+        let mut high = self.provider.best_block_number()?;
+        if nonce_after(high)? <= nonce {
+            return Ok(None)
+        }
+
+        // The nonce is monotonically increasing, so binary search for the first block after
+        // which the nonce is higher than the requested one.
+        let mut low = 0;
+        while low < high {
+            let mid = low + (high - low) / 2;
+            if nonce_after(mid)? > nonce {
+                high = mid;
+            } else {
+                low = mid + 1;
+            }
+        }
+        Ok(Some(high))
+    }
+
+    /// Returns the block with senders and its receipts from the cache, or `None` if the block is
+    /// unknown.
+    ///
+    /// The receipts of the block might have been pruned, in which case they are `None`.
+    async fn block_and_receipts(
+        &self,
+        block_id: BlockId,
+    ) -> EthResult<Option<(SealedBlockWithSenders, Option<Arc<Vec<Receipt>>>)>> {
+        let Some(block_hash) = self.provider.block_hash_for_id(block_id)? else { return Ok(None) };
+        let (block, receipts) = futures::try_join!(
+            self.eth_cache.get_sealed_block_with_senders(block_hash),
+            self.eth_cache.get_receipts(block_hash),
+        )?;
+        let Some(block) = block else { return Ok(None) };
+
+        // Contract logs pruning keeps only some of the receipts of a block
+        let receipts = receipts.filter(|receipts| receipts.len() == block.body.len());
+        Ok(Some((block, receipts)))
+    }
+
+    /// Returns the details of the block, or `None` if the block is unknown.
+    ///
+    /// The receipts of the block might have been pruned, in which case its total fees are zero.
+    async fn block_details_by_id(&self, block_id: BlockId) -> EthResult<Option<BlockDetails>> {
+        let Some((block, receipts)) = self.block_and_receipts(block_id).await? else {
+            return Ok(None)
+        };
+        Ok(Some(self.block_details(block, receipts.as_deref().map(Vec::as_slice))?))
+    }
+
+    /// Returns the block with full transactions, along with its issuance and the fees paid by its
+    /// transactions, which are zero if the receipts aren't given.
+    fn block_details(
+        &self,
+        block: SealedBlockWithSenders,
+        receipts: Option<&[Receipt]>,
+    ) -> EthResult<BlockDetails> {
+        let total_difficulty = self
+            .provider
+            .header_td_by_number(block.number)?
+            .ok_or(EthApiError::UnknownBlockNumber)?;
+        let issuance = block_issuance(&self.provider.chain_spec(), &block, total_difficulty);
+        let total_fees = receipts.map_or(U256::ZERO, |receipts| block_fees(&block, receipts));
+
+        let block_hash = block.hash;
+        let block = from_block(
+            block.unseal(),
+            total_difficulty,
+            BlockTransactionsKind::Full,
+            Some(block_hash),
+        )?;
+        Ok(BlockDetails::new(block.into(), issuance, total_fees))
+    }
+
+    /// Returns the first block after which the account has code, or `None` if the account has no
+    /// code at the latest block.
+    fn find_block_by_contract_code(&self, address: Address) -> EthResult<Option<u64>> {
+        let has_code_after = |block_number| -> EthResult<bool> {
+            Ok(self
+                .provider
+                .history_by_block_number(block_number)?
+                .basic_account(address)?
+                .is_some_and(|account| account.has_bytecode()))
+        };
+
+        let mut high = self.provider.best_block_number()?;
+        if !has_code_after(high)? {
+            return Ok(None)
+        }
+
+        let mut low = 0;
+        while low < high {
+            let mid = low + (high - low) / 2;
+            if has_code_after(mid)? {
+                high = mid;
+            } else {
+                low = mid + 1;
+            }
+        }
+        Ok(Some(high))
+    }
+
+    /// Looks up the creation of the contract at the given address.
+    ///
+    /// The contract creators index is used if the contract is indexed. Otherwise, the block in
+    /// which the contract was deployed is found using the account history and traced to find the
+    /// creating call frame.
+    async fn contract_creation(&self, address: Address) -> EthResult<Option<ContractCreation>> {
+        if let Some(creation) = self.provider.contract_creation(address)? {
+            return Ok(Some(creation))
+        }
+
+        let Some(block_number) = self.find_block_by_contract_code(address)? else {
+            return Ok(None)
+        };
+
+        // If the contract was created more than once in the block, only the last creation was
+        // persisted.
+        let creation = self
+            .eth
+            .trace_block_with(
+                block_number.into(),
+                TracingInspectorConfig::default_parity(),
+                move |tx_info, inspector, _, _, _| {
+                    let creator = inspector
+                        .get_traces()
+                        .nodes()
+                        .iter()
+                        .rev()
+                        .map(|node| &node.trace)
+                        .find(|trace| {
+                            matches!(trace.kind, CallKind::Create | CallKind::Create2) &&
+                                trace.address == address
+                        })
+                        .map(|trace| trace.caller);
+                    Ok(creator.zip(tx_info.hash))
+                },
+            )
+            .await?
+            .unwrap_or_default()
+            .into_iter()
+            .flatten()
+            .last()
+            .map(|(creator, tx_hash)| ContractCreation { block_number, tx_hash, creator });
+        Ok(creation)
     }
 }
 
 #[async_trait]
-impl<Eth> OtterscanServer for OtterscanApi<Eth>
+impl<Provider, Eth> OtterscanServer for OtterscanApi<Provider, Eth>
 where
-    Eth: EthApiServer,
+    Provider: BlockReaderIdExt
+        + ChainSpecProvider
+        + AddressAppearancesReader
+        + ContractCreatorReader
+        + StateProviderFactory
+        + 'static,
+    Eth: EthApiServer + EthTransactions + 'static,
 {
     /// Handler for `ots_hasCode`
     async fn has_code(&self, address: Address, block_number: Option<BlockId>) -> RpcResult<bool> {
@@ -39,18 +321,56 @@ where
     }
 
     /// Handler for `ots_getInternalOperations`
-    async fn get_internal_operations(&self, _tx_hash: TxHash) -> RpcResult<Vec<InternalOperation>> {
-        Err(internal_rpc_err("unimplemented"))
+    async fn get_internal_operations(&self, tx_hash: TxHash) -> RpcResult<Vec<InternalOperation>> {
+        let operations = self
+            .eth
+            .spawn_trace_transaction_in_block(
+                tx_hash,
+                TracingInspectorConfig::default_parity(),
+                move |_, inspector, _, db| {
+                    let nodes = inspector.get_traces().nodes();
+                    Ok(internal_operations(nodes, initial_balances(nodes, &db)?))
+                },
+            )
+            .await?
+            .ok_or(EthApiError::TransactionNotFound)?;
+        Ok(operations)
     }
 
     /// Handler for `ots_getTransactionError`
-    async fn get_transaction_error(&self, _tx_hash: TxHash) -> RpcResult<String> {
-        Err(internal_rpc_err("unimplemented"))
+    async fn get_transaction_error(&self, tx_hash: TxHash) -> RpcResult<Bytes> {
+        let output = self
+            .eth
+            .spawn_trace_transaction_in_block(
+                tx_hash,
+                TracingInspectorConfig::none(),
+                move |_, _, res, _| {
+                    Ok(match res.result {
+                        ExecutionResult::Revert { output, .. } => output,
+                        _ => Bytes::default(),
+                    })
+                },
+            )
+            .await?
+            .ok_or(EthApiError::TransactionNotFound)?;
+        Ok(output)
     }
 
     /// Handler for `ots_traceTransaction`
-    async fn trace_transaction(&self, _tx_hash: TxHash) -> RpcResult<TraceEntry> {
-        Err(internal_rpc_err("unimplemented"))
+    async fn trace_transaction(&self, tx_hash: TxHash) -> RpcResult<Vec<TraceEntry>> {
+        let entries = self
+            .eth
+            .spawn_trace_transaction_in_block(
+                tx_hash,
+                TracingInspectorConfig::default_parity(),
+                move |_, inspector, _, db| {
+                    let nodes = inspector.get_traces().nodes();
+                    Ok(trace_entries(nodes, initial_balances(nodes, &db)?))
+                },
+            )
+            .await?
+            .ok_or(EthApiError::TransactionNotFound)?;
+        Ok(entries)
     }
 
     /// Handler for `ots_getBlockDetails`
@@ -58,57 +378,560 @@ where
         &self,
         block_number: BlockNumberOrTag,
     ) -> RpcResult<Option<BlockDetails>> {
-        let block = self.eth.block_by_number(block_number, true).await?;
-        Ok(block.map(Into::into))
+        Ok(self.block_details_by_id(block_number.into()).await?)
     }
 
     /// Handler for `getBlockDetailsByHash`
     async fn get_block_details_by_hash(&self, block_hash: B256) -> RpcResult<Option<BlockDetails>> {
-        let block = self.eth.block_by_hash(block_hash, true).await?;
-        Ok(block.map(Into::into))
+        Ok(self.block_details_by_id(block_hash.into()).await?)
     }
 
     /// Handler for `getBlockTransactions`
+    ///
+    /// Pages are counted from the end of the block, so the first page contains the last
+    /// transactions of the block.
     async fn get_block_transactions(
         &self,
-        _block_number: BlockNumberOrTag,
-        _page_number: usize,
-        _page_size: usize,
+        block_number: BlockNumberOrTag,
+        page_number: usize,
+        page_size: usize,
     ) -> RpcResult<OtsBlockTransactions> {
-        Err(internal_rpc_err("unimplemented"))
+        let (block, receipts) = self
+            .block_and_receipts(block_number.into())
+            .await?
+            .ok_or(EthApiError::UnknownBlockNumber)?;
+
+        let page_end = block.body.len().saturating_sub(page_number.saturating_mul(page_size));
+        let page_start = page_end.saturating_sub(page_size);
+
+        // Without the receipts of the block, none are returned and its total fees are zero
+        let receipts = receipts.as_deref().map(Vec::as_slice);
+        let block_receipts = receipts.unwrap_or_default();
+        let mut page_receipts = Vec::with_capacity(page_end - page_start);
+        for (index, (tx, receipt)) in
+            block.body.iter().zip(block_receipts).enumerate().take(page_end).skip(page_start)
+        {
+            let meta = TransactionMeta {
+                tx_hash: tx.hash,
+                index: index as u64,
+                block_hash: block.hash,
+                block_number: block.number,
+                base_fee: block.base_fee_per_gas,
+                excess_blob_gas: block.excess_blob_gas,
+            };
+            let mut receipt = build_transaction_receipt_with_block_receipts(
+                tx.clone(),
+                meta,
+                receipt.clone(),
+                block_receipts,
+                // L1 fees are not displayed by Otterscan
+                #[cfg(feature = "optimism")]
+                OptimismTxMeta::default(),
+            )?;
+            // Logs are fetched separately by Otterscan
+            receipt.logs = Vec::new();
+            receipt.logs_bloom = Bloom::ZERO;
+            page_receipts.push(OtsTransactionReceipt { receipt, timestamp: block.timestamp });
+        }
+
+        let BlockDetails { block: mut fullblock, issuance, total_fees } =
+            self.block_details(block, receipts)?;
+        if let BlockTransactions::Full(transactions) = &mut fullblock.block.transactions {
+            transactions.truncate(page_end);
+            transactions.drain(..page_start);
+            // Only the function selector is kept
+            for tx in transactions {
+                tx.input.0.truncate(4);
+            }
+        }
+
+        Ok(OtsBlockTransactions { fullblock, receipts: page_receipts, issuance, total_fees })
     }
 
     /// Handler for `searchTransactionsBefore`
+    ///
+    /// Block number `0` starts the search from the most recent block.
     async fn search_transactions_before(
         &self,
-        _address: Address,
-        _block_number: BlockNumberOrTag,
-        _page_size: usize,
+        address: Address,
+        block_number: BlockNumberOrTag,
+        page_size: usize,
     ) -> RpcResult<TransactionsWithReceipts> {
-        Err(internal_rpc_err("unimplemented"))
+        let block_number = block_number.as_number().unwrap_or_default();
+        let first_page = block_number == 0;
+        let tx_number = if first_page {
+            TxNumber::MAX
+        } else {
+            self.provider
+                .block_body_indices(block_number)
+                .map_err(EthApiError::from)?
+                .ok_or(EthApiError::UnknownBlockNumber)?
+                .first_tx_num()
+        };
+
+        let (tx_numbers, has_more) =
+            self.collect_appearances(address, tx_number, page_size, false)?;
+        let (txs, receipts) = self.transactions_with_receipts(tx_numbers).await?;
+
+        Ok(TransactionsWithReceipts { txs, receipts, first_page, last_page: !has_more })
     }
 
     /// Handler for `searchTransactionsAfter`
+    ///
+    /// Block number `0` starts the search from the genesis block.
     async fn search_transactions_after(
         &self,
-        _address: Address,
-        _block_number: BlockNumberOrTag,
-        _page_size: usize,
+        address: Address,
+        block_number: BlockNumberOrTag,
+        page_size: usize,
     ) -> RpcResult<TransactionsWithReceipts> {
-        Err(internal_rpc_err("unimplemented"))
+        let block_number = block_number.as_number().unwrap_or_default();
+        let last_page = block_number == 0;
+        let tx_number = if last_page {
+            0
+        } else {
+            match self.provider.block_body_indices(block_number).map_err(EthApiError::from)? {
+                Some(indices) => indices.next_tx_num(),
+                None => return Err(EthApiError::UnknownBlockNumber.into()),
+            }
+        };
+
+        let (mut tx_numbers, has_more) =
+            self.collect_appearances(address, tx_number, page_size, true)?;
+        // Pages are always returned newest first
+        tx_numbers.reverse();
+        let (txs, receipts) = self.transactions_with_receipts(tx_numbers).await?;
+
+        Ok(TransactionsWithReceipts { txs, receipts, first_page: !has_more, last_page })
     }
 
     /// Handler for `getTransactionBySenderAndNonce`
     async fn get_transaction_by_sender_and_nonce(
         &self,
-        _sender: Address,
-        _nonce: u64,
+        sender: Address,
+        nonce: u64,
     ) -> RpcResult<Option<Transaction>> {
-        Err(internal_rpc_err("unimplemented"))
+        let Some(block_number) = self.find_block_by_sender_nonce(sender, nonce)? else {
+            return Ok(None)
+        };
+
+        let block = self
+            .provider
+            .block_with_senders(
+                BlockHashOrNumber::Number(block_number),
+                TransactionVariant::WithHash,
+            )
+            .map_err(EthApiError::from)?
+            .ok_or(EthApiError::UnknownBlockNumber)?;
+        let Some(hash) = block
+            .transactions_with_sender()
+            .find(|(tx_sender, tx)| **tx_sender == sender && tx.nonce() == nonce)
+            .map(|(_, tx)| tx.hash())
+        else {
+            return Ok(None)
+        };
+
+        EthApiServer::transaction_by_hash(&self.eth, hash).await
     }
 
     /// Handler for `getContractCreator`
-    async fn get_contract_creator(&self, _address: Address) -> RpcResult<Option<ContractCreator>> {
-        Err(internal_rpc_err("unimplemented"))
+    async fn get_contract_creator(&self, address: Address) -> RpcResult<Option<ContractCreator>> {
+        let Some(ContractCreation { tx_hash, creator, .. }) =
+            self.contract_creation(address).await?
+        else {
+            return Ok(None)
+        };
+        let tx = EthApiServer::transaction_by_hash(&self.eth, tx_hash).await?;
+        Ok(tx.map(|tx| ContractCreator { tx, creator }))
+    }
+}
+
+/// Returns the issuance of the block, which consists of the block and uncle rewards before the
+/// merge.
+fn block_issuance(
+    chain_spec: &ChainSpec,
+    block: &SealedBlock,
+    total_difficulty: U256,
+) -> InternalIssuance {
+    let Some(base_block_reward) =
+        base_block_reward(chain_spec, block.number, block.difficulty, total_difficulty)
+    else {
+        return InternalIssuance::default()
+    };
+
+    let block_reward = U256::from(block_reward(base_block_reward, block.ommers.len()));
+    let uncle_reward = U256::from(
+        block
+            .ommers
+            .iter()
+            .map(|ommer| ommer_reward(base_block_reward, block.number, ommer.number))
+            .sum::<u128>(),
+    );
+    InternalIssuance { block_reward, uncle_reward, issuance: block_reward + uncle_reward }
+}
+
+/// Returns the sum of the fees paid by the transactions of the block, at their effective gas
+/// price.
+fn block_fees(block: &SealedBlock, receipts: &[Receipt]) -> U256 {
+    let mut cumulative_gas_used = 0;
+    block.body.iter().zip(receipts).fold(U256::ZERO, |fees, (tx, receipt)| {
+        let gas_used = receipt.cumulative_gas_used - cumulative_gas_used;
+        cumulative_gas_used = receipt.cumulative_gas_used;
+        fees + U256::from(tx.effective_gas_price(block.base_fee_per_gas)) * U256::from(gas_used)
+    })
+}
+
+/// Returns the balances of all accounts involved in the recorded call frames, before the
+/// transaction was executed.
+fn initial_balances<DB>(nodes: &[CallTraceNode], db: &DB) -> EthResult<HashMap<Address, U256>>
+where
+    DB: DatabaseRef,
+    EthApiError: From<<DB as DatabaseRef>::Error>,
+{
+    let mut balances = HashMap::new();
+    for node in nodes {
+        let trace = &node.trace;
+        for address in [Some(trace.caller), Some(trace.address), trace.selfdestruct_refund_target]
+            .into_iter()
+            .flatten()
+        {
+            if let Entry::Vacant(entry) = balances.entry(address) {
+                entry.insert(db.basic_ref(address)?.map(|acc| acc.balance).unwrap_or_default());
+            }
+        }
+    }
+    Ok(balances)
+}
+
+/// Replays the value transfers of the recorded call frames on top of the initial balances, so
+/// that the balance a contract transfers to the beneficiary of its self-destruct is known.
+#[derive(Debug)]
+struct FrameBalances {
+    balances: HashMap<Address, U256>,
+    /// The previous balances of modified accounts, to undo the transfers of reverted frames.
+    journal: Vec<(Address, U256)>,
+}
+
+impl FrameBalances {
+    fn balance(&self, address: Address) -> U256 {
+        self.balances.get(&address).copied().unwrap_or_default()
+    }
+
+    fn set_balance(&mut self, address: Address, balance: U256) {
+        let previous = self.balances.insert(address, balance).unwrap_or_default();
+        self.journal.push((address, previous));
+    }
+
+    fn transfer(&mut self, from: Address, to: Address, value: U256) {
+        if value.is_zero() || from == to {
+            return
+        }
+        self.set_balance(from, self.balance(from).saturating_sub(value));
+        self.set_balance(to, self.balance(to).saturating_add(value));
+    }
+
+    /// Transfers the entire balance of the contract to the beneficiary and returns it.
+    fn selfdestruct(&mut self, contract: Address, beneficiary: Address) -> U256 {
+        let value = self.balance(contract);
+        self.set_balance(contract, U256::ZERO);
+        if beneficiary != contract {
+            self.set_balance(beneficiary, self.balance(beneficiary).saturating_add(value));
+        }
+        value
+    }
+
+    fn revert_to(&mut self, checkpoint: usize) {
+        while self.journal.len() > checkpoint {
+            let (address, balance) = self.journal.pop().expect("not empty");
+            self.balances.insert(address, balance);
+        }
+    }
+}
+
+/// Visits the recorded call frames in execution order.
+///
+/// Self-destructs are not recorded as frames of their own, so the callback is invoked a second
+/// time for a frame that self-destructed, after all of its subcalls, with the balance that was
+/// transferred to the beneficiary. It's determined from the given balances before the
+/// transaction.
+fn visit_frames(
+    nodes: &[CallTraceNode],
+    balances: HashMap<Address, U256>,
+    mut f: impl FnMut(&CallTrace, Option<U256>),
+) {
+    fn visit(
+        nodes: &[CallTraceNode],
+        idx: usize,
+        balances: &mut FrameBalances,
+        f: &mut impl FnMut(&CallTrace, Option<U256>),
+    ) {
+        let trace = &nodes[idx].trace;
+        let checkpoint = balances.journal.len();
+        f(trace, None);
+        if matches!(trace.kind, CallKind::Call | CallKind::Create | CallKind::Create2) {
+            balances.transfer(trace.caller, trace.address, trace.value);
+        }
+        for child in &nodes[idx].children {
+            visit(nodes, *child, balances, f);
+        }
+        if let Some(beneficiary) = trace.selfdestruct_refund_target {
+            let value = balances.selfdestruct(trace.address, beneficiary);
+            f(trace, Some(value));
+        }
+        if !trace.success {
+            balances.revert_to(checkpoint);
+        }
+    }
+
+    if !nodes.is_empty() {
+        visit(nodes, 0, &mut FrameBalances { balances, journal: Vec::new() }, &mut f);
+    }
+}
+
+/// Converts the recorded call frames into Otterscan trace entries.
+///
+/// A self-destruct is reported one level deeper than the destroyed contract.
+fn trace_entries(nodes: &[CallTraceNode], balances: HashMap<Address, U256>) -> Vec<TraceEntry> {
+    let mut entries = Vec::with_capacity(nodes.len());
+    visit_frames(nodes, balances, |trace, selfdestruct| {
+        let entry = if let Some(value) = selfdestruct {
+            TraceEntry {
+                r#type: "SELFDESTRUCT".to_string(),
+                depth: trace.depth as u32 + 1,
+                from: trace.address,
+                to: trace.selfdestruct_refund_target.unwrap_or_default(),
+                value,
+                input: Bytes::default(),
+                output: Bytes::default(),
+            }
+        } else {
+            TraceEntry {
+                r#type: trace.kind.to_string(),
+                depth: trace.depth as u32,
+                from: trace.caller,
+                to: trace.address,
+                value: trace.value,
+                input: trace.data.clone(),
+                output: trace.output.clone(),
+            }
+        };
+        entries.push(entry);
+    });
+    entries
+}
+
+/// Extracts the internal value transfers, contract creations and self-destructs from the recorded
+/// call frames.
+///
+/// The top level frame is the transaction itself and therefore not an internal operation, but a
+/// self-destruct of the called contract is.
+fn internal_operations(
+    nodes: &[CallTraceNode],
+    balances: HashMap<Address, U256>,
+) -> Vec<InternalOperation> {
+    let mut operations = Vec::new();
+    visit_frames(nodes, balances, |trace, selfdestruct| {
+        let operation = if let Some(value) = selfdestruct {
+            InternalOperation {
+                r#type: OperationType::OpSelfDestruct,
+                from: trace.address,
+                to: trace.selfdestruct_refund_target.unwrap_or_default(),
+                value,
+            }
+        } else if trace.depth == 0 {
+            return
+        } else {
+            let r#type = match trace.kind {
+                CallKind::Call if trace.value > U256::ZERO => OperationType::OpTransfer,
+                CallKind::Create => OperationType::OpCreate,
+                CallKind::Create2 => OperationType::OpCreate2,
+                _ => return,
+            };
+            InternalOperation { r#type, from: trace.caller, to: trace.address, value: trace.value }
+        };
+        operations.push(operation);
+    });
+    operations
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use reth_primitives::{
+        constants::ETH_TO_WEI, Header, Signature, TransactionSigned, TxLegacy, MAINNET,
+    };
+
+    fn node(idx: usize, children: Vec<usize>, trace: CallTrace) -> CallTraceNode {
+        CallTraceNode { idx, parent: (idx > 0).then_some(0), children, trace, ..Default::default() }
+    }
+
+    /// A call into `b` that transfers value to `c`, which self-destructs in favor of `e`, and then
+    /// deploys `d` with CREATE2.
+    fn nodes() -> Vec<CallTraceNode> {
+        let (a, b, c, d, e) = (
+            Address::with_last_byte(1),
+            Address::with_last_byte(2),
+            Address::with_last_byte(3),
+            Address::with_last_byte(4),
+            Address::with_last_byte(5),
+        );
+        vec![
+            node(
+                0,
+                vec![1, 2],
+                CallTrace {
+                    depth: 0,
+                    kind: CallKind::Call,
+                    caller: a,
+                    address: b,
+                    success: true,
+                    ..Default::default()
+                },
+            ),
+            node(
+                1,
+                vec![],
+                CallTrace {
+                    depth: 1,
+                    kind: CallKind::Call,
+                    caller: b,
+                    address: c,
+                    value: U256::from(5),
+                    selfdestruct_refund_target: Some(e),
+                    success: true,
+                    ..Default::default()
+                },
+            ),
+            node(
+                2,
+                vec![],
+                CallTrace {
+                    depth: 1,
+                    kind: CallKind::Create2,
+                    caller: b,
+                    address: d,
+                    success: true,
+                    ..Default::default()
+                },
+            ),
+        ]
+    }
+
+    #[test]
+    fn trace_entries_in_execution_order() {
+        let entries = trace_entries(&nodes(), HashMap::new());
+        assert_eq!(
+            entries
+                .iter()
+                .map(|entry| (entry.r#type.as_str(), entry.depth, entry.from, entry.to))
+                .collect::<Vec<_>>(),
+            vec![
+                ("CALL", 0, Address::with_last_byte(1), Address::with_last_byte(2)),
+                ("CALL", 1, Address::with_last_byte(2), Address::with_last_byte(3)),
+                ("SELFDESTRUCT", 2, Address::with_last_byte(3), Address::with_last_byte(5)),
+                ("CREATE2", 1, Address::with_last_byte(2), Address::with_last_byte(4)),
+            ]
+        );
+    }
+
+    #[test]
+    fn internal_operations_skip_top_level_frame() {
+        let operations = internal_operations(&nodes(), HashMap::new());
+        assert_eq!(
+            operations,
+            vec![
+                InternalOperation {
+                    r#type: OperationType::OpTransfer,
+                    from: Address::with_last_byte(2),
+                    to: Address::with_last_byte(3),
+                    value: U256::from(5),
+                },
+                InternalOperation {
+                    r#type: OperationType::OpSelfDestruct,
+                    from: Address::with_last_byte(3),
+                    to: Address::with_last_byte(5),
+                    value: U256::from(5),
+                },
+                InternalOperation {
+                    r#type: OperationType::OpCreate2,
+                    from: Address::with_last_byte(2),
+                    to: Address::with_last_byte(4),
+                    value: U256::ZERO,
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn selfdestruct_transfers_contract_balance() {
+        // `c` already holds a balance before it receives 5 wei and self-destructs.
+        let balances = HashMap::from([
+            (Address::with_last_byte(2), U256::from(5)),
+            (Address::with_last_byte(3), U256::from(100)),
+        ]);
+
+        let entries = trace_entries(&nodes(), balances.clone());
+        assert_eq!(entries[2].r#type, "SELFDESTRUCT");
+        assert_eq!(entries[2].value, U256::from(105));
+
+        let operations = internal_operations(&nodes(), balances);
+        assert_eq!(operations[1].r#type, OperationType::OpSelfDestruct);
+        assert_eq!(operations[1].value, U256::from(105));
+    }
+
+    #[test]
+    fn reverted_transfers_are_not_selfdestructed() {
+        // The transfer into `c` is reverted before `b` self-destructs.
+        let mut nodes = nodes();
+        nodes[0].trace.selfdestruct_refund_target = Some(Address::with_last_byte(6));
+        nodes[1].trace.selfdestruct_refund_target = None;
+        nodes[1].trace.success = false;
+        let balances = HashMap::from([(Address::with_last_byte(2), U256::from(5))]);
+
+        let entries = trace_entries(&nodes, balances);
+        assert_eq!(entries.last().unwrap().r#type, "SELFDESTRUCT");
+        assert_eq!(entries.last().unwrap().value, U256::from(5));
+    }
+
+    #[test]
+    fn block_issuance_with_ommer() {
+        // This is block 126 on mainnet.
+        let block = SealedBlock {
+            header: Header {
+                number: 126,
+                difficulty: U256::from(18_145_285_642usize),
+                ..Default::default()
+            }
+            .seal_slow(),
+            ommers: vec![Header { number: 125, ..Default::default() }],
+            ..Default::default()
+        };
+        let total_difficulty = U256::from(2_235_668_675_900usize);
+
+        let issuance = block_issuance(&MAINNET, &block, total_difficulty);
+        let block_reward = U256::from(ETH_TO_WEI * 5 + ((ETH_TO_WEI * 5) >> 5));
+        let uncle_reward = U256::from((ETH_TO_WEI * 5 * 7) >> 3);
+        assert_eq!(
+            issuance,
+            InternalIssuance { block_reward, uncle_reward, issuance: block_reward + uncle_reward }
+        );
+    }
+
+    #[test]
+    fn block_fees_at_effective_gas_price() {
+        let transaction = |gas_price| {
+            TransactionSigned::from_transaction_and_signature(
+                reth_primitives::Transaction::Legacy(TxLegacy { gas_price, ..Default::default() }),
+                Signature::default(),
+            )
+        };
+        let block =
+            SealedBlock { body: vec![transaction(10), transaction(20)], ..Default::default() };
+        let receipts = vec![
+            Receipt { cumulative_gas_used: 21_000, ..Default::default() },
+            Receipt { cumulative_gas_used: 50_000, ..Default::default() },
+        ];
+
+        assert_eq!(block_fees(&block, &receipts), U256::from(10 * 21_000 + 20 * 29_000));
     }
 }
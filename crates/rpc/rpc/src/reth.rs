@@ -1,39 +1,67 @@
-use crate::eth::error::{EthApiError, EthResult};
+use crate::eth::{
+    build_transaction_receipt_with_block_receipts,
+    error::{EthApiError, EthResult},
+};
 use async_trait::async_trait;
-use jsonrpsee::core::RpcResult;
+use futures::StreamExt;
+use jsonrpsee::{
+    core::{RpcResult, SubscriptionResult},
+    server::SubscriptionMessage,
+    PendingSubscriptionSink, SubscriptionSink,
+};
 use reth_interfaces::RethResult;
-use reth_primitives::{Address, BlockId, U256};
-use reth_provider::{BlockReaderIdExt, ChangeSetReader, StateProviderFactory};
+use reth_primitives::{
+    Account, Address, BlockId, BlockNumber, Bytes, Receipt, SealedBlockWithSenders, StorageEntry,
+    TransactionMeta, B256, U256, U64,
+};
+use reth_provider::{
+    BlockReaderIdExt, CanonStateNotification, CanonStateNotificationStream,
+    CanonStateNotifications, CanonStateSubscriptions, Chain, ChangeSetReader, StateProvider,
+    StateProviderFactory,
+};
 use reth_rpc_api::RethApiServer;
+use reth_rpc_types::{
+    trace::parity::{AccountDiff, ChangedType, Delta, StateDiff},
+    BlockStateDiff, ChainNotification, NotifiedBlock, TransactionReceipt,
+};
 use reth_tasks::TaskSpawner;
-use std::{collections::HashMap, future::Future, sync::Arc};
-use tokio::sync::oneshot;
+use std::{
+    collections::{BTreeMap, HashMap},
+    future::Future,
+    sync::Arc,
+};
+use tokio::sync::{broadcast::error::RecvError, oneshot};
+use tracing::debug;
+
+#[cfg(feature = "optimism")]
+use crate::eth::OptimismTxMeta;
 
 /// `reth` API implementation.
 ///
 /// This type provides the functionality for handling `reth` prototype RPC requests.
-pub struct RethApi<Provider> {
-    inner: Arc<RethApiInner<Provider>>,
+pub struct RethApi<Provider, Events> {
+    inner: Arc<RethApiInner<Provider, Events>>,
 }
 
 // === impl RethApi ===
 
-impl<Provider> RethApi<Provider> {
+impl<Provider, Events> RethApi<Provider, Events> {
     /// The provider that can interact with the chain.
     pub fn provider(&self) -> &Provider {
         &self.inner.provider
     }
 
     /// Create a new instance of the [RethApi]
-    pub fn new(provider: Provider, task_spawner: Box<dyn TaskSpawner>) -> Self {
-        let inner = Arc::new(RethApiInner { provider, task_spawner });
+    pub fn new(provider: Provider, task_spawner: Box<dyn TaskSpawner>, events: Events) -> Self {
+        let inner = Arc::new(RethApiInner { provider, task_spawner, events });
         Self { inner }
     }
 }
 
-impl<Provider> RethApi<Provider>
+impl<Provider, Events> RethApi<Provider, Events>
 where
     Provider: BlockReaderIdExt + ChangeSetReader + StateProviderFactory + 'static,
+    Events: CanonStateSubscriptions + 'static,
 {
     /// Executes the future on a new blocking task.
     async fn on_blocking_task<C, F, R>(&self, c: C) -> EthResult<R>
@@ -81,12 +109,251 @@ where
         )?;
         Ok(hash_map)
     }
+
+    /// Returns the nonce, balance, code and changed storage slots of every account touched by the
+    /// block, before and after the block.
+    pub async fn state_diff_in_block(&self, block_id: BlockId) -> EthResult<StateDiff> {
+        self.on_blocking_task(|this| async move {
+            let Some(block_number) = this.provider().block_number_for_id(block_id)? else {
+                return Err(EthApiError::UnknownBlockNumber)
+            };
+            this.try_state_diff_in_block(block_number)
+        })
+        .await
+    }
+
+    /// Builds the state diff of the block from its changesets, which contain the values before the
+    /// block, and the state after the block. The block is not re-executed.
+    fn try_state_diff_in_block(&self, block_number: BlockNumber) -> EthResult<StateDiff> {
+        let state = self.provider().state_by_block_id(block_number.into())?;
+        let accounts_before = self.provider().account_block_changeset(block_number)?;
+        let storage_before = self.provider().storage_block_changeset(block_number)?;
+
+        let mut state_diff = StateDiff::default();
+        for account_before in accounts_before {
+            let (address, before) = (account_before.address, account_before.info);
+            let after = state.basic_account(address)?;
+            let code_before = account_code(&*state, before)?;
+            let code_after = account_code(&*state, after)?;
+            state_diff.0.insert(
+                address,
+                AccountDiff {
+                    balance: delta(before.map(|acc| acc.balance), after.map(|acc| acc.balance)),
+                    nonce: delta(
+                        before.map(|acc| U64::from(acc.nonce)),
+                        after.map(|acc| U64::from(acc.nonce)),
+                    ),
+                    code: delta(code_before, code_after),
+                    storage: BTreeMap::new(),
+                },
+            );
+        }
+
+        for (address, StorageEntry { key, value: before }) in storage_before {
+            let after = state.storage(address, key)?.unwrap_or_default();
+            // storage can change without any change of the account itself
+            let account_diff = state_diff.0.entry(address).or_insert_with(|| AccountDiff {
+                balance: Delta::Unchanged,
+                nonce: Delta::Unchanged,
+                code: Delta::Unchanged,
+                storage: BTreeMap::new(),
+            });
+            // an empty slot does not exist
+            let before = (before != U256::ZERO).then(|| B256::from(before));
+            let after = (after != U256::ZERO).then(|| B256::from(after));
+            account_diff.storage.insert(key, delta(before, after));
+        }
+
+        Ok(state_diff)
+    }
+
+    /// Sends the state diff of every block of new canonical chains to the sink until the sink is
+    /// closed.
+    ///
+    /// Returns an error, which closes the subscription with it, if the state diff of a block can't
+    /// be built, because the subscriber would otherwise miss the block.
+    async fn pipe_state_diffs(
+        self,
+        mut stream: CanonStateNotificationStream,
+        sink: SubscriptionSink,
+    ) -> SubscriptionResult {
+        loop {
+            tokio::select! {
+                _ = sink.closed() => {
+                    // connection dropped
+                    return Ok(())
+                },
+                maybe_notification = stream.next() => {
+                    let Some(notification) = maybe_notification else {
+                        // stream ended
+                        return Ok(())
+                    };
+                    let Some(committed) = notification.committed() else { continue };
+                    for (block_number, block) in committed.blocks() {
+                        let block_number = *block_number;
+                        let block_hash = block.hash;
+                        let res = self
+                            .on_blocking_task(|this| async move {
+                                this.try_state_diff_in_block(block_number)
+                            })
+                            .await;
+                        let state_diff = match res {
+                            Ok(state_diff) => state_diff,
+                            Err(err) => {
+                                debug!(
+                                    target: "rpc::reth",
+                                    %err,
+                                    block_number,
+                                    "Failed to build state diff"
+                                );
+                                return Err(err.into())
+                            }
+                        };
+                        let diff = BlockStateDiff { block_number, block_hash, state_diff };
+                        let msg = SubscriptionMessage::from_json(&diff)?;
+                        if sink.send(msg).await.is_err() {
+                            return Ok(())
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// Sends every change of the canonical chain to the sink until the sink is closed.
+///
+/// Returns an error, which closes the subscription with it, if a notification can't be converted
+/// or if the subscriber fell so far behind that notifications were dropped, because the
+/// subscriber can't follow the chain once it has missed a change.
+async fn pipe_chain_notifications(
+    mut notifications: CanonStateNotifications,
+    sink: SubscriptionSink,
+    include_receipts: bool,
+) -> SubscriptionResult {
+    loop {
+        tokio::select! {
+            _ = sink.closed() => {
+                // connection dropped
+                return Ok(())
+            },
+            notification = notifications.recv() => {
+                let notification = match notification {
+                    Ok(notification) => notification,
+                    // the node is shutting down
+                    Err(RecvError::Closed) => return Ok(()),
+                    Err(RecvError::Lagged(skipped)) => {
+                        debug!(target: "rpc::reth", skipped, "Chain notifications lagged");
+                        return Err(format!(
+                            "subscriber lagged behind, {skipped} chain notifications were dropped"
+                        )
+                        .into())
+                    }
+                };
+                let notification = chain_notification(&notification, include_receipts)?;
+                let msg = SubscriptionMessage::from_json(&notification)?;
+                if sink.send(msg).await.is_err() {
+                    return Ok(())
+                }
+            }
+        }
+    }
+}
+
+/// Converts the [CanonStateNotification] into its RPC representation.
+fn chain_notification(
+    notification: &CanonStateNotification,
+    include_receipts: bool,
+) -> EthResult<ChainNotification> {
+    let notification = match notification {
+        CanonStateNotification::Commit { new } => {
+            ChainNotification::Commit { committed: notified_blocks(new, include_receipts)? }
+        }
+        CanonStateNotification::Reorg { old, new } => ChainNotification::Reorg {
+            reverted: notified_blocks(old, include_receipts)?,
+            committed: notified_blocks(new, include_receipts)?,
+        },
+    };
+    Ok(notification)
+}
+
+/// Returns the blocks of the chain in ascending order.
+fn notified_blocks(chain: &Chain, include_receipts: bool) -> EthResult<Vec<NotifiedBlock>> {
+    chain
+        .blocks_and_receipts()
+        .map(|(block, receipts)| {
+            let receipts = if include_receipts { block_receipts(block, receipts)? } else { None };
+            Ok(NotifiedBlock {
+                number: block.number,
+                hash: block.hash,
+                parent_hash: block.parent_hash,
+                receipts,
+            })
+        })
+        .collect()
+}
+
+/// Builds the RPC receipts of the block, `None` if any receipt of the block was pruned.
+fn block_receipts(
+    block: &SealedBlockWithSenders,
+    receipts: &[Option<Receipt>],
+) -> EthResult<Option<Vec<TransactionReceipt>>> {
+    let Some(receipts) = receipts.iter().cloned().collect::<Option<Vec<_>>>() else {
+        return Ok(None)
+    };
+
+    let mut rpc_receipts = Vec::with_capacity(receipts.len());
+    for (index, (tx, receipt)) in block.body.iter().zip(receipts.iter()).enumerate() {
+        let meta = TransactionMeta {
+            tx_hash: tx.hash,
+            index: index as u64,
+            block_hash: block.hash,
+            block_number: block.number,
+            base_fee: block.base_fee_per_gas,
+            excess_blob_gas: block.excess_blob_gas,
+        };
+        rpc_receipts.push(build_transaction_receipt_with_block_receipts(
+            tx.clone(),
+            meta,
+            receipt.clone(),
+            &receipts,
+            // L1 fees are not part of the notification
+            #[cfg(feature = "optimism")]
+            OptimismTxMeta::default(),
+        )?);
+    }
+    Ok(Some(rpc_receipts))
+}
+
+/// Returns the code of the account, empty if the account has no code.
+fn account_code(state: &dyn StateProvider, account: Option<Account>) -> RethResult<Option<Bytes>> {
+    let Some(account) = account else { return Ok(None) };
+    let code = match account.bytecode_hash {
+        Some(code_hash) => {
+            state.bytecode_by_hash(code_hash)?.map(|code| code.original_bytes()).unwrap_or_default()
+        }
+        None => Bytes::new(),
+    };
+    Ok(Some(code))
+}
+
+/// Returns the [Delta] between the value before and after a block, `None` if the value did not
+/// exist.
+fn delta<T: PartialEq>(before: Option<T>, after: Option<T>) -> Delta<T> {
+    match (before, after) {
+        (None, None) => Delta::Unchanged,
+        (None, Some(to)) => Delta::Added(to),
+        (Some(from), None) => Delta::Removed(from),
+        (Some(from), Some(to)) if from == to => Delta::Unchanged,
+        (Some(from), Some(to)) => Delta::Changed(ChangedType { from, to }),
+    }
 }
 
 #[async_trait]
-impl<Provider> RethApiServer for RethApi<Provider>
+impl<Provider, Events> RethApiServer for RethApi<Provider, Events>
 where
     Provider: BlockReaderIdExt + ChangeSetReader + StateProviderFactory + 'static,
+    Events: CanonStateSubscriptions + 'static,
 {
     /// Handler for `reth_getBalanceChangesInBlock`
     async fn reth_get_balance_changes_in_block(
@@ -95,23 +362,145 @@ where
     ) -> RpcResult<HashMap<Address, U256>> {
         Ok(RethApi::balance_changes_in_block(self, block_id).await?)
     }
+
+    /// Handler for `reth_getStateDiffInBlock`
+    async fn reth_get_state_diff_in_block(&self, block_id: BlockId) -> RpcResult<StateDiff> {
+        Ok(RethApi::state_diff_in_block(self, block_id).await?)
+    }
+
+    /// Handler for `reth_subscribeStateDiffs`
+    async fn reth_subscribe_state_diffs(
+        &self,
+        pending: PendingSubscriptionSink,
+    ) -> SubscriptionResult {
+        let sink = pending.accept().await?;
+        let stream = self.inner.events.canonical_state_stream();
+        // Subscriptions run on their own task, which sends the error of the pipe before closing the
+        // subscription.
+        self.clone().pipe_state_diffs(stream, sink).await
+    }
+
+    /// Handler for `reth_subscribeChainNotifications`
+    async fn reth_subscribe_chain_notifications(
+        &self,
+        pending: PendingSubscriptionSink,
+        include_receipts: Option<bool>,
+    ) -> SubscriptionResult {
+        let sink = pending.accept().await?;
+        let notifications = self.inner.events.subscribe_to_canonical_state();
+        // Subscriptions run on their own task, which sends the error of the pipe before closing the
+        // subscription.
+        pipe_chain_notifications(notifications, sink, include_receipts.unwrap_or_default()).await
+    }
 }
 
-impl<Provider> std::fmt::Debug for RethApi<Provider> {
+impl<Provider, Events> std::fmt::Debug for RethApi<Provider, Events> {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         f.debug_struct("RethApi").finish_non_exhaustive()
     }
 }
 
-impl<Provider> Clone for RethApi<Provider> {
+impl<Provider, Events> Clone for RethApi<Provider, Events> {
     fn clone(&self) -> Self {
         Self { inner: Arc::clone(&self.inner) }
     }
 }
 
-struct RethApiInner<Provider> {
+struct RethApiInner<Provider, Events> {
     /// The provider that can interact with the chain.
     provider: Provider,
     /// The type that can spawn tasks which would otherwise block.
     task_spawner: Box<dyn TaskSpawner>,
+    /// A type that allows to create new event subscriptions.
+    events: Events,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use reth_db::models::AccountBeforeTx;
+    use reth_primitives::BlockNumberOrTag;
+    use reth_provider::test_utils::{
+        ExtendedAccount, MockEthProvider, TestCanonStateSubscriptions,
+    };
+    use reth_tasks::TokioTaskExecutor;
+
+    #[tokio::test]
+    async fn state_diff_in_block() {
+        let provider = MockEthProvider::default();
+        let changed = Address::with_last_byte(1);
+        let storage_only = Address::with_last_byte(2);
+        let created = Address::with_last_byte(3);
+        let slot = B256::with_last_byte(1);
+        let code = Bytes::from_static(&[0x60, 0x00]);
+
+        // the mock state is the state after the block
+        provider.extend_accounts([
+            (
+                changed,
+                ExtendedAccount::new(2, U256::from(5))
+                    .with_bytecode(code.clone())
+                    .extend_storage([(slot, U256::from(2))]),
+            ),
+            (
+                storage_only,
+                ExtendedAccount::new(0, U256::ZERO).extend_storage([(slot, U256::from(3))]),
+            ),
+            (created, ExtendedAccount::new(1, U256::from(1))),
+        ]);
+        let changed_before = Account { nonce: 1, balance: U256::from(10), bytecode_hash: None };
+        provider.add_changesets(
+            1,
+            [
+                AccountBeforeTx { address: changed, info: Some(changed_before) },
+                AccountBeforeTx { address: created, info: None },
+            ],
+            [
+                (changed, StorageEntry { key: slot, value: U256::from(1) }),
+                (storage_only, StorageEntry { key: slot, value: U256::ZERO }),
+            ],
+        );
+
+        let api = RethApi::new(
+            provider,
+            Box::<TokioTaskExecutor>::default(),
+            TestCanonStateSubscriptions::default(),
+        );
+        let state_diff = api.state_diff_in_block(BlockNumberOrTag::Number(1).into()).await.unwrap();
+
+        assert_eq!(state_diff.0.len(), 3);
+        assert_eq!(
+            state_diff.0[&changed],
+            AccountDiff {
+                balance: Delta::Changed(ChangedType { from: U256::from(10), to: U256::from(5) }),
+                nonce: Delta::Changed(ChangedType { from: U64::from(1), to: U64::from(2) }),
+                code: Delta::Changed(ChangedType { from: Bytes::new(), to: code }),
+                storage: BTreeMap::from([(
+                    slot,
+                    Delta::Changed(ChangedType {
+                        from: B256::with_last_byte(1),
+                        to: B256::with_last_byte(2),
+                    }),
+                )]),
+            }
+        );
+        assert_eq!(
+            state_diff.0[&storage_only],
+            AccountDiff {
+                balance: Delta::Unchanged,
+                nonce: Delta::Unchanged,
+                code: Delta::Unchanged,
+                storage: BTreeMap::from([(slot, Delta::Added(B256::with_last_byte(3)))]),
+            }
+        );
+        assert_eq!(
+            state_diff.0[&created],
+            AccountDiff {
+                balance: Delta::Added(U256::from(1)),
+                nonce: Delta::Added(U64::from(1)),
+                code: Delta::Added(Bytes::new()),
+                storage: BTreeMap::new(),
+            }
+        );
+    }
 }
@@ -22,7 +22,7 @@ use reth_rpc_api::TraceApiServer;
 use reth_rpc_types::{
     state::StateOverride,
     trace::{filter::TraceFilter, parity::*, tracerequest::TraceCallRequest},
-    BlockError, BlockOverrides, CallRequest, Index,
+    BlockOverrides, CallRequest, Index,
 };
 use revm::{
     db::{CacheDB, DatabaseCommit},
@@ -47,8 +47,20 @@ impl<Provider, Eth> TraceApi<Provider, Eth> {
     }
 
     /// Create a new instance of the [TraceApi]
-    pub fn new(provider: Provider, eth_api: Eth, blocking_task_guard: BlockingTaskGuard) -> Self {
-        let inner = Arc::new(TraceApiInner { provider, eth_api, blocking_task_guard });
+    ///
+    /// `max_trace_filter_blocks` is the maximum block range a `trace_filter` request may span.
+    pub fn new(
+        provider: Provider,
+        eth_api: Eth,
+        blocking_task_guard: BlockingTaskGuard,
+        max_trace_filter_blocks: u64,
+    ) -> Self {
+        let inner = Arc::new(TraceApiInner {
+            provider,
+            eth_api,
+            blocking_task_guard,
+            max_trace_filter_blocks,
+        });
         Self { inner }
     }
 
@@ -231,12 +243,15 @@ where
     ///
     /// This is similar to [Self::trace_block] but only returns traces for transactions that match
     /// the filter.
+    ///
+    /// The blocks of the range are traced one after another, tracing stops as soon as enough
+    /// traces have been collected to skip `after` traces and return `count` traces.
     pub async fn trace_filter(
         &self,
         filter: TraceFilter,
     ) -> EthResult<Vec<LocalizedTransactionTrace>> {
         let matcher = filter.matcher();
-        let TraceFilter { from_block, to_block, after: _after, count: _count, .. } = filter;
+        let TraceFilter { from_block, to_block, after, count, .. } = filter;
         let start = from_block.unwrap_or(0);
         let end = if let Some(to_block) = to_block {
             to_block
@@ -244,68 +259,77 @@ where
             self.provider().best_block_number()?
         };
 
-        // ensure that the range is not too large, since we need to fetch all blocks in the range
-        let distance = end.saturating_sub(start);
-        if distance > 100 {
+        if start > end {
             return Err(EthApiError::InvalidParams(
-                "Block range too large; currently limited to 100 blocks".to_string(),
+                "invalid parameters: fromBlock cannot be greater than toBlock".to_string(),
             ))
         }
 
-        // fetch all blocks in that range
-        let blocks = self.provider().block_range(start..=end)?;
+        // ensure that the range is not too large, since we may need to trace all blocks in the
+        // range
+        let max_blocks = self.inner.max_trace_filter_blocks;
+        if end - start > max_blocks {
+            return Err(EthApiError::InvalidParams(format!(
+                "Block range too large; currently limited to {max_blocks} blocks"
+            )))
+        }
+
+        let after = after.unwrap_or_default() as usize;
+        // number of traces after which the remaining blocks of the range don't need to be traced
+        let limit = count.map(|count| after.saturating_add(count as usize));
+
+        let mut all_traces = Vec::new();
+        for block_number in start..=end {
+            let Some(block) =
+                self.inner.eth_api.block_by_id_with_senders(block_number.into()).await?
+            else {
+                break
+            };
 
-        // find relevant blocks to trace
-        let mut target_blocks = Vec::new();
-        for block in blocks {
+            // find the transactions of the block to trace, senders are read from the database
             let mut transaction_indices = HashSet::new();
-            let mut highest_matching_index = 0;
-            for (tx_idx, tx) in block.body.iter().enumerate() {
-                let from = tx.recover_signer().ok_or(BlockError::InvalidSignature)?;
-                let to = tx.to();
-                if matcher.matches(from, to) {
-                    let idx = tx_idx as u64;
+            let mut highest_matching_index = None;
+            for (idx, (tx, from)) in block.body.iter().zip(block.senders.iter()).enumerate() {
+                if matcher.matches(*from, tx.to()) {
+                    let idx = idx as u64;
                     transaction_indices.insert(idx);
-                    highest_matching_index = idx;
+                    highest_matching_index = Some(idx);
                 }
             }
-            if !transaction_indices.is_empty() {
-                target_blocks.push((block.number, transaction_indices, highest_matching_index));
-            }
-        }
-
-        // trace all relevant blocks
-        let mut block_traces = Vec::with_capacity(target_blocks.len());
-        for (num, indices, highest_idx) in target_blocks {
-            let traces = self.inner.eth_api.trace_block_until(
-                num.into(),
-                Some(highest_idx),
-                TracingInspectorConfig::default_parity(),
-                move |tx_info, inspector, res, _, _| {
-                    if let Some(idx) = tx_info.index {
-                        if !indices.contains(&idx) {
-                            // only record traces for relevant transactions
-                            return Ok(None)
+            let Some(highest_idx) = highest_matching_index else { continue };
+
+            let traces = self
+                .inner
+                .eth_api
+                .trace_block_until(
+                    block_number.into(),
+                    // the highest matching transaction needs to be executed as well
+                    Some(highest_idx + 1),
+                    TracingInspectorConfig::default_parity(),
+                    move |tx_info, inspector, res, _, _| {
+                        if let Some(idx) = tx_info.index {
+                            if !transaction_indices.contains(&idx) {
+                                // only record traces for relevant transactions
+                                return Ok(None)
+                            }
                         }
-                    }
-                    let traces = inspector
-                        .with_transaction_gas_used(res.gas_used())
-                        .into_parity_builder()
-                        .into_localized_transaction_traces(tx_info);
-                    Ok(Some(traces))
-                },
-            );
-            block_traces.push(traces);
+                        let traces = inspector
+                            .with_transaction_gas_used(res.gas_used())
+                            .into_parity_builder()
+                            .into_localized_transaction_traces(tx_info);
+                        Ok(Some(traces))
+                    },
+                )
+                .await?;
+            all_traces.extend(traces.into_iter().flatten().flatten().flatten());
+
+            if limit.is_some_and(|limit| all_traces.len() >= limit) {
+                break
+            }
         }
 
-        let block_traces = futures::future::try_join_all(block_traces).await?;
-        let all_traces = block_traces
-            .into_iter()
-            .flatten()
-            .flat_map(|traces| traces.into_iter().flatten().flat_map(|traces| traces.into_iter()))
-            .collect();
-
-        Ok(all_traces)
+        let count = count.map_or(usize::MAX, |count| count as usize);
+        Ok(all_traces.into_iter().skip(after).take(count).collect())
     }
 
     /// Returns all traces for the given transaction hash
@@ -498,9 +522,9 @@ where
     ///
     /// This is similar to `eth_getLogs` but for traces.
     ///
-    /// # Limitations
-    /// This currently requires block filter fields, since reth does not have address indices yet.
+    /// The range is limited to the configured maximum number of blocks.
     async fn trace_filter(&self, filter: TraceFilter) -> Result<Vec<LocalizedTransactionTrace>> {
+        let _permit = self.acquire_trace_permit().await;
         Ok(TraceApi::trace_filter(self, filter).await?)
     }
 
@@ -543,6 +567,8 @@ struct TraceApiInner<Provider, Eth> {
     eth_api: Eth,
     // restrict the number of concurrent calls to `trace_*`
     blocking_task_guard: BlockingTaskGuard,
+    /// Maximum block range of a `trace_filter` request
+    max_trace_filter_blocks: u64,
 }
 
 /// Helper to construct a [`LocalizedTransactionTrace`] that describes a reward to the block
@@ -0,0 +1,131 @@
+//! Support for custom `debug_trace*` tracers.
+
+use crate::eth::error::EthResult;
+use parking_lot::RwLock;
+use reth_primitives::B256;
+use reth_provider::StateProviderBox;
+use reth_revm::database::SubState;
+use revm::primitives::EnvWithHandlerCfg;
+use revm_primitives::State;
+use std::{collections::HashMap, fmt, sync::Arc};
+
+/// A tracer that can be selected by name in the `tracer` field of the geth debug tracing
+/// options.
+///
+/// The result of a custom tracer is an arbitrary JSON value that is returned in the same way as
+/// the result of a JavaScript tracer.
+pub trait CustomTracer: Send + Sync + 'static {
+    /// Executes the transaction of the given environment on top of the database and returns the
+    /// JSON trace and the state changes of the transaction.
+    ///
+    /// The `config` is the `tracerConfig` of the request, `null` if it is not set. Tracers are
+    /// expected to execute the transaction with an inspector of their own, for example with
+    /// [inspect](crate::eth::revm_utils::inspect).
+    ///
+    /// Caution: this is called on a blocking task.
+    fn trace(
+        &self,
+        config: serde_json::Value,
+        env: EnvWithHandlerCfg,
+        db: &mut SubState<StateProviderBox>,
+        context: TransactionContext,
+    ) -> EthResult<(serde_json::Value, State)>;
+}
+
+/// Describes where the traced transaction is located, if it is part of a block.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct TransactionContext {
+    /// Hash of the block the transaction is contained within.
+    pub block_hash: Option<B256>,
+    /// Index of the transaction within the block.
+    pub tx_index: Option<usize>,
+    /// Hash of the transaction.
+    pub tx_hash: Option<B256>,
+}
+
+#[cfg(feature = "js-tracer")]
+impl From<TransactionContext> for revm_inspectors::tracing::js::TransactionContext {
+    fn from(context: TransactionContext) -> Self {
+        let TransactionContext { block_hash, tx_index, tx_hash } = context;
+        Self { block_hash, tx_index, tx_hash }
+    }
+}
+
+/// Registry of the [CustomTracer]s available to the `debug` namespace.
+///
+/// The registry is cheap to clone and all clones share the registered tracers, so tracers can be
+/// registered after the `debug` handlers were created.
+#[derive(Clone, Default)]
+pub struct TracerRegistry {
+    tracers: Arc<RwLock<HashMap<String, Arc<dyn CustomTracer>>>>,
+}
+
+impl TracerRegistry {
+    /// Registers the tracer under the given name, replacing any tracer previously registered
+    /// under that name.
+    ///
+    /// Names of the built-in tracers, like `callTracer`, always select the built-in tracer.
+    pub fn register<T: CustomTracer>(&self, name: impl Into<String>, tracer: T) {
+        self.tracers.write().insert(name.into(), Arc::new(tracer));
+    }
+
+    /// Removes the tracer registered under the given name.
+    ///
+    /// Returns `true` if a tracer was registered under the name.
+    pub fn unregister(&self, name: &str) -> bool {
+        self.tracers.write().remove(name).is_some()
+    }
+
+    /// Returns the tracer registered under the given name.
+    pub fn get(&self, name: &str) -> Option<Arc<dyn CustomTracer>> {
+        self.tracers.read().get(name).cloned()
+    }
+
+    /// Returns the names of all registered tracers.
+    pub fn names(&self) -> Vec<String> {
+        self.tracers.read().keys().cloned().collect()
+    }
+}
+
+impl fmt::Debug for TracerRegistry {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("TracerRegistry").field("tracers", &self.names()).finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Debug)]
+    struct NameTracer(&'static str);
+
+    impl CustomTracer for NameTracer {
+        fn trace(
+            &self,
+            _config: serde_json::Value,
+            _env: EnvWithHandlerCfg,
+            _db: &mut SubState<StateProviderBox>,
+            _context: TransactionContext,
+        ) -> EthResult<(serde_json::Value, State)> {
+            Ok((self.0.into(), State::default()))
+        }
+    }
+
+    #[test]
+    fn registry_is_shared_between_clones() {
+        let registry = TracerRegistry::default();
+        let clone = registry.clone();
+
+        registry.register("nameTracer", NameTracer("first"));
+        assert!(clone.get("nameTracer").is_some());
+        assert!(clone.get("otherTracer").is_none());
+
+        clone.register("nameTracer", NameTracer("second"));
+        assert_eq!(registry.names(), vec!["nameTracer".to_string()]);
+
+        assert!(registry.unregister("nameTracer"));
+        assert!(!clone.unregister("nameTracer"));
+        assert!(clone.get("nameTracer").is_none());
+    }
+}
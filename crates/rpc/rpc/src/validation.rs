@@ -0,0 +1,534 @@
+use crate::result::{internal_rpc_err, invalid_params_rpc_err};
+use async_trait::async_trait;
+use jsonrpsee::{core::RpcResult, types::ErrorObject};
+use reth_interfaces::{blockchain_tree::error::InsertBlockErrorKind, provider::ProviderError};
+use reth_primitives::{
+    constants::MINIMUM_GAS_LIMIT, revm_primitives::AccountInfo, Address, BlockHash, GotExpected,
+    SealedBlockWithSenders, B256, U256,
+};
+use reth_provider::{BlockReader, BlockSource, BlockValidationProvider, BundleStateWithReceipts};
+use reth_rpc_api::BlockSubmissionValidationApiServer;
+use reth_rpc_types::{
+    engine::PayloadError,
+    relay::{BuilderBlockValidationRequest, BuilderBlockValidationRequestV2, SubmitBlockRequest},
+};
+use reth_rpc_types_compat::engine::payload::try_into_sealed_block;
+use reth_tasks::TaskSpawner;
+use std::{future::Future, sync::Arc};
+use tokio::sync::oneshot;
+
+/// The bound divisor of the gas limit, used in update calculations.
+const GAS_LIMIT_BOUND_DIVISOR: u64 = 1024;
+
+/// `flashbots` block submission validation API implementation.
+///
+/// This type validates blocks submitted by builders to a relay: the execution payload is executed
+/// on top of its parent in a scratch state, and the bid is checked against the outcome.
+pub struct ValidationApi<Provider> {
+    inner: Arc<ValidationApiInner<Provider>>,
+}
+
+// === impl ValidationApi ===
+
+impl<Provider> ValidationApi<Provider> {
+    /// The provider that can interact with the chain.
+    pub fn provider(&self) -> &Provider {
+        &self.inner.provider
+    }
+
+    /// Create a new instance of the [ValidationApi]
+    pub fn new(provider: Provider, task_spawner: Box<dyn TaskSpawner>) -> Self {
+        let inner = Arc::new(ValidationApiInner { provider, task_spawner });
+        Self { inner }
+    }
+}
+
+impl<Provider> ValidationApi<Provider>
+where
+    Provider: BlockReader + BlockValidationProvider + 'static,
+{
+    /// Executes the future on a new blocking task.
+    async fn on_blocking_task<C, F, R>(&self, c: C) -> ValidationApiResult<R>
+    where
+        C: FnOnce(Self) -> F,
+        F: Future<Output = ValidationApiResult<R>> + Send + 'static,
+        R: Send + 'static,
+    {
+        let (tx, rx) = oneshot::channel();
+        let this = self.clone();
+        let f = c(this);
+        self.inner.task_spawner.spawn_blocking(Box::pin(async move {
+            let res = f.await;
+            let _ = tx.send(res);
+        }));
+        rx.await.map_err(|_| ValidationApiError::InternalBlockingTaskError)?
+    }
+
+    /// Validates the given block submission.
+    ///
+    /// If `withdrawals_root` is set, the withdrawals root of the submitted block must match it.
+    pub async fn validate_builder_submission(
+        &self,
+        request: SubmitBlockRequest,
+        registered_gas_limit: u64,
+        withdrawals_root: Option<B256>,
+    ) -> ValidationApiResult<()> {
+        self.on_blocking_task(|this| async move {
+            this.try_validate_builder_submission(request, registered_gas_limit, withdrawals_root)
+        })
+        .await
+    }
+
+    fn try_validate_builder_submission(
+        &self,
+        request: SubmitBlockRequest,
+        registered_gas_limit: u64,
+        withdrawals_root: Option<B256>,
+    ) -> ValidationApiResult<()> {
+        let SubmitBlockRequest { message, execution_payload, .. } = request;
+        let block = try_into_sealed_block(execution_payload, None)?;
+
+        // the bid must describe the submitted block
+        if message.block_hash != block.hash() {
+            return Err(ValidationApiError::BlockHashMismatch(GotExpected {
+                got: block.hash(),
+                expected: message.block_hash,
+            }))
+        }
+        if message.parent_hash != block.parent_hash {
+            return Err(ValidationApiError::ParentHashMismatch(GotExpected {
+                got: block.parent_hash,
+                expected: message.parent_hash,
+            }))
+        }
+        if message.gas_limit != block.gas_limit {
+            return Err(ValidationApiError::GasLimitMismatch(GotExpected {
+                got: block.gas_limit,
+                expected: message.gas_limit,
+            }))
+        }
+        if message.gas_used != block.gas_used {
+            return Err(ValidationApiError::GasUsedMismatch(GotExpected {
+                got: block.gas_used,
+                expected: message.gas_used,
+            }))
+        }
+        if let Some(expected) = withdrawals_root {
+            if block.withdrawals_root != Some(expected) {
+                return Err(ValidationApiError::WithdrawalsRootMismatch(GotExpected {
+                    got: block.withdrawals_root.unwrap_or_default(),
+                    expected,
+                }))
+            }
+        }
+
+        // the gas limit must move towards the gas limit registered by the proposer
+        let parent = self
+            .provider()
+            .find_block_by_hash(block.parent_hash, BlockSource::Any)?
+            .ok_or(ValidationApiError::UnknownParent(block.parent_hash))?;
+        let expected_gas_limit = calc_gas_limit(parent.gas_limit, registered_gas_limit);
+        if block.gas_limit != expected_gas_limit {
+            return Err(ValidationApiError::InvalidGasLimit(GotExpected {
+                got: block.gas_limit,
+                expected: expected_gas_limit,
+            }))
+        }
+
+        let block = block
+            .try_seal_with_senders()
+            .map_err(|_| ValidationApiError::InvalidBlock(InsertBlockErrorKind::SenderRecovery))?;
+        let state = self
+            .provider()
+            .validate_block_detached(block.clone())
+            .map_err(ValidationApiError::InvalidBlock)?;
+
+        verify_proposer_payment(&block, &state, message.proposer_fee_recipient, message.value)
+    }
+}
+
+#[async_trait]
+impl<Provider> BlockSubmissionValidationApiServer for ValidationApi<Provider>
+where
+    Provider: BlockReader + BlockValidationProvider + 'static,
+{
+    /// Handler for `flashbots_validateBuilderSubmissionV1`
+    async fn validate_builder_submission_v1(
+        &self,
+        request: BuilderBlockValidationRequest,
+    ) -> RpcResult<()> {
+        Ok(ValidationApi::validate_builder_submission(
+            self,
+            request.request,
+            request.registered_gas_limit,
+            None,
+        )
+        .await?)
+    }
+
+    /// Handler for `flashbots_validateBuilderSubmissionV2`
+    async fn validate_builder_submission_v2(
+        &self,
+        request: BuilderBlockValidationRequestV2,
+    ) -> RpcResult<()> {
+        Ok(ValidationApi::validate_builder_submission(
+            self,
+            request.request,
+            request.registered_gas_limit,
+            Some(request.withdrawals_root),
+        )
+        .await?)
+    }
+}
+
+impl<Provider> std::fmt::Debug for ValidationApi<Provider> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ValidationApi").finish_non_exhaustive()
+    }
+}
+
+impl<Provider> Clone for ValidationApi<Provider> {
+    fn clone(&self) -> Self {
+        Self { inner: Arc::clone(&self.inner) }
+    }
+}
+
+struct ValidationApiInner<Provider> {
+    /// The provider that can interact with the chain.
+    provider: Provider,
+    /// The type that can spawn tasks which would otherwise block.
+    task_spawner: Box<dyn TaskSpawner>,
+}
+
+/// Returns the gas limit of a block on top of a parent with the given gas limit that moves as
+/// close as allowed towards the desired gas limit.
+///
+/// See also <https://github.com/ethereum/go-ethereum/blob/v1.13.10/core/block_validator.go#L135-L161>
+fn calc_gas_limit(parent_gas_limit: u64, desired_gas_limit: u64) -> u64 {
+    let delta = (parent_gas_limit / GAS_LIMIT_BOUND_DIVISOR).saturating_sub(1);
+    let desired_gas_limit = desired_gas_limit.max(MINIMUM_GAS_LIMIT);
+    if parent_gas_limit < desired_gas_limit {
+        (parent_gas_limit + delta).min(desired_gas_limit)
+    } else {
+        parent_gas_limit.saturating_sub(delta).max(desired_gas_limit)
+    }
+}
+
+/// Verifies that the executed block pays the value of the bid to the fee recipient of the proposer.
+///
+/// The proposer is paid either by the balance increase of the fee recipient over the block, or by
+/// a transfer from the beneficiary of the block to the fee recipient in the last transaction of the
+/// block. In the latter case, the balance of the fee recipient must still have increased by the
+/// value of the bid once the funds spent by its own transactions in the block are added back.
+fn verify_proposer_payment(
+    block: &SealedBlockWithSenders,
+    state: &BundleStateWithReceipts,
+    fee_recipient: Address,
+    value: U256,
+) -> ValidationApiResult<()> {
+    let (balance_before, balance_after) = state
+        .state()
+        .account(&fee_recipient)
+        .map(|account| {
+            let balance = |info: Option<&AccountInfo>| info.map_or(U256::ZERO, |info| info.balance);
+            (balance(account.original_info.as_ref()), balance(account.info.as_ref()))
+        })
+        .unwrap_or_default();
+    if balance_after.saturating_sub(balance_before) >= value {
+        return Ok(())
+    }
+
+    let receipts = state.receipts_by_block(block.number);
+    let (Some((sender, payment_tx)), Some(Some(receipt))) =
+        (block.transactions_with_sender().last(), receipts.last())
+    else {
+        return Err(ValidationApiError::MissingProposerPayment)
+    };
+    if !receipt.success {
+        return Err(ValidationApiError::ProposerPaymentReverted)
+    }
+    if payment_tx.to() != Some(fee_recipient) {
+        return Err(ValidationApiError::InvalidProposerPaymentRecipient(GotExpected {
+            got: payment_tx.to().unwrap_or_default(),
+            expected: fee_recipient,
+        }))
+    }
+    if *sender == fee_recipient {
+        return Err(ValidationApiError::ProposerPaymentFromFeeRecipient)
+    }
+    if *sender != block.beneficiary {
+        return Err(ValidationApiError::InvalidProposerPaymentSender(GotExpected {
+            got: *sender,
+            expected: block.beneficiary,
+        }))
+    }
+    let payment = U256::from(payment_tx.value());
+    if payment != value {
+        return Err(ValidationApiError::InvalidProposerPaymentValue(GotExpected {
+            got: payment,
+            expected: value,
+        }))
+    }
+
+    // Receipts of detached validations are never pruned.
+    let mut spent = U256::ZERO;
+    let mut cumulative_gas_used = 0;
+    for ((sender, transaction), receipt) in
+        block.transactions_with_sender().zip(receipts.iter().flatten())
+    {
+        if *sender == fee_recipient {
+            let gas_used = receipt.cumulative_gas_used - cumulative_gas_used;
+            spent += U256::from(gas_used) *
+                U256::from(transaction.effective_gas_price(block.base_fee_per_gas));
+            if let Some(blob_gas_used) = transaction.blob_gas_used() {
+                spent +=
+                    U256::from(blob_gas_used) * U256::from(block.blob_fee().unwrap_or_default());
+            }
+            if receipt.success {
+                spent += U256::from(transaction.value());
+            }
+        }
+        cumulative_gas_used = receipt.cumulative_gas_used;
+    }
+    let received = (balance_after + spent).saturating_sub(balance_before);
+    if received < value {
+        return Err(ValidationApiError::InsufficientProposerPayment(GotExpected {
+            got: received,
+            expected: value,
+        }))
+    }
+
+    Ok(())
+}
+
+/// Result alias
+pub type ValidationApiResult<T> = Result<T, ValidationApiError>;
+
+/// Errors that can occur when validating a block submission.
+#[derive(Debug, thiserror::Error)]
+pub enum ValidationApiError {
+    /// The execution payload could not be converted into a block.
+    #[error(transparent)]
+    InvalidPayload(#[from] PayloadError),
+    /// The block hash of the bid does not match the submitted block.
+    #[error("block hash mismatch: {0}")]
+    BlockHashMismatch(GotExpected<BlockHash>),
+    /// The parent hash of the bid does not match the submitted block.
+    #[error("parent hash mismatch: {0}")]
+    ParentHashMismatch(GotExpected<BlockHash>),
+    /// The gas limit of the bid does not match the submitted block.
+    #[error("gas limit mismatch: {0}")]
+    GasLimitMismatch(GotExpected<u64>),
+    /// The gas used of the bid does not match the submitted block.
+    #[error("gas used mismatch: {0}")]
+    GasUsedMismatch(GotExpected<u64>),
+    /// The withdrawals root of the request does not match the submitted block.
+    #[error("withdrawals root mismatch: {0}")]
+    WithdrawalsRootMismatch(GotExpected<B256>),
+    /// The parent of the submitted block is unknown.
+    #[error("unknown parent block {0}")]
+    UnknownParent(BlockHash),
+    /// The gas limit of the submitted block does not respect the registered gas limit.
+    #[error("incorrect gas limit set: {0}")]
+    InvalidGasLimit(GotExpected<u64>),
+    /// The submitted block failed validation or execution.
+    #[error(transparent)]
+    InvalidBlock(InsertBlockErrorKind),
+    /// The submitted block does not pay the proposer.
+    #[error("missing proposer payment")]
+    MissingProposerPayment,
+    /// The proposer payment transaction reverted.
+    #[error("proposer payment transaction reverted")]
+    ProposerPaymentReverted,
+    /// The proposer payment transaction is not sent to the fee recipient.
+    #[error("proposer payment recipient mismatch: {0}")]
+    InvalidProposerPaymentRecipient(GotExpected<Address>),
+    /// The proposer payment transaction is sent by the fee recipient itself.
+    #[error("proposer payment is sent by the fee recipient")]
+    ProposerPaymentFromFeeRecipient,
+    /// The proposer payment transaction is not sent by the beneficiary of the block.
+    #[error("proposer payment sender mismatch: {0}")]
+    InvalidProposerPaymentSender(GotExpected<Address>),
+    /// The proposer payment transaction does not transfer the value of the bid.
+    #[error("proposer payment value mismatch: {0}")]
+    InvalidProposerPaymentValue(GotExpected<U256>),
+    /// The balance of the fee recipient did not increase by the value of the bid.
+    #[error("proposer payment balance mismatch: {0}")]
+    InsufficientProposerPayment(GotExpected<U256>),
+    /// Error thrown when a database or provider operation failed.
+    #[error(transparent)]
+    Provider(#[from] ProviderError),
+    /// Error thrown when a spawned blocking task failed to deliver an anticipated response.
+    #[error("internal blocking task error")]
+    InternalBlockingTaskError,
+}
+
+impl From<ValidationApiError> for ErrorObject<'static> {
+    fn from(error: ValidationApiError) -> Self {
+        match error {
+            ValidationApiError::Provider(_) | ValidationApiError::InternalBlockingTaskError => {
+                internal_rpc_err(error.to_string())
+            }
+            ValidationApiError::InvalidBlock(ref err) if !err.is_invalid_block() => {
+                internal_rpc_err(error.to_string())
+            }
+            err => invalid_params_rpc_err(err.to_string()),
+        }
+    }
+}
+
+impl From<ValidationApiError> for jsonrpsee::core::Error {
+    fn from(error: ValidationApiError) -> Self {
+        jsonrpsee::core::Error::Call(error.into())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use assert_matches::assert_matches;
+    use reth_primitives::{
+        Account, Header, Receipt, Receipts, SealedBlock, Signature, Transaction, TransactionKind,
+        TransactionSigned, TxLegacy,
+    };
+    use std::collections::HashMap;
+
+    /// Value of the bid.
+    const VALUE: u64 = 100;
+    /// Gas price of all transactions.
+    const GAS_PRICE: u64 = 7;
+    /// Gas used by all transactions.
+    const GAS_USED: u64 = 21_000;
+
+    fn fee_recipient() -> Address {
+        Address::with_last_byte(1)
+    }
+
+    fn beneficiary() -> Address {
+        Address::with_last_byte(2)
+    }
+
+    fn transfer(to: Address, value: u64) -> TransactionSigned {
+        TransactionSigned::from_transaction_and_signature(
+            Transaction::Legacy(TxLegacy {
+                gas_price: GAS_PRICE as u128,
+                gas_limit: GAS_USED,
+                to: TransactionKind::Call(to),
+                value: U256::from(value).into(),
+                ..Default::default()
+            }),
+            Signature::default(),
+        )
+    }
+
+    /// Verifies the payment of a block with the given transactions, their senders and whether they
+    /// succeeded, in which the balance of the fee recipient changed from `before` to `after`.
+    fn verify(
+        transactions: Vec<(Address, TransactionSigned, bool)>,
+        (before, after): (u64, u64),
+    ) -> ValidationApiResult<()> {
+        let mut senders = Vec::new();
+        let mut body = Vec::new();
+        let mut receipts = Vec::new();
+        for (sender, transaction, success) in transactions {
+            senders.push(sender);
+            body.push(transaction);
+            receipts.push(Some(Receipt {
+                success,
+                cumulative_gas_used: GAS_USED * receipts.len() as u64 + GAS_USED,
+                ..Default::default()
+            }));
+        }
+        let header = Header {
+            number: 1,
+            beneficiary: beneficiary(),
+            base_fee_per_gas: Some(GAS_PRICE),
+            ..Default::default()
+        };
+        let block = SealedBlockWithSenders::new(
+            SealedBlock { header: header.seal_slow(), body, ommers: Vec::new(), withdrawals: None },
+            senders,
+        )
+        .unwrap();
+
+        let account =
+            |balance| Some(Account { balance: U256::from(balance), ..Default::default() });
+        let state = BundleStateWithReceipts::new_init(
+            HashMap::from([(fee_recipient(), (account(before), account(after), HashMap::new()))]),
+            HashMap::new(),
+            Vec::new(),
+            Receipts::from_vec(vec![receipts]),
+            1,
+        );
+
+        verify_proposer_payment(&block, &state, fee_recipient(), U256::from(VALUE))
+    }
+
+    #[test]
+    fn proposer_payment_by_balance_increase() {
+        assert_matches!(verify(Vec::new(), (1_000, 1_000 + VALUE)), Ok(()));
+        assert_matches!(
+            verify(Vec::new(), (1_000, 1_000 + VALUE - 1)),
+            Err(ValidationApiError::MissingProposerPayment)
+        );
+    }
+
+    #[test]
+    fn proposer_payment_by_last_transaction() {
+        let fee = GAS_USED * GAS_PRICE;
+        let before = 1_000_000;
+        // the fee recipient spends some of its balance in the block before it is paid
+        let spend = (fee_recipient(), transfer(Address::with_last_byte(3), 5), true);
+        let payment = (beneficiary(), transfer(fee_recipient(), VALUE), true);
+        let after = before - 5 - fee + VALUE;
+
+        assert_matches!(verify(vec![spend.clone(), payment.clone()], (before, after)), Ok(()));
+
+        // the balance of the fee recipient decreased by more than it spent
+        assert_matches!(
+            verify(vec![spend.clone(), payment.clone()], (before, after - 1)),
+            Err(ValidationApiError::InsufficientProposerPayment(GotExpected { got, .. }))
+                if got == U256::from(VALUE - 1)
+        );
+
+        // the payment reverted
+        let reverted = (payment.0, payment.1.clone(), false);
+        assert_matches!(
+            verify(vec![spend.clone(), reverted], (before, after)),
+            Err(ValidationApiError::ProposerPaymentReverted)
+        );
+
+        // the payment is not sent by the beneficiary of the block
+        let other_sender = (Address::with_last_byte(3), payment.1.clone(), true);
+        assert_matches!(
+            verify(vec![spend.clone(), other_sender], (before, after)),
+            Err(ValidationApiError::InvalidProposerPaymentSender(_))
+        );
+
+        // the fee recipient pays itself
+        let self_payment = (fee_recipient(), payment.1.clone(), true);
+        assert_matches!(
+            verify(vec![spend.clone(), self_payment], (before, before - 5 - 2 * fee)),
+            Err(ValidationApiError::ProposerPaymentFromFeeRecipient)
+        );
+
+        // the payment transfers less than the value of the bid
+        let underpayment = (beneficiary(), transfer(fee_recipient(), VALUE - 1), true);
+        assert_matches!(
+            verify(vec![spend, underpayment], (before, after - 1)),
+            Err(ValidationApiError::InvalidProposerPaymentValue(_))
+        );
+    }
+
+    #[test]
+    fn gas_limit_moves_towards_registered_limit() {
+        let parent_gas_limit = 30_000_000;
+        let delta = parent_gas_limit / GAS_LIMIT_BOUND_DIVISOR - 1;
+
+        assert_eq!(calc_gas_limit(parent_gas_limit, parent_gas_limit), parent_gas_limit);
+        assert_eq!(calc_gas_limit(parent_gas_limit, 36_000_000), parent_gas_limit + delta);
+        assert_eq!(calc_gas_limit(parent_gas_limit, 24_000_000), parent_gas_limit - delta);
+        assert_eq!(calc_gas_limit(parent_gas_limit, parent_gas_limit + 1), parent_gas_limit + 1);
+        assert_eq!(calc_gas_limit(parent_gas_limit, 0), parent_gas_limit - delta);
+    }
+}
@@ -0,0 +1,660 @@
+//! Export and import of snapshot bundles, which let a node start from the snapshots of another
+//! node instead of downloading and snapshotting the covered block range itself.
+
+use crate::SnapshotterError;
+use reth_consensus_common::validation::validate_block_standalone;
+use reth_db::{
+    cursor::DbCursorRW,
+    database::Database,
+    models::{StoredBlockBodyIndices, StoredBlockOmmers, StoredBlockWithdrawals},
+    snapshot::iter_snapshots,
+    table::{Compress, Decompress},
+    tables,
+    transaction::{DbTx, DbTxMut},
+};
+use reth_interfaces::provider::ProviderResult;
+use reth_nippy_jar::{ColumnResult, NippyJar, NippyJarCursor};
+use reth_primitives::{
+    fs,
+    snapshot::{
+        HighestSnapshots, ManifestEntry, ManifestVerification, SegmentManifest, SnapshotBundle,
+    },
+    stage::{StageCheckpoint, StageId},
+    BlockNumber, SealedBlock, SnapshotSegment, TransactionSignedNoHash,
+};
+use reth_provider::{
+    providers::SnapshotProvider, BlockHashReader, BlockNumReader, ChainSpecProvider,
+    DatabaseProviderRO, HeaderProvider, ProviderError, ProviderFactory, StageCheckpointReader,
+    StageCheckpointWriter, TransactionsProvider, TransactionsProviderExt,
+};
+use std::{ops::RangeInclusive, path::Path};
+use tracing::info;
+
+/// Segments that are exported into a [`SnapshotBundle`]. Headers and transactions are required,
+/// since the importing node rebuilds the blocks of the bundle from them.
+const BUNDLE_SEGMENTS: [SnapshotSegment; 3] =
+    [SnapshotSegment::Headers, SnapshotSegment::Transactions, SnapshotSegment::Receipts];
+
+/// Number of columns of the blocks jar: body indices, ommers and withdrawals.
+const BLOCKS_JAR_COLUMNS: usize = 3;
+
+/// Number of blocks that are read from the snapshots at once during import.
+const IMPORT_BATCH_SIZE: u64 = 10_000;
+
+/// Exports the snapshots of the bundle segments into a self-describing [`SnapshotBundle`] in the
+/// `bundle_path` directory.
+///
+/// The snapshots are copied alongside their manifests. The body indices, ommers and withdrawals of
+/// the bundled blocks are read from the database, since they aren't part of any snapshot.
+pub fn export_bundle<DB: Database>(
+    provider_factory: &ProviderFactory<DB>,
+    snapshots_path: impl AsRef<Path>,
+    bundle_path: impl AsRef<Path>,
+) -> Result<SnapshotBundle, SnapshotterError> {
+    let (snapshots_path, bundle_path) = (snapshots_path.as_ref(), bundle_path.as_ref());
+    fs::create_dir_all(bundle_path)?;
+
+    let mut segments = Vec::new();
+    let mut highest_snapshots = HighestSnapshots::default();
+    for segment in BUNDLE_SEGMENTS {
+        let Some(manifest) = SegmentManifest::load(snapshots_path, segment)? else { continue };
+        if let Some(corruption) =
+            manifest.verify(snapshots_path, ManifestVerification::Fast)?.into_iter().next()
+        {
+            return Err(ProviderError::from(corruption).into())
+        }
+
+        for entry in &manifest.snapshots {
+            for name in entry.files.keys() {
+                fs::copy(snapshots_path.join(name), bundle_path.join(name))?;
+            }
+        }
+        manifest.save(bundle_path)?;
+
+        *highest_snapshots.as_mut(segment) = manifest.highest_block();
+        segments.push(segment);
+    }
+
+    let tip_number = highest_snapshots
+        .headers
+        .zip(highest_snapshots.transactions)
+        .map(|(headers, transactions)| headers.min(transactions))
+        .ok_or_else(|| {
+            SnapshotterError::InvalidBundle(
+                "headers and transactions snapshots are required".to_string(),
+            )
+        })?;
+
+    let provider = provider_factory.provider()?;
+    let tip_hash = provider
+        .block_hash(tip_number)?
+        .ok_or_else(|| ProviderError::HeaderNotFound(tip_number.into()))?;
+    let tx_range = provider.transaction_range_by_block_range(0..=tip_number)?;
+
+    let mut jar = NippyJar::new_without_header(
+        BLOCKS_JAR_COLUMNS,
+        &bundle_path.join(SnapshotBundle::blocks_filename(tip_number)),
+    );
+    let columns = (0..BLOCKS_JAR_COLUMNS)
+        .map(|column| {
+            let provider = &provider;
+            (0..=tip_number).map(move |number| -> ColumnResult<Vec<u8>> {
+                Ok(block_column(provider, column, number)?)
+            })
+        })
+        .collect::<Vec<_>>();
+    jar.freeze(columns, tip_number + 1)?;
+
+    let chain_spec = provider_factory.chain_spec();
+    let bundle = SnapshotBundle {
+        chain_id: chain_spec.chain.id(),
+        genesis_hash: chain_spec.genesis_hash(),
+        tip_number,
+        tip_hash,
+        segments,
+        highest_snapshots,
+        blocks: ManifestEntry::new(
+            0..=tip_number,
+            tx_range,
+            [
+                jar.data_path().to_path_buf(),
+                jar.index_path(),
+                jar.offsets_path(),
+                jar.config_path(),
+            ],
+        )?,
+    };
+    bundle.save(bundle_path)?;
+
+    Ok(bundle)
+}
+
+/// Returns the compressed value of the given blocks jar column for a block.
+fn block_column<DB: Database>(
+    provider: &DatabaseProviderRO<DB>,
+    column: usize,
+    number: BlockNumber,
+) -> ProviderResult<Vec<u8>> {
+    let tx = provider.tx_ref();
+    Ok(match column {
+        0 => tx
+            .get::<tables::BlockBodyIndices>(number)?
+            .ok_or(ProviderError::BlockBodyIndicesNotFound(number))?
+            .compress()
+            .into(),
+        1 => tx.get::<tables::BlockOmmers>(number)?.unwrap_or_default().compress().into(),
+        _ => tx.get::<tables::BlockWithdrawals>(number)?.unwrap_or_default().compress().into(),
+    })
+}
+
+/// Imports the [`SnapshotBundle`] in the `bundle_path` directory.
+///
+/// The bundle is validated against the chain spec, its manifests and the hashes of its headers.
+/// Then the headers and bodies of the bundled blocks are validated against each other and written
+/// to the database, the `Headers`, `TotalDifficulty` and `Bodies` stage checkpoints are set to the
+/// bundle tip, and the snapshots are moved to `snapshots_path`. This way, the pipeline continues
+/// from the bundle tip without downloading the bundled blocks again.
+///
+/// The database is expected to hold the genesis block only, unless a previous import of the same
+/// bundle was interrupted after writing the blocks. In that case, the import only finishes moving
+/// the snapshots.
+pub fn import_bundle<DB: Database>(
+    provider_factory: &ProviderFactory<DB>,
+    bundle_path: impl AsRef<Path>,
+    snapshots_path: impl AsRef<Path>,
+) -> Result<SnapshotBundle, SnapshotterError> {
+    let (bundle_path, snapshots_path) = (bundle_path.as_ref(), snapshots_path.as_ref());
+    let bundle = SnapshotBundle::load(bundle_path)?;
+
+    validate_chain(provider_factory, &bundle)?;
+
+    if blocks_written(provider_factory, &bundle)? {
+        info!(target: "snapshot", tip = bundle.tip_number, "Bundled blocks already written to database, resuming import");
+    } else {
+        validate_bundle(provider_factory, &bundle, bundle_path, snapshots_path)?;
+
+        let snapshot_provider = SnapshotProvider::new(bundle_path)?;
+        validate_headers(&snapshot_provider, &bundle)?;
+        info!(target: "snapshot", tip = bundle.tip_number, "Snapshot bundle validated");
+
+        write_blocks(provider_factory, &snapshot_provider, &bundle, bundle_path)?;
+        drop(snapshot_provider);
+        info!(target: "snapshot", tip = bundle.tip_number, "Bundled blocks written to database");
+    }
+
+    // The manifests stay in the bundle, so that an interrupted import can be resumed from them.
+    for segment in &bundle.segments {
+        let manifest = SegmentManifest::load(bundle_path, *segment)?
+            .ok_or_else(|| missing_manifest(*segment))?;
+        for entry in &manifest.snapshots {
+            for name in entry.files.keys() {
+                move_file(&bundle_path.join(name), &snapshots_path.join(name))?;
+            }
+        }
+        manifest.save(snapshots_path)?;
+    }
+
+    Ok(bundle)
+}
+
+/// Checks the bundle against the chain spec.
+fn validate_chain<DB: Database>(
+    provider_factory: &ProviderFactory<DB>,
+    bundle: &SnapshotBundle,
+) -> Result<(), SnapshotterError> {
+    let chain_spec = provider_factory.chain_spec();
+    if bundle.chain_id != chain_spec.chain.id() || bundle.genesis_hash != chain_spec.genesis_hash()
+    {
+        return Err(SnapshotterError::InvalidBundle(format!(
+            "bundle of chain {} with genesis {} doesn't match the chain spec",
+            bundle.chain_id, bundle.genesis_hash
+        )))
+    }
+    Ok(())
+}
+
+/// Returns `true` if the bundled blocks were written to the database by an import of the bundle,
+/// which ends with the bundle tip being the canonical block at the `Bodies` checkpoint.
+fn blocks_written<DB: Database>(
+    provider_factory: &ProviderFactory<DB>,
+    bundle: &SnapshotBundle,
+) -> ProviderResult<bool> {
+    let provider = provider_factory.provider()?;
+    let bodies = provider.get_stage_checkpoint(StageId::Bodies)?.map(|c| c.block_number);
+    Ok(bundle.tip_number > 0 &&
+        bodies == Some(bundle.tip_number) &&
+        provider.block_hash(bundle.tip_number)? == Some(bundle.tip_hash))
+}
+
+/// Checks the bundle against its manifests, and checks that neither the database nor the snapshots
+/// directory hold data that the bundle would overlap with.
+fn validate_bundle<DB: Database>(
+    provider_factory: &ProviderFactory<DB>,
+    bundle: &SnapshotBundle,
+    bundle_path: &Path,
+    snapshots_path: &Path,
+) -> Result<(), SnapshotterError> {
+    for segment in [SnapshotSegment::Headers, SnapshotSegment::Transactions] {
+        if bundle
+            .highest_snapshots
+            .highest(segment)
+            .map_or(true, |highest| highest < bundle.tip_number)
+        {
+            return Err(SnapshotterError::InvalidBundle(format!(
+                "{segment} snapshots don't reach the bundle tip"
+            )))
+        }
+    }
+
+    if let Some(corruption) =
+        bundle.blocks.verify(bundle_path, ManifestVerification::Full).into_iter().next()
+    {
+        return Err(ProviderError::from(corruption).into())
+    }
+
+    fs::create_dir_all(snapshots_path)?;
+    let local_snapshots = iter_snapshots(snapshots_path)?;
+
+    for segment in &bundle.segments {
+        let manifest = SegmentManifest::load(bundle_path, *segment)?
+            .ok_or_else(|| missing_manifest(*segment))?;
+        if let Some(corruption) =
+            manifest.verify(bundle_path, ManifestVerification::Full)?.into_iter().next()
+        {
+            return Err(ProviderError::from(corruption).into())
+        }
+
+        let is_contiguous = manifest
+            .snapshots
+            .iter()
+            .try_fold(0, |next_block, entry| {
+                (*entry.block_range.start() == next_block).then(|| entry.block_range.end() + 1)
+            })
+            .is_some();
+        if !is_contiguous || manifest.highest_block() != bundle.highest_snapshots.highest(*segment)
+        {
+            return Err(SnapshotterError::InvalidBundle(format!(
+                "{segment} snapshots are not contiguous from genesis to the recorded highest block"
+            )))
+        }
+
+        if local_snapshots.contains_key(segment) ||
+            SegmentManifest::load(snapshots_path, *segment)?.is_some()
+        {
+            return Err(SnapshotterError::InvalidBundle(format!(
+                "{segment} snapshots already exist in {snapshots_path:?}"
+            )))
+        }
+    }
+
+    if provider_factory.provider()?.last_block_number()? != 0 {
+        return Err(SnapshotterError::InvalidBundle(
+            "database already holds blocks after genesis".to_string(),
+        ))
+    }
+
+    Ok(())
+}
+
+/// Checks that the bundled headers hash to their recorded hashes and form a chain from the
+/// genesis block to the bundle tip.
+fn validate_headers(
+    snapshot_provider: &SnapshotProvider,
+    bundle: &SnapshotBundle,
+) -> Result<(), SnapshotterError> {
+    let mut next_number = 0;
+    let mut parent_hash = None;
+
+    for range in batches(bundle.tip_number) {
+        for header in snapshot_provider.sealed_headers_while(range, |_| true)? {
+            let expected_parent_hash = parent_hash.unwrap_or_default();
+            if header.number != next_number ||
+                header.header.hash_slow() != header.hash() ||
+                (header.number == 0 && header.hash() != bundle.genesis_hash) ||
+                (header.number > 0 && header.parent_hash != expected_parent_hash)
+            {
+                return Err(SnapshotterError::InvalidBundle(format!(
+                    "header #{next_number} doesn't match the chain of bundled headers"
+                )))
+            }
+
+            next_number += 1;
+            parent_hash = Some(header.hash());
+        }
+    }
+
+    if next_number != bundle.tip_number + 1 || parent_hash != Some(bundle.tip_hash) {
+        return Err(SnapshotterError::InvalidBundle(format!(
+            "bundled headers don't reach the bundle tip #{} ({})",
+            bundle.tip_number, bundle.tip_hash
+        )))
+    }
+
+    Ok(())
+}
+
+/// Writes the headers and bodies of the bundled blocks to the database, as the `Headers`,
+/// `TotalDifficulty` and `Bodies` stages would, and sets the checkpoints of those stages to the
+/// bundle tip.
+///
+/// Like the bodies downloaded by the `Bodies` stage, each body is validated against its header
+/// before it's written.
+fn write_blocks<DB: Database>(
+    provider_factory: &ProviderFactory<DB>,
+    snapshot_provider: &SnapshotProvider,
+    bundle: &SnapshotBundle,
+    bundle_path: &Path,
+) -> Result<(), SnapshotterError> {
+    let jar = NippyJar::load_without_header(
+        &bundle_path.join(SnapshotBundle::blocks_filename(bundle.tip_number)),
+    )?;
+    let mut blocks_cursor = NippyJarCursor::new(&jar)?;
+    let chain_spec = provider_factory.chain_spec();
+
+    let provider = provider_factory.provider_rw()?;
+    let tx = provider.tx_ref();
+    let mut header_cursor = tx.cursor_write::<tables::Headers>()?;
+    let mut canonical_cursor = tx.cursor_write::<tables::CanonicalHeaders>()?;
+    let mut td_cursor = tx.cursor_write::<tables::HeaderTD>()?;
+    let mut block_indices_cursor = tx.cursor_write::<tables::BlockBodyIndices>()?;
+    let mut tx_cursor = tx.cursor_write::<tables::Transactions>()?;
+    let mut tx_block_cursor = tx.cursor_write::<tables::TransactionBlock>()?;
+    let mut ommers_cursor = tx.cursor_write::<tables::BlockOmmers>()?;
+    let mut withdrawals_cursor = tx.cursor_write::<tables::BlockWithdrawals>()?;
+
+    for range in batches(bundle.tip_number) {
+        for header in snapshot_provider.sealed_headers_while(range, |_| true)? {
+            // The genesis block is already part of the database.
+            if header.number == 0 {
+                continue
+            }
+
+            let number = header.number;
+            let td = snapshot_provider
+                .header_td_by_number(number)?
+                .ok_or(ProviderError::TotalDifficultyNotFound(number))?;
+            let (block_indices, ommers, withdrawals) = {
+                let row = blocks_cursor
+                    .row_by_number(number as usize)?
+                    .ok_or(ProviderError::BlockBodyIndicesNotFound(number))?;
+                (
+                    StoredBlockBodyIndices::decompress(row[0])?,
+                    StoredBlockOmmers::decompress(row[1])?,
+                    StoredBlockWithdrawals::decompress(row[2])?,
+                )
+            };
+
+            let transactions = if block_indices.is_empty() {
+                Vec::new()
+            } else {
+                snapshot_provider.transactions_by_tx_range(block_indices.tx_num_range())?
+            };
+            if transactions.len() as u64 != block_indices.tx_count {
+                return Err(
+                    ProviderError::TransactionNotFound(block_indices.last_tx_num().into()).into()
+                )
+            }
+
+            let block = SealedBlock {
+                body: transactions
+                    .iter()
+                    .cloned()
+                    .map(TransactionSignedNoHash::with_hash)
+                    .collect(),
+                ommers: ommers.ommers,
+                withdrawals: header.withdrawals_root.is_some().then_some(withdrawals.withdrawals),
+                header,
+            };
+            validate_block_standalone(&block, &chain_spec).map_err(|err| {
+                SnapshotterError::InvalidBundle(format!(
+                    "body of block #{number} doesn't match its header: {err}"
+                ))
+            })?;
+            let SealedBlock { header, ommers, withdrawals, .. } = block;
+
+            tx.put::<tables::HeaderNumbers>(header.hash(), number)?;
+            canonical_cursor.append(number, header.hash())?;
+            header_cursor.append(number, header.unseal())?;
+            td_cursor.append(number, td.into())?;
+
+            if !transactions.is_empty() {
+                tx_block_cursor.append(block_indices.last_tx_num(), number)?;
+                for (tx_num, transaction) in block_indices.tx_num_range().zip(transactions) {
+                    tx_cursor.append(tx_num, transaction)?;
+                }
+            }
+
+            if !ommers.is_empty() {
+                ommers_cursor.append(number, StoredBlockOmmers { ommers })?;
+            }
+
+            if let Some(withdrawals) = withdrawals.filter(|withdrawals| !withdrawals.is_empty()) {
+                withdrawals_cursor.append(number, StoredBlockWithdrawals { withdrawals })?;
+            }
+
+            block_indices_cursor.append(number, block_indices)?;
+        }
+    }
+
+    for stage_id in [StageId::Headers, StageId::TotalDifficulty, StageId::Bodies] {
+        provider.save_stage_checkpoint(stage_id, StageCheckpoint::new(bundle.tip_number))?;
+    }
+    provider.commit()?;
+
+    Ok(())
+}
+
+/// Splits the block range from genesis to the tip into batches of [`IMPORT_BATCH_SIZE`] blocks.
+fn batches(tip_number: BlockNumber) -> impl Iterator<Item = RangeInclusive<BlockNumber>> {
+    (0..=tip_number)
+        .step_by(IMPORT_BATCH_SIZE as usize)
+        .map(move |start| start..=(start + IMPORT_BATCH_SIZE - 1).min(tip_number))
+}
+
+/// Moves a file, falling back to copying it if the target is located on another filesystem.
+///
+/// A file that only exists at the target was already moved by an interrupted import.
+fn move_file(from: &Path, to: &Path) -> Result<(), SnapshotterError> {
+    if !from.exists() && to.exists() {
+        return Ok(())
+    }
+    if fs::rename(from, to).is_err() {
+        fs::copy(from, to)?;
+        fs::remove_file(from)?;
+    }
+    Ok(())
+}
+
+/// Returns the error for a bundle segment without manifest.
+fn missing_manifest(segment: SnapshotSegment) -> SnapshotterError {
+    SnapshotterError::InvalidBundle(format!("{segment} manifest is missing"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Snapshotter;
+    use assert_matches::assert_matches;
+    use reth_interfaces::test_utils::{
+        generators,
+        generators::{random_block_range, random_receipt},
+    };
+    use reth_primitives::{ChainSpec, B256, MAINNET};
+    use reth_stages::test_utils::TestStageDB;
+    use std::sync::Arc;
+    use tempfile::TempDir;
+
+    fn factory(
+        db: &TestStageDB,
+        chain_spec: &Arc<ChainSpec>,
+    ) -> ProviderFactory<impl Database + Clone> {
+        ProviderFactory::new(db.factory.db_ref().clone(), chain_spec.clone())
+    }
+
+    /// Snapshots the given blocks on an exporting node and exports them into a bundle.
+    ///
+    /// Returns the chain spec of the blocks, the database of the exporting node and the bundle
+    /// directory.
+    fn export(blocks: &[SealedBlock]) -> (Arc<ChainSpec>, TestStageDB, TempDir) {
+        let mut rng = generators::rng();
+        let chain_spec =
+            Arc::new(ChainSpec { genesis_hash: Some(blocks[0].hash()), ..(**MAINNET).clone() });
+
+        let export_db = TestStageDB::default();
+        export_db.insert_headers_with_td(blocks.iter().map(|block| &block.header)).unwrap();
+        export_db.insert_blocks(blocks.iter(), None).unwrap();
+        export_db
+            .insert_receipts(
+                blocks
+                    .iter()
+                    .flat_map(|block| block.body.iter())
+                    .enumerate()
+                    .map(|(tx_num, tx)| (tx_num as u64, random_receipt(&mut rng, tx, Some(0)))),
+            )
+            .unwrap();
+
+        let snapshots_dir = TempDir::new().unwrap();
+        let mut snapshotter =
+            Snapshotter::new(factory(&export_db, &chain_spec), snapshots_dir.path(), 2).unwrap();
+        let targets = snapshotter.get_snapshot_targets(blocks.len() as u64 - 1).unwrap();
+        snapshotter.run(targets).unwrap();
+
+        let bundle_dir = TempDir::new().unwrap();
+        export_bundle(&factory(&export_db, &chain_spec), snapshots_dir.path(), bundle_dir.path())
+            .unwrap();
+
+        (chain_spec, export_db, bundle_dir)
+    }
+
+    /// Returns the database of a node that only holds the given genesis block.
+    fn genesis_db(genesis: &SealedBlock) -> TestStageDB {
+        let db = TestStageDB::default();
+        db.insert_headers_with_td(std::iter::once(&genesis.header)).unwrap();
+        db.insert_blocks(std::iter::once(genesis), None).unwrap();
+        db
+    }
+
+    #[test]
+    fn export_and_import_bundle() {
+        let mut rng = generators::rng();
+        let blocks = random_block_range(&mut rng, 0..=3, B256::ZERO, 2..3);
+        let (chain_spec, export_db, bundle_dir) = export(&blocks);
+
+        let bundle = SnapshotBundle::load(bundle_dir.path()).unwrap();
+        assert_eq!(bundle.tip_number, 3);
+        assert_eq!(bundle.tip_hash, blocks[3].hash());
+        assert_eq!(
+            bundle.segments,
+            vec![
+                SnapshotSegment::Headers,
+                SnapshotSegment::Transactions,
+                SnapshotSegment::Receipts
+            ]
+        );
+
+        // Import the bundle into a node that only holds the genesis block
+        let import_db = genesis_db(&blocks[0]);
+        let import_snapshots_dir = TempDir::new().unwrap();
+        let imported = import_bundle(
+            &factory(&import_db, &chain_spec),
+            bundle_dir.path(),
+            import_snapshots_dir.path(),
+        )
+        .unwrap();
+        assert_eq!(imported, bundle);
+
+        let provider = factory(&import_db, &chain_spec).provider().unwrap();
+        assert_eq!(provider.last_block_number().unwrap(), 3);
+        assert_eq!(
+            provider.get_stage_checkpoint(StageId::Bodies).unwrap(),
+            Some(StageCheckpoint::new(3))
+        );
+        assert_eq!(
+            import_db.table::<tables::Transactions>().unwrap().len(),
+            export_db.table::<tables::Transactions>().unwrap().len()
+        );
+        assert_eq!(
+            import_db.table::<tables::BlockBodyIndices>().unwrap(),
+            export_db.table::<tables::BlockBodyIndices>().unwrap()
+        );
+        assert!(SegmentManifest::load(import_snapshots_dir.path(), SnapshotSegment::Headers)
+            .unwrap()
+            .is_some());
+
+        // Importing the bundle again has nothing left to do
+        import_bundle(
+            &factory(&import_db, &chain_spec),
+            bundle_dir.path(),
+            import_snapshots_dir.path(),
+        )
+        .unwrap();
+    }
+
+    #[test]
+    fn import_rejects_body_mismatching_header() {
+        let mut rng = generators::rng();
+        let mut blocks = random_block_range(&mut rng, 0..=3, B256::ZERO, 2..3);
+        // The transactions root of the header doesn't cover the body anymore
+        blocks[2].body.pop();
+        let (chain_spec, _, bundle_dir) = export(&blocks);
+
+        let import_db = genesis_db(&blocks[0]);
+        let import_snapshots_dir = TempDir::new().unwrap();
+        assert_matches!(
+            import_bundle(
+                &factory(&import_db, &chain_spec),
+                bundle_dir.path(),
+                import_snapshots_dir.path(),
+            ),
+            Err(SnapshotterError::InvalidBundle(err)) if err.contains("block #2")
+        );
+
+        // Nothing was written
+        let provider = factory(&import_db, &chain_spec).provider().unwrap();
+        assert_eq!(provider.last_block_number().unwrap(), 0);
+        assert!(SegmentManifest::load(import_snapshots_dir.path(), SnapshotSegment::Headers)
+            .unwrap()
+            .is_none());
+    }
+
+    #[test]
+    fn resume_interrupted_import() {
+        let mut rng = generators::rng();
+        let blocks = random_block_range(&mut rng, 0..=3, B256::ZERO, 2..3);
+        let (chain_spec, _, bundle_dir) = export(&blocks);
+        let bundle = SnapshotBundle::load(bundle_dir.path()).unwrap();
+
+        // The import was interrupted after committing the blocks and moving the first file
+        let import_db = genesis_db(&blocks[0]);
+        let import_snapshots_dir = TempDir::new().unwrap();
+        let snapshot_provider = SnapshotProvider::new(bundle_dir.path()).unwrap();
+        write_blocks(
+            &factory(&import_db, &chain_spec),
+            &snapshot_provider,
+            &bundle,
+            bundle_dir.path(),
+        )
+        .unwrap();
+        drop(snapshot_provider);
+
+        let manifest =
+            SegmentManifest::load(bundle_dir.path(), SnapshotSegment::Headers).unwrap().unwrap();
+        let name = manifest.snapshots[0].files.keys().next().unwrap();
+        move_file(&bundle_dir.path().join(name), &import_snapshots_dir.path().join(name)).unwrap();
+
+        import_bundle(
+            &factory(&import_db, &chain_spec),
+            bundle_dir.path(),
+            import_snapshots_dir.path(),
+        )
+        .unwrap();
+
+        let snapshot_provider = SnapshotProvider::new(import_snapshots_dir.path()).unwrap();
+        for segment in bundle.segments {
+            assert_eq!(
+                snapshot_provider.get_highest_snapshot_block(segment),
+                bundle.highest_snapshots.highest(segment)
+            );
+        }
+    }
+}
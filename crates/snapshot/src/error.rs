@@ -1,5 +1,7 @@
 use reth_db::DatabaseError;
 use reth_interfaces::RethError;
+use reth_nippy_jar::NippyJarError;
+use reth_primitives::fs::FsPathError;
 use reth_provider::ProviderError;
 use thiserror::Error;
 
@@ -11,6 +13,10 @@ pub enum SnapshotterError {
     #[error("inconsistent data: {0}")]
     InconsistentData(&'static str),
 
+    /// Snapshot bundle can't be exported or imported.
+    #[error("invalid snapshot bundle: {0}")]
+    InvalidBundle(String),
+
     /// Error related to the interface.
     #[error(transparent)]
     Interface(#[from] RethError),
@@ -22,4 +28,12 @@ pub enum SnapshotterError {
     /// Error related to the provider.
     #[error(transparent)]
     Provider(#[from] ProviderError),
+
+    /// Error related to the filesystem.
+    #[error(transparent)]
+    FsPath(#[from] FsPathError),
+
+    /// Error related to a snapshot file.
+    #[error(transparent)]
+    NippyJar(#[from] NippyJarError),
 }
@@ -0,0 +1,55 @@
+use crate::segments::{snapshot_block_rows, Segment};
+use reth_db::{database::Database, models::BlockAccountChangeSet, table::Compress};
+use reth_interfaces::provider::ProviderResult;
+use reth_primitives::{
+    snapshot::{Compression, Filters, SegmentConfig},
+    BlockNumber, SnapshotSegment,
+};
+use reth_provider::{ChangeSetReader, DatabaseProviderRO};
+use std::{ops::RangeInclusive, path::Path};
+
+/// Snapshot segment responsible for [SnapshotSegment::AccountChangeSets] part of data.
+///
+/// Each row holds all account changes of one block, so the segment has no filters.
+#[derive(Debug)]
+pub struct AccountChangeSets {
+    config: SegmentConfig,
+}
+
+impl AccountChangeSets {
+    /// Creates new instance of [AccountChangeSets] snapshot segment.
+    pub fn new(compression: Compression) -> Self {
+        Self { config: SegmentConfig { compression, filters: Filters::WithoutFilters } }
+    }
+}
+
+impl Default for AccountChangeSets {
+    fn default() -> Self {
+        Self { config: SnapshotSegment::AccountChangeSets.config() }
+    }
+}
+
+impl Segment for AccountChangeSets {
+    fn segment(&self) -> SnapshotSegment {
+        SnapshotSegment::AccountChangeSets
+    }
+
+    fn snapshot<DB: Database>(
+        &self,
+        provider: &DatabaseProviderRO<DB>,
+        directory: impl AsRef<Path>,
+        block_range: RangeInclusive<BlockNumber>,
+    ) -> ProviderResult<()> {
+        snapshot_block_rows(
+            provider,
+            directory,
+            self.segment(),
+            self.config,
+            block_range,
+            |block_number| {
+                Ok(BlockAccountChangeSet(provider.account_block_changeset(block_number)?)
+                    .compress())
+            },
+        )
+    }
+}
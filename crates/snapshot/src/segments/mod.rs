@@ -9,11 +9,17 @@ pub use headers::Headers;
 mod receipts;
 pub use receipts::Receipts;
 
+mod account_changesets;
+pub use account_changesets::AccountChangeSets;
+
+mod storage_changesets;
+pub use storage_changesets::StorageChangeSets;
+
 use reth_db::{
     cursor::DbCursorRO, database::Database, table::Table, transaction::DbTx, RawKey, RawTable,
 };
 use reth_interfaces::provider::ProviderResult;
-use reth_nippy_jar::NippyJar;
+use reth_nippy_jar::{ColumnResult, NippyJar};
 use reth_primitives::{
     snapshot::{
         Compression, Filters, InclusionFilter, PerfectHashingFunction, SegmentConfig, SegmentHeader,
@@ -98,3 +104,40 @@ pub(crate) fn prepare_jar<DB: Database, const COLUMNS: usize>(
 
     Ok(nippy_jar)
 }
+
+/// Snapshots a block based segment with a single column, where each row holds the data of one
+/// block of the range as returned by `block_row`.
+pub(crate) fn snapshot_block_rows<DB: Database>(
+    provider: &DatabaseProviderRO<DB>,
+    directory: impl AsRef<Path>,
+    segment: SnapshotSegment,
+    segment_config: SegmentConfig,
+    block_range: RangeInclusive<BlockNumber>,
+    block_row: impl Fn(BlockNumber) -> ProviderResult<Vec<u8>>,
+) -> ProviderResult<()> {
+    let range_len = block_range.clone().count();
+    let mut jar = prepare_jar::<DB, 1>(
+        provider,
+        directory,
+        segment,
+        segment_config,
+        block_range.clone(),
+        range_len,
+        || {
+            // Most recent rows (at most 1000)
+            Ok([block_range
+                .clone()
+                .rev()
+                .take(range_len.min(1000))
+                .map(&block_row)
+                .collect::<ProviderResult<Vec<_>>>()?])
+        },
+    )?;
+
+    let rows = block_range
+        .clone()
+        .map(|block_number| -> ColumnResult<Vec<u8>> { Ok(block_row(block_number)?) });
+    jar.freeze(vec![rows], range_len as u64)?;
+
+    Ok(())
+}
@@ -0,0 +1,63 @@
+use crate::segments::{snapshot_block_rows, Segment};
+use reth_db::{
+    database::Database,
+    models::{BlockStorageChangeSet, StorageBeforeTx},
+    table::Compress,
+};
+use reth_interfaces::provider::ProviderResult;
+use reth_primitives::{
+    snapshot::{Compression, Filters, SegmentConfig},
+    BlockNumber, SnapshotSegment,
+};
+use reth_provider::{ChangeSetReader, DatabaseProviderRO};
+use std::{ops::RangeInclusive, path::Path};
+
+/// Snapshot segment responsible for [SnapshotSegment::StorageChangeSets] part of data.
+///
+/// Each row holds all storage changes of one block, so the segment has no filters.
+#[derive(Debug)]
+pub struct StorageChangeSets {
+    config: SegmentConfig,
+}
+
+impl StorageChangeSets {
+    /// Creates new instance of [StorageChangeSets] snapshot segment.
+    pub fn new(compression: Compression) -> Self {
+        Self { config: SegmentConfig { compression, filters: Filters::WithoutFilters } }
+    }
+}
+
+impl Default for StorageChangeSets {
+    fn default() -> Self {
+        Self { config: SnapshotSegment::StorageChangeSets.config() }
+    }
+}
+
+impl Segment for StorageChangeSets {
+    fn segment(&self) -> SnapshotSegment {
+        SnapshotSegment::StorageChangeSets
+    }
+
+    fn snapshot<DB: Database>(
+        &self,
+        provider: &DatabaseProviderRO<DB>,
+        directory: impl AsRef<Path>,
+        block_range: RangeInclusive<BlockNumber>,
+    ) -> ProviderResult<()> {
+        snapshot_block_rows(
+            provider,
+            directory,
+            self.segment(),
+            self.config,
+            block_range,
+            |block_number| {
+                let slots = provider
+                    .storage_block_changeset(block_number)?
+                    .into_iter()
+                    .map(|(address, entry)| StorageBeforeTx { address, entry })
+                    .collect();
+                Ok(BlockStorageChangeSet(slots).compress())
+            },
+        )
+    }
+}
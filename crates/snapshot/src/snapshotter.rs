@@ -3,7 +3,11 @@
 use crate::{segments, segments::Segment, SnapshotterError};
 use reth_db::{database::Database, snapshot::iter_snapshots};
 use reth_interfaces::{RethError, RethResult};
-use reth_primitives::{snapshot::HighestSnapshots, BlockNumber, TxNumber};
+use reth_nippy_jar::NippyJar;
+use reth_primitives::{
+    snapshot::{HighestSnapshots, ManifestEntry, SegmentHeader, SegmentManifest},
+    BlockNumber, SnapshotSegment, TxNumber,
+};
 use reth_provider::{BlockReader, DatabaseProviderRO, ProviderFactory, TransactionsProviderExt};
 use std::{
     collections::HashMap,
@@ -49,12 +53,18 @@ pub struct SnapshotTargets {
     headers: Option<RangeInclusive<BlockNumber>>,
     receipts: Option<(RangeInclusive<BlockNumber>, RangeInclusive<TxNumber>)>,
     transactions: Option<(RangeInclusive<BlockNumber>, RangeInclusive<TxNumber>)>,
+    account_changesets: Option<RangeInclusive<BlockNumber>>,
+    storage_changesets: Option<RangeInclusive<BlockNumber>>,
 }
 
 impl SnapshotTargets {
     /// Returns `true` if any of the targets are [Some].
     pub fn any(&self) -> bool {
-        self.headers.is_some() || self.receipts.is_some() || self.transactions.is_some()
+        self.headers.is_some() ||
+            self.receipts.is_some() ||
+            self.transactions.is_some() ||
+            self.account_changesets.is_some() ||
+            self.storage_changesets.is_some()
     }
 
     /// Returns `true` if all targets are either [None] or multiple of `block_interval`.
@@ -63,6 +73,8 @@ impl SnapshotTargets {
             self.headers.as_ref(),
             self.receipts.as_ref().map(|(blocks, _)| blocks),
             self.transactions.as_ref().map(|(blocks, _)| blocks),
+            self.account_changesets.as_ref(),
+            self.storage_changesets.as_ref(),
         ]
         .iter()
         .all(|blocks| blocks.map_or(true, |blocks| (blocks.end() + 1) % block_interval == 0))
@@ -75,6 +87,8 @@ impl SnapshotTargets {
             (self.headers.as_ref(), snapshots.headers),
             (self.receipts.as_ref().map(|(blocks, _)| blocks), snapshots.receipts),
             (self.transactions.as_ref().map(|(blocks, _)| blocks), snapshots.transactions),
+            (self.account_changesets.as_ref(), snapshots.account_changesets),
+            (self.storage_changesets.as_ref(), snapshots.storage_changesets),
         ]
         .iter()
         .all(|(target, highest)| {
@@ -106,6 +120,7 @@ impl<DB: Database> Snapshotter<DB> {
         };
 
         snapshotter.create_directory()?;
+        snapshotter.reconcile_manifests()?;
         snapshotter.update_highest_snapshots_tracker()?;
 
         Ok(snapshotter)
@@ -133,6 +148,44 @@ impl<DB: Database> Snapshotter<DB> {
         Ok(())
     }
 
+    /// Records every snapshot that isn't listed in the [`SegmentManifest`] of its segment.
+    ///
+    /// Such snapshots are left behind by a crash between moving the snapshot files into place and
+    /// saving the manifest (see [`Snapshotter::run_segment`]), and by snapshots created before
+    /// manifests existed.
+    fn reconcile_manifests(&self) -> RethResult<()> {
+        let segments = iter_snapshots(&self.snapshots_path)
+            .map_err(|err| RethError::Provider(err.into()))?
+            .into_keys();
+
+        for segment in segments {
+            let mut manifest = SegmentManifest::load(&self.snapshots_path, segment)?
+                .unwrap_or_else(|| SegmentManifest::new(segment));
+
+            let unlisted = manifest.unlisted_files(&self.snapshots_path)?;
+            if unlisted.is_empty() {
+                continue
+            }
+
+            for path in unlisted {
+                let Some((_, block_range, tx_range)) =
+                    path.file_name().and_then(SnapshotSegment::parse_filename)
+                else {
+                    continue
+                };
+
+                warn!(target: "snapshot", ?path, "Recording snapshot missing from the manifest");
+                let jar = NippyJar::<SegmentHeader>::load(&path)
+                    .map_err(|err| RethError::Provider(err.into()))?;
+                manifest.insert(ManifestEntry::new(block_range, tx_range, jar_files(&jar))?);
+            }
+
+            manifest.save(&self.snapshots_path)?;
+        }
+
+        Ok(())
+    }
+
     #[cfg(test)]
     fn set_highest_snapshots_from_targets(&mut self, targets: &SnapshotTargets) {
         if let Some(block_number) = &targets.headers {
@@ -144,6 +197,12 @@ impl<DB: Database> Snapshotter<DB> {
         if let Some((block_number, _)) = &targets.transactions {
             self.highest_snapshots.transactions = Some(*block_number.end());
         }
+        if let Some(block_number) = &targets.account_changesets {
+            self.highest_snapshots.account_changesets = Some(*block_number.end());
+        }
+        if let Some(block_number) = &targets.storage_changesets {
+            self.highest_snapshots.storage_changesets = Some(*block_number.end());
+        }
     }
 
     /// Looks into the snapshot directory to find the highest snapshotted block of each segment, and
@@ -188,6 +247,10 @@ impl<DB: Database> Snapshotter<DB> {
 
         self.run_segment::<segments::Headers>(targets.headers.clone())?;
 
+        self.run_segment::<segments::AccountChangeSets>(targets.account_changesets.clone())?;
+
+        self.run_segment::<segments::StorageChangeSets>(targets.storage_changesets.clone())?;
+
         self.update_highest_snapshots_tracker()?;
 
         Ok(targets)
@@ -200,7 +263,9 @@ impl<DB: Database> Snapshotter<DB> {
     /// up on boot (on [`Snapshotter::new`]) and the snapshot process restarted from scratch for
     /// this block range and segment.
     ///
-    /// If it succeeds, then we move the snapshot file from the temporary directory to its main one.
+    /// If it succeeds, then we move the snapshot files from the temporary directory to its main one
+    /// and record them in the [`SegmentManifest`] of the segment. If the node is terminated in
+    /// between, the snapshot is recorded on boot (on [`Snapshotter::new`]).
     fn run_segment<S: Segment>(
         &self,
         block_range: Option<RangeInclusive<BlockNumber>>,
@@ -212,9 +277,23 @@ impl<DB: Database> Snapshotter<DB> {
             let segment = S::default();
             let filename = segment.segment().filename(&block_range, &tx_range);
 
-            segment.snapshot::<DB>(&provider, temp.clone(), block_range)?;
+            segment.snapshot::<DB>(&provider, temp.clone(), block_range.clone())?;
+
+            let jar = NippyJar::<SegmentHeader>::load(&temp.join(&filename))
+                .map_err(|err| RethError::Provider(err.into()))?;
+            let files = jar_files(&jar);
+            let entry = ManifestEntry::new(block_range, tx_range, &files)?;
 
-            reth_primitives::fs::rename(temp.join(&filename), self.snapshots_path.join(filename))?;
+            // Moves the data file alongside all its sibling files out of the temporary directory.
+            for file in files {
+                let name = file.file_name().expect("snapshot files are named");
+                reth_primitives::fs::rename(&file, self.snapshots_path.join(name))?;
+            }
+
+            let mut manifest = SegmentManifest::load(&self.snapshots_path, segment.segment())?
+                .unwrap_or_else(|| SegmentManifest::new(segment.segment()));
+            manifest.insert(entry);
+            manifest.save(&self.snapshots_path)?;
         }
         Ok(())
     }
@@ -240,6 +319,14 @@ impl<DB: Database> Snapshotter<DB> {
             self.get_snapshot_target_block_range(to_block_number, self.highest_snapshots.receipts);
         let transactions_block_range = self
             .get_snapshot_target_block_range(to_block_number, self.highest_snapshots.transactions);
+        let account_changesets_block_range = self.get_snapshot_target_block_range(
+            to_block_number,
+            self.highest_snapshots.account_changesets,
+        );
+        let storage_changesets_block_range = self.get_snapshot_target_block_range(
+            to_block_number,
+            self.highest_snapshots.storage_changesets,
+        );
 
         // Calculate transaction ranges to snapshot
         let mut block_to_tx_number_cache = HashMap::default();
@@ -275,6 +362,18 @@ impl<DB: Database> Snapshotter<DB> {
                 .expect("finalized block should be >= last transactions snapshot")
                 .ge(&(self.block_interval as usize))
                 .then_some((transactions_block_range, transactions_tx_range)),
+            account_changesets: account_changesets_block_range
+                .size_hint()
+                .1
+                .expect("finalized block should be >= last account changesets snapshot")
+                .ge(&(self.block_interval as usize))
+                .then_some(account_changesets_block_range),
+            storage_changesets: storage_changesets_block_range
+                .size_hint()
+                .1
+                .expect("finalized block should be >= last storage changesets snapshot")
+                .ge(&(self.block_interval as usize))
+                .then_some(storage_changesets_block_range),
         })
     }
 
@@ -319,15 +418,29 @@ impl<DB: Database> Snapshotter<DB> {
     }
 }
 
+/// Returns the data file of the snapshot alongside all its sibling files.
+fn jar_files(jar: &NippyJar<SegmentHeader>) -> [PathBuf; 4] {
+    [jar.data_path().to_path_buf(), jar.index_path(), jar.offsets_path(), jar.config_path()]
+}
+
 #[cfg(test)]
 mod tests {
-    use crate::{snapshotter::SnapshotTargets, Snapshotter};
+    use crate::{
+        snapshotter::{jar_files, SnapshotTargets},
+        Snapshotter,
+    };
     use assert_matches::assert_matches;
     use reth_interfaces::{
         test_utils::{generators, generators::random_block_range},
         RethError,
     };
-    use reth_primitives::{snapshot::HighestSnapshots, B256};
+    use reth_nippy_jar::{ColumnResult, NippyJar};
+    use reth_primitives::{
+        snapshot::{
+            HighestSnapshots, ManifestEntry, ManifestVerification, SegmentHeader, SegmentManifest,
+        },
+        SnapshotSegment, B256,
+    };
     use reth_stages::test_utils::TestStageDB;
 
     #[test]
@@ -342,6 +455,42 @@ mod tests {
         );
     }
 
+    #[test]
+    fn new_records_unlisted_snapshots() {
+        let db = TestStageDB::default();
+        let snapshots_dir = tempfile::TempDir::new().unwrap();
+        let segment = SnapshotSegment::AccountChangeSets;
+
+        // The older snapshot predates the manifest, which only records the newer one.
+        let mut manifest = SegmentManifest::new(segment);
+        for range in [0..=1u64, 2..=3] {
+            let path = snapshots_dir.path().join(segment.filename(&range, &(0..=0)));
+            let mut jar =
+                NippyJar::new(1, &path, SegmentHeader::new(range.clone(), 0..=0, segment));
+            let rows = range.clone().map(|_| -> ColumnResult<Vec<u8>> { Ok(vec![]) });
+            jar.freeze(vec![rows], range.clone().count() as u64).unwrap();
+
+            if *range.start() == 2 {
+                manifest.insert(ManifestEntry::new(range, 0..=0, jar_files(&jar)).unwrap());
+            }
+        }
+        manifest.save(snapshots_dir.path()).unwrap();
+        assert_eq!(manifest.unlisted_files(snapshots_dir.path()).unwrap().len(), 1);
+
+        let snapshotter = Snapshotter::new(db.factory, snapshots_dir.path(), 2).unwrap();
+        assert_eq!(
+            snapshotter.highest_snapshot_receiver().borrow().unwrap().account_changesets,
+            Some(3)
+        );
+
+        let manifest = SegmentManifest::load(snapshots_dir.path(), segment).unwrap().unwrap();
+        assert_eq!(manifest.snapshots.len(), 2);
+        assert_eq!(
+            manifest.verify(snapshots_dir.path(), ManifestVerification::Full).unwrap(),
+            vec![]
+        );
+    }
+
     #[test]
     fn get_snapshot_targets() {
         let db = TestStageDB::default();
@@ -361,7 +510,9 @@ mod tests {
             SnapshotTargets {
                 headers: Some(0..=1),
                 receipts: Some((0..=1, 0..=3)),
-                transactions: Some((0..=1, 0..=3))
+                transactions: Some((0..=1, 0..=3)),
+                account_changesets: Some(0..=1),
+                storage_changesets: Some(0..=1),
             }
         );
         assert!(targets.is_multiple_of_block_interval(snapshotter.block_interval));
@@ -372,7 +523,13 @@ mod tests {
         // Nothing to snapshot, last snapshots state of snapshotter doesn't pass the thresholds
         assert_eq!(
             snapshotter.get_snapshot_targets(2),
-            Ok(SnapshotTargets { headers: None, receipts: None, transactions: None })
+            Ok(SnapshotTargets {
+                headers: None,
+                receipts: None,
+                transactions: None,
+                account_changesets: None,
+                storage_changesets: None,
+            })
         );
 
         // Snapshot targets has data per part up to the passed finalized block number,
@@ -383,7 +540,9 @@ mod tests {
             SnapshotTargets {
                 headers: Some(2..=3),
                 receipts: Some((2..=3, 4..=7)),
-                transactions: Some((2..=3, 4..=7))
+                transactions: Some((2..=3, 4..=7)),
+                account_changesets: Some(2..=3),
+                storage_changesets: Some(2..=3),
             }
         );
         assert!(targets.is_multiple_of_block_interval(snapshotter.block_interval));
@@ -49,6 +49,7 @@ use tracing::*;
 /// - [tables::Bytecodes]
 /// - [tables::AccountChangeSet]
 /// - [tables::StorageChangeSet]
+/// - [tables::ContractCreators] if the executors of the factory record contract creations
 ///
 /// For unwinds we are accessing:
 /// - [tables::BlockBodyIndices] get tx index to know what needs to be unwinded
@@ -371,6 +372,12 @@ impl<EF: ExecutorFactory, DB: Database> Stage<DB> for ExecutionStage<EF> {
         let account_changeset_batch =
             account_changeset.walk_range(range.clone())?.collect::<Result<Vec<_>, _>>()?;
 
+        // remove creators of contracts created in the unwound range
+        provider.unwind_contract_creators(
+            *range.start(),
+            account_changeset_batch.iter().map(|(_, changeset)| changeset.address),
+        )?;
+
         // revert all changes to PlainState
         for (_, changeset) in account_changeset_batch.into_iter().rev() {
             if let Some(account_info) = changeset.info {
@@ -501,11 +508,15 @@ mod tests {
     use alloy_rlp::Decodable;
     use assert_matches::assert_matches;
     use reth_db::{models::AccountBeforeTx, test_utils::create_test_rw_db};
-    use reth_interfaces::executor::BlockValidationError;
+    use reth_interfaces::{
+        executor::BlockValidationError,
+        test_utils::generators::{self, random_block_range},
+    };
     use reth_node_ethereum::EthEvmConfig;
     use reth_primitives::{
-        address, hex_literal::hex, keccak256, stage::StageUnitCheckpoint, Account, Bytecode,
-        ChainSpecBuilder, PruneModes, SealedBlock, StorageEntry, B256, MAINNET, U256,
+        address, hex_literal::hex, keccak256, stage::StageUnitCheckpoint, Account, Address,
+        Bytecode, ChainSpecBuilder, ContractCreation, PruneModes, SealedBlock, StorageEntry, B256,
+        MAINNET, U256,
     };
     use reth_provider::{AccountReader, BlockWriter, ProviderFactory, ReceiptProvider};
     use reth_revm::EvmProcessorFactory;
@@ -974,4 +985,62 @@ mod tests {
             ]
         );
     }
+
+    #[test]
+    fn unwind_contract_creators() {
+        let test_db = TestStageDB::default();
+        let mut rng = generators::rng();
+        let blocks = random_block_range(&mut rng, 0..=2, B256::ZERO, 0..1);
+        test_db.insert_blocks(blocks.iter(), None).unwrap();
+
+        // `created` is deployed in block 1, while `prefunded` already held a balance when it was
+        // deployed with CREATE2 in block 2.
+        let created = Address::with_last_byte(1);
+        let prefunded = Address::with_last_byte(2);
+        let prefunded_info = Account { balance: U256::from(1), ..Default::default() };
+        test_db
+            .commit(|tx| {
+                tx.put::<tables::AccountChangeSet>(
+                    1,
+                    AccountBeforeTx { address: created, info: None },
+                )?;
+                tx.put::<tables::AccountChangeSet>(
+                    2,
+                    AccountBeforeTx { address: prefunded, info: Some(prefunded_info) },
+                )?;
+                for (address, block_number) in [(created, 1), (prefunded, 2)] {
+                    tx.put::<tables::PlainAccountState>(
+                        address,
+                        Account { nonce: 1, ..Default::default() },
+                    )?;
+                    tx.put::<tables::ContractCreators>(
+                        address,
+                        ContractCreation { block_number, ..Default::default() },
+                    )?;
+                }
+                Ok(())
+            })
+            .unwrap();
+
+        let provider = test_db.factory.provider_rw().unwrap();
+        stage()
+            .unwind(
+                &provider,
+                UnwindInput { checkpoint: StageCheckpoint::new(2), unwind_to: 1, bad_block: None },
+            )
+            .unwrap();
+        provider.commit().unwrap();
+
+        assert_eq!(
+            test_db.table::<tables::ContractCreators>().unwrap(),
+            vec![(created, ContractCreation { block_number: 1, ..Default::default() })]
+        );
+        assert_eq!(
+            test_db.table::<tables::PlainAccountState>().unwrap(),
+            vec![
+                (created, Account { nonce: 1, ..Default::default() }),
+                (prefunded, prefunded_info)
+            ]
+        );
+    }
 }
@@ -0,0 +1,277 @@
+use crate::{ExecInput, ExecOutput, Stage, StageError, UnwindInput, UnwindOutput};
+use reth_db::database::Database;
+use reth_interfaces::provider::ProviderError;
+use reth_primitives::{
+    stage::{StageCheckpoint, StageId},
+    PruneCheckpoint, PruneMode, PruneSegment,
+};
+use reth_provider::{
+    AddressAppearancesReader, BlockReader, DatabaseProviderRW, HistoryWriter,
+    PruneCheckpointReader, PruneCheckpointWriter,
+};
+use std::fmt::Debug;
+use tracing::*;
+
+/// Stage is indexing the transactions in which an address appears, either as the sender, the
+/// recipient, the created contract, the emitter of a log or a participant of an internal call.
+/// Internal calls are recorded by the execution stage in [`reth_db::tables::CallParticipants`]
+/// while this stage is enabled, so they are only indexed for blocks executed since. For more
+/// information on index sharding take a look at [`reth_db::tables::AddressAppearances`].
+///
+/// This stage is optional and not part of the default pipeline. Once it has been executed, the
+/// index is kept up to date by the provider when blocks are appended or unwound outside of the
+/// pipeline.
+#[derive(Debug)]
+pub struct IndexAddressAppearancesStage {
+    /// Number of blocks after which the control
+    /// flow will be returned to the pipeline for commit.
+    pub commit_threshold: u64,
+    /// Pruning configuration.
+    pub prune_mode: Option<PruneMode>,
+}
+
+impl IndexAddressAppearancesStage {
+    /// Create new instance of [IndexAddressAppearancesStage].
+    pub fn new(commit_threshold: u64, prune_mode: Option<PruneMode>) -> Self {
+        Self { commit_threshold, prune_mode }
+    }
+}
+
+impl Default for IndexAddressAppearancesStage {
+    fn default() -> Self {
+        Self { commit_threshold: 100_000, prune_mode: None }
+    }
+}
+
+impl<DB: Database> Stage<DB> for IndexAddressAppearancesStage {
+    /// Return the id of the stage
+    fn id(&self) -> StageId {
+        StageId::IndexAddressAppearances
+    }
+
+    /// Execute the stage.
+    fn execute(
+        &mut self,
+        provider: &DatabaseProviderRW<DB>,
+        mut input: ExecInput,
+    ) -> Result<ExecOutput, StageError> {
+        if let Some((target_prunable_block, prune_mode)) = self
+            .prune_mode
+            .map(|mode| mode.prune_target_block(input.target(), PruneSegment::AddressAppearances))
+            .transpose()?
+            .flatten()
+        {
+            if target_prunable_block > input.checkpoint().block_number {
+                input.checkpoint = Some(StageCheckpoint::new(target_prunable_block));
+
+                // Save prune checkpoint only if we don't have one already.
+                // Otherwise, pruner may skip the unpruned range of blocks.
+                if provider.get_prune_checkpoint(PruneSegment::AddressAppearances)?.is_none() {
+                    let target_prunable_tx_number = provider
+                        .block_body_indices(target_prunable_block)?
+                        .ok_or(ProviderError::BlockBodyIndicesNotFound(target_prunable_block))?
+                        .last_tx_num();
+
+                    provider.save_prune_checkpoint(
+                        PruneSegment::AddressAppearances,
+                        PruneCheckpoint {
+                            block_number: Some(target_prunable_block),
+                            tx_number: Some(target_prunable_tx_number),
+                            prune_mode,
+                        },
+                    )?;
+                }
+            }
+        }
+
+        if input.target_reached() {
+            return Ok(ExecOutput::done(input.checkpoint()))
+        }
+
+        let (range, is_final_range) = input.next_block_range_with_threshold(self.commit_threshold);
+
+        debug!(target: "sync::stages::index_address_appearances", ?range, "Indexing address appearances");
+
+        let appearances = provider.address_appearances_with_range(range.clone())?;
+        // Insert appearances to the index
+        provider.insert_address_appearances_index(appearances)?;
+
+        Ok(ExecOutput { checkpoint: StageCheckpoint::new(*range.end()), done: is_final_range })
+    }
+
+    /// Unwind the stage.
+    fn unwind(
+        &mut self,
+        provider: &DatabaseProviderRW<DB>,
+        input: UnwindInput,
+    ) -> Result<UnwindOutput, StageError> {
+        let (range, unwind_progress, _) =
+            input.unwind_block_range_with_threshold(self.commit_threshold);
+
+        provider.unwind_address_appearances_index(range)?;
+
+        Ok(UnwindOutput { checkpoint: StageCheckpoint::new(unwind_progress) })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::{
+        stage_test_suite_ext, ExecuteStageTestRunner, StageTestRunner, TestRunnerError,
+        TestStageDB, UnwindStageTestRunner,
+    };
+    use reth_db::{models::ShardedKey, tables, transaction::DbTxMut};
+    use reth_interfaces::test_utils::{generators, generators::random_block_range};
+    use reth_primitives::{Address, TransactionKind, TxNumber, B256};
+    use std::collections::BTreeMap;
+
+    fn cast(
+        table: Vec<(ShardedKey<Address>, reth_db::TxNumberList)>,
+    ) -> BTreeMap<Address, Vec<TxNumber>> {
+        table.into_iter().fold(BTreeMap::new(), |mut map, (key, list)| {
+            map.entry(key.key).or_insert_with(Vec::new).extend(list.iter(0).map(|i| i as u64));
+            map
+        })
+    }
+
+    #[tokio::test]
+    async fn index_senders_and_recipients() {
+        let db = TestStageDB::default();
+        let mut rng = generators::rng();
+
+        let blocks = random_block_range(&mut rng, 0..=10, B256::ZERO, 1..3);
+        db.insert_blocks(blocks.iter(), None).expect("insert blocks");
+
+        let provider = db.factory.provider_rw().unwrap();
+        let input = ExecInput { target: Some(10), ..Default::default() };
+        let out = IndexAddressAppearancesStage::default().execute(&provider, input).unwrap();
+        assert_eq!(out, ExecOutput { checkpoint: StageCheckpoint::new(10), done: true });
+        provider.commit().unwrap();
+
+        // genesis block is not indexed by the stage
+        let table = cast(db.table::<tables::AddressAppearances>().unwrap());
+        let mut tx_number = blocks[0].body.len() as TxNumber;
+        for block in blocks.iter().skip(1) {
+            for transaction in &block.body {
+                let sender = transaction.recover_signer().unwrap();
+                assert!(table[&sender].contains(&tx_number));
+                if let TransactionKind::Call(to) = transaction.kind() {
+                    assert!(table[to].contains(&tx_number));
+                }
+                tx_number += 1;
+            }
+        }
+
+        // unwind
+        let provider = db.factory.provider_rw().unwrap();
+        let input =
+            UnwindInput { checkpoint: StageCheckpoint::new(10), unwind_to: 0, bad_block: None };
+        IndexAddressAppearancesStage::default().unwind(&provider, input).unwrap();
+        provider.commit().unwrap();
+
+        // verify initial state
+        let table = db.table::<tables::AddressAppearances>().unwrap();
+        assert!(table.is_empty());
+    }
+
+    #[tokio::test]
+    async fn index_call_participants() {
+        let db = TestStageDB::default();
+        let mut rng = generators::rng();
+
+        let blocks = random_block_range(&mut rng, 0..=2, B256::ZERO, 1..3);
+        db.insert_blocks(blocks.iter(), None).expect("insert blocks");
+
+        // The first transaction of block 1 calls into another contract.
+        let tx_number = blocks[0].body.len() as TxNumber;
+        let participant = Address::with_last_byte(0x42);
+        db.commit(|tx| Ok(tx.put::<tables::CallParticipants>(tx_number, participant)?))
+            .expect("insert call participants");
+
+        let provider = db.factory.provider_rw().unwrap();
+        let input = ExecInput { target: Some(2), ..Default::default() };
+        IndexAddressAppearancesStage::default().execute(&provider, input).unwrap();
+        provider.commit().unwrap();
+
+        let table = cast(db.table::<tables::AddressAppearances>().unwrap());
+        assert_eq!(table[&participant], vec![tx_number]);
+    }
+
+    stage_test_suite_ext!(IndexAddressAppearancesTestRunner, index_address_appearances);
+
+    struct IndexAddressAppearancesTestRunner {
+        pub(crate) db: TestStageDB,
+        commit_threshold: u64,
+        prune_mode: Option<PruneMode>,
+    }
+
+    impl Default for IndexAddressAppearancesTestRunner {
+        fn default() -> Self {
+            Self { db: TestStageDB::default(), commit_threshold: 1000, prune_mode: None }
+        }
+    }
+
+    impl StageTestRunner for IndexAddressAppearancesTestRunner {
+        type S = IndexAddressAppearancesStage;
+
+        fn db(&self) -> &TestStageDB {
+            &self.db
+        }
+
+        fn stage(&self) -> Self::S {
+            Self::S { commit_threshold: self.commit_threshold, prune_mode: self.prune_mode }
+        }
+    }
+
+    impl ExecuteStageTestRunner for IndexAddressAppearancesTestRunner {
+        type Seed = ();
+
+        fn seed_execution(&mut self, input: ExecInput) -> Result<Self::Seed, TestRunnerError> {
+            let stage_progress = input.checkpoint().block_number;
+            let end = input.target();
+            let mut rng = generators::rng();
+
+            let blocks = random_block_range(&mut rng, stage_progress..=end, B256::ZERO, 0..3);
+            self.db.insert_blocks(blocks.iter(), None)?;
+
+            Ok(())
+        }
+
+        fn validate_execution(
+            &self,
+            input: ExecInput,
+            output: Option<ExecOutput>,
+        ) -> Result<(), TestRunnerError> {
+            if let Some(output) = output {
+                let start_block = input.next_block();
+                let end_block = output.checkpoint.block_number;
+                if start_block > end_block {
+                    return Ok(())
+                }
+
+                assert_eq!(
+                    output,
+                    ExecOutput { checkpoint: StageCheckpoint::new(input.target()), done: true }
+                );
+
+                let expected = self
+                    .db
+                    .factory
+                    .provider()?
+                    .address_appearances_with_range(start_block..=end_block)?;
+                let table = cast(self.db.table::<tables::AddressAppearances>().unwrap());
+                assert_eq!(table, expected);
+            }
+            Ok(())
+        }
+    }
+
+    impl UnwindStageTestRunner for IndexAddressAppearancesTestRunner {
+        fn validate_unwind(&self, _input: UnwindInput) -> Result<(), TestRunnerError> {
+            let table = self.db.table::<tables::AddressAppearances>().unwrap();
+            assert!(table.is_empty());
+            Ok(())
+        }
+    }
+}
@@ -0,0 +1,232 @@
+use crate::{ExecInput, ExecOutput, Stage, StageError, UnwindInput, UnwindOutput};
+use reth_db::database::Database;
+use reth_primitives::stage::{StageCheckpoint, StageId};
+use reth_provider::{DatabaseProviderRW, HistoryWriter, LogIndexReader};
+use std::fmt::Debug;
+use tracing::*;
+
+/// Stage is indexing the blocks in which an address emitted a log and in which a topic was
+/// logged. For more information on index sharding take a look at
+/// [`reth_db::tables::LogAddressIndex`] and [`reth_db::tables::LogTopicIndex`].
+///
+/// This stage is optional and not part of the default pipeline. Once it has been executed, the
+/// index is kept up to date by the provider when blocks are appended or unwound outside of the
+/// pipeline.
+#[derive(Debug)]
+pub struct IndexLogsStage {
+    /// Number of blocks after which the control
+    /// flow will be returned to the pipeline for commit.
+    pub commit_threshold: u64,
+}
+
+impl IndexLogsStage {
+    /// Create new instance of [IndexLogsStage].
+    pub fn new(commit_threshold: u64) -> Self {
+        Self { commit_threshold }
+    }
+}
+
+impl Default for IndexLogsStage {
+    fn default() -> Self {
+        Self { commit_threshold: 100_000 }
+    }
+}
+
+impl<DB: Database> Stage<DB> for IndexLogsStage {
+    /// Return the id of the stage
+    fn id(&self) -> StageId {
+        StageId::IndexLogs
+    }
+
+    /// Execute the stage.
+    fn execute(
+        &mut self,
+        provider: &DatabaseProviderRW<DB>,
+        input: ExecInput,
+    ) -> Result<ExecOutput, StageError> {
+        if input.target_reached() {
+            return Ok(ExecOutput::done(input.checkpoint()))
+        }
+
+        let (range, is_final_range) = input.next_block_range_with_threshold(self.commit_threshold);
+
+        debug!(target: "sync::stages::index_logs", ?range, "Indexing log addresses and topics");
+
+        let (addresses, topics) = provider.log_addresses_and_topics_with_range(range.clone())?;
+        // Insert addresses and topics to the index
+        provider.insert_log_index(addresses, topics)?;
+
+        Ok(ExecOutput { checkpoint: StageCheckpoint::new(*range.end()), done: is_final_range })
+    }
+
+    /// Unwind the stage.
+    fn unwind(
+        &mut self,
+        provider: &DatabaseProviderRW<DB>,
+        input: UnwindInput,
+    ) -> Result<UnwindOutput, StageError> {
+        let (range, unwind_progress, _) =
+            input.unwind_block_range_with_threshold(self.commit_threshold);
+
+        provider.unwind_log_index(range)?;
+
+        Ok(UnwindOutput { checkpoint: StageCheckpoint::new(unwind_progress) })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::{
+        stage_test_suite_ext, ExecuteStageTestRunner, StageTestRunner, TestRunnerError,
+        TestStageDB, UnwindStageTestRunner,
+    };
+    use reth_db::{models::ShardedKey, tables, transaction::DbTx, BlockNumberList};
+    use reth_interfaces::test_utils::{
+        generators,
+        generators::{random_block_range, random_receipt},
+    };
+    use reth_primitives::{BlockNumber, SealedBlock, TxNumber, B256};
+    use std::collections::BTreeMap;
+
+    fn cast<K: Ord>(table: Vec<(ShardedKey<K>, BlockNumberList)>) -> BTreeMap<K, Vec<BlockNumber>> {
+        table.into_iter().fold(BTreeMap::new(), |mut map, (key, list)| {
+            map.entry(key.key).or_insert_with(Vec::new).extend(list.iter(0).map(|i| i as u64));
+            map
+        })
+    }
+
+    /// Inserts the blocks and a receipt with logs for each of their transactions.
+    fn insert_blocks_with_receipts(db: &TestStageDB, blocks: &[SealedBlock]) {
+        let mut rng = generators::rng();
+        db.insert_blocks(blocks.iter(), None).expect("insert blocks");
+
+        let mut tx_number: TxNumber = 0;
+        let mut receipts = Vec::new();
+        for transaction in blocks.iter().flat_map(|block| block.body.iter()) {
+            receipts.push((tx_number, random_receipt(&mut rng, transaction, Some(2))));
+            tx_number += 1;
+        }
+        db.insert_receipts(receipts).expect("insert receipts");
+    }
+
+    #[tokio::test]
+    async fn index_log_addresses_and_topics() {
+        let db = TestStageDB::default();
+        let mut rng = generators::rng();
+
+        let blocks = random_block_range(&mut rng, 0..=10, B256::ZERO, 1..3);
+        insert_blocks_with_receipts(&db, &blocks);
+
+        let provider = db.factory.provider_rw().unwrap();
+        let input = ExecInput { target: Some(10), ..Default::default() };
+        let out = IndexLogsStage::default().execute(&provider, input).unwrap();
+        assert_eq!(out, ExecOutput { checkpoint: StageCheckpoint::new(10), done: true });
+        provider.commit().unwrap();
+
+        // genesis block is not indexed by the stage
+        let addresses = cast(db.table::<tables::LogAddressIndex>().unwrap());
+        let topics = cast(db.table::<tables::LogTopicIndex>().unwrap());
+        let mut tx_number = blocks[0].body.len() as TxNumber;
+        for block in blocks.iter().skip(1) {
+            for _ in &block.body {
+                let receipt =
+                    db.query(|tx| Ok(tx.get::<tables::Receipts>(tx_number)?)).unwrap().unwrap();
+                for log in &receipt.logs {
+                    assert!(addresses[&log.address].contains(&block.number));
+                    for topic in &log.topics {
+                        assert!(topics[topic].contains(&block.number));
+                    }
+                }
+                tx_number += 1;
+            }
+        }
+
+        // unwind
+        let provider = db.factory.provider_rw().unwrap();
+        let input =
+            UnwindInput { checkpoint: StageCheckpoint::new(10), unwind_to: 0, bad_block: None };
+        IndexLogsStage::default().unwind(&provider, input).unwrap();
+        provider.commit().unwrap();
+
+        // verify initial state
+        assert!(db.table::<tables::LogAddressIndex>().unwrap().is_empty());
+        assert!(db.table::<tables::LogTopicIndex>().unwrap().is_empty());
+    }
+
+    stage_test_suite_ext!(IndexLogsTestRunner, index_logs);
+
+    struct IndexLogsTestRunner {
+        pub(crate) db: TestStageDB,
+        commit_threshold: u64,
+    }
+
+    impl Default for IndexLogsTestRunner {
+        fn default() -> Self {
+            Self { db: TestStageDB::default(), commit_threshold: 1000 }
+        }
+    }
+
+    impl StageTestRunner for IndexLogsTestRunner {
+        type S = IndexLogsStage;
+
+        fn db(&self) -> &TestStageDB {
+            &self.db
+        }
+
+        fn stage(&self) -> Self::S {
+            Self::S { commit_threshold: self.commit_threshold }
+        }
+    }
+
+    impl ExecuteStageTestRunner for IndexLogsTestRunner {
+        type Seed = ();
+
+        fn seed_execution(&mut self, input: ExecInput) -> Result<Self::Seed, TestRunnerError> {
+            let stage_progress = input.checkpoint().block_number;
+            let end = input.target();
+            let mut rng = generators::rng();
+
+            let blocks = random_block_range(&mut rng, stage_progress..=end, B256::ZERO, 0..3);
+            insert_blocks_with_receipts(&self.db, &blocks);
+
+            Ok(())
+        }
+
+        fn validate_execution(
+            &self,
+            input: ExecInput,
+            output: Option<ExecOutput>,
+        ) -> Result<(), TestRunnerError> {
+            if let Some(output) = output {
+                let start_block = input.next_block();
+                let end_block = output.checkpoint.block_number;
+                if start_block > end_block {
+                    return Ok(())
+                }
+
+                assert_eq!(
+                    output,
+                    ExecOutput { checkpoint: StageCheckpoint::new(input.target()), done: true }
+                );
+
+                let (addresses, topics) = self
+                    .db
+                    .factory
+                    .provider()?
+                    .log_addresses_and_topics_with_range(start_block..=end_block)?;
+                assert_eq!(cast(self.db.table::<tables::LogAddressIndex>().unwrap()), addresses);
+                assert_eq!(cast(self.db.table::<tables::LogTopicIndex>().unwrap()), topics);
+            }
+            Ok(())
+        }
+    }
+
+    impl UnwindStageTestRunner for IndexLogsTestRunner {
+        fn validate_unwind(&self, _input: UnwindInput) -> Result<(), TestRunnerError> {
+            assert!(self.db.table::<tables::LogAddressIndex>().unwrap().is_empty());
+            assert!(self.db.table::<tables::LogTopicIndex>().unwrap().is_empty());
+            Ok(())
+        }
+    }
+}
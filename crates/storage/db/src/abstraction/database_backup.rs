@@ -0,0 +1,40 @@
+use crate::DatabaseError;
+use std::{path::Path, sync::Arc};
+
+/// Progress of a [DatabaseBackup::backup].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct BackupProgress {
+    /// Number of bytes written to the backup so far.
+    pub copied_bytes: u64,
+    /// Number of bytes in use by the database when the backup was started.
+    ///
+    /// Compacting backups skip free pages, so they may finish before reaching this size.
+    pub total_bytes: u64,
+}
+
+/// Represents a type that can create a consistent copy of the database while it's in use.
+pub trait DatabaseBackup {
+    /// Copies the database into the directory at `path`, which is created if it doesn't exist
+    /// and must not contain a database yet. The resulting directory can be opened as a database.
+    ///
+    /// If `compact` is set, free pages are omitted from the copy.
+    ///
+    /// `on_progress` is called periodically while the copy is being written.
+    fn backup(
+        &self,
+        path: &Path,
+        compact: bool,
+        on_progress: &mut dyn FnMut(BackupProgress),
+    ) -> Result<(), DatabaseError>;
+}
+
+impl<DB: DatabaseBackup> DatabaseBackup for Arc<DB> {
+    fn backup(
+        &self,
+        path: &Path,
+        compact: bool,
+        on_progress: &mut dyn FnMut(BackupProgress),
+    ) -> Result<(), DatabaseError> {
+        <DB as DatabaseBackup>::backup(self, path, compact, on_progress)
+    }
+}
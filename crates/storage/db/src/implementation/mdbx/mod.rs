@@ -2,21 +2,29 @@
 
 use crate::{
     database::Database,
+    database_backup::{BackupProgress, DatabaseBackup},
     database_metrics::{DatabaseMetadata, DatabaseMetadataValue, DatabaseMetrics},
     tables::{TableType, Tables},
     utils::default_page_size,
+    version::create_db_version_file,
     DatabaseError,
 };
 use eyre::Context;
 use metrics::{gauge, Label};
 use once_cell::sync::Lazy;
-use reth_interfaces::db::LogLevel;
+use reth_interfaces::db::{DatabaseErrorInfo, LogLevel};
 use reth_libmdbx::{
     DatabaseFlags, Environment, EnvironmentFlags, Geometry, MaxReadTransactionDuration, Mode,
     PageSize, SyncMode, RO, RW,
 };
 use reth_tracing::tracing::error;
-use std::{ops::Deref, path::Path};
+use std::{
+    io,
+    ops::Deref,
+    path::Path,
+    sync::mpsc::{self, RecvTimeoutError},
+    time::Duration,
+};
 use tx::Tx;
 
 pub mod cursor;
@@ -25,6 +33,12 @@ pub mod tx;
 const GIGABYTE: usize = 1024 * 1024 * 1024;
 const TERABYTE: usize = GIGABYTE * 1024;
 
+/// Name of the MDBX data file within the database directory.
+const DATA_FILE_NAME: &str = "mdbx.dat";
+
+/// Interval at which the progress of a backup is reported.
+const BACKUP_PROGRESS_INTERVAL: Duration = Duration::from_secs(1);
+
 /// MDBX allows up to 32767 readers (`MDBX_READERS_LIMIT`), but we limit it to slightly below that
 const DEFAULT_MAX_READERS: u64 = 32_000;
 
@@ -184,6 +198,66 @@ impl DatabaseMetadata for DatabaseEnv {
     }
 }
 
+impl DatabaseBackup for DatabaseEnv {
+    fn backup(
+        &self,
+        path: &Path,
+        compact: bool,
+        on_progress: &mut dyn FnMut(BackupProgress),
+    ) -> Result<(), DatabaseError> {
+        std::fs::create_dir_all(path).map_err(backup_io_error)?;
+        create_db_version_file(path).map_err(backup_io_error)?;
+
+        let info = self.inner.info().map_err(|e| DatabaseError::Stats(e.into()))?;
+        let stat = self.inner.stat().map_err(|e| DatabaseError::Stats(e.into()))?;
+        // pgno is 0 based.
+        let total_bytes = (info.last_pgno() as u64 + 1) * stat.page_size() as u64;
+
+        let data_path = path.join(DATA_FILE_NAME);
+        std::thread::scope(|scope| {
+            let (tx, rx) = mpsc::sync_channel(1);
+            // The sender is moved into the thread, so that the channel disconnects if it panics.
+            let copy_path = &data_path;
+            let copy = scope.spawn(move || {
+                let _ = tx.send(self.inner.copy_to(copy_path, compact));
+            });
+
+            loop {
+                match rx.recv_timeout(BACKUP_PROGRESS_INTERVAL) {
+                    Ok(result) => {
+                        result.map_err(|e| DatabaseError::Backup(e.into()))?;
+                        on_progress(BackupProgress { copied_bytes: total_bytes, total_bytes });
+                        return Ok(())
+                    }
+                    Err(RecvTimeoutError::Timeout) => {
+                        let copied_bytes = std::fs::metadata(&data_path)
+                            .map(|metadata| metadata.len().min(total_bytes))
+                            .unwrap_or_default();
+                        on_progress(BackupProgress { copied_bytes, total_bytes });
+                    }
+                    Err(RecvTimeoutError::Disconnected) => {
+                        // Joining the thread takes its panic, so that it doesn't propagate out of
+                        // the scope.
+                        let _ = copy.join();
+                        return Err(DatabaseError::Backup(DatabaseErrorInfo {
+                            message: "backup thread panicked".to_string(),
+                            code: 0,
+                        }))
+                    }
+                }
+            }
+        })
+    }
+}
+
+/// Converts an error of a file system operation of a backup into a [DatabaseError].
+fn backup_io_error(err: io::Error) -> DatabaseError {
+    DatabaseError::Backup(DatabaseErrorInfo {
+        message: err.to_string(),
+        code: err.raw_os_error().unwrap_or_default(),
+    })
+}
+
 impl DatabaseEnv {
     /// Opens the database at the specified path with the given `EnvKind`.
     ///
@@ -418,6 +492,36 @@ mod tests {
         tx.commit().expect(ERROR_COMMIT);
     }
 
+    #[test]
+    fn db_backup() {
+        let env = create_test_db(DatabaseEnvKind::RW);
+
+        let value = Header::default();
+        let key = 1u64;
+
+        let tx = env.tx_mut().expect(ERROR_INIT_TX);
+        tx.put::<Headers>(key, value.clone()).expect(ERROR_PUT);
+        tx.commit().expect(ERROR_COMMIT);
+
+        for compact in [false, true] {
+            let backup_path = TempDir::new().expect(ERROR_TEMPDIR).into_path().join("db");
+            let mut progress = Vec::new();
+            env.backup(&backup_path, compact, &mut |p| progress.push(p)).unwrap();
+
+            let last = progress.last().expect("progress is reported");
+            assert_eq!(last.copied_bytes, last.total_bytes);
+
+            // Backing up into an existing database should fail
+            assert!(env.backup(&backup_path, compact, &mut |_| {}).is_err());
+
+            let backup =
+                DatabaseEnv::open(&backup_path, DatabaseEnvKind::RO, DatabaseArguments::default())
+                    .expect(ERROR_DB_OPEN);
+            let tx = backup.tx().expect(ERROR_INIT_TX);
+            assert_eq!(tx.get::<Headers>(key).expect(ERROR_GET), Some(value.clone()));
+        }
+    }
+
     #[test]
     fn db_cursor_walk() {
         let env = create_test_db(DatabaseEnvKind::RW);
@@ -153,6 +153,7 @@ pub mod test_utils {
     use super::*;
     use crate::{
         database::Database,
+        database_backup::{BackupProgress, DatabaseBackup},
         database_metrics::{DatabaseMetadata, DatabaseMetadataValue, DatabaseMetrics},
     };
     use reth_libmdbx::MaxReadTransactionDuration;
@@ -225,6 +226,17 @@ pub mod test_utils {
         }
     }
 
+    impl<DB: DatabaseBackup> DatabaseBackup for TempDatabase<DB> {
+        fn backup(
+            &self,
+            path: &Path,
+            compact: bool,
+            on_progress: &mut dyn FnMut(BackupProgress),
+        ) -> Result<(), DatabaseError> {
+            self.db().backup(path, compact, on_progress)
+        }
+    }
+
     /// Get a temporary directory path to use for the database
     pub fn tempdir_path() -> PathBuf {
         let builder = tempfile::Builder::new().prefix("reth-test-").rand_bytes(8).tempdir();
@@ -1,6 +1,7 @@
-use super::{ReceiptMask, TransactionMask};
+use super::{AccountChangeSetMask, ReceiptMask, StorageChangeSetMask, TransactionMask};
 use crate::{
     add_snapshot_mask,
+    models::{BlockAccountChangeSet, BlockStorageChangeSet},
     snapshot::mask::{ColumnSelectorOne, ColumnSelectorTwo, HeaderMask},
     table::Table,
     CanonicalHeaders, HeaderTD, Receipts, Transactions,
@@ -26,3 +27,9 @@ add_snapshot_mask!(ReceiptMask, <Receipts as Table>::Value, 0b1);
 
 // TRANSACTION MASKS
 add_snapshot_mask!(TransactionMask, <Transactions as Table>::Value, 0b1);
+
+// ACCOUNT CHANGESET MASKS
+add_snapshot_mask!(AccountChangeSetMask, BlockAccountChangeSet, 0b1);
+
+// STORAGE CHANGESET MASKS
+add_snapshot_mask!(StorageChangeSetMask, BlockStorageChangeSet, 0b1);
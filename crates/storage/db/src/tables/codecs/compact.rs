@@ -42,8 +42,12 @@ impl_compression_for_compact!(
     StoredBlockBodyIndices,
     StoredBlockOmmers,
     StoredBlockWithdrawals,
+    StoredBadBlock,
     Bytecode,
+    ContractCreation,
     AccountBeforeTx,
+    BlockAccountChangeSet,
+    BlockStorageChangeSet,
     TransactionSignedNoHash,
     CompactU256,
     StageCheckpoint,
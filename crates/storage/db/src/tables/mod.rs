@@ -30,15 +30,16 @@ use crate::{
             accounts::{AccountBeforeTx, BlockNumberAddress},
             blocks::{HeaderHash, StoredBlockOmmers},
             storage_sharded_key::StorageShardedKey,
-            ShardedKey, StoredBlockBodyIndices, StoredBlockWithdrawals,
+            ShardedKey, StoredBadBlock, StoredBlockBodyIndices, StoredBlockWithdrawals,
         },
     },
 };
 use reth_primitives::{
     stage::StageCheckpoint,
     trie::{StorageTrieEntry, StoredBranchNode, StoredNibbles, StoredNibblesSubKey},
-    Account, Address, BlockHash, BlockNumber, Bytecode, Header, IntegerList, PruneCheckpoint,
-    PruneSegment, Receipt, StorageEntry, TransactionSignedNoHash, TxHash, TxNumber, B256,
+    Account, Address, BlockHash, BlockNumber, Bytecode, ContractCreation, Header, IntegerList,
+    PruneCheckpoint, PruneSegment, Receipt, StorageEntry, TransactionSignedNoHash, TxHash,
+    TxNumber, B256,
 };
 
 /// Enum for the types of tables present in libmdbx.
@@ -51,7 +52,7 @@ pub enum TableType {
 }
 
 /// Number of tables that should be present inside database.
-pub const NUM_TABLES: usize = 26;
+pub const NUM_TABLES: usize = 33;
 
 /// The general purpose of this is to use with a combination of Tables enum,
 /// by implementing a `TableViewer` trait you can operate on db tables in an abstract way.
@@ -215,12 +216,25 @@ tables!([
             TxSenders,
             SyncStage,
             SyncStageProgress,
-            PruneCheckpoints
+            PruneCheckpoints,
+            AddressAppearances,
+            ContractCreators,
+            BadBlocks,
+            BadBlockHashes,
+            LogAddressIndex,
+            LogTopicIndex
         ]
     ),
     (
         TableType::DupSort,
-        [PlainStorageState, AccountChangeSet, StorageChangeSet, HashedStorage, StoragesTrie]
+        [
+            PlainStorageState,
+            AccountChangeSet,
+            StorageChangeSet,
+            HashedStorage,
+            StoragesTrie,
+            CallParticipants
+        ]
     )
 ]);
 
@@ -451,10 +465,76 @@ table!(
     ( PruneCheckpoints ) PruneSegment | PruneCheckpoint
 );
 
+table!(
+    /// Stores pointers to the transactions in which an address appears: as the sender, the
+    /// recipient, the created contract, the emitter of a log or a participant of an internal call
+    /// recorded in [`CallParticipants`].
+    ///
+    /// Sharded the same way as [`AccountHistory`], except that the lists contain transaction
+    /// numbers instead of block numbers. The last shard key of the address contains `u64::MAX`
+    /// as the highest transaction number.
+    ///
+    /// This table is only populated when the optional `IndexAddressAppearances` stage is enabled.
+    ( AddressAppearances ) ShardedKey<Address> | TxNumberList
+);
+
+dupsort!(
+    /// Stores the accounts that take part in the internal calls of a transaction: the targets of
+    /// calls, the contracts created by other contracts and the beneficiaries of self-destructs.
+    ///
+    /// This table is only populated when the optional `IndexAddressAppearances` stage is enabled,
+    /// and only for the transactions that were executed after it was enabled.
+    ( CallParticipants ) TxNumber | [Address] Address
+);
+
+table!(
+    /// Stores the transaction and the creator of each contract.
+    ///
+    /// This table is only populated when indexing of contract creators is enabled, and may miss
+    /// contracts which could not be attributed to a single creator.
+    ( ContractCreators ) Address | ContractCreation
+);
+
+table!(
+    /// Stores the most recent blocks that failed validation, keyed by an increasing sequence
+    /// number.
+    ///
+    /// The table is bounded: once it is full, the oldest entry is evicted on insertion.
+    ( BadBlocks ) u64 | StoredBadBlock
+);
+
+table!(
+    /// Stores the sequence number of every block in [`BadBlocks`] by its hash.
+    ( BadBlockHashes ) BlockHash | u64
+);
+
+table!(
+    /// Stores pointers to the blocks in which an address emitted a log.
+    ///
+    /// Sharded the same way as [`AccountHistory`]. The last shard key of the address contains
+    /// `u64::MAX` as the highest block number.
+    ///
+    /// This table is only populated when the optional `IndexLogs` stage is enabled.
+    ( LogAddressIndex ) ShardedKey<Address> | BlockNumberList
+);
+
+table!(
+    /// Stores pointers to the blocks in which a topic was logged, at any position of the log's
+    /// topics.
+    ///
+    /// Sharded the same way as [`AccountHistory`]. The last shard key of the topic contains
+    /// `u64::MAX` as the highest block number.
+    ///
+    /// This table is only populated when the optional `IndexLogs` stage is enabled.
+    ( LogTopicIndex ) ShardedKey<B256> | BlockNumberList
+);
+
 /// Alias Types
 
-/// List with transaction numbers.
+/// List with block numbers.
 pub type BlockNumberList = IntegerList;
+/// List with transaction numbers.
+pub type TxNumberList = IntegerList;
 /// Encoded stage id.
 pub type StageId = String;
 
@@ -485,11 +565,18 @@ mod tests {
         (TableType::Table, SyncStage::NAME),
         (TableType::Table, SyncStageProgress::NAME),
         (TableType::Table, PruneCheckpoints::NAME),
+        (TableType::Table, AddressAppearances::NAME),
+        (TableType::Table, ContractCreators::NAME),
+        (TableType::Table, BadBlocks::NAME),
+        (TableType::Table, BadBlockHashes::NAME),
+        (TableType::Table, LogAddressIndex::NAME),
+        (TableType::Table, LogTopicIndex::NAME),
         (TableType::DupSort, PlainStorageState::NAME),
         (TableType::DupSort, AccountChangeSet::NAME),
         (TableType::DupSort, StorageChangeSet::NAME),
         (TableType::DupSort, HashedStorage::NAME),
         (TableType::DupSort, StoragesTrie::NAME),
+        (TableType::DupSort, CallParticipants::NAME),
     ];
 
     #[test]
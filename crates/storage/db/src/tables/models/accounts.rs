@@ -8,7 +8,7 @@ use crate::{
     DatabaseError,
 };
 use reth_codecs::{derive_arbitrary, Compact};
-use reth_primitives::{Account, Address, BlockNumber, Buf};
+use reth_primitives::{Account, Address, BlockNumber, Buf, StorageEntry};
 use serde::{Deserialize, Serialize};
 
 /// Account as it is saved inside [`AccountChangeSet`][crate::tables::AccountChangeSet].
@@ -56,6 +56,75 @@ impl Compact for AccountBeforeTx {
     }
 }
 
+/// Storage slot as it is saved inside the storage changeset snapshot segment.
+///
+/// Unlike [`StorageChangeSet`](crate::tables::StorageChangeSet) entries, the [`Address`] is part
+/// of the value, since all slots changed by a block share a single row.
+#[derive_arbitrary(compact)]
+#[derive(Debug, Default, Clone, Copy, Eq, PartialEq, Serialize)]
+pub struct StorageBeforeTx {
+    /// Address of the account the slot belongs to.
+    pub address: Address,
+    /// Storage slot and its value before the transaction.
+    pub entry: StorageEntry,
+}
+
+impl Compact for StorageBeforeTx {
+    fn to_compact<B>(self, buf: &mut B) -> usize
+    where
+        B: bytes::BufMut + AsMut<[u8]>,
+    {
+        buf.put_slice(self.address.as_slice());
+        self.entry.to_compact(buf) + 20
+    }
+
+    fn from_compact(mut buf: &[u8], len: usize) -> (Self, &[u8]) {
+        let address = Address::from_slice(&buf[..20]);
+        buf.advance(20);
+
+        let (entry, buf) = StorageEntry::from_compact(buf, len - 20);
+        (Self { address, entry }, buf)
+    }
+}
+
+/// All accounts changed by a block, as they were before the block. A row of the account changeset
+/// snapshot segment.
+#[derive(Debug, Default, Clone, Eq, PartialEq, Serialize)]
+pub struct BlockAccountChangeSet(pub Vec<AccountBeforeTx>);
+
+impl Compact for BlockAccountChangeSet {
+    fn to_compact<B>(self, buf: &mut B) -> usize
+    where
+        B: bytes::BufMut + AsMut<[u8]>,
+    {
+        self.0.to_compact(buf)
+    }
+
+    fn from_compact(buf: &[u8], len: usize) -> (Self, &[u8]) {
+        let (accounts, buf) = Vec::from_compact(buf, len);
+        (Self(accounts), buf)
+    }
+}
+
+/// All storage slots changed by a block, as they were before the block. A row of the storage
+/// changeset snapshot segment.
+#[derive(Debug, Default, Clone, Eq, PartialEq, Serialize)]
+pub struct BlockStorageChangeSet(pub Vec<StorageBeforeTx>);
+
+impl Compact for BlockStorageChangeSet {
+    fn to_compact<B>(self, buf: &mut B) -> usize
+    where
+        B: bytes::BufMut + AsMut<[u8]>,
+    {
+        self.0.to_compact(buf)
+    }
+
+    fn from_compact(buf: &[u8], len: usize) -> (Self, &[u8]) {
+        let (slots, buf) = Vec::from_compact(buf, len);
+        (Self(slots), buf)
+    }
+}
+
 /// [`BlockNumber`] concatenated with [`Address`]. Used as the key for
 /// [`StorageChangeSet`](crate::tables::StorageChangeSet)
 ///
@@ -126,6 +195,7 @@ impl_fixed_arbitrary!(BlockNumberAddress, 28);
 mod tests {
     use super::*;
     use rand::{thread_rng, Rng};
+    use reth_primitives::{B256, U256};
     use std::str::FromStr;
 
     #[test]
@@ -152,4 +222,24 @@ mod tests {
         let key = BlockNumberAddress::arbitrary(&mut Unstructured::new(&bytes)).unwrap();
         assert_eq!(bytes, Encode::encode(key));
     }
+
+    #[test]
+    fn test_block_storage_changeset_compact() {
+        let changeset = BlockStorageChangeSet(vec![
+            StorageBeforeTx {
+                address: Address::with_last_byte(1),
+                entry: StorageEntry { key: B256::with_last_byte(1), value: U256::ZERO },
+            },
+            StorageBeforeTx {
+                address: Address::with_last_byte(2),
+                entry: StorageEntry { key: B256::with_last_byte(2), value: U256::from(42) },
+            },
+        ]);
+
+        let mut buf = Vec::new();
+        changeset.clone().to_compact(&mut buf);
+        let (decoded, rest) = BlockStorageChangeSet::from_compact(&buf, buf.len());
+        assert_eq!(decoded, changeset);
+        assert!(rest.is_empty());
+    }
 }
@@ -1,7 +1,9 @@
 //! Block related models and types.
 
 use reth_codecs::{main_codec, Compact};
-use reth_primitives::{Header, TxNumber, Withdrawals, B256};
+use reth_primitives::{
+    BadBlock, Header, SealedBlock, TransactionSignedNoHash, TxNumber, Withdrawals, B256,
+};
 use std::ops::Range;
 
 /// Total number of transactions.
@@ -86,6 +88,57 @@ pub struct StoredBlockWithdrawals {
 /// Hash of the block header. Value for [`CanonicalHeaders`][crate::tables::CanonicalHeaders]
 pub type HeaderHash = B256;
 
+/// The storage representation of a block that failed validation.
+///
+/// Value for [`BadBlocks`][crate::tables::BadBlocks].
+#[main_codec]
+#[derive(Debug, Default, Eq, PartialEq, Clone)]
+pub struct StoredBadBlock {
+    /// The hash of the block.
+    pub hash: B256,
+    /// The transactions of the block.
+    pub body: Vec<TransactionSignedNoHash>,
+    /// The block headers of this block's uncles.
+    pub ommers: Vec<Header>,
+    /// The block withdrawals.
+    pub withdrawals: Option<Withdrawals>,
+    /// The UTF-8 encoded validation error of the block.
+    pub error: Vec<u8>,
+    /// The block header.
+    ///
+    /// NOTE: Has to be the last field, since the header has fields of unknown size.
+    pub header: Header,
+}
+
+impl From<BadBlock> for StoredBadBlock {
+    fn from(bad_block: BadBlock) -> Self {
+        let BadBlock { block, error } = bad_block;
+        Self {
+            hash: block.hash(),
+            body: block.body.into_iter().map(Into::into).collect(),
+            ommers: block.ommers,
+            withdrawals: block.withdrawals,
+            error: error.into_bytes(),
+            header: block.header.unseal(),
+        }
+    }
+}
+
+impl From<StoredBadBlock> for BadBlock {
+    fn from(stored: StoredBadBlock) -> Self {
+        let StoredBadBlock { hash, body, ommers, withdrawals, error, header } = stored;
+        Self {
+            block: SealedBlock {
+                header: header.seal(hash),
+                body: body.into_iter().map(TransactionSignedNoHash::with_hash).collect(),
+                ommers,
+                withdrawals,
+            },
+            error: String::from_utf8_lossy(&error).into_owned(),
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -101,6 +154,22 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_bad_block() {
+        let block = SealedBlock {
+            header: Header { number: 1, ..Default::default() }.seal_slow(),
+            ommers: vec![Header::default()],
+            withdrawals: Some(Withdrawals::default()),
+            ..Default::default()
+        };
+        let bad_block = BadBlock { block, error: "invalid state root".to_string() };
+
+        let stored = StoredBadBlock::from(bad_block.clone());
+        let decompressed = StoredBadBlock::decompress::<Vec<_>>(stored.clone().compress()).unwrap();
+        assert_eq!(decompressed, stored);
+        assert_eq!(BadBlock::from(decompressed), bad_block);
+    }
+
     #[test]
     fn block_indices() {
         let first_tx_num = 10;
@@ -213,6 +213,44 @@ impl Environment {
 
         Ok(freelist)
     }
+
+    /// Copies the environment to a new database file at the given path.
+    ///
+    /// The copy is made from a read-only transaction, so it is consistent and can be taken while
+    /// the environment is being written to. Note that the transaction is held for the whole
+    /// duration of the copy, so pages freed by concurrent writes can't be reused until it's done.
+    ///
+    /// If `compact` is set, free pages are omitted and all pages are renumbered sequentially, which
+    /// makes the copy smaller than the original file.
+    ///
+    /// The file at `path` must not exist yet, but its parent directory must be writable.
+    pub fn copy_to(&self, path: impl AsRef<Path>, compact: bool) -> Result<()> {
+        let path = path_to_cstring(path.as_ref())?;
+        let flags = if compact { ffi::MDBX_CP_COMPACT } else { ffi::MDBX_CP_DEFAULTS };
+        mdbx_result(unsafe { ffi::mdbx_env_copy(self.env_ptr(), path.as_ptr(), flags) })?;
+        Ok(())
+    }
+}
+
+/// Converts a path into a C string that can be passed to MDBX.
+///
+/// The path may not contain the null character.
+fn path_to_cstring(path: &Path) -> Result<CString> {
+    #[cfg(unix)]
+    fn path_to_bytes<P: AsRef<Path>>(path: P) -> Vec<u8> {
+        use std::os::unix::ffi::OsStrExt;
+        path.as_ref().as_os_str().as_bytes().to_vec()
+    }
+
+    #[cfg(windows)]
+    fn path_to_bytes<P: AsRef<Path>>(path: P) -> Vec<u8> {
+        // On Windows, could use std::os::windows::ffi::OsStrExt to encode_wide(),
+        // but we end up with a Vec<u16> instead of a Vec<u8>, so that doesn't
+        // really help.
+        path.as_ref().to_string_lossy().to_string().into_bytes()
+    }
+
+    CString::new(path_to_bytes(path)).map_err(|_| Error::Invalid)
 }
 
 /// Container type for Environment internals.
@@ -667,24 +705,7 @@ impl EnvironmentBuilder {
                     ))?;
                 }
 
-                #[cfg(unix)]
-                fn path_to_bytes<P: AsRef<Path>>(path: P) -> Vec<u8> {
-                    use std::os::unix::ffi::OsStrExt;
-                    path.as_ref().as_os_str().as_bytes().to_vec()
-                }
-
-                #[cfg(windows)]
-                fn path_to_bytes<P: AsRef<Path>>(path: P) -> Vec<u8> {
-                    // On Windows, could use std::os::windows::ffi::OsStrExt to encode_wide(),
-                    // but we end up with a Vec<u16> instead of a Vec<u8>, so that doesn't
-                    // really help.
-                    path.as_ref().to_string_lossy().to_string().into_bytes()
-                }
-
-                let path = match CString::new(path_to_bytes(path)) {
-                    Ok(path) => path,
-                    Err(_) => return Err(Error::Invalid),
-                };
+                let path = path_to_cstring(path)?;
                 mdbx_result(ffi::mdbx_env_open(
                     env,
                     path.as_ptr(),
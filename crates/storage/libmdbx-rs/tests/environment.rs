@@ -154,3 +154,30 @@ fn test_freelist() {
     freelist = env.freelist().unwrap();
     assert!(freelist > 0);
 }
+
+#[test]
+fn test_copy_to() {
+    let dir = tempdir().unwrap();
+    let env = Environment::builder().open(dir.path()).unwrap();
+
+    for i in 0..64 {
+        let mut value = [0u8; 8];
+        LittleEndian::write_u64(&mut value, i);
+        let tx = env.begin_rw_txn().expect("begin_rw_txn");
+        tx.put(tx.open_db(None).unwrap().dbi(), value, value, WriteFlags::default())
+            .expect("tx.put");
+        tx.commit().expect("tx.commit");
+    }
+
+    for compact in [false, true] {
+        let copy_dir = tempdir().unwrap();
+        env.copy_to(copy_dir.path().join("mdbx.dat"), compact).unwrap();
+
+        // Copying onto an existing file should fail
+        assert!(env.copy_to(copy_dir.path().join("mdbx.dat"), compact).is_err());
+
+        let copy =
+            Environment::builder().set_flags(Mode::ReadOnly.into()).open(copy_dir.path()).unwrap();
+        assert_eq!(copy.stat().unwrap().entries(), 64);
+    }
+}
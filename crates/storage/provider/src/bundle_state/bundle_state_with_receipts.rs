@@ -1,6 +1,6 @@
 use crate::{StateChanges, StateReverts};
 use reth_db::{
-    cursor::{DbCursorRO, DbCursorRW},
+    cursor::{DbCursorRO, DbCursorRW, DbDupCursorRW},
     tables,
     transaction::{DbTx, DbTxMut},
 };
@@ -8,15 +8,15 @@ use reth_interfaces::db::DatabaseError;
 use reth_primitives::{
     logs_bloom,
     revm::compat::{into_reth_acc, into_revm_acc},
-    Account, Address, BlockNumber, Bloom, Bytecode, Log, Receipt, Receipts, StorageEntry, B256,
-    U256,
+    Account, Address, BlockNumber, Bloom, Bytecode, ContractCreation, Log, Receipt, Receipts,
+    StorageEntry, B256, U256,
 };
 use reth_trie::HashedPostState;
 use revm::{
     db::{states::BundleState, BundleAccount},
     primitives::AccountInfo,
 };
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 
 pub use revm::db::states::OriginalValuesKnown;
 
@@ -33,6 +33,11 @@ pub struct BundleStateWithReceipts {
     receipts: Receipts,
     /// First block of bundle state.
     first_block: BlockNumber,
+    /// Contracts created in the blocks of the bundle state, if recorded by the executor.
+    contract_creations: Vec<(Address, ContractCreation)>,
+    /// The accounts taking part in the internal calls of each transaction, by block number, if
+    /// recorded by the executor.
+    call_participants: BTreeMap<BlockNumber, Vec<Vec<Address>>>,
 }
 
 /// Type used to initialize revms bundle state.
@@ -48,7 +53,32 @@ pub type RevertsInit = HashMap<BlockNumber, HashMap<Address, AccountRevertInit>>
 impl BundleStateWithReceipts {
     /// Create Bundle State.
     pub fn new(bundle: BundleState, receipts: Receipts, first_block: BlockNumber) -> Self {
-        Self { bundle, receipts, first_block }
+        Self {
+            bundle,
+            receipts,
+            first_block,
+            contract_creations: Vec::new(),
+            call_participants: BTreeMap::new(),
+        }
+    }
+
+    /// Sets the contracts created in the blocks of the bundle state.
+    pub fn with_contract_creations(
+        mut self,
+        contract_creations: Vec<(Address, ContractCreation)>,
+    ) -> Self {
+        self.contract_creations = contract_creations;
+        self
+    }
+
+    /// Sets the accounts taking part in the internal calls of the transactions of the bundle
+    /// state, which are listed by transaction index for each block.
+    pub fn with_call_participants(
+        mut self,
+        call_participants: BTreeMap<BlockNumber, Vec<Vec<Address>>>,
+    ) -> Self {
+        self.call_participants = call_participants;
+        self
     }
 
     /// Create new bundle state with receipts.
@@ -86,7 +116,13 @@ impl BundleStateWithReceipts {
             contracts_init.into_iter().map(|(code_hash, bytecode)| (code_hash, bytecode.0)),
         );
 
-        Self { bundle, receipts, first_block }
+        Self {
+            bundle,
+            receipts,
+            first_block,
+            contract_creations: Vec::new(),
+            call_participants: BTreeMap::new(),
+        }
     }
 
     /// Return revm bundle state.
@@ -191,6 +227,17 @@ impl BundleStateWithReceipts {
         &mut self.receipts
     }
 
+    /// Returns the contracts created in the blocks of the bundle state.
+    pub fn contract_creations(&self) -> &[(Address, ContractCreation)] {
+        &self.contract_creations
+    }
+
+    /// Returns the accounts taking part in the internal calls of the transactions of the bundle
+    /// state, which are listed by transaction index for each block.
+    pub fn call_participants(&self) -> &BTreeMap<BlockNumber, Vec<Vec<Address>>> {
+        &self.call_participants
+    }
+
     /// Return all block receipts
     pub fn receipts_by_block(&self, block_number: BlockNumber) -> &[Option<Receipt>] {
         let Some(index) = self.block_number_to_index(block_number) else { return &[] };
@@ -226,6 +273,10 @@ impl BundleStateWithReceipts {
 
         // remove receipts
         self.receipts.truncate(new_len);
+        // remove contract creations
+        self.contract_creations.retain(|(_, creation)| creation.block_number <= block_number);
+        // remove internal calls
+        self.call_participants.retain(|number, _| *number <= block_number);
         // Revert last n reverts.
         self.bundle.revert(rm_trx);
 
@@ -254,6 +305,8 @@ impl BundleStateWithReceipts {
         let at_idx = higher_state.block_number_to_index(at).unwrap();
         higher_state.receipts = Receipts::from_vec(higher_state.receipts.split_off(at_idx));
         higher_state.bundle.take_n_reverts(at_idx);
+        higher_state.contract_creations.retain(|(_, creation)| creation.block_number >= at);
+        higher_state.call_participants.retain(|number, _| *number >= at);
         higher_state.first_block = at;
 
         (Some(lower_state), higher_state)
@@ -267,6 +320,8 @@ impl BundleStateWithReceipts {
     pub fn extend(&mut self, other: Self) {
         self.bundle.extend(other.bundle);
         self.receipts.extend(other.receipts.receipt_vec);
+        self.contract_creations.extend(other.contract_creations);
+        self.call_participants.extend(other.call_participants);
     }
 
     /// Prepends present the state with the given BundleState.
@@ -322,6 +377,27 @@ impl BundleStateWithReceipts {
 
         StateChanges(plain_state).write_to_db(tx)?;
 
+        if !self.contract_creations.is_empty() {
+            let mut contract_creators_cursor = tx.cursor_write::<tables::ContractCreators>()?;
+            for (address, creation) in self.contract_creations {
+                contract_creators_cursor.upsert(address, creation)?;
+            }
+        }
+
+        if !self.call_participants.is_empty() {
+            let mut call_participants_cursor = tx.cursor_dup_write::<tables::CallParticipants>()?;
+            for (block_number, participants) in self.call_participants {
+                let (_, body_indices) = bodies_cursor
+                    .seek_exact(block_number)?
+                    .unwrap_or_else(|| panic!("body indices for block {block_number} must exist"));
+                for (tx_number, addresses) in body_indices.tx_num_range().zip(participants) {
+                    for address in addresses {
+                        call_participants_cursor.append_dup(tx_number, address)?;
+                    }
+                }
+            }
+        }
+
         Ok(())
     }
 }
@@ -333,7 +409,7 @@ mod tests {
     use reth_db::{
         cursor::{DbCursorRO, DbDupCursorRO},
         database::Database,
-        models::{AccountBeforeTx, BlockNumberAddress},
+        models::{AccountBeforeTx, BlockNumberAddress, StoredBlockBodyIndices},
         tables,
         test_utils::create_test_rw_db,
         transaction::DbTx,
@@ -1104,6 +1180,8 @@ mod tests {
             bundle: BundleState::default(),
             receipts: Receipts::from_vec(vec![vec![Some(Receipt::default()); 2]; 7]),
             first_block: 10,
+            contract_creations: Vec::new(),
+            call_participants: BTreeMap::new(),
         };
 
         let mut this = base.clone();
@@ -1127,6 +1205,73 @@ mod tests {
         assert_eq!(this.receipts.len(), 7);
     }
 
+    #[test]
+    fn contract_creations_follow_blocks() {
+        let creation = |block_number| {
+            (
+                Address::with_last_byte(block_number as u8),
+                ContractCreation { block_number, ..Default::default() },
+            )
+        };
+        let base = BundleStateWithReceipts::new(
+            BundleState::default(),
+            Receipts::from_vec(vec![vec![Some(Receipt::default())]; 3]),
+            10,
+        )
+        .with_contract_creations(vec![creation(10), creation(11), creation(12)]);
+
+        let mut this = base.clone();
+        assert!(this.revert_to(11));
+        assert_eq!(this.contract_creations(), [creation(10), creation(11)]);
+
+        let (lower, higher) = base.clone().split_at(11);
+        assert_eq!(lower.unwrap().contract_creations(), [creation(10)]);
+        assert_eq!(higher.contract_creations(), [creation(11), creation(12)]);
+
+        let provider_factory = create_test_provider_factory();
+        let provider = provider_factory.provider_rw().unwrap();
+        for number in 10..=12 {
+            provider.tx_ref().put::<tables::BlockBodyIndices>(number, Default::default()).unwrap();
+        }
+        base.write_to_db(provider.tx_ref(), OriginalValuesKnown::Yes).unwrap();
+        assert_eq!(
+            provider.tx_ref().get::<tables::ContractCreators>(Address::with_last_byte(12)),
+            Ok(Some(creation(12).1))
+        );
+    }
+
+    #[test]
+    fn call_participants_follow_blocks() {
+        let participants = |block_number| vec![vec![Address::with_last_byte(block_number as u8)]];
+        let base = BundleStateWithReceipts::new(
+            BundleState::default(),
+            Receipts::from_vec(vec![vec![Some(Receipt::default())]; 3]),
+            10,
+        )
+        .with_call_participants((10..=12).map(|number| (number, participants(number))).collect());
+
+        let mut this = base.clone();
+        assert!(this.revert_to(11));
+        assert_eq!(this.call_participants().keys().copied().collect::<Vec<_>>(), [10, 11]);
+
+        let (lower, higher) = base.clone().split_at(11);
+        assert_eq!(lower.unwrap().call_participants().keys().copied().collect::<Vec<_>>(), [10]);
+        assert_eq!(higher.call_participants().keys().copied().collect::<Vec<_>>(), [11, 12]);
+
+        let provider_factory = create_test_provider_factory();
+        let provider = provider_factory.provider_rw().unwrap();
+        for number in 10..=12 {
+            let indices = StoredBlockBodyIndices { first_tx_num: number * 2, tx_count: 2 };
+            provider.tx_ref().put::<tables::BlockBodyIndices>(number, indices).unwrap();
+        }
+        base.write_to_db(provider.tx_ref(), OriginalValuesKnown::Yes).unwrap();
+        assert_eq!(
+            provider.tx_ref().get::<tables::CallParticipants>(24),
+            Ok(Some(Address::with_last_byte(12)))
+        );
+        assert_eq!(provider.tx_ref().get::<tables::CallParticipants>(25), Ok(None));
+    }
+
     #[test]
     fn bundle_state_state_root() {
         type PreState = BTreeMap<Address, (Account, BTreeMap<B256, U256>)>;
@@ -1315,6 +1460,8 @@ mod tests {
             bundle: present_state,
             receipts: Receipts::from_vec(vec![vec![Some(Receipt::default()); 2]; 1]),
             first_block: 2,
+            contract_creations: Vec::new(),
+            call_participants: BTreeMap::new(),
         };
 
         test.prepend_state(previous_state);
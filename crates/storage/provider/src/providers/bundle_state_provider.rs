@@ -1,10 +1,10 @@
 use crate::{
     bundle_state::BundleStateWithReceipts, AccountReader, BlockHashReader, BundleStateDataProvider,
-    StateProvider, StateRootProvider,
+    StateProvider, StateRangeProvider, StateRootProvider,
 };
 use reth_interfaces::provider::{ProviderError, ProviderResult};
 use reth_primitives::{trie::AccountProof, Account, Address, BlockNumber, Bytecode, B256};
-use reth_trie::updates::TrieUpdates;
+use reth_trie::{updates::TrieUpdates, AccountRange, HashedPostState, StorageRange};
 
 /// A state provider that either resolves to data in a wrapped [`crate::BundleStateWithReceipts`],
 /// or an underlying state provider.
@@ -76,6 +76,34 @@ impl<SP: StateProvider, BSDP: BundleStateDataProvider> StateRootProvider
     }
 }
 
+impl<SP: StateProvider, BSDP: BundleStateDataProvider> StateRangeProvider
+    for BundleStateProvider<SP, BSDP>
+{
+    fn account_range(
+        &self,
+        post_state: &HashedPostState,
+        start: B256,
+        limit: usize,
+        storage_limit: usize,
+    ) -> ProviderResult<AccountRange> {
+        let mut state = self.bundle_state_data_provider.state().hash_state_slow();
+        state.extend(post_state.clone());
+        self.state_provider.account_range(&state, start, limit, storage_limit)
+    }
+
+    fn storage_range(
+        &self,
+        post_state: &HashedPostState,
+        hashed_address: B256,
+        start: B256,
+        limit: usize,
+    ) -> ProviderResult<StorageRange> {
+        let mut state = self.bundle_state_data_provider.state().hash_state_slow();
+        state.extend(post_state.clone());
+        self.state_provider.storage_range(&state, hashed_address, start, limit)
+    }
+}
+
 impl<SP: StateProvider, BSDP: BundleStateDataProvider> StateProvider
     for BundleStateProvider<SP, BSDP>
 {
@@ -157,6 +157,11 @@ impl<DB: Database> ProviderFactory<DB> {
 
         let mut state_provider = HistoricalStateProvider::new(provider.into_tx(), block_number);
 
+        // Changesets of snapshotted blocks might have been pruned from the database.
+        if let Some(snapshot_provider) = &self.snapshot_provider {
+            state_provider = state_provider.with_snapshot_provider(snapshot_provider.clone());
+        }
+
         // If we pruned account or storage history, we can't return state on every historical block.
         // Instead, we should cap it at the latest prune checkpoint for corresponding prune segment.
         if let Some(prune_checkpoint_block_number) =
@@ -527,8 +532,9 @@ impl<DB: Database> PruneCheckpointReader for ProviderFactory<DB> {
 mod tests {
     use super::ProviderFactory;
     use crate::{
-        test_utils::create_test_provider_factory, BlockHashReader, BlockNumReader, BlockWriter,
-        HeaderSyncGapProvider, HeaderSyncMode, TransactionsProvider,
+        test_utils::create_test_provider_factory, BadBlockReader, BadBlockWriter, BlockHashReader,
+        BlockNumReader, BlockWriter, HeaderSyncGapProvider, HeaderSyncMode, TransactionsProvider,
+        MAX_BAD_BLOCKS,
     };
     use alloy_rlp::Decodable;
     use assert_matches::assert_matches;
@@ -543,7 +549,8 @@ mod tests {
         RethError,
     };
     use reth_primitives::{
-        hex_literal::hex, ChainSpecBuilder, PruneMode, PruneModes, SealedBlock, TxNumber, B256,
+        hex_literal::hex, BadBlock, ChainSpecBuilder, PruneMode, PruneModes, SealedBlock, TxNumber,
+        B256,
     };
     use std::{ops::RangeInclusive, sync::Arc};
     use tokio::sync::watch;
@@ -737,4 +744,39 @@ mod tests {
             Err(RethError::Provider(ProviderError::InconsistentHeaderGap))
         );
     }
+
+    #[test]
+    fn bad_blocks() {
+        let factory = create_test_provider_factory();
+        let mut rng = generators::rng();
+        let blocks = (0..=MAX_BAD_BLOCKS as u64)
+            .map(|number| BadBlock {
+                block: random_block(&mut rng, number, None, Some(1), None),
+                error: format!("invalid block {number}"),
+            })
+            .collect::<Vec<_>>();
+
+        let provider = factory.provider_rw().unwrap();
+        for bad_block in &blocks {
+            provider.insert_bad_block(bad_block.clone()).unwrap();
+        }
+        // inserting a known bad block again is a no-op
+        provider.insert_bad_block(blocks[MAX_BAD_BLOCKS].clone()).unwrap();
+        provider.commit().unwrap();
+
+        // the oldest bad block was evicted, the most recent one is returned first
+        let provider = factory.provider().unwrap();
+        let bad_blocks = provider.bad_blocks().unwrap();
+        assert_eq!(bad_blocks.len(), MAX_BAD_BLOCKS);
+        assert_eq!(bad_blocks, blocks.iter().skip(1).rev().cloned().collect::<Vec<_>>());
+
+        assert_eq!(provider.bad_block(blocks[0].block.hash()).unwrap(), None);
+        assert_eq!(provider.bad_block(blocks[1].block.hash()).unwrap(), Some(blocks[1].clone()));
+
+        // an evicted bad block can be inserted again
+        let provider = factory.provider_rw().unwrap();
+        provider.insert_bad_block(blocks[0].clone()).unwrap();
+        assert_eq!(provider.bad_block(blocks[0].block.hash()).unwrap(), Some(blocks[0].clone()));
+        assert_eq!(provider.bad_block(blocks[1].block.hash()).unwrap(), None);
+    }
 }
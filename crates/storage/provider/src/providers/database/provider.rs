@@ -5,11 +5,13 @@ use crate::{
     traits::{
         AccountExtReader, BlockSource, ChangeSetReader, ReceiptProvider, StageCheckpointWriter,
     },
-    AccountReader, BlockExecutionWriter, BlockHashReader, BlockNumReader, BlockReader, BlockWriter,
-    Chain, EvmEnvProvider, HashingWriter, HeaderProvider, HeaderSyncGap, HeaderSyncGapProvider,
-    HeaderSyncMode, HistoryWriter, OriginalValuesKnown, ProviderError, PruneCheckpointReader,
-    PruneCheckpointWriter, StageCheckpointReader, StorageReader, TransactionVariant,
-    TransactionsProvider, TransactionsProviderExt, WithdrawalsProvider,
+    AccountReader, AddressAppearancesReader, BadBlockReader, BadBlockWriter, BlockExecutionWriter,
+    BlockHashReader, BlockNumReader, BlockReader, BlockWriter, Chain, ContractCreatorReader,
+    EvmEnvProvider, HashingWriter, HeaderProvider, HeaderSyncGap, HeaderSyncGapProvider,
+    HeaderSyncMode, HistoryWriter, LogIndexReader, OriginalValuesKnown, ProviderError,
+    PruneCheckpointReader, PruneCheckpointWriter, StageCheckpointReader, StorageReader,
+    TransactionVariant, TransactionsProvider, TransactionsProviderExt, WithdrawalsProvider,
+    MAX_BAD_BLOCKS,
 };
 use itertools::{izip, Itertools};
 use reth_db::{
@@ -23,7 +25,7 @@ use reth_db::{
     table::{Table, TableRow},
     tables,
     transaction::{DbTx, DbTxMut},
-    BlockNumberList, DatabaseError,
+    BlockNumberList, DatabaseError, TxNumberList,
 };
 use reth_interfaces::{
     p2p::headers::downloader::SyncTarget,
@@ -36,11 +38,12 @@ use reth_primitives::{
     revm::{config::revm_spec, env::fill_block_env},
     stage::{StageCheckpoint, StageId},
     trie::Nibbles,
-    Account, Address, Block, BlockHash, BlockHashOrNumber, BlockNumber, BlockWithSenders,
-    ChainInfo, ChainSpec, GotExpected, Hardfork, Head, Header, PruneCheckpoint, PruneModes,
-    PruneSegment, Receipt, SealedBlock, SealedBlockWithSenders, SealedHeader, SnapshotSegment,
-    StorageEntry, TransactionMeta, TransactionSigned, TransactionSignedEcRecovered,
-    TransactionSignedNoHash, TxHash, TxNumber, Withdrawal, Withdrawals, B256, U256,
+    Account, Address, BadBlock, Block, BlockHash, BlockHashOrNumber, BlockNumber, BlockWithSenders,
+    ChainInfo, ChainSpec, ContractCreation, GotExpected, Hardfork, Head, Header, PruneCheckpoint,
+    PruneModes, PruneSegment, Receipt, SealedBlock, SealedBlockWithSenders, SealedHeader,
+    SnapshotSegment, StorageEntry, TransactionKind, TransactionMeta, TransactionSigned,
+    TransactionSignedEcRecovered, TransactionSignedNoHash, TxHash, TxNumber, Withdrawal,
+    Withdrawals, B256, U256,
 };
 use reth_trie::{prefix_set::PrefixSetMut, updates::TrieUpdates, HashedPostState, StateRoot};
 use revm::primitives::{BlockEnv, CfgEnvWithHandlerCfg, SpecId};
@@ -281,11 +284,10 @@ impl<TX: DbTx> DatabaseProvider<TX> {
 
         if let Some(snapshot_provider) = &self.snapshot_provider {
             // If there is, check the maximum block or transaction number of the segment.
-            if let Some(snapshot_upper_bound) = match segment {
-                SnapshotSegment::Headers => snapshot_provider.get_highest_snapshot_block(segment),
-                SnapshotSegment::Transactions | SnapshotSegment::Receipts => {
-                    snapshot_provider.get_highest_snapshot_tx(segment)
-                }
+            if let Some(snapshot_upper_bound) = if segment.is_block_based() {
+                snapshot_provider.get_highest_snapshot_block(segment)
+            } else {
+                snapshot_provider.get_highest_snapshot_tx(segment)
             } {
                 if block_or_tx_range.start <= snapshot_upper_bound {
                     let end = block_or_tx_range.end.min(snapshot_upper_bound + 1);
@@ -328,11 +330,10 @@ impl<TX: DbTx> DatabaseProvider<TX> {
     {
         if let Some(provider) = &self.snapshot_provider {
             // If there is, check the maximum block or transaction number of the segment.
-            let snapshot_upper_bound = match segment {
-                SnapshotSegment::Headers => provider.get_highest_snapshot_block(segment),
-                SnapshotSegment::Transactions | SnapshotSegment::Receipts => {
-                    provider.get_highest_snapshot_tx(segment)
-                }
+            let snapshot_upper_bound = if segment.is_block_based() {
+                provider.get_highest_snapshot_block(segment)
+            } else {
+                provider.get_highest_snapshot_tx(segment)
             };
 
             if snapshot_upper_bound
@@ -344,6 +345,40 @@ impl<TX: DbTx> DatabaseProvider<TX> {
         fetch_from_database()
     }
 
+    /// Passes the blocks of the range whose changesets are snapshotted to `fetch_from_snapshot`,
+    /// and returns the rest of the range, whose changesets are read from the database.
+    ///
+    /// Returns [ProviderError::StateAtBlockPruned] if the changesets of a block in the range have
+    /// been pruned by `prune_segment` and aren't available in the snapshots either.
+    fn changeset_range_with_snapshot(
+        &self,
+        segment: SnapshotSegment,
+        prune_segment: PruneSegment,
+        range: RangeInclusive<BlockNumber>,
+        mut fetch_from_snapshot: impl FnMut(&SnapshotProvider, BlockNumber) -> ProviderResult<()>,
+    ) -> ProviderResult<RangeInclusive<BlockNumber>> {
+        let (mut start, end) = range.into_inner();
+
+        if let Some(provider) = &self.snapshot_provider {
+            if let Some(highest) = provider.get_highest_snapshot_block(segment) {
+                for block in start..=end.min(highest) {
+                    fetch_from_snapshot(provider, block)?;
+                }
+                start = start.max(highest.saturating_add(1));
+            }
+        }
+
+        if start <= end &&
+            self.get_prune_checkpoint(prune_segment)?
+                .and_then(|checkpoint| checkpoint.block_number)
+                .is_some_and(|pruned| start <= pruned)
+        {
+            return Err(ProviderError::StateAtBlockPruned(start))
+        }
+
+        Ok(start..=end)
+    }
+
     fn transactions_by_tx_range_with_cursor<C>(
         &self,
         range: impl RangeBounds<TxNumber>,
@@ -486,7 +521,13 @@ impl<TX: DbTxMut + DbTx> DatabaseProvider<TX> {
         }
 
         if UNWIND {
+            // remove the internal calls recorded for the unwound transactions.
+            self.get_or_take::<tables::CallParticipants, UNWIND>(
+                from_transaction_num..=to_transaction_num,
+            )?;
+
             // iterate over local plain state remove all account and all storages.
+            self.unwind_contract_creators(start_block_number, state.keys().copied())?;
             for (address, (old_account, new_account, storage)) in state.iter() {
                 // revert account if needed.
                 if old_account != new_account {
@@ -844,6 +885,28 @@ impl<TX: DbTxMut + DbTx> DatabaseProvider<TX> {
         Ok(())
     }
 
+    /// Removes the creators of the given accounts from [tables::ContractCreators] if they were
+    /// created at or after the given block.
+    ///
+    /// Every contract has an account changeset in the block it was created in, so passing the
+    /// accounts of the unwound changesets removes all contracts created in the unwound blocks.
+    pub fn unwind_contract_creators(
+        &self,
+        from_block: BlockNumber,
+        addresses: impl IntoIterator<Item = Address>,
+    ) -> Result<(), DatabaseError> {
+        let mut cursor = self.tx.cursor_write::<tables::ContractCreators>()?;
+        for address in addresses {
+            if cursor
+                .seek_exact(address)?
+                .is_some_and(|(_, creation)| creation.block_number >= from_block)
+            {
+                cursor.delete_current()?;
+            }
+        }
+        Ok(())
+    }
+
     /// Prune the table for the specified pre-sorted key iterator.
     ///
     /// Returns number of rows pruned.
@@ -966,6 +1029,36 @@ impl<TX: DbTxMut + DbTx> DatabaseProvider<TX> {
         }
         Ok(())
     }
+
+    /// Unwind a sharded index keyed by [`ShardedKey`].
+    ///
+    /// For each partial key, all indices greater than or equal to the lowest given index are
+    /// removed. The indices of each partial key must be sorted.
+    fn unwind_sharded_index<P, T>(&self, index_updates: BTreeMap<P, Vec<u64>>) -> ProviderResult<()>
+    where
+        P: Copy + PartialEq,
+        T: Table<Key = ShardedKey<P>, Value = BlockNumberList>,
+    {
+        let mut cursor = self.tx.cursor_write::<T>()?;
+        for (partial_key, indices) in index_updates {
+            let partial_shard = unwind_history_shards::<_, T, _>(
+                &mut cursor,
+                ShardedKey::last(partial_key),
+                indices[0],
+                |sharded_key| sharded_key.key == partial_key,
+            )?;
+
+            // Check the last returned partial shard.
+            // If it's not empty, the shard needs to be reinserted.
+            if !partial_shard.is_empty() {
+                cursor.insert(
+                    ShardedKey::last(partial_key),
+                    BlockNumberList::new_pre_sorted(partial_shard),
+                )?;
+            }
+        }
+        Ok(())
+    }
 }
 
 impl<TX: DbTx> AccountReader for DatabaseProvider<TX> {
@@ -1003,10 +1096,23 @@ impl<TX: DbTx> AccountExtReader for DatabaseProvider<TX> {
         &self,
         range: RangeInclusive<BlockNumber>,
     ) -> ProviderResult<BTreeMap<Address, Vec<u64>>> {
+        let mut snapshotted = BTreeMap::<Address, Vec<u64>>::new();
+        let range = self.changeset_range_with_snapshot(
+            SnapshotSegment::AccountChangeSets,
+            PruneSegment::AccountChangeSets,
+            range,
+            |snapshot, block| {
+                for account in snapshot.account_block_changeset(block)? {
+                    snapshotted.entry(account.address).or_default().push(block);
+                }
+                Ok(())
+            },
+        )?;
+
         let mut changeset_cursor = self.tx.cursor_read::<tables::AccountChangeSet>()?;
 
         let account_transitions = changeset_cursor.walk_range(range)?.try_fold(
-            BTreeMap::new(),
+            snapshotted,
             |mut accounts: BTreeMap<Address, Vec<u64>>, entry| -> ProviderResult<_> {
                 let (index, account) = entry?;
                 accounts.entry(account.address).or_default().push(index);
@@ -1023,15 +1129,290 @@ impl<TX: DbTx> ChangeSetReader for DatabaseProvider<TX> {
         &self,
         block_number: BlockNumber,
     ) -> ProviderResult<Vec<AccountBeforeTx>> {
-        let range = block_number..=block_number;
-        self.tx
-            .cursor_read::<tables::AccountChangeSet>()?
+        self.get_with_snapshot(
+            SnapshotSegment::AccountChangeSets,
+            block_number,
+            |snapshot| snapshot.account_block_changeset(block_number).map(Some),
+            || {
+                let range = block_number..=block_number;
+                self.tx
+                    .cursor_read::<tables::AccountChangeSet>()?
+                    .walk_range(range)?
+                    .map(|result| -> ProviderResult<_> {
+                        let (_, account_before) = result?;
+                        Ok(account_before)
+                    })
+                    .collect::<ProviderResult<_>>()
+                    .map(Some)
+            },
+        )
+        .map(Option::unwrap_or_default)
+    }
+
+    fn storage_block_changeset(
+        &self,
+        block_number: BlockNumber,
+    ) -> ProviderResult<Vec<(Address, StorageEntry)>> {
+        self.get_with_snapshot(
+            SnapshotSegment::StorageChangeSets,
+            block_number,
+            |snapshot| snapshot.storage_block_changeset(block_number).map(Some),
+            || {
+                let range = block_number..=block_number;
+                self.tx
+                    .cursor_read::<tables::StorageChangeSet>()?
+                    .walk_range(BlockNumberAddress::range(range))?
+                    .map(|result| -> ProviderResult<_> {
+                        let (BlockNumberAddress((_, address)), storage_entry) = result?;
+                        Ok((address, storage_entry))
+                    })
+                    .collect::<ProviderResult<_>>()
+                    .map(Some)
+            },
+        )
+        .map(Option::unwrap_or_default)
+    }
+}
+
+impl<TX: DbTx> AddressAppearancesReader for DatabaseProvider<TX> {
+    fn address_appearances_with_range(
+        &self,
+        range: RangeInclusive<BlockNumber>,
+    ) -> ProviderResult<BTreeMap<Address, Vec<TxNumber>>> {
+        let mut appearances: BTreeMap<Address, Vec<TxNumber>> = BTreeMap::new();
+
+        let body_indices = self
+            .tx
+            .cursor_read::<tables::BlockBodyIndices>()?
             .walk_range(range)?
-            .map(|result| -> ProviderResult<_> {
-                let (_, account_before) = result?;
-                Ok(account_before)
-            })
-            .collect()
+            .collect::<Result<Vec<_>, _>>()?;
+        let (Some((_, first)), Some((_, last))) = (body_indices.first(), body_indices.last())
+        else {
+            return Ok(appearances)
+        };
+
+        // Transactions are walked in ascending order, so it's enough to check the last pushed
+        // transaction number to avoid duplicates.
+        let mut append = |address: Address, tx_number: TxNumber| {
+            let tx_numbers = appearances.entry(address).or_default();
+            if tx_numbers.last() != Some(&tx_number) {
+                tx_numbers.push(tx_number);
+            }
+        };
+
+        let mut senders_cursor = self.tx.cursor_read::<tables::TxSenders>()?;
+        let mut receipts_cursor = self.tx.cursor_read::<tables::Receipts>()?;
+        let mut call_participants_cursor = self.tx.cursor_read::<tables::CallParticipants>()?;
+        let mut transactions_cursor = self.tx.cursor_read::<tables::Transactions>()?;
+        for entry in transactions_cursor.walk_range(first.first_tx_num()..last.next_tx_num())? {
+            let (tx_number, transaction) = entry?;
+
+            // Senders might have been pruned, recover them in this case.
+            let sender = match senders_cursor.seek_exact(tx_number)? {
+                Some((_, sender)) => sender,
+                None => transaction.recover_signer().ok_or(ProviderError::SenderRecoveryError)?,
+            };
+            append(sender, tx_number);
+
+            match transaction.kind() {
+                TransactionKind::Call(to) => append(*to, tx_number),
+                TransactionKind::Create => append(sender.create(transaction.nonce()), tx_number),
+            }
+
+            // Receipts might have been pruned, in which case the emitters of logs are only
+            // indexed if they take part in a recorded internal call.
+            if let Some((_, receipt)) = receipts_cursor.seek_exact(tx_number)? {
+                for log in &receipt.logs {
+                    append(log.address, tx_number);
+                }
+            }
+
+            for entry in call_participants_cursor.walk_range(tx_number..=tx_number)? {
+                let (_, address) = entry?;
+                append(address, tx_number);
+            }
+        }
+
+        Ok(appearances)
+    }
+
+    fn address_appearances_before(
+        &self,
+        address: Address,
+        tx_number: TxNumber,
+        limit: usize,
+    ) -> ProviderResult<Vec<TxNumber>> {
+        let mut tx_numbers = Vec::new();
+        if limit == 0 {
+            return Ok(tx_numbers)
+        }
+
+        // The last shard of the address has `u64::MAX` as its highest transaction number, so the
+        // seek always lands on a shard of the address if it has any appearances at all.
+        let mut cursor = self.tx.cursor_read::<tables::AddressAppearances>()?;
+        let mut item = cursor.seek(ShardedKey::new(address, tx_number))?;
+        while let Some((sharded_key, list)) = item {
+            if sharded_key.key != address {
+                break
+            }
+
+            let list = list.iter(0).map(|i| i as u64).collect::<Vec<_>>();
+            tx_numbers.extend(
+                list.into_iter().rev().filter(|i| *i < tx_number).take(limit - tx_numbers.len()),
+            );
+            if tx_numbers.len() == limit {
+                break
+            }
+
+            item = cursor.prev()?;
+        }
+
+        Ok(tx_numbers)
+    }
+
+    fn address_appearances_after(
+        &self,
+        address: Address,
+        tx_number: TxNumber,
+        limit: usize,
+    ) -> ProviderResult<Vec<TxNumber>> {
+        let mut tx_numbers = Vec::new();
+        if limit == 0 {
+            return Ok(tx_numbers)
+        }
+
+        let mut cursor = self.tx.cursor_read::<tables::AddressAppearances>()?;
+        let mut item = cursor.seek(ShardedKey::new(address, tx_number))?;
+        while let Some((sharded_key, list)) = item {
+            if sharded_key.key != address {
+                break
+            }
+
+            tx_numbers.extend(
+                list.iter(0)
+                    .map(|i| i as u64)
+                    .filter(|i| *i >= tx_number)
+                    .take(limit - tx_numbers.len()),
+            );
+            if tx_numbers.len() == limit {
+                break
+            }
+
+            item = cursor.next()?;
+        }
+
+        Ok(tx_numbers)
+    }
+}
+
+impl<TX: DbTx> ContractCreatorReader for DatabaseProvider<TX> {
+    fn contract_creation(&self, address: Address) -> ProviderResult<Option<ContractCreation>> {
+        Ok(self.tx.get::<tables::ContractCreators>(address)?)
+    }
+}
+
+impl<TX: DbTx> DatabaseProvider<TX> {
+    /// Returns the indices of the given range from a sharded index, starting at the shard of
+    /// `start_key`.
+    fn sharded_index_range<P, T>(
+        &self,
+        start_key: ShardedKey<P>,
+        range: RangeInclusive<u64>,
+    ) -> ProviderResult<Vec<u64>>
+    where
+        P: Copy + PartialEq,
+        T: Table<Key = ShardedKey<P>, Value = BlockNumberList>,
+    {
+        let partial_key = start_key.key;
+        let mut indices = Vec::new();
+
+        // The first shard with a highest index greater than or equal to the start of the range
+        // contains the first index of the range, if any.
+        let mut cursor = self.tx.cursor_read::<T>()?;
+        let mut item = cursor.seek(start_key)?;
+        while let Some((sharded_key, list)) = item {
+            if sharded_key.key != partial_key {
+                break
+            }
+
+            for index in list.iter(0).map(|i| i as u64) {
+                if index > *range.end() {
+                    return Ok(indices)
+                }
+                if index >= *range.start() {
+                    indices.push(index);
+                }
+            }
+
+            item = cursor.next()?;
+        }
+
+        Ok(indices)
+    }
+}
+
+impl<TX: DbTx> LogIndexReader for DatabaseProvider<TX> {
+    fn log_addresses_and_topics_with_range(
+        &self,
+        range: RangeInclusive<BlockNumber>,
+    ) -> ProviderResult<(BTreeMap<Address, Vec<BlockNumber>>, BTreeMap<B256, Vec<BlockNumber>>)>
+    {
+        let mut addresses: BTreeMap<Address, Vec<BlockNumber>> = BTreeMap::new();
+        let mut topics: BTreeMap<B256, Vec<BlockNumber>> = BTreeMap::new();
+
+        // Blocks are walked in ascending order, so it's enough to check the last pushed block
+        // number to avoid duplicates.
+        fn append<K: Ord>(
+            index: &mut BTreeMap<K, Vec<BlockNumber>>,
+            key: K,
+            block_number: BlockNumber,
+        ) {
+            let block_numbers = index.entry(key).or_default();
+            if block_numbers.last() != Some(&block_number) {
+                block_numbers.push(block_number);
+            }
+        }
+
+        let mut body_indices_cursor = self.tx.cursor_read::<tables::BlockBodyIndices>()?;
+        for entry in body_indices_cursor.walk_range(range)? {
+            let (block_number, body_indices) = entry?;
+            for receipt in self.receipts_by_tx_range(body_indices.tx_num_range())? {
+                for log in &receipt.logs {
+                    append(&mut addresses, log.address, block_number);
+                    for topic in &log.topics {
+                        append(&mut topics, *topic, block_number);
+                    }
+                }
+            }
+        }
+
+        Ok((addresses, topics))
+    }
+
+    fn log_index_highest_block(&self) -> ProviderResult<Option<BlockNumber>> {
+        Ok(self.get_stage_checkpoint(StageId::IndexLogs)?.map(|checkpoint| checkpoint.block_number))
+    }
+
+    fn log_address_blocks(
+        &self,
+        address: Address,
+        range: RangeInclusive<BlockNumber>,
+    ) -> ProviderResult<Vec<BlockNumber>> {
+        self.sharded_index_range::<_, tables::LogAddressIndex>(
+            ShardedKey::new(address, *range.start()),
+            range,
+        )
+    }
+
+    fn log_topic_blocks(
+        &self,
+        topic: B256,
+        range: RangeInclusive<BlockNumber>,
+    ) -> ProviderResult<Vec<BlockNumber>> {
+        self.sharded_index_range::<_, tables::LogTopicIndex>(
+            ShardedKey::new(topic, *range.start()),
+            range,
+        )
     }
 }
 
@@ -1878,6 +2259,19 @@ impl<TX: DbTxMut> StageCheckpointWriter for DatabaseProvider<TX> {
             )?;
         }
 
+        // optional stages are only updated if they were enabled at some point
+        for stage_id in StageId::OPTIONAL {
+            if let Some((_, checkpoint)) = cursor.seek_exact(stage_id.to_string())? {
+                cursor.upsert(
+                    stage_id.to_string(),
+                    StageCheckpoint {
+                        block_number,
+                        ..if drop_stage_checkpoint { Default::default() } else { checkpoint }
+                    },
+                )?;
+            }
+        }
+
         Ok(())
     }
 }
@@ -1926,11 +2320,24 @@ impl<TX: DbTx> StorageReader for DatabaseProvider<TX> {
         &self,
         range: RangeInclusive<BlockNumber>,
     ) -> ProviderResult<BTreeMap<(Address, B256), Vec<u64>>> {
+        let mut snapshotted = BTreeMap::<(Address, B256), Vec<u64>>::new();
+        let range = self.changeset_range_with_snapshot(
+            SnapshotSegment::StorageChangeSets,
+            PruneSegment::StorageChangeSets,
+            range,
+            |snapshot, block| {
+                for (address, storage) in snapshot.storage_block_changeset(block)? {
+                    snapshotted.entry((address, storage.key)).or_default().push(block);
+                }
+                Ok(())
+            },
+        )?;
+
         let mut changeset_cursor = self.tx.cursor_read::<tables::StorageChangeSet>()?;
 
         let storage_changeset_lists =
             changeset_cursor.walk_range(BlockNumberAddress::range(range))?.try_fold(
-                BTreeMap::new(),
+                snapshotted,
                 |mut storages: BTreeMap<(Address, B256), Vec<u64>>, entry| -> ProviderResult<_> {
                     let (index, storage) = entry?;
                     storages
@@ -2156,10 +2563,22 @@ impl<TX: DbTxMut + DbTx> HistoryWriter for DatabaseProvider<TX> {
 
         // storage history stage
         {
-            let indices = self.changed_storages_and_blocks_with_range(range)?;
+            let indices = self.changed_storages_and_blocks_with_range(range.clone())?;
             self.insert_storage_history_index(indices)?;
         }
 
+        // address appearances stage, only if the index is maintained
+        if self.get_stage_checkpoint(StageId::IndexAddressAppearances)?.is_some() {
+            let appearances = self.address_appearances_with_range(range.clone())?;
+            self.insert_address_appearances_index(appearances)?;
+        }
+
+        // logs stage, only if the index is maintained
+        if self.get_stage_checkpoint(StageId::IndexLogs)?.is_some() {
+            let (addresses, topics) = self.log_addresses_and_topics_with_range(range)?;
+            self.insert_log_index(addresses, topics)?;
+        }
+
         Ok(())
     }
 
@@ -2257,6 +2676,66 @@ impl<TX: DbTxMut + DbTx> HistoryWriter for DatabaseProvider<TX> {
         let changesets = last_indices.len();
         Ok(changesets)
     }
+
+    fn insert_address_appearances_index(
+        &self,
+        address_appearances: BTreeMap<Address, Vec<TxNumber>>,
+    ) -> ProviderResult<()> {
+        self.append_history_index::<_, tables::AddressAppearances>(
+            address_appearances,
+            ShardedKey::new,
+        )
+    }
+
+    fn unwind_address_appearances_index(
+        &self,
+        range: RangeInclusive<BlockNumber>,
+    ) -> ProviderResult<usize> {
+        let appearances = self.address_appearances_with_range(range)?;
+
+        let mut cursor = self.tx.cursor_write::<tables::AddressAppearances>()?;
+        for (&address, tx_numbers) in &appearances {
+            // Transaction numbers are sorted, so the first one is the lowest to remove.
+            let rem_index = tx_numbers[0];
+            let partial_shard = unwind_history_shards::<_, tables::AddressAppearances, _>(
+                &mut cursor,
+                ShardedKey::last(address),
+                rem_index,
+                |sharded_key| sharded_key.key == address,
+            )?;
+
+            // Check the last returned partial shard.
+            // If it's not empty, the shard needs to be reinserted.
+            if !partial_shard.is_empty() {
+                cursor.insert(
+                    ShardedKey::last(address),
+                    TxNumberList::new_pre_sorted(partial_shard),
+                )?;
+            }
+        }
+
+        Ok(appearances.len())
+    }
+
+    fn insert_log_index(
+        &self,
+        addresses: BTreeMap<Address, Vec<BlockNumber>>,
+        topics: BTreeMap<B256, Vec<BlockNumber>>,
+    ) -> ProviderResult<()> {
+        self.append_history_index::<_, tables::LogAddressIndex>(addresses, ShardedKey::new)?;
+        self.append_history_index::<_, tables::LogTopicIndex>(topics, ShardedKey::new)
+    }
+
+    fn unwind_log_index(&self, range: RangeInclusive<BlockNumber>) -> ProviderResult<usize> {
+        let (addresses, topics) = self.log_addresses_and_topics_with_range(range)?;
+        let walked = addresses.len() + topics.len();
+
+        // Block numbers are sorted, so the first one is the lowest to remove.
+        self.unwind_sharded_index::<_, tables::LogAddressIndex>(addresses)?;
+        self.unwind_sharded_index::<_, tables::LogTopicIndex>(topics)?;
+
+        Ok(walked)
+    }
 }
 
 impl<TX: DbTxMut + DbTx> BlockExecutionWriter for DatabaseProvider<TX> {
@@ -2302,6 +2781,16 @@ impl<TX: DbTxMut + DbTx> BlockExecutionWriter for DatabaseProvider<TX> {
             // Unwind storage history indices.
             self.unwind_storage_history_indices(storage_range)?;
 
+            // Unwind address appearances index, only if the index is maintained.
+            if self.get_stage_checkpoint(StageId::IndexAddressAppearances)?.is_some() {
+                self.unwind_address_appearances_index(range.clone())?;
+            }
+
+            // Unwind log index, only if the index is maintained.
+            if self.get_stage_checkpoint(StageId::IndexLogs)?.is_some() {
+                self.unwind_log_index(range.clone())?;
+            }
+
             // Calculate the reverted merkle root.
             // This is the same as `StateRoot::incremental_root_with_updates`, only the prefix sets
             // are pre-loaded.
@@ -2559,6 +3048,48 @@ impl<TX: DbTxMut> PruneCheckpointWriter for DatabaseProvider<TX> {
     }
 }
 
+impl<TX: DbTx> BadBlockReader for DatabaseProvider<TX> {
+    fn bad_blocks(&self) -> ProviderResult<Vec<BadBlock>> {
+        Ok(self
+            .tx
+            .cursor_read::<tables::BadBlocks>()?
+            .walk_back(None)?
+            .map(|entry| entry.map(|(_, bad_block)| bad_block.into()))
+            .collect::<Result<Vec<_>, _>>()?)
+    }
+
+    fn bad_block(&self, hash: BlockHash) -> ProviderResult<Option<BadBlock>> {
+        let Some(key) = self.tx.get::<tables::BadBlockHashes>(hash)? else { return Ok(None) };
+        Ok(self.tx.get::<tables::BadBlocks>(key)?.map(Into::into))
+    }
+}
+
+impl<TX: DbTxMut + DbTx> BadBlockWriter for DatabaseProvider<TX> {
+    fn insert_bad_block(&self, bad_block: BadBlock) -> ProviderResult<()> {
+        // The same invalid block can be received several times
+        let hash = bad_block.block.hash();
+        if self.tx.get::<tables::BadBlockHashes>(hash)?.is_some() {
+            return Ok(())
+        }
+
+        let mut cursor = self.tx.cursor_write::<tables::BadBlocks>()?;
+        let next_key = cursor.last()?.map(|(key, _)| key + 1).unwrap_or_default();
+        cursor.append(next_key, bad_block.into())?;
+        self.tx.put::<tables::BadBlockHashes>(hash, next_key)?;
+
+        // Evict the oldest bad blocks
+        let mut entries = self.tx.entries::<tables::BadBlocks>()?;
+        while entries > MAX_BAD_BLOCKS {
+            let Some((_, evicted)) = cursor.first()? else { break };
+            self.tx.delete::<tables::BadBlockHashes>(evicted.hash, None)?;
+            cursor.delete_current()?;
+            entries -= 1;
+        }
+
+        Ok(())
+    }
+}
+
 fn range_size_hint(range: &impl RangeBounds<TxNumber>) -> Option<usize> {
     let start = match range.start_bound().cloned() {
         Bound::Included(start) => start,
@@ -1,10 +1,12 @@
 use crate::{
-    AccountReader, BlockHashReader, BlockIdReader, BlockNumReader, BlockReader, BlockReaderIdExt,
-    BlockchainTreePendingStateProvider, BundleStateDataProvider, CanonChainTracker,
-    CanonStateNotifications, CanonStateSubscriptions, ChainSpecProvider, ChangeSetReader,
-    DatabaseProviderFactory, EvmEnvProvider, HeaderProvider, ProviderError, PruneCheckpointReader,
-    ReceiptProvider, ReceiptProviderIdExt, StageCheckpointReader, StateProviderBox,
-    StateProviderFactory, TransactionVariant, TransactionsProvider, WithdrawalsProvider,
+    AccountReader, AddressAppearancesReader, BadBlockReader, BlockHashReader, BlockIdReader,
+    BlockNumReader, BlockReader, BlockReaderIdExt, BlockValidationProvider,
+    BlockchainTreePendingStateProvider, BundleStateDataProvider, BundleStateWithReceipts,
+    CanonChainTracker, CanonStateNotifications, CanonStateSubscriptions, ChainSpecProvider,
+    ChangeSetReader, ContractCreatorReader, DatabaseProviderFactory, EvmEnvProvider,
+    HeaderProvider, LogIndexReader, ProviderError, PruneCheckpointReader, ReceiptProvider,
+    ReceiptProviderIdExt, StageCheckpointReader, StateProviderBox, StateProviderFactory,
+    TransactionVariant, TransactionsProvider, WithdrawalsProvider,
 };
 use reth_db::{database::Database, models::StoredBlockBodyIndices};
 use reth_interfaces::{
@@ -16,11 +18,11 @@ use reth_interfaces::{
 use reth_node_api::ConfigureEvmEnv;
 use reth_primitives::{
     stage::{StageCheckpoint, StageId},
-    Account, Address, Block, BlockHash, BlockHashOrNumber, BlockId, BlockNumHash, BlockNumber,
-    BlockNumberOrTag, BlockWithSenders, ChainInfo, ChainSpec, Header, PruneCheckpoint,
-    PruneSegment, Receipt, SealedBlock, SealedBlockWithSenders, SealedHeader, TransactionMeta,
-    TransactionSigned, TransactionSignedNoHash, TxHash, TxNumber, Withdrawal, Withdrawals, B256,
-    U256,
+    Account, Address, BadBlock, Block, BlockHash, BlockHashOrNumber, BlockId, BlockNumHash,
+    BlockNumber, BlockNumberOrTag, BlockWithSenders, ChainInfo, ChainSpec, ContractCreation,
+    Header, PruneCheckpoint, PruneSegment, Receipt, SealedBlock, SealedBlockWithSenders,
+    SealedHeader, StorageEntry, TransactionMeta, TransactionSigned, TransactionSignedNoHash,
+    TxHash, TxNumber, Withdrawal, Withdrawals, B256, U256,
 };
 use revm::primitives::{BlockEnv, CfgEnvWithHandlerCfg};
 use std::{
@@ -47,7 +49,8 @@ pub use bundle_state_provider::BundleStateProvider;
 pub use database::*;
 use reth_db::models::AccountBeforeTx;
 use reth_interfaces::blockchain_tree::{
-    error::InsertBlockError, BlockValidationKind, CanonicalOutcome, InsertPayloadOk,
+    error::{InsertBlockError, InsertBlockErrorKind},
+    BlockValidationKind, CanonicalOutcome, InsertPayloadOk,
 };
 
 /// The main type for interacting with the blockchain.
@@ -664,6 +667,10 @@ where
     fn unwind(&self, unwind_to: BlockNumber) -> RethResult<()> {
         self.tree.unwind(unwind_to)
     }
+
+    fn insert_bad_block(&self, bad_block: BadBlock) -> RethResult<()> {
+        self.tree.insert_bad_block(bad_block)
+    }
 }
 
 impl<DB, Tree> BlockchainTreeViewer for BlockchainProvider<DB, Tree>
@@ -861,6 +868,19 @@ where
     }
 }
 
+impl<DB, Tree> BlockValidationProvider for BlockchainProvider<DB, Tree>
+where
+    DB: Send + Sync,
+    Tree: BlockValidationProvider,
+{
+    fn validate_block_detached(
+        &self,
+        block: SealedBlockWithSenders,
+    ) -> Result<BundleStateWithReceipts, InsertBlockErrorKind> {
+        self.tree.validate_block_detached(block)
+    }
+}
+
 impl<DB, Tree> CanonStateSubscriptions for BlockchainProvider<DB, Tree>
 where
     DB: Send + Sync,
@@ -882,6 +902,102 @@ where
     ) -> ProviderResult<Vec<AccountBeforeTx>> {
         self.database.provider()?.account_block_changeset(block_number)
     }
+
+    fn storage_block_changeset(
+        &self,
+        block_number: BlockNumber,
+    ) -> ProviderResult<Vec<(Address, StorageEntry)>> {
+        self.database.provider()?.storage_block_changeset(block_number)
+    }
+}
+
+impl<DB, Tree> AddressAppearancesReader for BlockchainProvider<DB, Tree>
+where
+    DB: Database,
+    Tree: Sync + Send,
+{
+    fn address_appearances_with_range(
+        &self,
+        range: RangeInclusive<BlockNumber>,
+    ) -> ProviderResult<BTreeMap<Address, Vec<TxNumber>>> {
+        self.database.provider()?.address_appearances_with_range(range)
+    }
+
+    fn address_appearances_before(
+        &self,
+        address: Address,
+        tx_number: TxNumber,
+        limit: usize,
+    ) -> ProviderResult<Vec<TxNumber>> {
+        self.database.provider()?.address_appearances_before(address, tx_number, limit)
+    }
+
+    fn address_appearances_after(
+        &self,
+        address: Address,
+        tx_number: TxNumber,
+        limit: usize,
+    ) -> ProviderResult<Vec<TxNumber>> {
+        self.database.provider()?.address_appearances_after(address, tx_number, limit)
+    }
+}
+
+impl<DB, Tree> LogIndexReader for BlockchainProvider<DB, Tree>
+where
+    DB: Database,
+    Tree: Sync + Send,
+{
+    fn log_addresses_and_topics_with_range(
+        &self,
+        range: RangeInclusive<BlockNumber>,
+    ) -> ProviderResult<(BTreeMap<Address, Vec<BlockNumber>>, BTreeMap<B256, Vec<BlockNumber>>)>
+    {
+        self.database.provider()?.log_addresses_and_topics_with_range(range)
+    }
+
+    fn log_index_highest_block(&self) -> ProviderResult<Option<BlockNumber>> {
+        self.database.provider()?.log_index_highest_block()
+    }
+
+    fn log_address_blocks(
+        &self,
+        address: Address,
+        range: RangeInclusive<BlockNumber>,
+    ) -> ProviderResult<Vec<BlockNumber>> {
+        self.database.provider()?.log_address_blocks(address, range)
+    }
+
+    fn log_topic_blocks(
+        &self,
+        topic: B256,
+        range: RangeInclusive<BlockNumber>,
+    ) -> ProviderResult<Vec<BlockNumber>> {
+        self.database.provider()?.log_topic_blocks(topic, range)
+    }
+}
+
+impl<DB, Tree> ContractCreatorReader for BlockchainProvider<DB, Tree>
+where
+    DB: Database,
+    Tree: Sync + Send,
+{
+    fn contract_creation(&self, address: Address) -> ProviderResult<Option<ContractCreation>> {
+        self.database.provider()?.contract_creation(address)
+    }
+}
+
+impl<DB, Tree> BadBlockReader for BlockchainProvider<DB, Tree>
+where
+    DB: Database,
+    Tree: Sync + Send,
+{
+    fn bad_blocks(&self) -> ProviderResult<Vec<BadBlock>> {
+        self.database.provider()?.bad_blocks()
+    }
+
+    fn bad_block(&self, hash: BlockHash) -> ProviderResult<Option<BadBlock>> {
+        self.database.provider()?.bad_block(hash)
+    }
 }
 
 impl<DB, Tree> AccountReader for BlockchainProvider<DB, Tree>
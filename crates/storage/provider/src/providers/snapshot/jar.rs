@@ -1,16 +1,21 @@
 use super::LoadedJarRef;
 use crate::{
-    to_range, BlockHashReader, BlockNumReader, HeaderProvider, ReceiptProvider,
+    to_range, BlockHashReader, BlockNumReader, ChangeSetReader, HeaderProvider, ReceiptProvider,
     TransactionsProvider,
 };
 use reth_db::{
     codecs::CompactU256,
-    snapshot::{HeaderMask, ReceiptMask, SnapshotCursor, TransactionMask},
+    models::{AccountBeforeTx, BlockAccountChangeSet, BlockStorageChangeSet},
+    snapshot::{
+        AccountChangeSetMask, HeaderMask, ReceiptMask, SnapshotCursor, StorageChangeSetMask,
+        TransactionMask,
+    },
 };
 use reth_interfaces::provider::{ProviderError, ProviderResult};
 use reth_primitives::{
     Address, BlockHash, BlockHashOrNumber, BlockNumber, ChainInfo, Header, Receipt, SealedHeader,
-    TransactionMeta, TransactionSigned, TransactionSignedNoHash, TxHash, TxNumber, B256, U256,
+    StorageEntry, TransactionMeta, TransactionSigned, TransactionSignedNoHash, TxHash, TxNumber,
+    B256, U256,
 };
 use std::ops::{Deref, RangeBounds};
 
@@ -303,3 +308,29 @@ impl<'a> ReceiptProvider for SnapshotJarProvider<'a> {
         Ok(receipts)
     }
 }
+
+impl<'a> ChangeSetReader for SnapshotJarProvider<'a> {
+    fn account_block_changeset(
+        &self,
+        block_number: BlockNumber,
+    ) -> ProviderResult<Vec<AccountBeforeTx>> {
+        Ok(self
+            .cursor()?
+            .get_one::<AccountChangeSetMask<BlockAccountChangeSet>>(block_number.into())?
+            .map(|changeset| changeset.0)
+            .unwrap_or_default())
+    }
+
+    fn storage_block_changeset(
+        &self,
+        block_number: BlockNumber,
+    ) -> ProviderResult<Vec<(Address, StorageEntry)>> {
+        Ok(self
+            .cursor()?
+            .get_one::<StorageChangeSetMask<BlockStorageChangeSet>>(block_number.into())?
+            .map(|changeset| {
+                changeset.0.into_iter().map(|slot| (slot.address, slot.entry)).collect()
+            })
+            .unwrap_or_default())
+    }
+}
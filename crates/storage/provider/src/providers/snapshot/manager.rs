@@ -1,30 +1,38 @@
 use super::{LoadedJar, SnapshotJarProvider};
 use crate::{
-    to_range, BlockHashReader, BlockNumReader, BlockReader, BlockSource, HeaderProvider,
-    ReceiptProvider, TransactionVariant, TransactionsProvider, TransactionsProviderExt,
-    WithdrawalsProvider,
+    to_range, BlockHashReader, BlockNumReader, BlockReader, BlockSource, ChangeSetReader,
+    HeaderProvider, ReceiptProvider, TransactionVariant, TransactionsProvider,
+    TransactionsProviderExt, WithdrawalsProvider,
 };
 use dashmap::DashMap;
-use parking_lot::RwLock;
+use parking_lot::{Mutex, RwLock};
 use reth_db::{
     codecs::CompactU256,
-    models::StoredBlockBodyIndices,
+    models::{AccountBeforeTx, StoredBlockBodyIndices},
     snapshot::{iter_snapshots, HeaderMask, ReceiptMask, SnapshotCursor, TransactionMask},
 };
 use reth_interfaces::provider::{ProviderError, ProviderResult};
 use reth_nippy_jar::NippyJar;
 use reth_primitives::{
-    snapshot::HighestSnapshots, Address, Block, BlockHash, BlockHashOrNumber, BlockNumber,
-    BlockWithSenders, ChainInfo, Header, Receipt, SealedBlock, SealedBlockWithSenders,
-    SealedHeader, SnapshotSegment, TransactionMeta, TransactionSigned, TransactionSignedNoHash,
-    TxHash, TxNumber, Withdrawal, Withdrawals, B256, U256,
+    snapshot::{
+        CorruptionKind, HighestSnapshots, ManifestVerification, SegmentManifest, SnapshotCorruption,
+    },
+    Address, Block, BlockHash, BlockHashOrNumber, BlockNumber, BlockWithSenders, ChainInfo, Header,
+    Receipt, SealedBlock, SealedBlockWithSenders, SealedHeader, SnapshotSegment, StorageEntry,
+    TransactionMeta, TransactionSigned, TransactionSignedNoHash, TxHash, TxNumber, Withdrawal,
+    Withdrawals, B256, U256,
 };
+use schnellru::{ByLength, LruMap};
 use std::{
     collections::{hash_map::Entry, BTreeMap, HashMap},
+    fmt,
     ops::{Range, RangeBounds, RangeInclusive},
     path::{Path, PathBuf},
+    sync::Arc,
 };
+use strum::IntoEnumIterator;
 use tokio::sync::watch;
+use tracing::warn;
 
 /// Alias type for a map that can be queried for transaction/block ranges from a block/transaction
 /// segment respectively. It uses `BlockNumber` to represent the block end of a snapshot range or
@@ -35,6 +43,9 @@ use tokio::sync::watch;
 /// - `HashMap<SnapshotSegment, BTreeMap<TxNumber, RangeInclusive<BlockNumber>>>`
 type SegmentRanges = HashMap<SnapshotSegment, BTreeMap<u64, RangeInclusive<u64>>>;
 
+/// The number of blocks whose decoded changesets are cached, per changeset segment.
+const CHANGESET_CACHE_BLOCKS: u32 = 64;
+
 /// [`SnapshotProvider`] manages all existing [`SnapshotJarProvider`].
 #[derive(Debug, Default)]
 pub struct SnapshotProvider {
@@ -52,11 +63,37 @@ pub struct SnapshotProvider {
     /// Whether [`SnapshotJarProvider`] loads filters into memory. If not, `by_hash` queries won't
     /// be able to be queried directly.
     load_filters: bool,
+    /// Recently decoded changesets of snapshotted blocks.
+    changeset_cache: ChangeSetCache,
 }
 
 impl SnapshotProvider {
-    /// Creates a new [`SnapshotProvider`].
+    /// Creates a new [`SnapshotProvider`], after checking the snapshots against the manifests of
+    /// their segments with [`ManifestVerification::Fast`].
     pub fn new(path: impl AsRef<Path>) -> ProviderResult<Self> {
+        Self::new_with_verification(path, ManifestVerification::Fast)
+    }
+
+    /// Creates a new [`SnapshotProvider`], after checking the snapshots against the manifests of
+    /// their segments.
+    ///
+    /// Returns [`ProviderError::CorruptedSnapshot`] for the first file that doesn't match its
+    /// manifest. Segments without a manifest are not checked.
+    ///
+    /// Snapshots that aren't recorded in the manifest are only logged, since they're left behind
+    /// by a crash between moving the snapshot files into place and saving the manifest, and by
+    /// snapshots created before manifests existed. The snapshotter records them on boot.
+    pub fn new_with_verification(
+        path: impl AsRef<Path>,
+        verification: ManifestVerification,
+    ) -> ProviderResult<Self> {
+        for corruption in Self::verify(&path, verification)? {
+            if corruption.kind != CorruptionKind::Unlisted {
+                return Err(corruption.into())
+            }
+            warn!(target: "providers::snapshot", path = ?corruption.path, "Snapshot is not recorded in the manifest of its segment");
+        }
+
         let provider = Self {
             map: Default::default(),
             snapshots_block_index: Default::default(),
@@ -64,12 +101,28 @@ impl SnapshotProvider {
             highest_tracker: None,
             path: path.as_ref().to_path_buf(),
             load_filters: false,
+            changeset_cache: Default::default(),
         };
 
         provider.update_index()?;
         Ok(provider)
     }
 
+    /// Checks the snapshots in the directory against the manifests of their segments and returns
+    /// every file that doesn't match. Segments without a manifest are skipped.
+    pub fn verify(
+        path: impl AsRef<Path>,
+        verification: ManifestVerification,
+    ) -> ProviderResult<Vec<SnapshotCorruption>> {
+        let mut corruptions = Vec::new();
+        for segment in SnapshotSegment::iter() {
+            if let Some(manifest) = SegmentManifest::load(path.as_ref(), segment)? {
+                corruptions.extend(manifest.verify(path.as_ref(), verification)?);
+            }
+        }
+        Ok(corruptions)
+    }
+
     /// Loads filters into memory when creating a [`SnapshotJarProvider`].
     pub fn with_filters(mut self) -> Self {
         self.load_filters = true;
@@ -270,6 +323,36 @@ impl SnapshotProvider {
             .and_then(|index| index.last_key_value().map(|(last_block, _)| *last_block))
     }
 
+    /// Returns the account changeset of a snapshotted block, which is only decoded if the block
+    /// isn't cached.
+    pub fn cached_account_block_changeset(
+        &self,
+        block_number: BlockNumber,
+    ) -> ProviderResult<Arc<Vec<AccountBeforeTx>>> {
+        if let Some(changeset) = self.changeset_cache.accounts.lock().get(&block_number) {
+            return Ok(Arc::clone(changeset))
+        }
+
+        let changeset = Arc::new(self.account_block_changeset(block_number)?);
+        self.changeset_cache.accounts.lock().insert(block_number, Arc::clone(&changeset));
+        Ok(changeset)
+    }
+
+    /// Returns the storage changeset of a snapshotted block, which is only decoded if the block
+    /// isn't cached.
+    pub fn cached_storage_block_changeset(
+        &self,
+        block_number: BlockNumber,
+    ) -> ProviderResult<Arc<Vec<(Address, StorageEntry)>>> {
+        if let Some(changeset) = self.changeset_cache.storages.lock().get(&block_number) {
+            return Ok(Arc::clone(changeset))
+        }
+
+        let changeset = Arc::new(self.storage_block_changeset(block_number)?);
+        self.changeset_cache.storages.lock().insert(block_number, Arc::clone(&changeset));
+        Ok(changeset)
+    }
+
     /// Gets the highest snapshotted transaction.
     pub fn get_highest_snapshot_tx(&self, segment: SnapshotSegment) -> Option<TxNumber> {
         self.snapshots_tx_index
@@ -326,9 +409,10 @@ impl SnapshotProvider {
         F: Fn(&mut SnapshotCursor<'_>, u64) -> ProviderResult<Option<T>>,
         P: FnMut(&T) -> bool,
     {
-        let get_provider = |start: u64| match segment {
-            SnapshotSegment::Headers => self.get_segment_provider_from_block(segment, start, None),
-            SnapshotSegment::Transactions | SnapshotSegment::Receipts => {
+        let get_provider = |start: u64| {
+            if segment.is_block_based() {
+                self.get_segment_provider_from_block(segment, start, None)
+            } else {
                 self.get_segment_provider_from_transaction(segment, start, None)
             }
         };
@@ -590,6 +674,59 @@ impl TransactionsProvider for SnapshotProvider {
     }
 }
 
+impl ChangeSetReader for SnapshotProvider {
+    fn account_block_changeset(
+        &self,
+        block_number: BlockNumber,
+    ) -> ProviderResult<Vec<AccountBeforeTx>> {
+        self.get_segment_provider_from_block(
+            SnapshotSegment::AccountChangeSets,
+            block_number,
+            None,
+        )?
+        .account_block_changeset(block_number)
+    }
+
+    fn storage_block_changeset(
+        &self,
+        block_number: BlockNumber,
+    ) -> ProviderResult<Vec<(Address, StorageEntry)>> {
+        self.get_segment_provider_from_block(
+            SnapshotSegment::StorageChangeSets,
+            block_number,
+            None,
+        )?
+        .storage_block_changeset(block_number)
+    }
+}
+
+/// Cache of the decoded changesets of recently read snapshotted blocks.
+///
+/// A snapshot row holds the changesets of a whole block, so without it every lookup of a single
+/// account or storage slot would decompress and decode all changes of the block.
+struct ChangeSetCache {
+    accounts: Mutex<LruMap<BlockNumber, Arc<Vec<AccountBeforeTx>>>>,
+    storages: Mutex<LruMap<BlockNumber, Arc<Vec<(Address, StorageEntry)>>>>,
+}
+
+impl Default for ChangeSetCache {
+    fn default() -> Self {
+        Self {
+            accounts: Mutex::new(LruMap::new(ByLength::new(CHANGESET_CACHE_BLOCKS))),
+            storages: Mutex::new(LruMap::new(ByLength::new(CHANGESET_CACHE_BLOCKS))),
+        }
+    }
+}
+
+impl fmt::Debug for ChangeSetCache {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ChangeSetCache")
+            .field("accounts", &self.accounts.lock().len())
+            .field("storages", &self.storages.lock().len())
+            .finish()
+    }
+}
+
 /* Cannot be successfully implemented but must exist for trait requirements */
 
 impl BlockNumReader for SnapshotProvider {
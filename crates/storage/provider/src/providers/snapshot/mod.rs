@@ -41,17 +41,28 @@ impl Deref for LoadedJar {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::{test_utils::create_test_provider_factory, HeaderProvider};
+    use crate::{test_utils::create_test_provider_factory, ChangeSetReader, HeaderProvider};
     use rand::{self, seq::SliceRandom};
     use reth_db::{
         cursor::DbCursorRO,
+        models::{AccountBeforeTx, BlockAccountChangeSet},
         snapshot::create_snapshot_T1_T2_T3,
+        table::Compress,
         transaction::{DbTx, DbTxMut},
-        CanonicalHeaders, HeaderNumbers, HeaderTD, Headers, RawTable,
+        AccountChangeSet, CanonicalHeaders, HeaderNumbers, HeaderTD, Headers, RawTable,
+    };
+    use reth_interfaces::{
+        provider::ProviderError,
+        test_utils::generators::{self, random_header_range},
+    };
+    use reth_nippy_jar::{ColumnResult, NippyJar};
+    use reth_primitives::{
+        snapshot::{
+            CorruptionKind, ManifestEntry, ManifestVerification, SegmentManifest,
+            SnapshotCorruption,
+        },
+        Account, Address, BlockNumber, B256, U256,
     };
-    use reth_interfaces::test_utils::generators::{self, random_header_range};
-    use reth_nippy_jar::NippyJar;
-    use reth_primitives::{BlockNumber, B256, U256};
 
     #[test]
     fn test_snap() {
@@ -157,4 +168,124 @@ mod tests {
             }
         }
     }
+
+    #[test]
+    fn test_changeset_snap() {
+        let range = 0..=9u64;
+        let segment_header =
+            SegmentHeader::new(range.clone(), 0..=0, SnapshotSegment::AccountChangeSets);
+
+        let factory = create_test_provider_factory();
+        let snap_path = tempfile::tempdir().unwrap();
+        let snap_file =
+            snap_path.path().join(SnapshotSegment::AccountChangeSets.filename(&range, &(0..=0)));
+
+        // Every block changes one account more than the previous one, the first block none.
+        let provider_rw = factory.provider_rw().unwrap();
+        for block_number in range.clone() {
+            for idx in 0..block_number {
+                let account = Account { nonce: idx, ..Default::default() };
+                provider_rw
+                    .tx_ref()
+                    .put::<AccountChangeSet>(
+                        block_number,
+                        AccountBeforeTx {
+                            address: Address::with_last_byte(idx as u8),
+                            info: (idx % 2 == 0).then_some(account),
+                        },
+                    )
+                    .unwrap();
+            }
+        }
+        provider_rw.commit().unwrap();
+
+        // Create Snapshot with one row per block
+        {
+            let provider = factory.provider().unwrap();
+            let rows = range
+                .clone()
+                .map(|block_number| -> ColumnResult<Vec<u8>> {
+                    Ok(BlockAccountChangeSet(provider.account_block_changeset(block_number)?)
+                        .compress())
+                })
+                .collect::<Vec<_>>();
+
+            let mut nippy_jar = NippyJar::new(1, snap_file.as_path(), segment_header);
+            nippy_jar.freeze(vec![rows], range.clone().count() as u64).unwrap();
+        }
+
+        let db_provider = factory.provider().unwrap();
+        let manager = SnapshotProvider::new(snap_path.path()).unwrap();
+        assert_eq!(manager.get_highest_snapshot_block(SnapshotSegment::AccountChangeSets), Some(9));
+
+        for block_number in range {
+            let changeset = manager.account_block_changeset(block_number).unwrap();
+            assert_eq!(changeset.len(), block_number as usize);
+            assert_eq!(changeset, db_provider.account_block_changeset(block_number).unwrap());
+
+            // The decoded changeset is cached for subsequent lookups
+            let cached = manager.cached_account_block_changeset(block_number).unwrap();
+            assert_eq!(*cached, changeset);
+            assert!(Arc::ptr_eq(
+                &cached,
+                &manager.cached_account_block_changeset(block_number).unwrap()
+            ));
+        }
+    }
+
+    #[test]
+    fn test_unlisted_snapshots() {
+        let segment = SnapshotSegment::AccountChangeSets;
+        let snap_path = tempfile::tempdir().unwrap();
+
+        // The older snapshot predates the manifest, which only records the newer one.
+        let mut manifest = SegmentManifest::new(segment);
+        for range in [0..=9u64, 10..=19] {
+            let snap_file = snap_path.path().join(segment.filename(&range, &(0..=0)));
+            let mut nippy_jar = NippyJar::new(
+                1,
+                snap_file.as_path(),
+                SegmentHeader::new(range.clone(), 0..=0, segment),
+            );
+            let rows = range.clone().map(|_| -> ColumnResult<Vec<u8>> { Ok(vec![]) });
+            nippy_jar.freeze(vec![rows], range.clone().count() as u64).unwrap();
+
+            if *range.start() == 10 {
+                let files = [
+                    nippy_jar.data_path().to_path_buf(),
+                    nippy_jar.index_path(),
+                    nippy_jar.offsets_path(),
+                    nippy_jar.config_path(),
+                ];
+                manifest.insert(ManifestEntry::new(range, 0..=0, files).unwrap());
+            }
+        }
+        manifest.save(snap_path.path()).unwrap();
+
+        // Unlisted snapshots don't prevent the provider from being created.
+        let manager = SnapshotProvider::new(snap_path.path()).unwrap();
+        assert_eq!(manager.get_highest_snapshot_block(segment), Some(19));
+
+        // But they're still reported by the verification.
+        let corruptions =
+            SnapshotProvider::verify(snap_path.path(), ManifestVerification::Full).unwrap();
+        assert_eq!(
+            corruptions,
+            vec![SnapshotCorruption {
+                path: snap_path.path().join(segment.filename(&(0..=9), &(0..=0))),
+                kind: CorruptionKind::Unlisted,
+            }]
+        );
+
+        // Other corruptions still do.
+        std::fs::remove_file(snap_path.path().join(segment.filename(&(10..=19), &(0..=0))))
+            .unwrap();
+        assert!(matches!(
+            SnapshotProvider::new(snap_path.path()),
+            Err(ProviderError::CorruptedSnapshot(SnapshotCorruption {
+                kind: CorruptionKind::Missing,
+                ..
+            }))
+        ));
+    }
 }
@@ -1,10 +1,11 @@
 use crate::{
-    providers::state::macros::delegate_provider_impls, AccountReader, BlockHashReader,
-    BundleStateWithReceipts, ProviderError, StateProvider, StateRootProvider,
+    providers::{state::macros::delegate_provider_impls, SnapshotProvider},
+    AccountReader, BlockHashReader, BundleStateWithReceipts, ProviderError, StateProvider,
+    StateRangeProvider, StateRootProvider,
 };
 use reth_db::{
     cursor::{DbCursorRO, DbDupCursorRO},
-    models::{storage_sharded_key::StorageShardedKey, ShardedKey},
+    models::{storage_sharded_key::StorageShardedKey, AccountBeforeTx, ShardedKey},
     table::Table,
     tables,
     transaction::DbTx,
@@ -13,9 +14,10 @@ use reth_db::{
 use reth_interfaces::provider::ProviderResult;
 use reth_primitives::{
     constants::EPOCH_SLOTS, trie::AccountProof, Account, Address, BlockNumber, Bytecode,
-    StorageKey, StorageValue, B256,
+    SnapshotSegment, StorageEntry, StorageKey, StorageValue, B256,
 };
-use reth_trie::{updates::TrieUpdates, HashedPostState};
+use reth_trie::{updates::TrieUpdates, AccountRange, HashedPostState, StorageRange};
+use std::sync::Arc;
 
 /// State provider for a given block number which takes a tx reference.
 ///
@@ -28,6 +30,11 @@ use reth_trie::{updates::TrieUpdates, HashedPostState};
 /// - [tables::StorageHistory]
 /// - [tables::AccountChangeSet]
 /// - [tables::StorageChangeSet]
+///
+/// Changesets of blocks that were snapshotted are read from the
+/// [SnapshotSegment::AccountChangeSets] and [SnapshotSegment::StorageChangeSets] snapshots
+/// instead, if a [SnapshotProvider] is set. A snapshot row holds the changesets of a whole block,
+/// which the [SnapshotProvider] caches once decoded for subsequent lookups.
 #[derive(Debug)]
 pub struct HistoricalStateProviderRef<'b, TX: DbTx> {
     /// Transaction
@@ -36,6 +43,8 @@ pub struct HistoricalStateProviderRef<'b, TX: DbTx> {
     block_number: BlockNumber,
     /// Lowest blocks at which different parts of the state are available.
     lowest_available_blocks: LowestAvailableBlocks,
+    /// Snapshot provider for changesets that are no longer in the database.
+    snapshot_provider: Option<&'b SnapshotProvider>,
 }
 
 #[derive(Debug, Eq, PartialEq)]
@@ -49,7 +58,12 @@ pub enum HistoryInfo {
 impl<'b, TX: DbTx> HistoricalStateProviderRef<'b, TX> {
     /// Create new StateProvider for historical block number
     pub fn new(tx: &'b TX, block_number: BlockNumber) -> Self {
-        Self { tx, block_number, lowest_available_blocks: Default::default() }
+        Self {
+            tx,
+            block_number,
+            lowest_available_blocks: Default::default(),
+            snapshot_provider: None,
+        }
     }
 
     /// Create new StateProvider for historical block number and lowest block numbers at which
@@ -59,7 +73,85 @@ impl<'b, TX: DbTx> HistoricalStateProviderRef<'b, TX> {
         block_number: BlockNumber,
         lowest_available_blocks: LowestAvailableBlocks,
     ) -> Self {
-        Self { tx, block_number, lowest_available_blocks }
+        Self { tx, block_number, lowest_available_blocks, snapshot_provider: None }
+    }
+
+    /// Reads the changesets of snapshotted blocks from the snapshot provider.
+    pub fn with_snapshot_provider(mut self, snapshot_provider: &'b SnapshotProvider) -> Self {
+        self.snapshot_provider = Some(snapshot_provider);
+        self
+    }
+
+    /// Returns the snapshot provider if the block of the segment was snapshotted.
+    fn snapshot_provider_for(
+        &self,
+        segment: SnapshotSegment,
+        block_number: BlockNumber,
+    ) -> Option<&'b SnapshotProvider> {
+        self.snapshot_provider.filter(|provider| {
+            provider
+                .get_highest_snapshot_block(segment)
+                .map_or(false, |highest| highest >= block_number)
+        })
+    }
+
+    /// Returns the state of the account before the block, from the snapshot if the changesets of
+    /// the block were snapshotted.
+    ///
+    /// Reading from the snapshot decodes the account changes of the whole block, unless they are
+    /// cached by the snapshot provider.
+    fn account_changeset_entry(
+        &self,
+        block_number: BlockNumber,
+        address: Address,
+    ) -> ProviderResult<Option<AccountBeforeTx>> {
+        if let Some(provider) =
+            self.snapshot_provider_for(SnapshotSegment::AccountChangeSets, block_number)
+        {
+            // Accounts are sorted by address, like in the database
+            let changeset = provider.cached_account_block_changeset(block_number)?;
+            return Ok(changeset
+                .binary_search_by_key(&address, |account| account.address)
+                .ok()
+                .map(|idx| changeset[idx].clone()))
+        }
+
+        Ok(self
+            .tx
+            .cursor_dup_read::<tables::AccountChangeSet>()?
+            .seek_by_key_subkey(block_number, address)?
+            .filter(|acc| acc.address == address))
+    }
+
+    /// Returns the storage slot before the block, from the snapshot if the changesets of the block
+    /// were snapshotted.
+    ///
+    /// Reading from the snapshot decodes the storage changes of the whole block, unless they are
+    /// cached by the snapshot provider.
+    fn storage_changeset_entry(
+        &self,
+        block_number: BlockNumber,
+        address: Address,
+        storage_key: StorageKey,
+    ) -> ProviderResult<Option<StorageEntry>> {
+        if let Some(provider) =
+            self.snapshot_provider_for(SnapshotSegment::StorageChangeSets, block_number)
+        {
+            // Slots are sorted by address and key, like in the database
+            let changeset = provider.cached_storage_block_changeset(block_number)?;
+            return Ok(changeset
+                .binary_search_by_key(&(address, storage_key), |(address, entry)| {
+                    (*address, entry.key)
+                })
+                .ok()
+                .map(|idx| changeset[idx].1))
+        }
+
+        Ok(self
+            .tx
+            .cursor_dup_read::<tables::StorageChangeSet>()?
+            .seek_by_key_subkey((block_number, address).into(), storage_key)?
+            .filter(|entry| entry.key == storage_key))
     }
 
     /// Lookup an account in the AccountHistory table
@@ -184,10 +276,7 @@ impl<'b, TX: DbTx> AccountReader for HistoricalStateProviderRef<'b, TX> {
         match self.account_history_lookup(address)? {
             HistoryInfo::NotYetWritten => Ok(None),
             HistoryInfo::InChangeset(changeset_block_number) => Ok(self
-                .tx
-                .cursor_dup_read::<tables::AccountChangeSet>()?
-                .seek_by_key_subkey(changeset_block_number, address)?
-                .filter(|acc| acc.address == address)
+                .account_changeset_entry(changeset_block_number, address)?
                 .ok_or(ProviderError::AccountChangesetNotFound {
                     block_number: changeset_block_number,
                     address,
@@ -243,6 +332,38 @@ impl<'b, TX: DbTx> StateRootProvider for HistoricalStateProviderRef<'b, TX> {
     }
 }
 
+impl<'b, TX: DbTx> StateRangeProvider for HistoricalStateProviderRef<'b, TX> {
+    fn account_range(
+        &self,
+        post_state: &HashedPostState,
+        start: B256,
+        limit: usize,
+        storage_limit: usize,
+    ) -> ProviderResult<AccountRange> {
+        let mut revert_state = self.revert_state()?;
+        revert_state.extend(post_state.clone());
+        revert_state
+            .into_sorted()
+            .account_range(self.tx, start, limit, storage_limit)
+            .map_err(|err| ProviderError::Database(err.into()))
+    }
+
+    fn storage_range(
+        &self,
+        post_state: &HashedPostState,
+        hashed_address: B256,
+        start: B256,
+        limit: usize,
+    ) -> ProviderResult<StorageRange> {
+        let mut revert_state = self.revert_state()?;
+        revert_state.extend(post_state.clone());
+        revert_state
+            .into_sorted()
+            .storage_range(self.tx, hashed_address, start, limit)
+            .map_err(Into::into)
+    }
+}
+
 impl<'b, TX: DbTx> StateProvider for HistoricalStateProviderRef<'b, TX> {
     /// Get storage.
     fn storage(
@@ -253,10 +374,7 @@ impl<'b, TX: DbTx> StateProvider for HistoricalStateProviderRef<'b, TX> {
         match self.storage_history_lookup(address, storage_key)? {
             HistoryInfo::NotYetWritten => Ok(None),
             HistoryInfo::InChangeset(changeset_block_number) => Ok(Some(
-                self.tx
-                    .cursor_dup_read::<tables::StorageChangeSet>()?
-                    .seek_by_key_subkey((changeset_block_number, address).into(), storage_key)?
-                    .filter(|entry| entry.key == storage_key)
+                self.storage_changeset_entry(changeset_block_number, address, storage_key)?
                     .ok_or_else(|| ProviderError::StorageChangesetNotFound {
                         block_number: changeset_block_number,
                         address,
@@ -280,8 +398,10 @@ impl<'b, TX: DbTx> StateProvider for HistoricalStateProviderRef<'b, TX> {
     }
 
     /// Get account and storage proofs.
-    fn proof(&self, _address: Address, _keys: &[B256]) -> ProviderResult<AccountProof> {
-        Err(ProviderError::StateRootNotAvailableForHistoricalBlock)
+    fn proof(&self, address: Address, keys: &[B256]) -> ProviderResult<AccountProof> {
+        self.revert_state()?
+            .account_proof(self.tx, address, keys)
+            .map_err(|err| ProviderError::Database(err.into()))
     }
 }
 
@@ -295,12 +415,25 @@ pub struct HistoricalStateProvider<TX: DbTx> {
     block_number: BlockNumber,
     /// Lowest blocks at which different parts of the state are available.
     lowest_available_blocks: LowestAvailableBlocks,
+    /// Snapshot provider for changesets that are no longer in the database.
+    snapshot_provider: Option<Arc<SnapshotProvider>>,
 }
 
 impl<TX: DbTx> HistoricalStateProvider<TX> {
     /// Create new StateProvider for historical block number
     pub fn new(tx: TX, block_number: BlockNumber) -> Self {
-        Self { tx, block_number, lowest_available_blocks: Default::default() }
+        Self {
+            tx,
+            block_number,
+            lowest_available_blocks: Default::default(),
+            snapshot_provider: None,
+        }
+    }
+
+    /// Reads the changesets of snapshotted blocks from the snapshot provider.
+    pub fn with_snapshot_provider(mut self, snapshot_provider: Arc<SnapshotProvider>) -> Self {
+        self.snapshot_provider = Some(snapshot_provider);
+        self
     }
 
     /// Set the lowest block number at which the account history is available.
@@ -324,11 +457,15 @@ impl<TX: DbTx> HistoricalStateProvider<TX> {
     /// Returns a new provider that takes the `TX` as reference
     #[inline(always)]
     fn as_ref(&self) -> HistoricalStateProviderRef<'_, TX> {
-        HistoricalStateProviderRef::new_with_lowest_available_blocks(
+        let provider = HistoricalStateProviderRef::new_with_lowest_available_blocks(
             &self.tx,
             self.block_number,
             self.lowest_available_blocks,
-        )
+        );
+        match &self.snapshot_provider {
+            Some(snapshot_provider) => provider.with_snapshot_provider(snapshot_provider),
+            None => provider,
+        }
     }
 }
 
@@ -1,6 +1,6 @@
 use crate::{
     providers::state::macros::delegate_provider_impls, AccountReader, BlockHashReader,
-    BundleStateWithReceipts, StateProvider, StateRootProvider,
+    BundleStateWithReceipts, StateProvider, StateRangeProvider, StateRootProvider,
 };
 use reth_db::{
     cursor::{DbCursorRO, DbDupCursorRO},
@@ -11,7 +11,7 @@ use reth_interfaces::provider::{ProviderError, ProviderResult};
 use reth_primitives::{
     trie::AccountProof, Account, Address, BlockNumber, Bytecode, StorageKey, StorageValue, B256,
 };
-use reth_trie::{proof::Proof, updates::TrieUpdates};
+use reth_trie::{proof::Proof, updates::TrieUpdates, AccountRange, HashedPostState, StorageRange};
 
 /// State provider over latest state that takes tx reference.
 #[derive(Debug)]
@@ -77,6 +77,36 @@ impl<'b, TX: DbTx> StateRootProvider for LatestStateProviderRef<'b, TX> {
     }
 }
 
+impl<'b, TX: DbTx> StateRangeProvider for LatestStateProviderRef<'b, TX> {
+    fn account_range(
+        &self,
+        post_state: &HashedPostState,
+        start: B256,
+        limit: usize,
+        storage_limit: usize,
+    ) -> ProviderResult<AccountRange> {
+        post_state
+            .clone()
+            .into_sorted()
+            .account_range(self.db, start, limit, storage_limit)
+            .map_err(|err| ProviderError::Database(err.into()))
+    }
+
+    fn storage_range(
+        &self,
+        post_state: &HashedPostState,
+        hashed_address: B256,
+        start: B256,
+        limit: usize,
+    ) -> ProviderResult<StorageRange> {
+        post_state
+            .clone()
+            .into_sorted()
+            .storage_range(self.db, hashed_address, start, limit)
+            .map_err(Into::into)
+    }
+}
+
 impl<'b, TX: DbTx> StateProvider for LatestStateProviderRef<'b, TX> {
     /// Get storage.
     fn storage(
@@ -34,6 +34,10 @@ macro_rules! delegate_provider_impls {
                 fn state_root(&self, state: &crate::BundleStateWithReceipts) -> reth_interfaces::provider::ProviderResult<reth_primitives::B256>;
                 fn state_root_with_updates(&self, state: &crate::BundleStateWithReceipts) -> reth_interfaces::provider::ProviderResult<(reth_primitives::B256, reth_trie::updates::TrieUpdates)>;
             }
+            StateRangeProvider $(where [$($generics)*])? {
+                fn account_range(&self, post_state: &reth_trie::HashedPostState, start: reth_primitives::B256, limit: usize, storage_limit: usize) -> reth_interfaces::provider::ProviderResult<reth_trie::AccountRange>;
+                fn storage_range(&self, post_state: &reth_trie::HashedPostState, hashed_address: reth_primitives::B256, start: reth_primitives::B256, limit: usize) -> reth_interfaces::provider::ProviderResult<reth_trie::StorageRange>;
+            }
             AccountReader $(where [$($generics)*])? {
                 fn basic_account(&self, address: reth_primitives::Address) -> reth_interfaces::provider::ProviderResult<Option<reth_primitives::Account>>;
             }
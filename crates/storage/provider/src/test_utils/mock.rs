@@ -1,23 +1,28 @@
 use crate::{
     bundle_state::BundleStateWithReceipts,
     traits::{BlockSource, ReceiptProvider},
-    AccountReader, BlockHashReader, BlockIdReader, BlockNumReader, BlockReader, BlockReaderIdExt,
-    BundleStateDataProvider, ChainSpecProvider, ChangeSetReader, EvmEnvProvider, HeaderProvider,
-    ReceiptProviderIdExt, StateProvider, StateProviderBox, StateProviderFactory, StateRootProvider,
+    AccountReader, AddressAppearancesReader, BadBlockReader, BlockHashReader, BlockIdReader,
+    BlockNumReader, BlockReader, BlockReaderIdExt, BlockValidationProvider,
+    BundleStateDataProvider, ChainSpecProvider, ChangeSetReader, ContractCreatorReader,
+    EvmEnvProvider, HeaderProvider, LogIndexReader, ReceiptProviderIdExt, StateProvider,
+    StateProviderBox, StateProviderFactory, StateRangeProvider, StateRootProvider,
     TransactionVariant, TransactionsProvider, WithdrawalsProvider,
 };
 use parking_lot::Mutex;
 use reth_db::models::{AccountBeforeTx, StoredBlockBodyIndices};
-use reth_interfaces::provider::{ProviderError, ProviderResult};
+use reth_interfaces::{
+    blockchain_tree::error::{BlockchainTreeError, InsertBlockErrorKind},
+    provider::{ProviderError, ProviderResult},
+};
 use reth_node_api::ConfigureEvmEnv;
 use reth_primitives::{
-    keccak256, trie::AccountProof, Account, Address, Block, BlockHash, BlockHashOrNumber, BlockId,
-    BlockNumber, BlockWithSenders, Bytecode, Bytes, ChainInfo, ChainSpec, Header, Receipt,
-    SealedBlock, SealedBlockWithSenders, SealedHeader, StorageKey, StorageValue, TransactionMeta,
-    TransactionSigned, TransactionSignedNoHash, TxHash, TxNumber, Withdrawal, Withdrawals, B256,
-    U256,
+    keccak256, trie::AccountProof, Account, Address, BadBlock, Block, BlockHash, BlockHashOrNumber,
+    BlockId, BlockNumber, BlockWithSenders, Bytecode, Bytes, ChainInfo, ChainSpec,
+    ContractCreation, Header, Receipt, SealedBlock, SealedBlockWithSenders, SealedHeader,
+    StorageEntry, StorageKey, StorageValue, TransactionMeta, TransactionSigned,
+    TransactionSignedNoHash, TxHash, TxNumber, Withdrawal, Withdrawals, B256, U256,
 };
-use reth_trie::updates::TrieUpdates;
+use reth_trie::{updates::TrieUpdates, AccountRange, HashedPostState, StorageRange};
 use revm::primitives::{BlockEnv, CfgEnvWithHandlerCfg};
 use std::{
     collections::{BTreeMap, HashMap},
@@ -34,6 +39,10 @@ pub struct MockEthProvider {
     pub headers: Arc<Mutex<HashMap<B256, Header>>>,
     /// Local account store
     pub accounts: Arc<Mutex<HashMap<Address, ExtendedAccount>>>,
+    /// Local account changeset store
+    pub account_changesets: Arc<Mutex<HashMap<BlockNumber, Vec<AccountBeforeTx>>>>,
+    /// Local storage changeset store
+    pub storage_changesets: Arc<Mutex<HashMap<BlockNumber, Vec<(Address, StorageEntry)>>>>,
     /// Local chain spec
     pub chain_spec: Arc<ChainSpec>,
 }
@@ -44,6 +53,8 @@ impl Default for MockEthProvider {
             blocks: Default::default(),
             headers: Default::default(),
             accounts: Default::default(),
+            account_changesets: Default::default(),
+            storage_changesets: Default::default(),
             chain_spec: Arc::new(reth_primitives::ChainSpecBuilder::mainnet().build()),
         }
     }
@@ -124,6 +135,17 @@ impl MockEthProvider {
             self.add_account(address, account)
         }
     }
+
+    /// Add the account and storage values before the block to local changeset stores
+    pub fn add_changesets(
+        &self,
+        block_number: BlockNumber,
+        accounts: impl IntoIterator<Item = AccountBeforeTx>,
+        storage: impl IntoIterator<Item = (Address, StorageEntry)>,
+    ) {
+        self.account_changesets.lock().entry(block_number).or_default().extend(accounts);
+        self.storage_changesets.lock().entry(block_number).or_default().extend(storage);
+    }
 }
 
 impl HeaderProvider for MockEthProvider {
@@ -465,10 +487,10 @@ impl BlockReader for MockEthProvider {
 
     fn block_with_senders(
         &self,
-        _id: BlockHashOrNumber,
+        id: BlockHashOrNumber,
         _transaction_kind: TransactionVariant,
     ) -> ProviderResult<Option<BlockWithSenders>> {
-        Ok(None)
+        Ok(self.block(id)?.and_then(Block::with_recovered_senders))
     }
 
     fn block_range(&self, range: RangeInclusive<BlockNumber>) -> ProviderResult<Vec<Block>> {
@@ -528,6 +550,28 @@ impl StateRootProvider for MockEthProvider {
     }
 }
 
+impl StateRangeProvider for MockEthProvider {
+    fn account_range(
+        &self,
+        _post_state: &HashedPostState,
+        _start: B256,
+        _limit: usize,
+        _storage_limit: usize,
+    ) -> ProviderResult<AccountRange> {
+        Ok(AccountRange::default())
+    }
+
+    fn storage_range(
+        &self,
+        _post_state: &HashedPostState,
+        _hashed_address: B256,
+        _start: B256,
+        _limit: usize,
+    ) -> ProviderResult<StorageRange> {
+        Ok(StorageRange::default())
+    }
+}
+
 impl StateProvider for MockEthProvider {
     fn storage(
         &self,
@@ -702,11 +746,96 @@ impl WithdrawalsProvider for MockEthProvider {
     }
 }
 
+impl AddressAppearancesReader for MockEthProvider {
+    fn address_appearances_with_range(
+        &self,
+        _range: RangeInclusive<BlockNumber>,
+    ) -> ProviderResult<BTreeMap<Address, Vec<TxNumber>>> {
+        Ok(BTreeMap::default())
+    }
+
+    fn address_appearances_before(
+        &self,
+        _address: Address,
+        _tx_number: TxNumber,
+        _limit: usize,
+    ) -> ProviderResult<Vec<TxNumber>> {
+        Ok(Vec::default())
+    }
+
+    fn address_appearances_after(
+        &self,
+        _address: Address,
+        _tx_number: TxNumber,
+        _limit: usize,
+    ) -> ProviderResult<Vec<TxNumber>> {
+        Ok(Vec::default())
+    }
+}
+
+impl LogIndexReader for MockEthProvider {
+    fn log_addresses_and_topics_with_range(
+        &self,
+        _range: RangeInclusive<BlockNumber>,
+    ) -> ProviderResult<(BTreeMap<Address, Vec<BlockNumber>>, BTreeMap<B256, Vec<BlockNumber>>)>
+    {
+        Ok(Default::default())
+    }
+
+    fn log_index_highest_block(&self) -> ProviderResult<Option<BlockNumber>> {
+        Ok(None)
+    }
+
+    fn log_address_blocks(
+        &self,
+        _address: Address,
+        _range: RangeInclusive<BlockNumber>,
+    ) -> ProviderResult<Vec<BlockNumber>> {
+        Ok(Vec::default())
+    }
+
+    fn log_topic_blocks(
+        &self,
+        _topic: B256,
+        _range: RangeInclusive<BlockNumber>,
+    ) -> ProviderResult<Vec<BlockNumber>> {
+        Ok(Vec::default())
+    }
+}
+
+impl BadBlockReader for MockEthProvider {
+    fn bad_blocks(&self) -> ProviderResult<Vec<BadBlock>> {
+        Ok(Vec::new())
+    }
+}
+
+impl BlockValidationProvider for MockEthProvider {
+    fn validate_block_detached(
+        &self,
+        block: SealedBlockWithSenders,
+    ) -> Result<BundleStateWithReceipts, InsertBlockErrorKind> {
+        Err(BlockchainTreeError::BlockHashNotFoundInChain { block_hash: block.parent_hash }.into())
+    }
+}
+
+impl ContractCreatorReader for MockEthProvider {
+    fn contract_creation(&self, _address: Address) -> ProviderResult<Option<ContractCreation>> {
+        Ok(None)
+    }
+}
+
 impl ChangeSetReader for MockEthProvider {
     fn account_block_changeset(
         &self,
-        _block_number: BlockNumber,
+        block_number: BlockNumber,
     ) -> ProviderResult<Vec<AccountBeforeTx>> {
-        Ok(Vec::default())
+        Ok(self.account_changesets.lock().get(&block_number).cloned().unwrap_or_default())
+    }
+
+    fn storage_block_changeset(
+        &self,
+        block_number: BlockNumber,
+    ) -> ProviderResult<Vec<(Address, StorageEntry)>> {
+        Ok(self.storage_changesets.lock().get(&block_number).cloned().unwrap_or_default())
     }
 }
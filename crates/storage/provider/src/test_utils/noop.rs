@@ -1,27 +1,32 @@
 use crate::{
     bundle_state::BundleStateWithReceipts,
     traits::{BlockSource, ReceiptProvider},
-    AccountReader, BlockHashReader, BlockIdReader, BlockNumReader, BlockReader, BlockReaderIdExt,
-    ChainSpecProvider, ChangeSetReader, EvmEnvProvider, HeaderProvider, PruneCheckpointReader,
-    ReceiptProviderIdExt, StageCheckpointReader, StateProvider, StateProviderBox,
-    StateProviderFactory, StateRootProvider, TransactionVariant, TransactionsProvider,
-    WithdrawalsProvider,
+    AccountReader, AddressAppearancesReader, BadBlockReader, BlockHashReader, BlockIdReader,
+    BlockNumReader, BlockReader, BlockReaderIdExt, BlockValidationProvider, ChainSpecProvider,
+    ChangeSetReader, ContractCreatorReader, EvmEnvProvider, HeaderProvider, LogIndexReader,
+    PruneCheckpointReader, ReceiptProviderIdExt, StageCheckpointReader, StateProvider,
+    StateProviderBox, StateProviderFactory, StateRangeProvider, StateRootProvider,
+    TransactionVariant, TransactionsProvider, WithdrawalsProvider,
 };
 use reth_db::models::{AccountBeforeTx, StoredBlockBodyIndices};
-use reth_interfaces::provider::ProviderResult;
+use reth_interfaces::{
+    blockchain_tree::error::{BlockchainTreeError, InsertBlockErrorKind},
+    provider::ProviderResult,
+};
 use reth_node_api::ConfigureEvmEnv;
 use reth_primitives::{
     stage::{StageCheckpoint, StageId},
     trie::AccountProof,
-    Account, Address, Block, BlockHash, BlockHashOrNumber, BlockId, BlockNumber, Bytecode,
-    ChainInfo, ChainSpec, Header, PruneCheckpoint, PruneSegment, Receipt, SealedBlock,
-    SealedBlockWithSenders, SealedHeader, StorageKey, StorageValue, TransactionMeta,
-    TransactionSigned, TransactionSignedNoHash, TxHash, TxNumber, Withdrawal, Withdrawals, B256,
-    MAINNET, U256,
+    Account, Address, BadBlock, Block, BlockHash, BlockHashOrNumber, BlockId, BlockNumber,
+    Bytecode, ChainInfo, ChainSpec, ContractCreation, Header, PruneCheckpoint, PruneSegment,
+    Receipt, SealedBlock, SealedBlockWithSenders, SealedHeader, StorageEntry, StorageKey,
+    StorageValue, TransactionMeta, TransactionSigned, TransactionSignedNoHash, TxHash, TxNumber,
+    Withdrawal, Withdrawals, B256, MAINNET, U256,
 };
-use reth_trie::updates::TrieUpdates;
+use reth_trie::{updates::TrieUpdates, AccountRange, HashedPostState, StorageRange};
 use revm::primitives::{BlockEnv, CfgEnvWithHandlerCfg};
 use std::{
+    collections::BTreeMap,
     ops::{RangeBounds, RangeInclusive},
     sync::Arc,
 };
@@ -275,6 +280,84 @@ impl AccountReader for NoopProvider {
     }
 }
 
+impl AddressAppearancesReader for NoopProvider {
+    fn address_appearances_with_range(
+        &self,
+        _range: RangeInclusive<BlockNumber>,
+    ) -> ProviderResult<BTreeMap<Address, Vec<TxNumber>>> {
+        Ok(BTreeMap::default())
+    }
+
+    fn address_appearances_before(
+        &self,
+        _address: Address,
+        _tx_number: TxNumber,
+        _limit: usize,
+    ) -> ProviderResult<Vec<TxNumber>> {
+        Ok(Vec::default())
+    }
+
+    fn address_appearances_after(
+        &self,
+        _address: Address,
+        _tx_number: TxNumber,
+        _limit: usize,
+    ) -> ProviderResult<Vec<TxNumber>> {
+        Ok(Vec::default())
+    }
+}
+
+impl LogIndexReader for NoopProvider {
+    fn log_addresses_and_topics_with_range(
+        &self,
+        _range: RangeInclusive<BlockNumber>,
+    ) -> ProviderResult<(BTreeMap<Address, Vec<BlockNumber>>, BTreeMap<B256, Vec<BlockNumber>>)>
+    {
+        Ok(Default::default())
+    }
+
+    fn log_index_highest_block(&self) -> ProviderResult<Option<BlockNumber>> {
+        Ok(None)
+    }
+
+    fn log_address_blocks(
+        &self,
+        _address: Address,
+        _range: RangeInclusive<BlockNumber>,
+    ) -> ProviderResult<Vec<BlockNumber>> {
+        Ok(Vec::default())
+    }
+
+    fn log_topic_blocks(
+        &self,
+        _topic: B256,
+        _range: RangeInclusive<BlockNumber>,
+    ) -> ProviderResult<Vec<BlockNumber>> {
+        Ok(Vec::default())
+    }
+}
+
+impl BadBlockReader for NoopProvider {
+    fn bad_blocks(&self) -> ProviderResult<Vec<BadBlock>> {
+        Ok(Vec::new())
+    }
+}
+
+impl BlockValidationProvider for NoopProvider {
+    fn validate_block_detached(
+        &self,
+        block: SealedBlockWithSenders,
+    ) -> Result<BundleStateWithReceipts, InsertBlockErrorKind> {
+        Err(BlockchainTreeError::BlockHashNotFoundInChain { block_hash: block.parent_hash }.into())
+    }
+}
+
+impl ContractCreatorReader for NoopProvider {
+    fn contract_creation(&self, _address: Address) -> ProviderResult<Option<ContractCreation>> {
+        Ok(None)
+    }
+}
+
 impl ChangeSetReader for NoopProvider {
     fn account_block_changeset(
         &self,
@@ -282,6 +365,13 @@ impl ChangeSetReader for NoopProvider {
     ) -> ProviderResult<Vec<AccountBeforeTx>> {
         Ok(Vec::default())
     }
+
+    fn storage_block_changeset(
+        &self,
+        _block_number: BlockNumber,
+    ) -> ProviderResult<Vec<(Address, StorageEntry)>> {
+        Ok(Vec::default())
+    }
 }
 
 impl StateRootProvider for NoopProvider {
@@ -297,6 +387,28 @@ impl StateRootProvider for NoopProvider {
     }
 }
 
+impl StateRangeProvider for NoopProvider {
+    fn account_range(
+        &self,
+        _post_state: &HashedPostState,
+        _start: B256,
+        _limit: usize,
+        _storage_limit: usize,
+    ) -> ProviderResult<AccountRange> {
+        Ok(AccountRange::default())
+    }
+
+    fn storage_range(
+        &self,
+        _post_state: &HashedPostState,
+        _hashed_address: B256,
+        _start: B256,
+        _limit: usize,
+    ) -> ProviderResult<StorageRange> {
+        Ok(StorageRange::default())
+    }
+}
+
 impl StateProvider for NoopProvider {
     fn storage(
         &self,
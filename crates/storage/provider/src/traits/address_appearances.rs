@@ -0,0 +1,41 @@
+use auto_impl::auto_impl;
+use reth_interfaces::provider::ProviderResult;
+use reth_primitives::{Address, BlockNumber, TxNumber};
+use std::{collections::BTreeMap, ops::RangeInclusive};
+
+/// Address appearances reader
+#[auto_impl(&, Arc, Box)]
+pub trait AddressAppearancesReader: Send + Sync {
+    /// Collects the transactions of the given block range in which each address appears, either
+    /// as the sender, the recipient, the created contract, the emitter of a log or a participant
+    /// of an internal call.
+    ///
+    /// Internal calls are read from [`CallParticipants`](reth_db::tables::CallParticipants), so
+    /// they're only included for transactions whose calls were recorded by the executor.
+    ///
+    /// Transaction numbers of each address are sorted and deduplicated.
+    ///
+    /// NOTE: Get inclusive range of blocks.
+    fn address_appearances_with_range(
+        &self,
+        range: RangeInclusive<BlockNumber>,
+    ) -> ProviderResult<BTreeMap<Address, Vec<TxNumber>>>;
+
+    /// Returns at most `limit` indexed transaction numbers lower than `tx_number` in which the
+    /// address appears, in descending order.
+    fn address_appearances_before(
+        &self,
+        address: Address,
+        tx_number: TxNumber,
+        limit: usize,
+    ) -> ProviderResult<Vec<TxNumber>>;
+
+    /// Returns at most `limit` indexed transaction numbers higher than or equal to `tx_number` in
+    /// which the address appears, in ascending order.
+    fn address_appearances_after(
+        &self,
+        address: Address,
+        tx_number: TxNumber,
+        limit: usize,
+    ) -> ProviderResult<Vec<TxNumber>>;
+}
@@ -0,0 +1,28 @@
+use auto_impl::auto_impl;
+use reth_interfaces::provider::ProviderResult;
+use reth_primitives::{BadBlock, BlockHash};
+
+/// The maximum number of bad blocks kept in the database.
+pub const MAX_BAD_BLOCKS: usize = 64;
+
+/// Bad blocks reader
+#[auto_impl(&, Arc, Box)]
+pub trait BadBlockReader: Send + Sync {
+    /// Returns all stored bad blocks, the most recently inserted first.
+    fn bad_blocks(&self) -> ProviderResult<Vec<BadBlock>>;
+
+    /// Returns the stored bad block with the given hash, if any.
+    fn bad_block(&self, hash: BlockHash) -> ProviderResult<Option<BadBlock>> {
+        Ok(self.bad_blocks()?.into_iter().find(|bad_block| bad_block.block.hash() == hash))
+    }
+}
+
+/// Bad blocks writer
+#[auto_impl(&, Arc, Box)]
+pub trait BadBlockWriter: Send + Sync {
+    /// Stores a block that failed validation.
+    ///
+    /// Blocks that are already stored are ignored. If more than [MAX_BAD_BLOCKS] blocks are
+    /// stored, the oldest ones are evicted.
+    fn insert_bad_block(&self, bad_block: BadBlock) -> ProviderResult<()>;
+}
@@ -0,0 +1,20 @@
+use crate::BundleStateWithReceipts;
+use auto_impl::auto_impl;
+use reth_interfaces::blockchain_tree::error::InsertBlockErrorKind;
+use reth_primitives::SealedBlockWithSenders;
+
+/// Validates blocks against the state of their parent without inserting them into the tree.
+#[auto_impl(&, Arc, Box)]
+pub trait BlockValidationProvider: Send + Sync {
+    /// Validates the given block and executes it on top of the state of its parent.
+    ///
+    /// The block is executed in a scratch state that is discarded afterwards, so the tree and the
+    /// database are left untouched. The parent of the block must be part of the canonical chain or
+    /// of a sidechain of the tree. The state root of the block is always validated.
+    ///
+    /// Returns the state changes and the receipts of the block.
+    fn validate_block_detached(
+        &self,
+        block: SealedBlockWithSenders,
+    ) -> Result<BundleStateWithReceipts, InsertBlockErrorKind>;
+}
@@ -0,0 +1,12 @@
+use auto_impl::auto_impl;
+use reth_interfaces::provider::ProviderResult;
+use reth_primitives::{Address, ContractCreation};
+
+/// Contract creators reader
+#[auto_impl(&, Arc, Box)]
+pub trait ContractCreatorReader: Send + Sync {
+    /// Returns the indexed creation of the contract at the given address.
+    ///
+    /// Returns `None` if the contract is unknown or the index is disabled.
+    fn contract_creation(&self, address: Address) -> ProviderResult<Option<ContractCreation>>;
+}
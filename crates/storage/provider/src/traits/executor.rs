@@ -64,6 +64,21 @@ pub trait BlockExecutor {
 
     /// Returns the size hint of current in-memory changes.
     fn size_hint(&self) -> Option<usize>;
+
+    /// Enables or disables recording of the contracts created by executed transactions.
+    ///
+    /// The recorded creations are part of the [BundleStateWithReceipts] returned by
+    /// [BlockExecutor::take_output_state]. Recording is disabled by default, and not supported by
+    /// all executors.
+    fn set_record_contract_creations(&mut self, _record: bool) {}
+
+    /// Enables or disables recording of the accounts taking part in the internal calls of executed
+    /// transactions.
+    ///
+    /// The recorded accounts are part of the [BundleStateWithReceipts] returned by
+    /// [BlockExecutor::take_output_state]. Recording traces every transaction, so it's disabled by
+    /// default, and not supported by all executors.
+    fn set_record_call_participants(&mut self, _record: bool) {}
 }
 
 /// A [BlockExecutor] capable of in-memory pruning of the data that will be written to the database.
@@ -1,7 +1,7 @@
 use auto_impl::auto_impl;
 use reth_db::models::BlockNumberAddress;
 use reth_interfaces::provider::ProviderResult;
-use reth_primitives::{Address, BlockNumber, B256};
+use reth_primitives::{Address, BlockNumber, TxNumber, B256};
 use std::{
     collections::BTreeMap,
     ops::{Range, RangeInclusive},
@@ -38,6 +38,34 @@ pub trait HistoryWriter: Send + Sync {
         storage_transitions: BTreeMap<(Address, B256), Vec<u64>>,
     ) -> ProviderResult<()>;
 
+    /// Unwind and clear the address appearances index for the given block range.
+    ///
+    /// Returns number of addresses walked.
+    fn unwind_address_appearances_index(
+        &self,
+        range: RangeInclusive<BlockNumber>,
+    ) -> ProviderResult<usize>;
+
+    /// Insert address appearances index to database. Used inside IndexAddressAppearances stage
+    fn insert_address_appearances_index(
+        &self,
+        address_appearances: BTreeMap<Address, Vec<TxNumber>>,
+    ) -> ProviderResult<()>;
+
+    /// Unwind and clear the log index for the given block range.
+    ///
+    /// Returns number of addresses and topics walked.
+    fn unwind_log_index(&self, range: RangeInclusive<BlockNumber>) -> ProviderResult<usize>;
+
+    /// Insert log index to database. Used inside IndexLogs stage
+    fn insert_log_index(
+        &self,
+        addresses: BTreeMap<Address, Vec<BlockNumber>>,
+        topics: BTreeMap<B256, Vec<BlockNumber>>,
+    ) -> ProviderResult<()>;
+
     /// Read account/storage changesets and update account/storage history indices.
+    ///
+    /// The address appearances and log indices are updated as well, if they are maintained.
     fn update_history_indices(&self, range: RangeInclusive<BlockNumber>) -> ProviderResult<()>;
 }
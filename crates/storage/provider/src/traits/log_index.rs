@@ -0,0 +1,44 @@
+use auto_impl::auto_impl;
+use reth_interfaces::provider::ProviderResult;
+use reth_primitives::{Address, BlockNumber, B256};
+use std::{collections::BTreeMap, ops::RangeInclusive};
+
+/// Log index reader
+#[auto_impl(&, Arc, Box)]
+pub trait LogIndexReader: Send + Sync {
+    /// Collects the blocks of the given range in which each address emitted a log, and the blocks
+    /// in which each topic was logged at any position.
+    ///
+    /// Block numbers of each address and topic are sorted and deduplicated.
+    ///
+    /// NOTE: Get inclusive range of blocks.
+    #[allow(clippy::type_complexity)]
+    fn log_addresses_and_topics_with_range(
+        &self,
+        range: RangeInclusive<BlockNumber>,
+    ) -> ProviderResult<(BTreeMap<Address, Vec<BlockNumber>>, BTreeMap<B256, Vec<BlockNumber>>)>;
+
+    /// Returns the highest block covered by the log index, or `None` if the index is not
+    /// maintained.
+    fn log_index_highest_block(&self) -> ProviderResult<Option<BlockNumber>>;
+
+    /// Returns the indexed blocks of the given range in which the address emitted a log, in
+    /// ascending order.
+    ///
+    /// NOTE: Get inclusive range of blocks.
+    fn log_address_blocks(
+        &self,
+        address: Address,
+        range: RangeInclusive<BlockNumber>,
+    ) -> ProviderResult<Vec<BlockNumber>>;
+
+    /// Returns the indexed blocks of the given range in which the topic was logged, in ascending
+    /// order.
+    ///
+    /// NOTE: Get inclusive range of blocks.
+    fn log_topic_blocks(
+        &self,
+        topic: B256,
+        range: RangeInclusive<BlockNumber>,
+    ) -> ProviderResult<Vec<BlockNumber>>;
+}
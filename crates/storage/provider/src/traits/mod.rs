@@ -3,6 +3,15 @@
 mod account;
 pub use account::{AccountExtReader, AccountReader, ChangeSetReader};
 
+mod address_appearances;
+pub use address_appearances::AddressAppearancesReader;
+
+mod bad_blocks;
+pub use bad_blocks::{BadBlockReader, BadBlockWriter, MAX_BAD_BLOCKS};
+
+mod contract_creators;
+pub use contract_creators::ContractCreatorReader;
+
 mod storage;
 pub use storage::StorageReader;
 
@@ -12,6 +21,9 @@ pub use block::{
     TransactionVariant,
 };
 
+mod block_validation;
+pub use block_validation::BlockValidationProvider;
+
 mod block_hash;
 pub use block_hash::BlockHashReader;
 
@@ -30,13 +42,16 @@ pub use header::HeaderProvider;
 mod header_sync_gap;
 pub use header_sync_gap::{HeaderSyncGap, HeaderSyncGapProvider, HeaderSyncMode};
 
+mod log_index;
+pub use log_index::LogIndexReader;
+
 mod receipts;
 pub use receipts::{ReceiptProvider, ReceiptProviderIdExt};
 
 mod state;
 pub use state::{
     BlockchainTreePendingStateProvider, BundleStateDataProvider, StateProvider, StateProviderBox,
-    StateProviderFactory, StateRootProvider,
+    StateProviderFactory, StateRangeProvider, StateRootProvider,
 };
 
 mod transactions;
@@ -6,14 +6,16 @@ use reth_primitives::{
     trie::AccountProof, Address, BlockHash, BlockId, BlockNumHash, BlockNumber, BlockNumberOrTag,
     Bytecode, StorageKey, StorageValue, B256, KECCAK_EMPTY, U256,
 };
-use reth_trie::updates::TrieUpdates;
+use reth_trie::{updates::TrieUpdates, AccountRange, HashedPostState, StorageRange};
 
 /// Type alias of boxed [StateProvider].
 pub type StateProviderBox = Box<dyn StateProvider>;
 
 /// An abstraction for a type that provides state data.
 #[auto_impl(&, Arc, Box)]
-pub trait StateProvider: BlockHashReader + AccountReader + StateRootProvider + Send + Sync {
+pub trait StateProvider:
+    BlockHashReader + AccountReader + StateRootProvider + StateRangeProvider + Send + Sync
+{
     /// Get storage of given account.
     fn storage(
         &self,
@@ -244,3 +246,30 @@ pub trait StateRootProvider: Send + Sync {
         bundle_state: &BundleStateWithReceipts,
     ) -> ProviderResult<(B256, TrieUpdates)>;
 }
+
+/// A type that can iterate over the hashed state in key order.
+#[auto_impl[Box,&, Arc]]
+pub trait StateRangeProvider: Send + Sync {
+    /// Returns at most `limit` accounts of the `HashedPostState` on top of the current state,
+    /// ordered by hashed address and starting at the `start` hashed address.
+    ///
+    /// At most `storage_limit` storage slots of every returned account are included.
+    fn account_range(
+        &self,
+        post_state: &HashedPostState,
+        start: B256,
+        limit: usize,
+        storage_limit: usize,
+    ) -> ProviderResult<AccountRange>;
+
+    /// Returns at most `limit` non-zero storage slots of the account with the given hashed
+    /// address of the `HashedPostState` on top of the current state, ordered by hashed slot and
+    /// starting at the `start` hashed slot.
+    fn storage_range(
+        &self,
+        post_state: &HashedPostState,
+        hashed_address: B256,
+        start: B256,
+        limit: usize,
+    ) -> ProviderResult<StorageRange>;
+}
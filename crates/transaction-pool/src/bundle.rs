@@ -0,0 +1,561 @@
+//! A pool for bundles of transactions that are submitted by searchers.
+//!
+//! Bundles are ordered lists of transactions that must be included atomically and in order at the
+//! top of a block. Unlike regular transactions, bundles are only valid for a range of blocks and
+//! are not propagated to other peers.
+
+use parking_lot::RwLock;
+use reth_primitives::{Address, TransactionSignedEcRecovered, TxHash, B256, U256};
+use std::{
+    collections::{HashMap, HashSet},
+    ops::Range,
+    sync::Arc,
+};
+use tracing::trace;
+
+/// The default maximum number of bundles that are tracked by the [BundlePool].
+pub const DEFAULT_MAX_BUNDLES: usize = 10_000;
+
+/// The gas a builder reserves for every refund payout transaction of a bundle.
+pub const SBUNDLE_PAYOUT_MAX_COST: u64 = 30_000;
+
+/// A bundle tracked by the [BundlePool].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PooledBundle {
+    /// The hash that identifies the bundle.
+    pub hash: B256,
+    /// The first block the bundle is valid for.
+    pub block_number: u64,
+    /// The last block the bundle is valid for.
+    pub max_block_number: u64,
+    /// The earliest timestamp of a block the bundle is valid for.
+    pub min_timestamp: Option<u64>,
+    /// The latest timestamp of a block the bundle is valid for.
+    pub max_timestamp: Option<u64>,
+    /// The transactions of the bundle, in execution order.
+    pub transactions: Vec<TransactionSignedEcRecovered>,
+    /// Hashes of the transactions that are allowed to revert without invalidating the bundle.
+    pub reverting_tx_hashes: HashSet<TxHash>,
+    /// UUID that can be used to replace or cancel the bundle.
+    pub replacement_uuid: Option<String>,
+    /// Refund constraints of the bundle and of its nested bundles. Nested bundles come before
+    /// the bundles they are part of, which is the order their refunds are paid out in.
+    pub refunds: Vec<BundleRefunds>,
+    /// The recipients of a refund of the whole bundle, if it's refunded as part of another
+    /// bundle, with their share in percent. If empty, the signer of the first transaction is
+    /// refunded.
+    pub refund_recipients: Vec<(Address, u64)>,
+}
+
+impl PooledBundle {
+    /// Returns `true` if the bundle can be included in the block with the given number and
+    /// timestamp.
+    #[inline]
+    pub fn is_valid_for_block(&self, block_number: u64, timestamp: u64) -> bool {
+        (self.block_number..=self.max_block_number).contains(&block_number) &&
+            self.min_timestamp.map_or(true, |min| min <= timestamp) &&
+            self.max_timestamp.map_or(true, |max| timestamp <= max)
+    }
+
+    /// Returns `true` if the transaction with the given hash is allowed to revert.
+    #[inline]
+    pub fn can_revert(&self, tx_hash: &TxHash) -> bool {
+        self.reverting_tx_hashes.contains(tx_hash)
+    }
+
+    /// Returns `true` if the bundle can not be included in any block after the given block.
+    #[inline]
+    pub const fn is_expired_at(&self, block_number: u64) -> bool {
+        self.max_block_number <= block_number
+    }
+
+    /// Returns the signer of the first transaction of the bundle, which is considered the
+    /// submitter of the bundle and is the only one allowed to replace or cancel it by its
+    /// replacement UUID.
+    #[inline]
+    pub fn signer(&self) -> Address {
+        self.transactions.first().map(|tx| tx.signer()).unwrap_or_default()
+    }
+
+    /// Returns `true` if the bundle or any of its nested bundles pays out refunds.
+    #[inline]
+    pub fn has_refunds(&self) -> bool {
+        !self.refunds.is_empty()
+    }
+}
+
+/// Refund constraints of a bundle, or of one of the bundles nested in it.
+///
+/// The refunds are paid out right after the last transaction of the bundle, from the value the
+/// items without a refund generated for the coinbase.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BundleRefunds {
+    /// The transactions of every item of the bundle, as ranges of indices into
+    /// [PooledBundle::transactions].
+    pub items: Vec<Range<usize>>,
+    /// The refunds of the items.
+    pub refunds: Vec<BundleRefund>,
+}
+
+impl BundleRefunds {
+    /// Returns the indices of all transactions of the bundle.
+    pub fn span(&self) -> Range<usize> {
+        let start = self.items.first().map_or(0, |item| item.start);
+        let end = self.items.last().map_or(start, |item| item.end);
+        start..end
+    }
+
+    /// Returns `true` if the item with the given index is refunded.
+    pub fn is_refunded(&self, item: usize) -> bool {
+        self.refunds.iter().any(|refund| refund.item == item)
+    }
+}
+
+/// A refund of an item of a bundle, see [BundleRefunds].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BundleRefund {
+    /// Index of the refunded item.
+    pub item: usize,
+    /// Share of the refundable value of the bundle, in percent.
+    pub percent: u64,
+    /// The recipients of the refund, with their share in percent.
+    pub recipients: Vec<(Address, u64)>,
+}
+
+impl BundleRefund {
+    /// Splits the refund among its recipients and returns the value paid out to each of them.
+    ///
+    /// Every payout is a transaction of the builder that costs at most `payout_fee`, the fees of
+    /// all payouts are deducted from the refund before it's split. Returns `None` if the refund
+    /// doesn't cover these fees.
+    pub fn payouts(
+        &self,
+        refundable_value: U256,
+        payout_fee: U256,
+    ) -> Option<Vec<(Address, U256)>> {
+        let allocated = refundable_value * U256::from(self.percent) / U256::from(100);
+        let allocated = allocated.checked_sub(payout_fee * U256::from(self.recipients.len()))?;
+        Some(
+            self.recipients
+                .iter()
+                .map(|(address, percent)| {
+                    (*address, allocated * U256::from(*percent) / U256::from(100))
+                })
+                .collect(),
+        )
+    }
+}
+
+/// Errors that can occur when adding a bundle to the [BundlePool].
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum BundlePoolError {
+    /// Thrown if the bundle does not contain any transactions.
+    #[error("bundle {0} contains no transactions")]
+    EmptyBundle(B256),
+    /// Thrown if the bundle's block range is empty.
+    #[error("bundle {hash} has an invalid block range {block_number}..={max_block_number}")]
+    InvalidBlockRange {
+        /// Hash of the bundle.
+        hash: B256,
+        /// The first block the bundle is valid for.
+        block_number: u64,
+        /// The last block the bundle is valid for.
+        max_block_number: u64,
+    },
+    /// Thrown if the bundle's timestamp range is empty.
+    #[error("bundle {hash} has an invalid timestamp range {min_timestamp}..={max_timestamp}")]
+    InvalidTimestampRange {
+        /// Hash of the bundle.
+        hash: B256,
+        /// The earliest timestamp the bundle is valid for.
+        min_timestamp: u64,
+        /// The latest timestamp the bundle is valid for.
+        max_timestamp: u64,
+    },
+    /// Thrown if the bundle contains a blob transaction, bundles can not carry blob sidecars.
+    #[error("bundle {hash} contains blob transaction {tx_hash}")]
+    BlobTransaction {
+        /// Hash of the bundle.
+        hash: B256,
+        /// Hash of the blob transaction.
+        tx_hash: TxHash,
+    },
+    /// Thrown if the bundle can no longer be included because its last block is already mined.
+    #[error("bundle {hash} expired at block {max_block_number}, current tip is {tip}")]
+    Expired {
+        /// Hash of the bundle.
+        hash: B256,
+        /// The last block the bundle is valid for.
+        max_block_number: u64,
+        /// The current canonical tip.
+        tip: u64,
+    },
+    /// Thrown if the pool already tracks the maximum number of bundles.
+    #[error("bundle pool is full, max {0} bundles")]
+    PoolFull(usize),
+    /// Thrown if the bundle with a replacement UUID is replaced or cancelled by another signer
+    /// than the one that submitted it.
+    #[error("replacement uuid {uuid} belongs to another signer")]
+    UnauthorizedReplacement {
+        /// The replacement UUID of the bundle.
+        uuid: String,
+        /// The signer of the replacement or cancellation.
+        signer: Address,
+    },
+}
+
+/// A shared pool of [PooledBundle]s, keyed by their hash.
+///
+/// This type is cheap to clone, all clones share the same bundles. Bundles are added via RPC and
+/// consumed by the payload builder which requests all bundles that are valid for the block that
+/// is being built. Expired bundles are evicted once the canonical chain advances past their last
+/// valid block, see [BundlePool::on_canonical_block].
+///
+/// A bundle with a replacement UUID replaces the bundle that was previously submitted with the
+/// same UUID, and can be cancelled by its UUID.
+#[derive(Debug, Clone)]
+pub struct BundlePool {
+    inner: Arc<RwLock<BundlePoolInner>>,
+}
+
+impl BundlePool {
+    /// Creates a new, empty pool that tracks at most `max_bundles` bundles.
+    pub fn new(max_bundles: usize) -> Self {
+        Self {
+            inner: Arc::new(RwLock::new(BundlePoolInner {
+                bundles: Default::default(),
+                by_replacement_uuid: Default::default(),
+                max_bundles,
+                tip: 0,
+            })),
+        }
+    }
+
+    /// Adds a bundle to the pool and returns its hash.
+    ///
+    /// If a bundle with the same hash, or with the same replacement UUID, already exists, it is
+    /// replaced.
+    pub fn add_bundle(&self, bundle: PooledBundle) -> Result<B256, BundlePoolError> {
+        let hash = bundle.hash;
+        if bundle.transactions.is_empty() {
+            return Err(BundlePoolError::EmptyBundle(hash))
+        }
+        if bundle.block_number > bundle.max_block_number {
+            return Err(BundlePoolError::InvalidBlockRange {
+                hash,
+                block_number: bundle.block_number,
+                max_block_number: bundle.max_block_number,
+            })
+        }
+        if let (Some(min_timestamp), Some(max_timestamp)) =
+            (bundle.min_timestamp, bundle.max_timestamp)
+        {
+            if min_timestamp > max_timestamp {
+                return Err(BundlePoolError::InvalidTimestampRange {
+                    hash,
+                    min_timestamp,
+                    max_timestamp,
+                })
+            }
+        }
+        if let Some(tx) = bundle.transactions.iter().find(|tx| tx.is_eip4844()) {
+            return Err(BundlePoolError::BlobTransaction { hash, tx_hash: tx.hash() })
+        }
+
+        let mut inner = self.inner.write();
+        if bundle.is_expired_at(inner.tip) {
+            return Err(BundlePoolError::Expired {
+                hash,
+                max_block_number: bundle.max_block_number,
+                tip: inner.tip,
+            })
+        }
+        let replaced = bundle
+            .replacement_uuid
+            .as_ref()
+            .and_then(|uuid| inner.by_replacement_uuid.get(uuid))
+            .copied()
+            .filter(|replaced| *replaced != hash);
+        if let Some(replaced) = replaced.and_then(|replaced| inner.bundles.get(&replaced)) {
+            if replaced.signer() != bundle.signer() {
+                return Err(BundlePoolError::UnauthorizedReplacement {
+                    uuid: replaced.replacement_uuid.clone().unwrap_or_default(),
+                    signer: bundle.signer(),
+                })
+            }
+        }
+        if !inner.bundles.contains_key(&hash) &&
+            replaced.is_none() &&
+            inner.bundles.len() >= inner.max_bundles
+        {
+            return Err(BundlePoolError::PoolFull(inner.max_bundles))
+        }
+
+        if let Some(replaced) = replaced {
+            trace!(target: "txpool::bundle", %hash, %replaced, "replacing bundle");
+            inner.remove(&replaced);
+        }
+
+        trace!(target: "txpool::bundle", %hash, block_number=bundle.block_number, max_block_number=bundle.max_block_number, "adding bundle");
+        inner.insert(bundle);
+        Ok(hash)
+    }
+
+    /// Returns the bundle with the given hash.
+    pub fn get(&self, hash: &B256) -> Option<Arc<PooledBundle>> {
+        self.inner.read().bundles.get(hash).cloned()
+    }
+
+    /// Returns `true` if the pool contains a bundle with the given hash.
+    pub fn contains(&self, hash: &B256) -> bool {
+        self.inner.read().bundles.contains_key(hash)
+    }
+
+    /// Removes the bundle with the given hash.
+    pub fn remove(&self, hash: &B256) -> Option<Arc<PooledBundle>> {
+        self.inner.write().remove(hash)
+    }
+
+    /// Removes the bundle that was submitted with the given replacement UUID.
+    ///
+    /// Returns an error if the bundle was submitted by another signer, see [PooledBundle::signer].
+    pub fn remove_by_replacement_uuid(
+        &self,
+        uuid: &str,
+        signer: Address,
+    ) -> Result<Option<Arc<PooledBundle>>, BundlePoolError> {
+        let mut inner = self.inner.write();
+        let Some(hash) = inner.by_replacement_uuid.get(uuid).copied() else { return Ok(None) };
+        if inner.bundles.get(&hash).is_some_and(|bundle| bundle.signer() != signer) {
+            return Err(BundlePoolError::UnauthorizedReplacement { uuid: uuid.to_string(), signer })
+        }
+        Ok(inner.remove(&hash))
+    }
+
+    /// Returns all bundles that can be included in the block with the given number and timestamp.
+    ///
+    /// Bundles are returned in no particular order.
+    pub fn bundles_for_block(&self, block_number: u64, timestamp: u64) -> Vec<Arc<PooledBundle>> {
+        self.inner
+            .read()
+            .bundles
+            .values()
+            .filter(|bundle| bundle.is_valid_for_block(block_number, timestamp))
+            .cloned()
+            .collect()
+    }
+
+    /// Updates the tracked canonical tip and evicts all bundles that can no longer be included,
+    /// either because they expired or because some of their transactions were mined.
+    pub fn on_canonical_block(&self, tip: u64, mined_transactions: &[TxHash]) {
+        let mined = mined_transactions.iter().collect::<HashSet<_>>();
+        let mut inner = self.inner.write();
+        inner.tip = tip;
+        let evicted = inner
+            .bundles
+            .values()
+            .filter(|bundle| {
+                bundle.is_expired_at(tip) ||
+                    bundle.transactions.iter().any(|tx| mined.contains(&tx.hash))
+            })
+            .map(|bundle| bundle.hash)
+            .collect::<Vec<_>>();
+        for hash in &evicted {
+            inner.remove(hash);
+        }
+        trace!(target: "txpool::bundle", tip, evicted=evicted.len(), "evicted expired and included bundles");
+    }
+
+    /// Returns the number of bundles in the pool.
+    pub fn len(&self) -> usize {
+        self.inner.read().bundles.len()
+    }
+
+    /// Returns `true` if the pool contains no bundles.
+    pub fn is_empty(&self) -> bool {
+        self.inner.read().bundles.is_empty()
+    }
+}
+
+impl Default for BundlePool {
+    fn default() -> Self {
+        Self::new(DEFAULT_MAX_BUNDLES)
+    }
+}
+
+/// Container type for [BundlePool] internals.
+#[derive(Debug)]
+struct BundlePoolInner {
+    /// All tracked bundles, keyed by their hash.
+    bundles: HashMap<B256, Arc<PooledBundle>>,
+    /// Hashes of the bundles that were submitted with a replacement UUID.
+    by_replacement_uuid: HashMap<String, B256>,
+    /// The maximum number of bundles to track.
+    max_bundles: usize,
+    /// The last seen canonical tip.
+    tip: u64,
+}
+
+impl BundlePoolInner {
+    /// Inserts the bundle and tracks its replacement UUID.
+    fn insert(&mut self, bundle: PooledBundle) {
+        if let Some(uuid) = &bundle.replacement_uuid {
+            self.by_replacement_uuid.insert(uuid.clone(), bundle.hash);
+        }
+        self.bundles.insert(bundle.hash, Arc::new(bundle));
+    }
+
+    /// Removes the bundle with the given hash and its replacement UUID.
+    fn remove(&mut self, hash: &B256) -> Option<Arc<PooledBundle>> {
+        let bundle = self.bundles.remove(hash)?;
+        if let Some(uuid) = &bundle.replacement_uuid {
+            // the uuid may already point to a bundle that replaced this one
+            if self.by_replacement_uuid.get(uuid) == Some(hash) {
+                self.by_replacement_uuid.remove(uuid);
+            }
+        }
+        Some(bundle)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{test_utils::MockTransaction, PoolTransaction};
+
+    fn bundle(hash: u64, block_number: u64, max_block_number: u64) -> PooledBundle {
+        PooledBundle {
+            hash: B256::from(U256::from(hash)),
+            block_number,
+            max_block_number,
+            min_timestamp: None,
+            max_timestamp: None,
+            transactions: vec![MockTransaction::eip1559()
+                .with_sender(Address::ZERO)
+                .to_recovered_transaction()],
+            reverting_tx_hashes: Default::default(),
+            replacement_uuid: None,
+            refunds: Vec::new(),
+            refund_recipients: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn bundles_for_block_respects_range() {
+        let pool = BundlePool::default();
+        pool.add_bundle(bundle(1, 10, 10)).unwrap();
+        pool.add_bundle(bundle(2, 10, 12)).unwrap();
+        pool.add_bundle(bundle(3, 11, 15)).unwrap();
+
+        assert_eq!(pool.bundles_for_block(9, 0).len(), 0);
+        assert_eq!(pool.bundles_for_block(10, 0).len(), 2);
+        assert_eq!(pool.bundles_for_block(11, 0).len(), 2);
+        assert_eq!(pool.bundles_for_block(13, 0).len(), 1);
+    }
+
+    #[test]
+    fn evicts_expired_bundles() {
+        let pool = BundlePool::default();
+        pool.add_bundle(bundle(1, 10, 10)).unwrap();
+        pool.add_bundle(bundle(2, 10, 12)).unwrap();
+
+        pool.on_canonical_block(10, &[]);
+        assert_eq!(pool.len(), 1);
+        assert!(pool.contains(&B256::from(U256::from(2))));
+
+        let err = pool.add_bundle(bundle(3, 9, 10)).unwrap_err();
+        assert!(matches!(err, BundlePoolError::Expired { tip: 10, .. }));
+    }
+
+    #[test]
+    fn evicts_included_bundles() {
+        let pool = BundlePool::default();
+        let included = bundle(1, 10, 12);
+        let mined = included.transactions[0].hash;
+        pool.add_bundle(included).unwrap();
+        pool.add_bundle(bundle(2, 10, 12)).unwrap();
+
+        pool.on_canonical_block(10, &[mined]);
+        assert_eq!(pool.len(), 1);
+        assert!(pool.contains(&B256::from(U256::from(2))));
+    }
+
+    #[test]
+    fn splits_refunds() {
+        let refund = BundleRefund {
+            item: 0,
+            percent: 50,
+            recipients: vec![(Address::with_last_byte(1), 75), (Address::with_last_byte(2), 25)],
+        };
+        assert_eq!(
+            refund.payouts(U256::from(1_000), U256::from(100)),
+            Some(vec![
+                (Address::with_last_byte(1), U256::from(225)),
+                (Address::with_last_byte(2), U256::from(75)),
+            ])
+        );
+        // the refund doesn't cover the fees of the two payouts
+        assert_eq!(refund.payouts(U256::from(1_000), U256::from(300)), None);
+    }
+
+    #[test]
+    fn rejects_when_full() {
+        let pool = BundlePool::new(1);
+        pool.add_bundle(bundle(1, 10, 10)).unwrap();
+        // replacing an existing bundle is always possible
+        pool.add_bundle(bundle(1, 10, 11)).unwrap();
+        assert_eq!(pool.add_bundle(bundle(2, 10, 10)), Err(BundlePoolError::PoolFull(1)));
+    }
+
+    #[test]
+    fn bundles_for_block_respects_timestamps() {
+        let pool = BundlePool::default();
+        pool.add_bundle(PooledBundle { min_timestamp: Some(100), ..bundle(1, 10, 10) }).unwrap();
+        pool.add_bundle(PooledBundle { max_timestamp: Some(100), ..bundle(2, 10, 10) }).unwrap();
+
+        assert_eq!(pool.bundles_for_block(10, 99)[0].hash, B256::from(U256::from(2)));
+        assert_eq!(pool.bundles_for_block(10, 100).len(), 2);
+        assert_eq!(pool.bundles_for_block(10, 101)[0].hash, B256::from(U256::from(1)));
+    }
+
+    #[test]
+    fn replaces_and_cancels_by_uuid() {
+        let pool = BundlePool::new(1);
+        let uuid = Some("e4b5ad11-5d6d-4c5f-a1ab-1ab5fc3fa6e1".to_string());
+        pool.add_bundle(PooledBundle { replacement_uuid: uuid.clone(), ..bundle(1, 10, 10) })
+            .unwrap();
+        // replacing is possible even if the pool is full
+        pool.add_bundle(PooledBundle { replacement_uuid: uuid.clone(), ..bundle(2, 10, 10) })
+            .unwrap();
+        assert_eq!(pool.len(), 1);
+        assert!(pool.contains(&B256::from(U256::from(2))));
+
+        let uuid = uuid.as_deref().unwrap();
+        let removed = pool.remove_by_replacement_uuid(uuid, Address::ZERO).unwrap().unwrap();
+        assert_eq!(removed.hash, B256::from(U256::from(2)));
+        assert!(pool.is_empty());
+        assert!(pool.remove_by_replacement_uuid(uuid, Address::ZERO).unwrap().is_none());
+    }
+
+    #[test]
+    fn only_submitter_replaces_and_cancels_by_uuid() {
+        let pool = BundlePool::default();
+        let uuid = "e4b5ad11-5d6d-4c5f-a1ab-1ab5fc3fa6e1".to_string();
+        pool.add_bundle(PooledBundle { replacement_uuid: Some(uuid.clone()), ..bundle(1, 10, 10) })
+            .unwrap();
+
+        let other = Address::with_last_byte(1);
+        let mut replacement =
+            PooledBundle { replacement_uuid: Some(uuid.clone()), ..bundle(2, 10, 10) };
+        replacement.transactions =
+            vec![MockTransaction::eip1559().with_sender(other).to_recovered_transaction()];
+        let err = pool.add_bundle(replacement).unwrap_err();
+        assert!(
+            matches!(err, BundlePoolError::UnauthorizedReplacement { signer, .. } if signer == other)
+        );
+
+        let err = pool.remove_by_replacement_uuid(&uuid, other).unwrap_err();
+        assert!(matches!(err, BundlePoolError::UnauthorizedReplacement { .. }));
+        assert!(pool.contains(&B256::from(U256::from(1))));
+    }
+}
@@ -152,7 +152,9 @@
 use crate::{identifier::TransactionId, pool::PoolInner};
 use aquamarine as _;
 use reth_eth_wire::HandleAnnouncement;
-use reth_primitives::{Address, BlobTransactionSidecar, PooledTransactionsElement, TxHash, U256};
+use reth_primitives::{
+    Address, BlobTransactionSidecar, BlockNumber, PooledTransactionsElement, TxHash, U256,
+};
 use reth_provider::StateProviderFactory;
 use std::{collections::HashSet, sync::Arc};
 use tokio::sync::mpsc::Receiver;
@@ -160,6 +162,10 @@ use tracing::{instrument, trace};
 
 pub use crate::{
     blobstore::{BlobStore, BlobStoreError},
+    bundle::{
+        BundlePool, BundlePoolError, BundleRefund, BundleRefunds, PooledBundle,
+        SBUNDLE_PAYOUT_MAX_COST,
+    },
     config::{
         LocalTransactionConfig, PoolConfig, PriceBumpConfig, SubPoolLimit, DEFAULT_PRICE_BUMP,
         REPLACE_BLOB_PRICE_BUMP, TXPOOL_MAX_ACCOUNT_SLOTS_PER_SENDER,
@@ -186,6 +192,7 @@ pub mod pool;
 pub mod validate;
 
 pub mod blobstore;
+pub mod bundle;
 mod config;
 mod identifier;
 mod ordering;
@@ -353,6 +360,15 @@ where
         self.pool.add_transactions(origin, std::iter::once(tx)).pop().expect("exists; qed")
     }
 
+    async fn add_private_transaction(
+        &self,
+        transaction: Self::Transaction,
+        max_block_number: Option<BlockNumber>,
+    ) -> PoolResult<TxHash> {
+        let (_, tx) = self.validate(TransactionOrigin::Private, transaction).await;
+        self.pool.add_private_transaction(tx, max_block_number)
+    }
+
     async fn add_transactions(
         &self,
         origin: TransactionOrigin,
@@ -502,6 +518,10 @@ where
         self.pool.unique_senders()
     }
 
+    fn bundle_pool(&self) -> BundlePool {
+        self.pool.bundle_pool().clone()
+    }
+
     fn get_blob(&self, tx_hash: TxHash) -> Result<Option<BlobTransactionSidecar>, BlobStoreError> {
         self.pool.blob_store().get(tx_hash)
     }
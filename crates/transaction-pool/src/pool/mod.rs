@@ -66,6 +66,7 @@
 //!    category (2.) and become pending.
 
 use crate::{
+    bundle::BundlePool,
     error::{PoolError, PoolErrorKind, PoolResult},
     identifier::{SenderId, SenderIdentifiers, TransactionId},
     pool::{
@@ -84,7 +85,7 @@ use best::BestTransactions;
 use parking_lot::{Mutex, RwLock, RwLockReadGuard};
 use reth_eth_wire::HandleAnnouncement;
 use reth_primitives::{
-    Address, BlobTransaction, BlobTransactionSidecar, IntoRecoveredTransaction,
+    Address, BlobTransaction, BlobTransactionSidecar, BlockNumber, IntoRecoveredTransaction,
     PooledTransactionsElement, TransactionSigned, TxHash, B256,
 };
 use std::{
@@ -150,6 +151,10 @@ where
     blob_transaction_sidecar_listener: Mutex<Vec<BlobTransactionSidecarListener>>,
     /// Metrics for the blob store
     blob_store_metrics: BlobStoreMetrics,
+    /// Bundles submitted by searchers.
+    bundle_pool: BundlePool,
+    /// Highest block number in which a private transaction may be included, by hash.
+    private_transaction_deadlines: Mutex<HashMap<TxHash, BlockNumber>>,
 }
 
 // === impl PoolInner ===
@@ -173,9 +178,16 @@ where
             config,
             blob_store,
             blob_store_metrics: Default::default(),
+            bundle_pool: Default::default(),
+            private_transaction_deadlines: Default::default(),
         }
     }
 
+    /// Returns the pool of bundles.
+    pub(crate) const fn bundle_pool(&self) -> &BundlePool {
+        &self.bundle_pool
+    }
+
     /// Returns the configured blob store.
     pub(crate) const fn blob_store(&self) -> &S {
         &self.blob_store
@@ -318,7 +330,8 @@ where
         let transactions = self.get_all(tx_hashes);
         let mut elements = Vec::with_capacity(transactions.len());
         let mut size = 0;
-        for transaction in transactions {
+        // transactions that must not be propagated are never handed out to peers
+        for transaction in transactions.into_iter().filter(|tx| tx.propagate) {
             let tx = transaction.to_recovered_transaction().into_signed();
             let pooled = if tx.is_eip4844() {
                 if let Some(blob) = self.get_blob_transaction(tx) {
@@ -351,6 +364,9 @@ where
 
         let changed_senders = self.changed_senders(changed_accounts.into_iter());
 
+        // evict bundles that can no longer be included
+        self.bundle_pool.on_canonical_block(new_tip.number, &mined_transactions);
+
         // update the pool
         let outcome = self.pool.write().on_canonical_state_change(
             block_info,
@@ -361,6 +377,14 @@ where
         // This will discard outdated transactions based on the account's nonce
         self.delete_discarded_blobs(outcome.discarded.iter());
 
+        // forget the max block numbers of mined and discarded private transactions
+        self.remove_private_transaction_deadlines(
+            outcome.mined.iter().chain(outcome.discarded.iter().map(|tx| tx.hash())),
+        );
+
+        // drop private transactions that are past their max block number
+        self.remove_expired_private_transactions(new_tip.number);
+
         // notify listeners about updates
         self.notify_on_new_state(outcome);
     }
@@ -380,6 +404,7 @@ where
         // This deletes outdated blob txs from the blob store, based on the account's nonce. This is
         // called during txpool maintenance when the pool drifted.
         self.delete_discarded_blobs(discarded.iter());
+        self.remove_private_transaction_deadlines(discarded.iter().map(|tx| tx.hash()));
     }
 
     /// Add a single validated transaction into the pool.
@@ -438,6 +463,10 @@ where
                     self.delete_blob(replaced);
                 }
 
+                if let Some(replaced) = added.replaced() {
+                    self.remove_private_transaction_deadlines(std::iter::once(replaced.hash()));
+                }
+
                 // Notify about new pending transactions
                 if let Some(pending) = added.as_pending() {
                     self.on_new_pending_transaction(pending);
@@ -448,6 +477,7 @@ where
 
                 if let Some(discarded) = added.discarded_transactions() {
                     self.delete_discarded_blobs(discarded.iter());
+                    self.remove_private_transaction_deadlines(discarded.iter().map(|tx| tx.hash()));
                 }
 
                 // Notify listeners for _all_ transactions
@@ -661,6 +691,7 @@ where
             return Vec::new()
         }
         let removed = self.pool.write().remove_transactions(hashes);
+        self.remove_private_transaction_deadlines(removed.iter().map(|tx| tx.hash()));
 
         let mut listener = self.event_listener.write();
 
@@ -669,6 +700,65 @@ where
         removed
     }
 
+    /// Adds a validated transaction with a [TransactionOrigin::Private] origin.
+    ///
+    /// If a `max_block_number` is provided, the transaction is removed from the pool once a
+    /// canonical block with that number has been processed.
+    pub(crate) fn add_private_transaction(
+        &self,
+        transaction: TransactionValidationOutcome<T::Transaction>,
+        max_block_number: Option<BlockNumber>,
+    ) -> PoolResult<TxHash> {
+        let hash = self
+            .add_transactions(TransactionOrigin::Private, std::iter::once(transaction))
+            .pop()
+            .expect("exists; qed")?;
+
+        if let Some(max_block_number) = max_block_number {
+            self.private_transaction_deadlines.lock().insert(hash, max_block_number);
+        }
+
+        Ok(hash)
+    }
+
+    /// Forgets the max block numbers of the given transactions, which have left the pool.
+    fn remove_private_transaction_deadlines<'a>(
+        &self,
+        hashes: impl IntoIterator<Item = &'a TxHash>,
+    ) {
+        let mut deadlines = self.private_transaction_deadlines.lock();
+        if deadlines.is_empty() {
+            return
+        }
+        for hash in hashes {
+            deadlines.remove(hash);
+        }
+    }
+
+    /// Removes all private transactions that can no longer be included after the given block.
+    fn remove_expired_private_transactions(&self, tip: BlockNumber) {
+        let expired = {
+            let mut deadlines = self.private_transaction_deadlines.lock();
+            if deadlines.is_empty() {
+                return
+            }
+            let expired = deadlines
+                .iter()
+                .filter(|(_, max_block_number)| **max_block_number <= tip)
+                .map(|(hash, _)| *hash)
+                .collect::<Vec<_>>();
+            for hash in &expired {
+                deadlines.remove(hash);
+            }
+            expired
+        };
+
+        if !expired.is_empty() {
+            debug!(target: "txpool", count=%expired.len(), %tip, "removing expired private txs");
+            self.remove_transactions(expired);
+        }
+    }
+
     /// Removes all transactions that are present in the pool.
     pub(crate) fn retain_unknown<A: HandleAnnouncement>(&self, announcement: &mut A)
     where
@@ -753,6 +843,7 @@ where
 
         // delete any blobs associated with discarded blob transactions
         self.delete_discarded_blobs(discarded.iter());
+        self.remove_private_transaction_deadlines(discarded.iter().map(|tx| tx.hash()));
 
         // then collect into tx hashes
         discarded.into_iter().map(|tx| *tx.hash()).collect()
@@ -2,6 +2,7 @@
 
 use crate::{
     blobstore::BlobStoreError,
+    bundle::BundlePool,
     error::PoolResult,
     pool::{state::SubPool, BestTransactionFilter, TransactionEvents},
     validate::ValidPoolTransaction,
@@ -11,10 +12,11 @@ use futures_util::{ready, Stream};
 use reth_eth_wire::HandleAnnouncement;
 use reth_primitives::{
     kzg::KzgSettings, AccessList, Address, BlobTransactionSidecar, BlobTransactionValidationError,
-    FromRecoveredPooledTransaction, FromRecoveredTransaction, IntoRecoveredTransaction, PeerId,
-    PooledTransactionsElement, PooledTransactionsElementEcRecovered, SealedBlock, Transaction,
-    TransactionKind, TransactionSignedEcRecovered, TxEip4844, TxHash, B256, EIP1559_TX_TYPE_ID,
-    EIP4844_TX_TYPE_ID, U256,
+    BlockNumber, FromRecoveredPooledTransaction, FromRecoveredTransaction,
+    IntoRecoveredTransaction, PeerId, PooledTransactionsElement,
+    PooledTransactionsElementEcRecovered, SealedBlock, Transaction, TransactionKind,
+    TransactionSignedEcRecovered, TxEip4844, TxHash, B256, EIP1559_TX_TYPE_ID, EIP4844_TX_TYPE_ID,
+    U256,
 };
 #[cfg(feature = "serde")]
 use serde::{Deserialize, Serialize};
@@ -91,6 +93,19 @@ pub trait TransactionPool: Send + Sync + Clone {
         transaction: Self::Transaction,
     ) -> PoolResult<TxHash>;
 
+    /// Adds an _unvalidated_ transaction into the pool with a [TransactionOrigin::Private] origin.
+    ///
+    /// Private transactions are never propagated to the network. If a `max_block_number` is
+    /// provided, the transaction is dropped from the pool once a canonical block with that number
+    /// has been processed.
+    ///
+    /// Consumer: RPC
+    async fn add_private_transaction(
+        &self,
+        transaction: Self::Transaction,
+        max_block_number: Option<BlockNumber>,
+    ) -> PoolResult<TxHash>;
+
     /// Adds the given _unvalidated_ transaction into the pool.
     ///
     /// Returns a list of results.
@@ -212,6 +227,9 @@ pub trait TransactionPool: Send + Sync + Clone {
     ///
     /// If the transaction is a blob transaction, the sidecar will be included.
     ///
+    /// Transactions that are not allowed to be propagated, like [TransactionOrigin::Private]
+    /// transactions, are skipped.
+    ///
     /// Consumer: P2P
     fn get_pooled_transaction_elements(
         &self,
@@ -341,6 +359,11 @@ pub trait TransactionPool: Send + Sync + Clone {
     /// Returns a set of all senders of transactions in the pool
     fn unique_senders(&self) -> HashSet<Address>;
 
+    /// Returns a handle to the pool of bundles submitted by searchers.
+    ///
+    /// Consumer: RPC and block production
+    fn bundle_pool(&self) -> BundlePool;
+
     /// Returns the [BlobTransactionSidecar] for the given transaction hash if it exists in the blob
     /// store.
     fn get_blob(&self, tx_hash: TxHash) -> Result<Option<BlobTransactionSidecar>, BlobStoreError>;
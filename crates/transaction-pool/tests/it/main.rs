@@ -8,5 +8,7 @@ mod evict;
 mod listeners;
 #[cfg(feature = "test-utils")]
 mod pending;
+#[cfg(feature = "test-utils")]
+mod private;
 
 fn main() {}
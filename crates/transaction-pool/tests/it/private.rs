@@ -0,0 +1,96 @@
+use assert_matches::assert_matches;
+use reth_primitives::{Header, SealedBlock};
+use reth_transaction_pool::{
+    test_utils::{MockTransactionFactory, TestPoolBuilder},
+    CanonicalStateUpdate, GetPooledTransactionLimit, TransactionOrigin, TransactionPool,
+    TransactionPoolExt,
+};
+
+fn block_with_number(number: u64) -> SealedBlock {
+    SealedBlock {
+        header: Header { number, ..Default::default() }.seal_slow(),
+        ..Default::default()
+    }
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn txpool_private_transaction_not_served_to_peers() {
+    let txpool = TestPoolBuilder::default();
+    let mut mock_tx_factory = MockTransactionFactory::default();
+    let transaction = mock_tx_factory.create_eip1559();
+    let hash = *transaction.hash();
+
+    let added = txpool.add_private_transaction(transaction.transaction.clone(), None).await;
+    assert_matches!(added, Ok(added) if added == hash);
+
+    let tx = txpool.get(&hash).unwrap();
+    assert_eq!(tx.origin, TransactionOrigin::Private);
+    assert!(!tx.propagate);
+    assert!(txpool.pooled_transaction_hashes().is_empty());
+    assert!(txpool
+        .get_pooled_transaction_elements(vec![hash], GetPooledTransactionLimit::None)
+        .is_empty());
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn txpool_private_transaction_dropped_after_max_block() {
+    let txpool = TestPoolBuilder::default();
+    let mut mock_tx_factory = MockTransactionFactory::default();
+    let transaction = mock_tx_factory.create_eip1559();
+    let hash = *transaction.hash();
+
+    txpool.add_private_transaction(transaction.transaction.clone(), Some(2)).await.unwrap();
+
+    let block = block_with_number(1);
+    txpool.on_canonical_state_change(CanonicalStateUpdate {
+        new_tip: &block,
+        pending_block_base_fee: 0,
+        pending_block_blob_fee: None,
+        changed_accounts: vec![],
+        mined_transactions: vec![],
+    });
+    assert!(txpool.contains(&hash));
+
+    let block = block_with_number(2);
+    txpool.on_canonical_state_change(CanonicalStateUpdate {
+        new_tip: &block,
+        pending_block_base_fee: 0,
+        pending_block_blob_fee: None,
+        changed_accounts: vec![],
+        mined_transactions: vec![],
+    });
+    assert!(!txpool.contains(&hash));
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn txpool_private_transaction_deadline_forgotten_when_mined() {
+    let txpool = TestPoolBuilder::default();
+    let mut mock_tx_factory = MockTransactionFactory::default();
+    let transaction = mock_tx_factory.create_eip1559();
+    let hash = *transaction.hash();
+
+    txpool.add_private_transaction(transaction.transaction.clone(), Some(2)).await.unwrap();
+
+    let block = block_with_number(1);
+    txpool.on_canonical_state_change(CanonicalStateUpdate {
+        new_tip: &block,
+        pending_block_base_fee: 0,
+        pending_block_blob_fee: None,
+        changed_accounts: vec![],
+        mined_transactions: vec![hash],
+    });
+    assert!(!txpool.contains(&hash));
+
+    // The transaction is re-injected, e.g. after a reorg, and must not inherit the max block number
+    // of its private submission.
+    txpool.add_external_transaction(transaction.transaction.clone()).await.unwrap();
+    let block = block_with_number(2);
+    txpool.on_canonical_state_change(CanonicalStateUpdate {
+        new_tip: &block,
+        pending_block_base_fee: 0,
+        pending_block_blob_fee: None,
+        changed_accounts: vec![],
+        mined_transactions: vec![],
+    });
+    assert!(txpool.contains(&hash));
+}
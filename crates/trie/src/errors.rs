@@ -27,3 +27,11 @@ pub enum StorageRootError {
     #[error(transparent)]
     DB(#[from] reth_db::DatabaseError),
 }
+
+impl From<StorageRootError> for reth_db::DatabaseError {
+    fn from(err: StorageRootError) -> Self {
+        match err {
+            StorageRootError::DB(err) => err,
+        }
+    }
+}
@@ -39,6 +39,10 @@ pub use state::*;
 /// Merkle proof generation.
 pub mod proof;
 
+/// Iteration over ranges of the hashed state.
+mod range;
+pub use range::*;
+
 /// The implementation of the Merkle Patricia Trie.
 mod trie;
 pub use trie::{StateRoot, StorageRoot};
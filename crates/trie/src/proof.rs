@@ -14,6 +14,7 @@ use reth_primitives::{
     trie::{AccountProof, HashBuilder, Nibbles, StorageProof, TrieAccount},
     Address, B256,
 };
+use std::collections::HashMap;
 
 /// A struct for generating merkle proofs.
 ///
@@ -26,12 +27,49 @@ pub struct Proof<'a, TX, H> {
     tx: &'a TX,
     /// The factory for hashed cursors.
     hashed_cursor_factory: H,
+    /// A set of account prefixes that have changed compared to the trie in the database.
+    changed_account_prefixes: PrefixSetMut,
+    /// A map containing storage changes with the hashed address as key and a set of storage key
+    /// prefixes as the value.
+    changed_storage_prefixes: HashMap<B256, PrefixSetMut>,
 }
 
 impl<'a, TX> Proof<'a, TX, &'a TX> {
     /// Create a new [Proof] instance.
     pub fn new(tx: &'a TX) -> Self {
-        Self { tx, hashed_cursor_factory: tx }
+        Self {
+            tx,
+            hashed_cursor_factory: tx,
+            changed_account_prefixes: PrefixSetMut::default(),
+            changed_storage_prefixes: HashMap::default(),
+        }
+    }
+}
+
+impl<'a, TX, H> Proof<'a, TX, H> {
+    /// Set the changed account prefixes.
+    ///
+    /// The trie nodes on the paths of changed accounts are recomputed instead of being read from
+    /// the database.
+    pub fn with_changed_account_prefixes(mut self, prefixes: PrefixSetMut) -> Self {
+        self.changed_account_prefixes = prefixes;
+        self
+    }
+
+    /// Set the changed storage prefixes.
+    pub fn with_changed_storage_prefixes(mut self, prefixes: HashMap<B256, PrefixSetMut>) -> Self {
+        self.changed_storage_prefixes = prefixes;
+        self
+    }
+
+    /// Set the hashed cursor factory.
+    pub fn with_hashed_cursor_factory<HF>(self, hashed_cursor_factory: HF) -> Proof<'a, TX, HF> {
+        Proof {
+            tx: self.tx,
+            hashed_cursor_factory,
+            changed_account_prefixes: self.changed_account_prefixes,
+            changed_storage_prefixes: self.changed_storage_prefixes,
+        }
     }
 }
 
@@ -55,7 +93,7 @@ where
             DatabaseAccountTrieCursor::new(self.tx.cursor_read::<tables::AccountsTrie>()?);
 
         // Create the walker.
-        let mut prefix_set = PrefixSetMut::default();
+        let mut prefix_set = self.changed_account_prefixes.clone();
         prefix_set.insert(target_nibbles.clone());
         let walker = TrieWalker::new(trie_cursor, prefix_set.freeze());
 
@@ -119,7 +157,12 @@ where
         }
 
         let target_nibbles = proofs.iter().map(|p| p.nibbles.clone()).collect::<Vec<_>>();
-        let prefix_set = PrefixSetMut::from(target_nibbles.clone()).freeze();
+        let mut prefix_set =
+            self.changed_storage_prefixes.get(&hashed_address).cloned().unwrap_or_default();
+        for nibbles in &target_nibbles {
+            prefix_set.insert(nibbles.clone());
+        }
+        let prefix_set = prefix_set.freeze();
         let trie_cursor = DatabaseStorageTrieCursor::new(
             self.tx.cursor_dup_read::<tables::StoragesTrie>()?,
             hashed_address,
@@ -164,10 +207,10 @@ where
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::StateRoot;
+    use crate::{HashedPostState, HashedStorage, StateRoot};
     use alloy_chains::Chain;
     use once_cell::sync::Lazy;
-    use reth_db::database::Database;
+    use reth_db::{database::Database, transaction::DbTxMut};
     use reth_interfaces::RethResult;
     use reth_primitives::{Account, Bytes, ChainSpec, StorageEntry, HOLESKY, MAINNET, U256};
     use reth_provider::{test_utils::create_test_provider_factory, HashingWriter, ProviderFactory};
@@ -312,6 +355,47 @@ mod tests {
         }
     }
 
+    #[test]
+    fn post_state_overlay_proof() {
+        let target = Address::from_str("0x1ed9b1dd266b607ee278726d324b855a093394a6").unwrap();
+        let destroyed = Address::from_str("0x2031f89b3ea8014eb51a78c316e42af3e0d7695f").unwrap();
+        let account = Account { nonce: 1, balance: U256::from(10), bytecode_hash: None };
+        let slot = B256::with_last_byte(1);
+        let value = U256::from(2);
+
+        // Generate the proof with the changes applied in memory on top of the genesis trie.
+        let factory = create_test_provider_factory();
+        insert_genesis(&factory, TEST_SPEC.clone()).unwrap();
+
+        let mut hashed_state = HashedPostState::default();
+        hashed_state.accounts.insert(keccak256(target), Some(account));
+        hashed_state.accounts.insert(keccak256(destroyed), None);
+        hashed_state
+            .storages
+            .insert(keccak256(target), HashedStorage::from_iter(false, [(keccak256(slot), value)]));
+
+        let provider = factory.provider().unwrap();
+        let overlay_proof = hashed_state.account_proof(provider.tx_ref(), target, &[slot]).unwrap();
+
+        // Generate the proof with the same changes written to the database.
+        let factory = create_test_provider_factory();
+        insert_genesis(&factory, TEST_SPEC.clone()).unwrap();
+
+        let provider = factory.provider_rw().unwrap();
+        provider.insert_account_for_hashing([(target, Some(account)), (destroyed, None)]).unwrap();
+        provider
+            .insert_storage_for_hashing([(target, [StorageEntry { key: slot, value }])])
+            .unwrap();
+        provider.tx_ref().clear::<tables::AccountsTrie>().unwrap();
+        provider.tx_ref().clear::<tables::StoragesTrie>().unwrap();
+        let (_, updates) = StateRoot::from_tx(provider.tx_ref()).root_with_updates().unwrap();
+        updates.flush(provider.tx_ref()).unwrap();
+
+        let expected = Proof::new(provider.tx_ref()).account_proof(target, &[slot]).unwrap();
+        assert_eq!(overlay_proof.storage_proofs[0].value, value);
+        similar_asserts::assert_eq!(overlay_proof, expected);
+    }
+
     #[test]
     fn mainnet_genesis_account_proof() {
         // Create test database and insert genesis accounts.
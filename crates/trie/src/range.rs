@@ -0,0 +1,271 @@
+use crate::{
+    hashed_cursor::{
+        HashedAccountCursor, HashedCursorFactory, HashedPostStateCursorFactory, HashedStorageCursor,
+    },
+    prefix_set::PrefixSetMut,
+    HashedPostStateSorted, StorageRoot, StorageRootError,
+};
+use reth_db::{transaction::DbTx, DatabaseError};
+use reth_primitives::{trie::Nibbles, Account, B256, U256};
+
+/// A range of accounts of the hashed state.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct AccountRange {
+    /// The accounts in the range, ordered by hashed address.
+    pub accounts: Vec<AccountRangeEntry>,
+    /// The hashed address of the account following the last one in the range, if any.
+    pub next: Option<B256>,
+}
+
+/// An account in a range of the hashed state.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct AccountRangeEntry {
+    /// The hashed address of the account.
+    pub hashed_address: B256,
+    /// The account info.
+    pub account: Account,
+    /// The storage root of the account.
+    pub storage_root: B256,
+    /// The first storage slots of the account, ordered by hashed slot.
+    ///
+    /// Holds at most as many slots as requested.
+    pub storage: Vec<(B256, U256)>,
+}
+
+/// A range of storage slots of an account in the hashed state.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct StorageRange {
+    /// The non-zero storage slots in the range, ordered by hashed slot.
+    pub slots: Vec<(B256, U256)>,
+    /// The hashed slot following the last one in the range, if any.
+    pub next: Option<B256>,
+}
+
+impl HashedPostStateSorted {
+    /// Collects at most `limit` accounts of the state obtained by applying this
+    /// [HashedPostStateSorted] on top of the database, starting at the `start` hashed address.
+    ///
+    /// The storage root is calculated for every returned account, and at most `storage_limit` of
+    /// its storage slots are collected.
+    pub fn account_range<TX: DbTx>(
+        &self,
+        tx: &TX,
+        start: B256,
+        limit: usize,
+        storage_limit: usize,
+    ) -> Result<AccountRange, StorageRootError> {
+        let hashed_cursor_factory = HashedPostStateCursorFactory::new(tx, self);
+        let mut account_cursor = hashed_cursor_factory.hashed_account_cursor()?;
+
+        let mut range = AccountRange::default();
+        let mut entry = account_cursor.seek(start)?;
+        while let Some((hashed_address, account)) = entry {
+            if range.accounts.len() == limit {
+                range.next = Some(hashed_address);
+                break
+            }
+
+            let storage = if storage_limit > 0 {
+                self.storage_range(tx, hashed_address, B256::ZERO, storage_limit)?.slots
+            } else {
+                Vec::new()
+            };
+            range.accounts.push(AccountRangeEntry {
+                hashed_address,
+                account,
+                storage_root: self.storage_root(tx, hashed_address)?,
+                storage,
+            });
+
+            entry = account_cursor.next()?;
+        }
+
+        Ok(range)
+    }
+
+    /// Collects at most `limit` non-zero storage slots of the account with the given hashed address
+    /// of the state obtained by applying this [HashedPostStateSorted] on top of the database,
+    /// starting at the `start` hashed slot.
+    pub fn storage_range<TX: DbTx>(
+        &self,
+        tx: &TX,
+        hashed_address: B256,
+        start: B256,
+        limit: usize,
+    ) -> Result<StorageRange, DatabaseError> {
+        let hashed_cursor_factory = HashedPostStateCursorFactory::new(tx, self);
+        let mut storage_cursor = hashed_cursor_factory.hashed_storage_cursor()?;
+
+        let mut range = StorageRange::default();
+        if storage_cursor.is_storage_empty(hashed_address)? {
+            return Ok(range)
+        }
+
+        let mut entry = storage_cursor.seek(hashed_address, start)?;
+        while let Some(storage_entry) = entry {
+            if range.slots.len() == limit {
+                range.next = Some(storage_entry.key);
+                break
+            }
+            range.slots.push((storage_entry.key, storage_entry.value));
+            entry = storage_cursor.next()?;
+        }
+
+        Ok(range)
+    }
+
+    /// Calculates the storage root of the account with the given hashed address of the state
+    /// obtained by applying this [HashedPostStateSorted] on top of the database.
+    pub fn storage_root<TX: DbTx>(
+        &self,
+        tx: &TX,
+        hashed_address: B256,
+    ) -> Result<B256, StorageRootError> {
+        let mut prefix_set = PrefixSetMut::default();
+        if let Some(storage) = self.storages.get(&hashed_address) {
+            for (hashed_slot, _) in &storage.non_zero_valued_slots {
+                prefix_set.insert(Nibbles::unpack(hashed_slot));
+            }
+            for hashed_slot in &storage.zero_valued_slots {
+                prefix_set.insert(Nibbles::unpack(hashed_slot));
+            }
+        }
+
+        StorageRoot::from_tx_hashed(tx, hashed_address)
+            .with_hashed_cursor_factory(HashedPostStateCursorFactory::new(tx, self))
+            .with_changed_prefixes(prefix_set.freeze())
+            .root()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{HashedPostState, HashedStorage};
+    use reth_db::{
+        cursor::DbCursorRW, database::Database, tables, test_utils::create_test_rw_db,
+        transaction::DbTxMut,
+    };
+    use reth_primitives::StorageEntry;
+
+    fn account(nonce: u64) -> Account {
+        Account { nonce, balance: U256::ZERO, bytecode_hash: None }
+    }
+
+    #[test]
+    fn account_range_with_post_state() {
+        let db = create_test_rw_db();
+        let tx = db.tx_mut().unwrap();
+        for byte in [1, 2, 3, 4] {
+            tx.put::<tables::HashedAccount>(B256::with_last_byte(byte), account(byte as u64))
+                .unwrap();
+        }
+
+        // destroy the second account, update the third one and add a new one
+        let mut hashed_state = HashedPostState::default();
+        hashed_state.accounts.insert(B256::with_last_byte(2), None);
+        hashed_state.accounts.insert(B256::with_last_byte(3), Some(account(30)));
+        hashed_state.accounts.insert(B256::with_last_byte(5), Some(account(5)));
+        let sorted = hashed_state.into_sorted();
+
+        let range = sorted.account_range(&tx, B256::ZERO, 2, 0).unwrap();
+        let accounts = range
+            .accounts
+            .iter()
+            .map(|entry| (entry.hashed_address, entry.account))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            accounts,
+            vec![(B256::with_last_byte(1), account(1)), (B256::with_last_byte(3), account(30))]
+        );
+        assert_eq!(range.next, Some(B256::with_last_byte(4)));
+
+        let range = sorted.account_range(&tx, B256::with_last_byte(4), 2, 0).unwrap();
+        let accounts = range
+            .accounts
+            .iter()
+            .map(|entry| (entry.hashed_address, entry.account))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            accounts,
+            vec![(B256::with_last_byte(4), account(4)), (B256::with_last_byte(5), account(5))]
+        );
+        assert_eq!(range.next, None);
+    }
+
+    #[test]
+    fn storage_range_with_post_state() {
+        let db = create_test_rw_db();
+        let tx = db.tx_mut().unwrap();
+        let hashed_address = B256::with_last_byte(1);
+        let mut cursor = tx.cursor_dup_write::<tables::HashedStorage>().unwrap();
+        for byte in [1, 2, 3] {
+            cursor
+                .upsert(
+                    hashed_address,
+                    StorageEntry { key: B256::with_last_byte(byte), value: U256::from(byte) },
+                )
+                .unwrap();
+        }
+
+        // clear the first slot, update the third one and add a new one
+        let mut hashed_state = HashedPostState::default();
+        hashed_state.storages.insert(
+            hashed_address,
+            HashedStorage::from_iter(
+                false,
+                [
+                    (B256::with_last_byte(1), U256::ZERO),
+                    (B256::with_last_byte(3), U256::from(30)),
+                    (B256::with_last_byte(4), U256::from(4)),
+                ],
+            ),
+        );
+        let sorted = hashed_state.into_sorted();
+
+        let range = sorted.storage_range(&tx, hashed_address, B256::ZERO, 2).unwrap();
+        assert_eq!(
+            range,
+            StorageRange {
+                slots: vec![
+                    (B256::with_last_byte(2), U256::from(2)),
+                    (B256::with_last_byte(3), U256::from(30))
+                ],
+                next: Some(B256::with_last_byte(4)),
+            }
+        );
+
+        let range = sorted.storage_range(&tx, B256::with_last_byte(2), B256::ZERO, 2).unwrap();
+        assert_eq!(range, StorageRange::default());
+    }
+
+    #[test]
+    fn account_range_with_storage_limit() {
+        let db = create_test_rw_db();
+        let tx = db.tx_mut().unwrap();
+        let hashed_address = B256::with_last_byte(1);
+        tx.put::<tables::HashedAccount>(hashed_address, account(1)).unwrap();
+        let mut cursor = tx.cursor_dup_write::<tables::HashedStorage>().unwrap();
+        for byte in [1, 2, 3] {
+            cursor
+                .upsert(
+                    hashed_address,
+                    StorageEntry { key: B256::with_last_byte(byte), value: U256::from(byte) },
+                )
+                .unwrap();
+        }
+        let sorted = HashedPostState::default().into_sorted();
+
+        let range = sorted.account_range(&tx, B256::ZERO, 1, 2).unwrap();
+        assert_eq!(
+            range.accounts[0].storage,
+            vec![
+                (B256::with_last_byte(1), U256::from(1)),
+                (B256::with_last_byte(2), U256::from(2))
+            ]
+        );
+
+        let range = sorted.account_range(&tx, B256::ZERO, 1, 0).unwrap();
+        assert!(range.accounts[0].storage.is_empty());
+    }
+}
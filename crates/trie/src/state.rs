@@ -1,6 +1,7 @@
 use crate::{
     hashed_cursor::HashedPostStateCursorFactory,
     prefix_set::{PrefixSet, PrefixSetMut},
+    proof::Proof,
     updates::TrieUpdates,
     StateRoot, StateRootError,
 };
@@ -12,8 +13,10 @@ use reth_db::{
     DatabaseError,
 };
 use reth_primitives::{
-    keccak256, revm::compat::into_reth_acc, trie::Nibbles, Account, Address, BlockNumber, B256,
-    U256,
+    keccak256,
+    revm::compat::into_reth_acc,
+    trie::{AccountProof, Nibbles},
+    Account, Address, BlockNumber, B256, U256,
 };
 use revm::db::BundleAccount;
 use std::{
@@ -145,6 +148,16 @@ impl HashedPostState {
     /// The prefix sets contain the hashed account and storage keys that have been changed in the
     /// post state.
     pub fn construct_prefix_sets(&self) -> (PrefixSet, HashMap<B256, PrefixSet>) {
+        let (account_prefix_set, storage_prefix_set) = self.construct_prefix_sets_mut();
+        (
+            account_prefix_set.freeze(),
+            storage_prefix_set.into_iter().map(|(k, v)| (k, v.freeze())).collect(),
+        )
+    }
+
+    /// Construct mutable [PrefixSetMut] from hashed post state.
+    /// See [Self::construct_prefix_sets] for more info.
+    pub fn construct_prefix_sets_mut(&self) -> (PrefixSetMut, HashMap<B256, PrefixSetMut>) {
         // Initialize prefix sets.
         let mut account_prefix_set = PrefixSetMut::default();
         let mut storage_prefix_set: HashMap<B256, PrefixSetMut> = HashMap::default();
@@ -164,10 +177,7 @@ impl HashedPostState {
             }
         }
 
-        (
-            account_prefix_set.freeze(),
-            storage_prefix_set.into_iter().map(|(k, v)| (k, v.freeze())).collect(),
-        )
+        (account_prefix_set, storage_prefix_set)
     }
 
     /// Calculate the state root for this [HashedPostState].
@@ -223,6 +233,23 @@ impl HashedPostState {
             .with_changed_storage_prefixes(storage_prefix_set)
             .root_with_updates()
     }
+
+    /// Generates the account proof for the target address and storage slots against the state
+    /// obtained by applying this [HashedPostState] on top of the database.
+    pub fn account_proof<TX: DbTx>(
+        &self,
+        tx: &TX,
+        address: Address,
+        slots: &[B256],
+    ) -> Result<AccountProof, StateRootError> {
+        let sorted = self.clone().into_sorted();
+        let (account_prefix_set, storage_prefix_set) = self.construct_prefix_sets_mut();
+        Proof::new(tx)
+            .with_hashed_cursor_factory(HashedPostStateCursorFactory::new(tx, &sorted))
+            .with_changed_account_prefixes(account_prefix_set)
+            .with_changed_storage_prefixes(storage_prefix_set)
+            .account_proof(address, slots)
+    }
 }
 
 /// Representation of in-memory hashed storage.